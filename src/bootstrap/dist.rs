@@ -768,7 +768,9 @@ impl Step for Src {
             "src/build_helper",
             "src/dlmalloc",
             "src/liballoc",
+            "src/liballoc_counting",
             "src/liballoc_jemalloc",
+            "src/liballoc_poison",
             "src/liballoc_system",
             "src/libbacktrace",
             "src/libcompiler_builtins",
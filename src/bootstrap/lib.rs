@@ -418,6 +418,9 @@ impl Build {
         if self.config.use_jemalloc {
             features.push_str(" jemalloc");
         }
+        if self.config.debug_allocators {
+            features.push_str(" alloc-counting alloc-poison");
+        }
         if self.config.backtrace {
             features.push_str(" backtrace");
         }
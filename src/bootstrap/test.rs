@@ -900,6 +900,10 @@ impl Step for Compiletest {
             cmd.env("PROFILER_SUPPORT", "1");
         }
 
+        if build.config.debug_allocators {
+            cmd.env("DEBUG_ALLOCATORS_SUPPORT", "1");
+        }
+
         cmd.env("RUST_TEST_TMPDIR", build.out.join("tmp"));
 
         cmd.arg("--adb-path").arg("adb");
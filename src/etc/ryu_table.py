@@ -0,0 +1,87 @@
+#!/usr/bin/env python2.7
+#
+# Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+# file at the top-level directory of this distribution and at
+# http://rust-lang.org/COPYRIGHT.
+#
+# Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+# http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+# <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+# option. This file may not be copied, modified, or distributed
+# except according to those terms.
+
+"""
+Generate the powers of five used by the Ryu shortest float to decimal
+conversion in `libcore/num/flt2dec/strategy/ryu`.
+
+Two tables are printed, both as 128 bit integers:
+
+* `POW5_SPLIT[i]` holds the leading `POW5_BITCOUNT` bits of 5^i. It is used
+  for negative binary exponents.
+
+* `POW5_INV_SPLIT[i]` holds the leading `POW5_INV_BITCOUNT` bits of 5^-i,
+  rounded up. It is used for non-negative binary exponents.
+
+The table sizes cover every binary exponent of `f64` (and hence `f32`).
+"""
+from __future__ import print_function
+
+POW5_BITCOUNT = 125
+POW5_INV_BITCOUNT = 125
+POW5_TABLE_SIZE = 326
+POW5_INV_TABLE_SIZE = 342
+
+HEADER = """
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Tables of truncated powers of five and their inverses.
+//! DO NOT MODIFY: Generated by `src/etc/ryu_table.py`
+"""
+
+
+def pow5_split(i):
+    pow5 = 5 ** i
+    shift = pow5.bit_length() - POW5_BITCOUNT
+    if shift >= 0:
+        return pow5 >> shift
+    else:
+        return pow5 << -shift
+
+
+def pow5_inv_split(i):
+    pow5 = 5 ** i
+    shift = pow5.bit_length() - 1 + POW5_INV_BITCOUNT
+    return (1 << shift) // pow5 + 1
+
+
+def print_table(name, values):
+    typ = "[u128; {}]".format(len(values))
+    print("pub static ", name, ": ", typ, " = [", sep='')
+    for v in values:
+        assert v < 2 ** 128
+        print("    0x{:032x},".format(v))
+    print("];")
+
+
+def main():
+    print(HEADER.strip())
+    print()
+    print("pub const POW5_BITCOUNT: i32 = {};".format(POW5_BITCOUNT))
+    print("pub const POW5_INV_BITCOUNT: i32 = {};".format(POW5_INV_BITCOUNT))
+    print()
+    print_table("POW5_SPLIT", [pow5_split(i) for i in range(POW5_TABLE_SIZE)])
+    print()
+    print_table("POW5_INV_SPLIT",
+                [pow5_inv_split(i) for i in range(POW5_INV_TABLE_SIZE)])
+
+
+if __name__ == '__main__':
+    main()
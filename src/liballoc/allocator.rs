@@ -373,6 +373,48 @@ impl fmt::Display for CannotReallocInPlace {
     }
 }
 
+/// The `CollectionAllocErr` error is returned by the fallible allocation
+/// methods of collections, such as `RawVec::try_reserve`. On top of the
+/// errors of the allocator, it reports requests for a capacity whose size
+/// in bytes overflows `usize`.
+///
+/// On targets with pointers narrower than 64 bits, sizes above `isize::MAX`
+/// are also reported as `CapacityOverflow`. On 64-bit targets such a request
+/// is passed on to the allocator, which is expected to fail it with an
+/// `AllocErr`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum CollectionAllocErr {
+    /// The size of the requested capacity overflowed, or, on targets with
+    /// pointers narrower than 64 bits, exceeded `isize::MAX`. No allocation
+    /// was attempted.
+    CapacityOverflow,
+
+    /// The allocator failed to provide the memory.
+    AllocErr(AllocErr),
+}
+
+impl CollectionAllocErr {
+    pub fn description(&self) -> &str {
+        match *self {
+            CollectionAllocErr::CapacityOverflow => "capacity overflow",
+            CollectionAllocErr::AllocErr(ref err) => err.description(),
+        }
+    }
+}
+
+impl From<AllocErr> for CollectionAllocErr {
+    fn from(err: AllocErr) -> Self {
+        CollectionAllocErr::AllocErr(err)
+    }
+}
+
+// (we need this for downstream impl of trait Error)
+impl fmt::Display for CollectionAllocErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.description())
+    }
+}
+
 /// An implementation of `Alloc` can allocate, reallocate, and
 /// deallocate arbitrary blocks of data described via `Layout`.
 ///
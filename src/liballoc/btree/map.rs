@@ -17,7 +17,7 @@ use core::ops::Index;
 use core::{fmt, intrinsics, mem, ptr};
 
 use borrow::Borrow;
-use Bound::{Excluded, Included, Unbounded};
+use Bound::{self, Excluded, Included, Unbounded};
 use range::RangeArgument;
 
 use super::node::{self, Handle, NodeRef, marker};
@@ -419,6 +419,71 @@ impl<'a, K: 'a + fmt::Debug, V: 'a + fmt::Debug> fmt::Debug for RangeMut<'a, K,
     }
 }
 
+/// A cursor over a `BTreeMap`.
+///
+/// A cursor sits in the gap between two entries of the map, or before the first or after
+/// the last entry, and can move in either direction from there. Unlike an iterator, it can
+/// move back and forth any number of times.
+///
+/// This `struct` is created by the [`lower_bound`] and [`upper_bound`] methods on
+/// [`BTreeMap`]. See their documentation for more.
+///
+/// [`lower_bound`]: struct.BTreeMap.html#method.lower_bound
+/// [`upper_bound`]: struct.BTreeMap.html#method.upper_bound
+/// [`BTreeMap`]: struct.BTreeMap.html
+#[unstable(feature = "btree_cursors", issue = "0")]
+pub struct Cursor<'a, K: 'a, V: 'a> {
+    edge: Handle<NodeRef<marker::Immut<'a>, K, V, marker::Leaf>, marker::Edge>,
+}
+
+#[unstable(feature = "btree_cursors", issue = "0")]
+impl<'a, K, V> Clone for Cursor<'a, K, V> {
+    fn clone(&self) -> Cursor<'a, K, V> {
+        Cursor { edge: self.edge }
+    }
+}
+
+#[unstable(feature = "btree_cursors", issue = "0")]
+impl<'a, K: 'a + fmt::Debug, V: 'a + fmt::Debug> fmt::Debug for Cursor<'a, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Cursor")
+         .field(&self.peek_prev())
+         .field(&self.peek_next())
+         .finish()
+    }
+}
+
+/// A cursor over a `BTreeMap` which can also insert and remove entries.
+///
+/// Insertions and removals happen right where the cursor is, without searching the map
+/// for the key again.
+///
+/// This `struct` is created by the [`lower_bound_mut`] and [`upper_bound_mut`] methods on
+/// [`BTreeMap`]. See their documentation for more.
+///
+/// [`lower_bound_mut`]: struct.BTreeMap.html#method.lower_bound_mut
+/// [`upper_bound_mut`]: struct.BTreeMap.html#method.upper_bound_mut
+/// [`BTreeMap`]: struct.BTreeMap.html
+#[unstable(feature = "btree_cursors", issue = "0")]
+pub struct CursorMut<'a, K: 'a, V: 'a> {
+    edge: Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, marker::Edge>,
+    length: &'a mut usize,
+
+    // Be invariant in `K` and `V`
+    _marker: PhantomData<&'a mut (K, V)>,
+}
+
+#[unstable(feature = "btree_cursors", issue = "0")]
+impl<'a, K: 'a + fmt::Debug, V: 'a + fmt::Debug> fmt::Debug for CursorMut<'a, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let cursor = self.as_cursor();
+        f.debug_tuple("CursorMut")
+         .field(&cursor.peek_prev())
+         .field(&cursor.peek_next())
+         .finish()
+    }
+}
+
 /// A view into a single entry in a map, which may either be vacant or occupied.
 ///
 /// This `enum` is constructed from the [`entry`] method on [`BTreeMap`].
@@ -843,6 +908,138 @@ impl<K: Ord, V> BTreeMap<K, V> {
         }
     }
 
+    /// Returns a cursor pointing at the gap before the smallest key above the given bound.
+    ///
+    /// Passing `Included(x)` puts the cursor right before the first key which is greater
+    /// than or equal to `x`, `Excluded(x)` right before the first key which is greater than
+    /// `x`, and `Unbounded` before the first key of the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(btree_cursors)]
+    ///
+    /// use std::collections::BTreeMap;
+    /// use std::collections::Bound::{Excluded, Included};
+    ///
+    /// let map: BTreeMap<i32, &str> = [(1, "a"), (2, "b"), (3, "c"), (4, "d")]
+    ///     .iter().cloned().collect();
+    ///
+    /// let cursor = map.lower_bound(Included(&2));
+    /// assert_eq!(cursor.peek_prev(), Some((&1, &"a")));
+    /// assert_eq!(cursor.peek_next(), Some((&2, &"b")));
+    ///
+    /// let cursor = map.lower_bound(Excluded(&2));
+    /// assert_eq!(cursor.peek_prev(), Some((&2, &"b")));
+    /// assert_eq!(cursor.peek_next(), Some((&3, &"c")));
+    /// ```
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn lower_bound<Q: ?Sized>(&self, bound: Bound<&Q>) -> Cursor<K, V>
+        where K: Borrow<Q>, Q: Ord
+    {
+        Cursor { edge: bound_search(self.root.as_ref(), bound, false) }
+    }
+
+    /// Returns a cursor pointing at the gap after the greatest key below the given bound.
+    ///
+    /// Passing `Included(x)` puts the cursor right after the last key which is less than or
+    /// equal to `x`, `Excluded(x)` right after the last key which is less than `x`, and
+    /// `Unbounded` after the last key of the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(btree_cursors)]
+    ///
+    /// use std::collections::BTreeMap;
+    /// use std::collections::Bound::{Excluded, Included};
+    ///
+    /// let map: BTreeMap<i32, &str> = [(1, "a"), (2, "b"), (3, "c"), (4, "d")]
+    ///     .iter().cloned().collect();
+    ///
+    /// let mut cursor = map.upper_bound(Included(&3));
+    /// assert_eq!(cursor.prev(), Some((&3, &"c")));
+    /// assert_eq!(cursor.prev(), Some((&2, &"b")));
+    ///
+    /// let cursor = map.upper_bound(Excluded(&3));
+    /// assert_eq!(cursor.peek_next(), Some((&3, &"c")));
+    /// ```
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn upper_bound<Q: ?Sized>(&self, bound: Bound<&Q>) -> Cursor<K, V>
+        where K: Borrow<Q>, Q: Ord
+    {
+        Cursor { edge: bound_search(self.root.as_ref(), bound, true) }
+    }
+
+    /// Returns a mutable cursor pointing at the gap before the smallest key above the given
+    /// bound. See [`lower_bound`] for how the bound is interpreted.
+    ///
+    /// [`lower_bound`]: #method.lower_bound
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(btree_cursors)]
+    ///
+    /// use std::collections::BTreeMap;
+    /// use std::collections::Bound::Included;
+    ///
+    /// let mut map: BTreeMap<i32, &str> = [(1, "a"), (2, "b"), (4, "d")]
+    ///     .iter().cloned().collect();
+    ///
+    /// let mut cursor = map.lower_bound_mut(Included(&3));
+    /// cursor.insert_before(3, "c");
+    /// assert_eq!(cursor.remove_next(), Some((4, "d")));
+    /// if let Some((_, v)) = cursor.prev() {
+    ///     *v = "C";
+    /// }
+    ///
+    /// assert_eq!(map.into_iter().collect::<Vec<_>>(), [(1, "a"), (2, "b"), (3, "C")]);
+    /// ```
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn lower_bound_mut<Q: ?Sized>(&mut self, bound: Bound<&Q>) -> CursorMut<K, V>
+        where K: Borrow<Q>, Q: Ord
+    {
+        CursorMut {
+            edge: bound_search(self.root.as_mut(), bound, false),
+            length: &mut self.length,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns a mutable cursor pointing at the gap after the greatest key below the given
+    /// bound. See [`upper_bound`] for how the bound is interpreted.
+    ///
+    /// [`upper_bound`]: #method.upper_bound
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(btree_cursors)]
+    ///
+    /// use std::collections::BTreeMap;
+    /// use std::collections::Bound::Unbounded;
+    ///
+    /// let mut map: BTreeMap<i32, i32> = (0..10).map(|i| (i, i)).collect();
+    ///
+    /// // Remove the three largest entries.
+    /// let mut cursor = map.upper_bound_mut(Unbounded);
+    /// for _ in 0..3 {
+    ///     cursor.remove_prev();
+    /// }
+    /// assert_eq!(map.keys().next_back(), Some(&6));
+    /// ```
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn upper_bound_mut<Q: ?Sized>(&mut self, bound: Bound<&Q>) -> CursorMut<K, V>
+        where K: Borrow<Q>, Q: Ord
+    {
+        CursorMut {
+            edge: bound_search(self.root.as_mut(), bound, true),
+            length: &mut self.length,
+            _marker: PhantomData,
+        }
+    }
+
     /// Gets the given key's corresponding entry in the map for in-place manipulation.
     ///
     /// # Examples
@@ -1785,6 +1982,83 @@ fn last_leaf_edge<BorrowType, K, V>
     }
 }
 
+fn left_leaf_edge<BorrowType, K, V>
+    (kv: Handle<NodeRef<BorrowType, K, V, marker::LeafOrInternal>, marker::KV>)
+     -> Handle<NodeRef<BorrowType, K, V, marker::Leaf>, marker::Edge> {
+    match kv.force() {
+        Leaf(leaf_kv) => leaf_kv.left_edge(),
+        Internal(internal_kv) => last_leaf_edge(internal_kv.left_edge().descend()),
+    }
+}
+
+fn right_leaf_edge<BorrowType, K, V>
+    (kv: Handle<NodeRef<BorrowType, K, V, marker::LeafOrInternal>, marker::KV>)
+     -> Handle<NodeRef<BorrowType, K, V, marker::Leaf>, marker::Edge> {
+    match kv.force() {
+        Leaf(leaf_kv) => leaf_kv.right_edge(),
+        Internal(internal_kv) => first_leaf_edge(internal_kv.right_edge().descend()),
+    }
+}
+
+/// Finds the key/value pair right after a leaf edge, which is either in the same leaf or in
+/// one of its ancestors. Returns `None` if the edge is the last one of the tree.
+///
+/// This is unsafe because for mutable borrows the result aliases `edge`.
+unsafe fn next_kv<BorrowType, K, V>
+    (edge: &Handle<NodeRef<BorrowType, K, V, marker::Leaf>, marker::Edge>)
+     -> Option<Handle<NodeRef<BorrowType, K, V, marker::LeafOrInternal>, marker::KV>> {
+    let mut edge = ptr::read(edge).forget_node_type();
+    loop {
+        edge = match edge.right_kv() {
+            Ok(kv) => return Some(kv),
+            Err(last_edge) => match last_edge.into_node().ascend() {
+                Ok(parent_edge) => parent_edge.forget_node_type(),
+                Err(_) => return None,
+            },
+        }
+    }
+}
+
+/// Finds the key/value pair right before a leaf edge, which is either in the same leaf or in
+/// one of its ancestors. Returns `None` if the edge is the first one of the tree.
+///
+/// This is unsafe because for mutable borrows the result aliases `edge`.
+unsafe fn prev_kv<BorrowType, K, V>
+    (edge: &Handle<NodeRef<BorrowType, K, V, marker::Leaf>, marker::Edge>)
+     -> Option<Handle<NodeRef<BorrowType, K, V, marker::LeafOrInternal>, marker::KV>> {
+    let mut edge = ptr::read(edge).forget_node_type();
+    loop {
+        edge = match edge.left_kv() {
+            Ok(kv) => return Some(kv),
+            Err(first_edge) => match first_edge.into_node().ascend() {
+                Ok(parent_edge) => parent_edge.forget_node_type(),
+                Err(_) => return None,
+            },
+        }
+    }
+}
+
+/// Finds the leaf edge right before the first key above `bound`, or, if `upper` is set, the
+/// leaf edge right after the last key below `bound`.
+fn bound_search<BorrowType, K, V, Q: ?Sized>(
+    root: NodeRef<BorrowType, K, V, marker::LeafOrInternal>,
+    bound: Bound<&Q>,
+    upper: bool
+) -> Handle<NodeRef<BorrowType, K, V, marker::Leaf>, marker::Edge>
+        where Q: Ord, K: Borrow<Q>
+{
+    let (key, after_match) = match bound {
+        Included(key) => (key, upper),
+        Excluded(key) => (key, !upper),
+        Unbounded if upper => return last_leaf_edge(root),
+        Unbounded => return first_leaf_edge(root),
+    };
+    match search::search_tree(root, key) {
+        Found(kv) => if after_match { right_leaf_edge(kv) } else { left_leaf_edge(kv) },
+        GoDown(edge) => edge,
+    }
+}
+
 fn range_search<BorrowType, K, V, Q: ?Sized, R: RangeArgument<Q>>(
     root1: NodeRef<BorrowType, K, V, marker::LeafOrInternal>,
     root2: NodeRef<BorrowType, K, V, marker::LeafOrInternal>,
@@ -2226,43 +2500,7 @@ impl<'a, K: Ord, V> VacantEntry<'a, K, V> {
     #[stable(feature = "rust1", since = "1.0.0")]
     pub fn insert(self, value: V) -> &'a mut V {
         *self.length += 1;
-
-        let out_ptr;
-
-        let mut ins_k;
-        let mut ins_v;
-        let mut ins_edge;
-
-        let mut cur_parent = match self.handle.insert(self.key, value) {
-            (Fit(handle), _) => return handle.into_kv_mut().1,
-            (Split(left, k, v, right), ptr) => {
-                ins_k = k;
-                ins_v = v;
-                ins_edge = right;
-                out_ptr = ptr;
-                left.ascend().map_err(|n| n.into_root_mut())
-            }
-        };
-
-        loop {
-            match cur_parent {
-                Ok(parent) => {
-                    match parent.insert(ins_k, ins_v, ins_edge) {
-                        Fit(_) => return unsafe { &mut *out_ptr },
-                        Split(left, k, v, right) => {
-                            ins_k = k;
-                            ins_v = v;
-                            ins_edge = right;
-                            cur_parent = left.ascend().map_err(|n| n.into_root_mut());
-                        }
-                    }
-                }
-                Err(root) => {
-                    root.push_level().push(ins_k, ins_v, ins_edge);
-                    return unsafe { &mut *out_ptr };
-                }
-            }
-        }
+        insert_at_leaf_edge(self.handle, self.key, value).into_kv_mut().1
     }
 }
 
@@ -2417,56 +2655,285 @@ impl<'a, K: Ord, V> OccupiedEntry<'a, K, V> {
 
     fn remove_kv(self) -> (K, V) {
         *self.length -= 1;
+        remove_kv_tracking(self.handle).0
+    }
+}
 
-        let (small_leaf, old_key, old_val) = match self.handle.force() {
-            Leaf(leaf) => {
-                let (hole, old_key, old_val) = leaf.remove();
-                (hole.into_node(), old_key, old_val)
-            }
-            Internal(mut internal) => {
-                let key_loc = internal.kv_mut().0 as *mut K;
-                let val_loc = internal.kv_mut().1 as *mut V;
+impl<'a, K, V> Cursor<'a, K, V> {
+    /// Moves the cursor past the next entry and returns it, or returns `None` if the cursor
+    /// is already after the last entry.
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn next(&mut self) -> Option<(&'a K, &'a V)> {
+        let kv = unsafe { next_kv(&self.edge)? };
+        self.edge = right_leaf_edge(kv);
+        Some(kv.into_kv())
+    }
+
+    /// Moves the cursor back past the previous entry and returns it, or returns `None` if
+    /// the cursor is already before the first entry.
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn prev(&mut self) -> Option<(&'a K, &'a V)> {
+        let kv = unsafe { prev_kv(&self.edge)? };
+        self.edge = left_leaf_edge(kv);
+        Some(kv.into_kv())
+    }
+
+    /// Returns the entry after the cursor without moving it.
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn peek_next(&self) -> Option<(&'a K, &'a V)> {
+        unsafe { next_kv(&self.edge) }.map(|kv| kv.into_kv())
+    }
+
+    /// Returns the entry before the cursor without moving it.
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn peek_prev(&self) -> Option<(&'a K, &'a V)> {
+        unsafe { prev_kv(&self.edge) }.map(|kv| kv.into_kv())
+    }
+}
+
+impl<'a, K, V> CursorMut<'a, K, V> {
+    /// Moves the cursor past the next entry and returns it, or returns `None` if the cursor
+    /// is already after the last entry.
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn next(&mut self) -> Option<(&K, &mut V)> {
+        unsafe {
+            let kv = next_kv(&self.edge)?;
+            let (k, v) = ptr::read(&kv).into_kv_mut();
+            self.edge = right_leaf_edge(kv);
+            Some((k, v))
+        }
+    }
 
-                let to_remove = first_leaf_edge(internal.right_edge().descend()).right_kv().ok();
-                let to_remove = unsafe { unwrap_unchecked(to_remove) };
+    /// Moves the cursor back past the previous entry and returns it, or returns `None` if
+    /// the cursor is already before the first entry.
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn prev(&mut self) -> Option<(&K, &mut V)> {
+        unsafe {
+            let kv = prev_kv(&self.edge)?;
+            let (k, v) = ptr::read(&kv).into_kv_mut();
+            self.edge = left_leaf_edge(kv);
+            Some((k, v))
+        }
+    }
 
-                let (hole, key, val) = to_remove.remove();
+    /// Returns the entry after the cursor without moving it.
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn peek_next(&mut self) -> Option<(&K, &mut V)> {
+        let kv = unsafe { next_kv(&self.edge)? };
+        let (k, v) = kv.into_kv_mut();
+        Some((k, v))
+    }
 
-                let old_key = unsafe { mem::replace(&mut *key_loc, key) };
-                let old_val = unsafe { mem::replace(&mut *val_loc, val) };
+    /// Returns the entry before the cursor without moving it.
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn peek_prev(&mut self) -> Option<(&K, &mut V)> {
+        let kv = unsafe { prev_kv(&self.edge)? };
+        let (k, v) = kv.into_kv_mut();
+        Some((k, v))
+    }
 
-                (hole.into_node(), old_key, old_val)
+    /// Returns a read-only cursor at the current position.
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn as_cursor(&self) -> Cursor<K, V> {
+        Cursor { edge: self.edge.reborrow() }
+    }
+
+    /// Removes the entry after the cursor and returns it, leaving the cursor in the gap
+    /// between the entries around the removed one. Returns `None` if the cursor is already
+    /// after the last entry.
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn remove_next(&mut self) -> Option<(K, V)> {
+        let kv = unsafe { next_kv(&self.edge)? };
+        *self.length -= 1;
+        let (old_kv, pos) = remove_kv_tracking(kv);
+        self.edge = pos;
+        Some(old_kv)
+    }
+
+    /// Removes the entry before the cursor and returns it, leaving the cursor in the gap
+    /// between the entries around the removed one. Returns `None` if the cursor is already
+    /// before the first entry.
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn remove_prev(&mut self) -> Option<(K, V)> {
+        let kv = unsafe { prev_kv(&self.edge)? };
+        *self.length -= 1;
+        let (old_kv, pos) = remove_kv_tracking(kv);
+        self.edge = pos;
+        Some(old_kv)
+    }
+}
+
+impl<'a, K: Ord, V> CursorMut<'a, K, V> {
+    /// Inserts a new entry into the gap the cursor points at, leaving the cursor before it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `key` is not strictly between the keys of the entries around the cursor,
+    /// as the map would no longer be sorted otherwise.
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn insert_after(&mut self, key: K, value: V) {
+        let kv = self.insert(key, value);
+        self.edge = kv.left_edge();
+    }
+
+    /// Inserts a new entry into the gap the cursor points at, leaving the cursor after it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `key` is not strictly between the keys of the entries around the cursor,
+    /// as the map would no longer be sorted otherwise.
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn insert_before(&mut self, key: K, value: V) {
+        let kv = self.insert(key, value);
+        self.edge = kv.right_edge();
+    }
+
+    /// Inserts a key/value pair at the cursor's edge, checking that it keeps the map
+    /// ordered, and returns a KV handle to the inserted pair. The cursor's edge is left
+    /// dangling, so callers must move it.
+    fn insert(&mut self, key: K, value: V)
+              -> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, marker::KV> {
+        {
+            let cursor = self.as_cursor();
+            if let Some((prev, _)) = cursor.peek_prev() {
+                assert!(*prev < key, "key does not fit at the position of BTreeMap cursor");
             }
-        };
+            if let Some((next, _)) = cursor.peek_next() {
+                assert!(key < *next, "key does not fit at the position of BTreeMap cursor");
+            }
+        }
+        *self.length += 1;
+        insert_at_leaf_edge(unsafe { ptr::read(&self.edge) }, key, value)
+    }
+}
 
-        // Handle underflow
-        let mut cur_node = small_leaf.forget_type();
-        while cur_node.len() < node::CAPACITY / 2 {
-            match handle_underfull_node(cur_node) {
-                AtRoot => break,
-                EmptyParent(_) => unreachable!(),
-                Merged(parent) => {
-                    if parent.len() == 0 {
-                        // We must be at the root
-                        parent.into_root_mut().pop_level();
-                        break;
-                    } else {
-                        cur_node = parent.forget_type();
+/// Inserts a key/value pair at the given leaf edge, splitting nodes all the way up to the
+/// root if necessary, and returns a handle to the inserted pair.
+fn insert_at_leaf_edge<'a, K, V>(
+    handle: Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, marker::Edge>,
+    key: K,
+    value: V
+) -> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, marker::KV> {
+    let out;
+
+    let mut ins_k;
+    let mut ins_v;
+    let mut ins_edge;
+
+    let mut cur_parent = match handle.insert(key, value) {
+        (Fit(_), kv) => return kv,
+        (Split(left, k, v, right), kv) => {
+            ins_k = k;
+            ins_v = v;
+            ins_edge = right;
+            out = kv;
+            left.ascend().map_err(|n| n.into_root_mut())
+        }
+    };
+
+    loop {
+        match cur_parent {
+            Ok(parent) => {
+                match parent.insert(ins_k, ins_v, ins_edge) {
+                    Fit(_) => return out,
+                    Split(left, k, v, right) => {
+                        ins_k = k;
+                        ins_v = v;
+                        ins_edge = right;
+                        cur_parent = left.ascend().map_err(|n| n.into_root_mut());
                     }
                 }
-                Stole(_) => break,
+            }
+            Err(root) => {
+                root.push_level().push(ins_k, ins_v, ins_edge);
+                return out;
+            }
+        }
+    }
+}
+
+/// Removes the key/value pair at the given handle and rebalances the tree. Besides the
+/// removed pair, this returns the leaf edge between the pairs which used to be its
+/// neighbours, so that callers can carry on from there without searching again.
+fn remove_kv_tracking<'a, K, V>(
+    handle: Handle<NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal>, marker::KV>
+) -> ((K, V), Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, marker::Edge>) {
+    let (hole, old_key, old_val, was_internal) = match handle.force() {
+        Leaf(leaf) => {
+            let (hole, old_key, old_val) = leaf.remove();
+            (hole, old_key, old_val, false)
+        }
+        Internal(mut internal) => {
+            let key_loc = internal.kv_mut().0 as *mut K;
+            let val_loc = internal.kv_mut().1 as *mut V;
+
+            let to_remove = first_leaf_edge(internal.right_edge().descend()).right_kv().ok();
+            let to_remove = unsafe { unwrap_unchecked(to_remove) };
+
+            let (hole, key, val) = to_remove.remove();
+
+            let old_key = unsafe { mem::replace(&mut *key_loc, key) };
+            let old_val = unsafe { mem::replace(&mut *val_loc, val) };
+
+            (hole, old_key, old_val, true)
+        }
+    };
+
+    // Handle underflow. Only the leaf holding the hole can have its contents moved around,
+    // fixing up the nodes above it just moves edges.
+    let mut pos = hole;
+    let mut cur_node = unsafe { ptr::read(&pos).into_node().forget_type() };
+    let mut at_leaf = true;
+    while cur_node.len() < node::CAPACITY / 2 {
+        match handle_underfull_node(cur_node) {
+            AtRoot => break,
+            EmptyParent(_) => unreachable!(),
+            Merged(edge, merged_with_left, offset) => {
+                if at_leaf && merged_with_left {
+                    let merged = match unsafe { ptr::read(&edge) }.descend().force() {
+                        Leaf(leaf) => leaf,
+                        Internal(_) => unreachable!(),
+                    };
+                    pos = Handle::new_edge(merged, offset + pos.idx());
+                }
+                let parent = edge.into_node();
+                if parent.len() == 0 {
+                    // We must be at the root
+                    parent.into_root_mut().pop_level();
+                    break;
+                } else {
+                    cur_node = parent.forget_type();
+                }
+            }
+            Stole(stole_from_left) => {
+                if at_leaf && stole_from_left {
+                    let idx = pos.idx() + 1;
+                    pos = Handle::new_edge(pos.into_node(), idx);
+                }
+                break;
             }
         }
+        at_leaf = false;
+    }
 
-        (old_key, old_val)
+    // A pair removed from an internal node was replaced with its successor, which was taken
+    // from the start of a leaf. The hole ends up right of the successor in that case.
+    if was_internal {
+        let successor = unsafe { unwrap_unchecked(prev_kv(&pos)) };
+        pos = left_leaf_edge(successor);
     }
+
+    ((old_key, old_val), pos)
 }
 
 enum UnderflowResult<'a, K, V> {
     AtRoot,
     EmptyParent(NodeRef<marker::Mut<'a>, K, V, marker::Internal>),
-    Merged(NodeRef<marker::Mut<'a>, K, V, marker::Internal>),
-    Stole(NodeRef<marker::Mut<'a>, K, V, marker::Internal>),
+    // The edge to the merged node, whether the underfull node was merged into its left
+    // sibling, and if so, how far its contents were shifted.
+    Merged(Handle<NodeRef<marker::Mut<'a>, K, V, marker::Internal>, marker::Edge>, bool, usize),
+    // Whether the underfull node stole from its left sibling.
+    Stole(bool),
 }
 
 fn handle_underfull_node<'a, K, V>(node: NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal>)
@@ -2490,14 +2957,19 @@ fn handle_underfull_node<'a, K, V>(node: NodeRef<marker::Mut<'a>, K, V, marker::
     };
 
     if handle.can_merge() {
-        Merged(handle.merge().into_node())
+        let offset = if is_left {
+            handle.reborrow().left_edge().descend().len() + 1
+        } else {
+            0
+        };
+        Merged(handle.merge(), is_left, offset)
     } else {
         if is_left {
             handle.steal_left();
         } else {
             handle.steal_right();
         }
-        Stole(handle.into_node())
+        Stole(is_left)
     }
 }
 
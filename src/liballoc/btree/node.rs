@@ -802,6 +802,22 @@ impl<BorrowType, K, V, NodeType, HandleType> PartialEq
 impl<BorrowType, K, V, NodeType, HandleType>
         Handle<NodeRef<BorrowType, K, V, NodeType>, HandleType> {
 
+    /// Returns the position of this handle within its node.
+    pub fn idx(&self) -> usize {
+        self.idx
+    }
+
+    /// Removes any static information about whether the underlying node is a `Leaf` or
+    /// an `Internal` node.
+    pub fn forget_node_type(self)
+            -> Handle<NodeRef<BorrowType, K, V, marker::LeafOrInternal>, HandleType> {
+        Handle {
+            node: self.node.forget_type(),
+            idx: self.idx,
+            _marker: PhantomData
+        }
+    }
+
     /// Temporarily takes out another, immutable handle on the same location.
     pub fn reborrow(&self)
             -> Handle<NodeRef<marker::Immut, K, V, NodeType>, HandleType> {
@@ -900,29 +916,42 @@ impl<'a, K, V> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, marker::Edge
     /// Inserts a new key/value pair between the key/value pairs to the right and left of
     /// this edge. This method splits the node if there isn't enough room.
     ///
-    /// The returned pointer points to the inserted value.
+    /// Returns whether the node had to be split, along with a KV handle to the inserted
+    /// pair. That handle points into whichever half of the split the pair ended up in, and
+    /// stays valid while the split is propagated up the tree, since that only moves edges
+    /// around and never moves the contents of a leaf.
     pub fn insert(mut self, key: K, val: V)
-            -> (InsertResult<'a, K, V, marker::Leaf>, *mut V) {
+            -> (InsertResult<'a, K, V, marker::Leaf>,
+                Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, marker::KV>) {
 
         if self.node.len() < CAPACITY {
-            let ptr = self.insert_fit(key, val);
-            (InsertResult::Fit(Handle::new_kv(self.node, self.idx)), ptr)
+            self.insert_fit(key, val);
+            let kv = Handle::new_kv(unsafe { ptr::read(&self.node) }, self.idx);
+            (InsertResult::Fit(Handle::new_kv(self.node, self.idx)), kv)
         } else {
             let middle = Handle::new_kv(self.node, B);
-            let (mut left, k, v, mut right) = middle.split();
-            let ptr = if self.idx <= B {
+            let (mut left, k, v, right) = middle.split();
+            let kv = if self.idx <= B {
                 unsafe {
-                    Handle::new_edge(left.reborrow_mut(), self.idx).insert_fit(key, val)
+                    Handle::new_edge(left.reborrow_mut(), self.idx).insert_fit(key, val);
+                    Handle::new_kv(ptr::read(&left), self.idx)
                 }
             } else {
+                // Unlike `right.as_mut()`, this points at the root of the whole tree, which
+                // the new node will be part of by the time the handle is used.
+                let mut right_leaf: NodeRef<marker::Mut, K, V, marker::Leaf> = NodeRef {
+                    height: 0,
+                    node: right.node.as_ptr(),
+                    root: left.root,
+                    _marker: PhantomData
+                };
                 unsafe {
-                    Handle::new_edge(
-                        right.as_mut().cast_unchecked::<marker::Leaf>(),
-                        self.idx - (B + 1)
-                    ).insert_fit(key, val)
+                    Handle::new_edge(right_leaf.reborrow_mut(), self.idx - (B + 1))
+                        .insert_fit(key, val);
                 }
+                Handle::new_kv(right_leaf, self.idx - (B + 1))
             };
-            (InsertResult::Split(left, k, v, right), ptr)
+            (InsertResult::Split(left, k, v, right), kv)
         }
     }
 }
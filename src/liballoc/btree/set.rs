@@ -22,6 +22,7 @@ use borrow::Borrow;
 use btree_map::{BTreeMap, Keys};
 use super::Recover;
 use range::RangeArgument;
+use Bound;
 
 // FIXME(conventions): implement bounded iterators
 
@@ -121,6 +122,34 @@ pub struct Range<'a, T: 'a> {
     iter: ::btree_map::Range<'a, T, ()>,
 }
 
+/// A cursor over a `BTreeSet`.
+///
+/// This `struct` is created by the [`lower_bound`] and [`upper_bound`] methods on
+/// [`BTreeSet`]. See their documentation for more.
+///
+/// [`BTreeSet`]: struct.BTreeSet.html
+/// [`lower_bound`]: struct.BTreeSet.html#method.lower_bound
+/// [`upper_bound`]: struct.BTreeSet.html#method.upper_bound
+#[derive(Debug)]
+#[unstable(feature = "btree_cursors", issue = "0")]
+pub struct Cursor<'a, T: 'a> {
+    inner: ::btree_map::Cursor<'a, T, ()>,
+}
+
+/// A cursor over a `BTreeSet` which can also insert and remove elements.
+///
+/// This `struct` is created by the [`lower_bound_mut`] and [`upper_bound_mut`] methods on
+/// [`BTreeSet`]. See their documentation for more.
+///
+/// [`BTreeSet`]: struct.BTreeSet.html
+/// [`lower_bound_mut`]: struct.BTreeSet.html#method.lower_bound_mut
+/// [`upper_bound_mut`]: struct.BTreeSet.html#method.upper_bound_mut
+#[derive(Debug)]
+#[unstable(feature = "btree_cursors", issue = "0")]
+pub struct CursorMut<'a, T: 'a> {
+    inner: ::btree_map::CursorMut<'a, T, ()>,
+}
+
 /// A lazy iterator producing elements in the difference of `BTreeSet`s.
 ///
 /// This `struct` is created by the [`difference`] method on [`BTreeSet`].
@@ -258,6 +287,103 @@ impl<T: Ord> BTreeSet<T> {
         Range { iter: self.map.range(range) }
     }
 
+    /// Returns a cursor pointing at the gap before the smallest element above the given
+    /// bound.
+    ///
+    /// Passing `Included(x)` puts the cursor right before the first element which is
+    /// greater than or equal to `x`, `Excluded(x)` right before the first element which is
+    /// greater than `x`, and `Unbounded` before the first element of the set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(btree_cursors)]
+    ///
+    /// use std::collections::BTreeSet;
+    /// use std::collections::Bound::Included;
+    ///
+    /// let set: BTreeSet<_> = [1, 3, 5, 7].iter().cloned().collect();
+    ///
+    /// let mut cursor = set.lower_bound(Included(&4));
+    /// assert_eq!(cursor.peek_prev(), Some(&3));
+    /// assert_eq!(cursor.next(), Some(&5));
+    /// assert_eq!(cursor.next(), Some(&7));
+    /// assert_eq!(cursor.next(), None);
+    /// ```
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn lower_bound<K: ?Sized>(&self, bound: Bound<&K>) -> Cursor<T>
+        where K: Ord, T: Borrow<K>
+    {
+        Cursor { inner: self.map.lower_bound(bound) }
+    }
+
+    /// Returns a cursor pointing at the gap after the greatest element below the given
+    /// bound.
+    ///
+    /// Passing `Included(x)` puts the cursor right after the last element which is less
+    /// than or equal to `x`, `Excluded(x)` right after the last element which is less than
+    /// `x`, and `Unbounded` after the last element of the set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(btree_cursors)]
+    ///
+    /// use std::collections::BTreeSet;
+    /// use std::collections::Bound::Excluded;
+    ///
+    /// let set: BTreeSet<_> = [1, 3, 5, 7].iter().cloned().collect();
+    ///
+    /// let mut cursor = set.upper_bound(Excluded(&5));
+    /// assert_eq!(cursor.peek_next(), Some(&5));
+    /// assert_eq!(cursor.prev(), Some(&3));
+    /// ```
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn upper_bound<K: ?Sized>(&self, bound: Bound<&K>) -> Cursor<T>
+        where K: Ord, T: Borrow<K>
+    {
+        Cursor { inner: self.map.upper_bound(bound) }
+    }
+
+    /// Returns a mutable cursor pointing at the gap before the smallest element above the
+    /// given bound. See [`lower_bound`] for how the bound is interpreted.
+    ///
+    /// [`lower_bound`]: #method.lower_bound
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(btree_cursors)]
+    ///
+    /// use std::collections::BTreeSet;
+    /// use std::collections::Bound::Included;
+    ///
+    /// let mut set: BTreeSet<_> = [1, 3, 5, 7].iter().cloned().collect();
+    ///
+    /// let mut cursor = set.lower_bound_mut(Included(&3));
+    /// assert_eq!(cursor.remove_next(), Some(3));
+    /// cursor.insert_after(4);
+    ///
+    /// assert_eq!(set.into_iter().collect::<Vec<_>>(), [1, 4, 5, 7]);
+    /// ```
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn lower_bound_mut<K: ?Sized>(&mut self, bound: Bound<&K>) -> CursorMut<T>
+        where K: Ord, T: Borrow<K>
+    {
+        CursorMut { inner: self.map.lower_bound_mut(bound) }
+    }
+
+    /// Returns a mutable cursor pointing at the gap after the greatest element below the
+    /// given bound. See [`upper_bound`] for how the bound is interpreted.
+    ///
+    /// [`upper_bound`]: #method.upper_bound
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn upper_bound_mut<K: ?Sized>(&mut self, bound: Bound<&K>) -> CursorMut<T>
+        where K: Ord, T: Borrow<K>
+    {
+        CursorMut { inner: self.map.upper_bound_mut(bound) }
+    }
+
     /// Visits the values representing the difference,
     /// i.e. the values that are in `self` but not in `other`,
     /// in ascending order.
@@ -1000,6 +1126,113 @@ impl<'a, T> DoubleEndedIterator for Range<'a, T> {
 #[unstable(feature = "fused", issue = "35602")]
 impl<'a, T> FusedIterator for Range<'a, T> {}
 
+#[unstable(feature = "btree_cursors", issue = "0")]
+impl<'a, T> Clone for Cursor<'a, T> {
+    fn clone(&self) -> Cursor<'a, T> {
+        Cursor { inner: self.inner.clone() }
+    }
+}
+
+impl<'a, T> Cursor<'a, T> {
+    /// Moves the cursor past the next element and returns it, or returns `None` if the
+    /// cursor is already after the last element.
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn next(&mut self) -> Option<&'a T> {
+        self.inner.next().map(|(k, _)| k)
+    }
+
+    /// Moves the cursor back past the previous element and returns it, or returns `None` if
+    /// the cursor is already before the first element.
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn prev(&mut self) -> Option<&'a T> {
+        self.inner.prev().map(|(k, _)| k)
+    }
+
+    /// Returns the element after the cursor without moving it.
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn peek_next(&self) -> Option<&'a T> {
+        self.inner.peek_next().map(|(k, _)| k)
+    }
+
+    /// Returns the element before the cursor without moving it.
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn peek_prev(&self) -> Option<&'a T> {
+        self.inner.peek_prev().map(|(k, _)| k)
+    }
+}
+
+impl<'a, T> CursorMut<'a, T> {
+    /// Moves the cursor past the next element and returns it, or returns `None` if the
+    /// cursor is already after the last element.
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn next(&mut self) -> Option<&T> {
+        self.inner.next().map(|(k, _)| k)
+    }
+
+    /// Moves the cursor back past the previous element and returns it, or returns `None` if
+    /// the cursor is already before the first element.
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn prev(&mut self) -> Option<&T> {
+        self.inner.prev().map(|(k, _)| k)
+    }
+
+    /// Returns the element after the cursor without moving it.
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn peek_next(&mut self) -> Option<&T> {
+        self.inner.peek_next().map(|(k, _)| k)
+    }
+
+    /// Returns the element before the cursor without moving it.
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn peek_prev(&mut self) -> Option<&T> {
+        self.inner.peek_prev().map(|(k, _)| k)
+    }
+
+    /// Returns a read-only cursor at the current position.
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn as_cursor(&self) -> Cursor<T> {
+        Cursor { inner: self.inner.as_cursor() }
+    }
+
+    /// Removes the element after the cursor and returns it, or returns `None` if the cursor
+    /// is already after the last element.
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn remove_next(&mut self) -> Option<T> {
+        self.inner.remove_next().map(|(k, _)| k)
+    }
+
+    /// Removes the element before the cursor and returns it, or returns `None` if the cursor
+    /// is already before the first element.
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn remove_prev(&mut self) -> Option<T> {
+        self.inner.remove_prev().map(|(k, _)| k)
+    }
+}
+
+impl<'a, T: Ord> CursorMut<'a, T> {
+    /// Inserts a new element into the gap the cursor points at, leaving the cursor before
+    /// it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `value` is not strictly between the elements around the cursor.
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn insert_after(&mut self, value: T) {
+        self.inner.insert_after(value, ())
+    }
+
+    /// Inserts a new element into the gap the cursor points at, leaving the cursor after
+    /// it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `value` is not strictly between the elements around the cursor.
+    #[unstable(feature = "btree_cursors", issue = "0")]
+    pub fn insert_before(&mut self, value: T) {
+        self.inner.insert_before(value, ())
+    }
+}
+
 /// Compare `x` and `y`, but return `short` if x is None and `long` if y is None
 fn cmp_opt<T: Ord>(x: Option<&T>, y: Option<&T>, short: Ordering, long: Ordering) -> Ordering {
     match (x, y) {
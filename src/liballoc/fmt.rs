@@ -527,6 +527,8 @@ pub use core::fmt::Error;
 pub use core::fmt::{ArgumentV1, Arguments, write};
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use core::fmt::{DebugList, DebugMap, DebugSet, DebugStruct, DebugTuple};
+#[unstable(feature = "fmt_num_buffer", issue = "0")]
+pub use core::fmt::{NumBuffer, FormatNum};
 
 use string;
 
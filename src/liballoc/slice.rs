@@ -1442,6 +1442,372 @@ impl<T> [T] {
         core_slice::SliceExt::sort_unstable_by_key(self, f);
     }
 
+    /// Reorders the slice such that the element at `index` is at its final sorted position.
+    ///
+    /// This reordering has the additional property that any value at position `i < index` will
+    /// be less than or equal to any value at a position `j > index`. Additionally, this reordering
+    /// is unstable (i.e. any number of equal elements may end up at position `index`), in-place
+    /// (i.e. does not allocate), and `O(n)` on average. The worst-case performance is
+    /// `O(n log n)`.
+    ///
+    /// It returns a triplet of the following values: all elements less than the one at the given
+    /// index, the value at the given index, and all elements greater than the one at the given
+    /// index.
+    ///
+    /// # Current implementation
+    ///
+    /// The current algorithm uses the partitioning of the [pattern-defeating quicksort][pdqsort]
+    /// used by [`sort_unstable`], but only continues into the side that contains `index`. It
+    /// falls back to heapsort if it keeps choosing bad pivots.
+    ///
+    /// # Panics
+    ///
+    /// Panics when `index >= len()`, meaning it always panics on empty slices.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(slice_select_nth_unstable)]
+    ///
+    /// let mut v = [-5i32, 4, 1, -3, 2];
+    ///
+    /// // Find the median
+    /// let (lesser, median, greater) = v.select_nth_unstable(2);
+    /// assert_eq!(*median, 1);
+    /// assert!(lesser.iter().all(|&x| x <= 1));
+    /// assert!(greater.iter().all(|&x| x >= 1));
+    /// ```
+    ///
+    /// [pdqsort]: https://github.com/orlp/pdqsort
+    /// [`sort_unstable`]: #method.sort_unstable
+    #[unstable(feature = "slice_select_nth_unstable", issue = "0")]
+    #[inline]
+    pub fn select_nth_unstable(&mut self, index: usize) -> (&mut [T], &mut T, &mut [T])
+        where T: Ord
+    {
+        core_slice::SliceExt::select_nth_unstable(self, index)
+    }
+
+    /// Reorders the slice with a comparator function such that the element at `index` is at its
+    /// final sorted position.
+    ///
+    /// See [`select_nth_unstable`] for the details; the only difference is that the elements are
+    /// ordered by `compare`.
+    ///
+    /// # Panics
+    ///
+    /// Panics when `index >= len()`, meaning it always panics on empty slices.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(slice_select_nth_unstable)]
+    ///
+    /// let mut v = [-5i32, 4, 1, -3, 2];
+    ///
+    /// // Find the second largest element
+    /// let (_, second, _) = v.select_nth_unstable_by(1, |a, b| b.cmp(a));
+    /// assert_eq!(*second, 2);
+    /// ```
+    ///
+    /// [`select_nth_unstable`]: #method.select_nth_unstable
+    #[unstable(feature = "slice_select_nth_unstable", issue = "0")]
+    #[inline]
+    pub fn select_nth_unstable_by<F>(&mut self, index: usize, compare: F)
+        -> (&mut [T], &mut T, &mut [T])
+        where F: FnMut(&T, &T) -> Ordering
+    {
+        core_slice::SliceExt::select_nth_unstable_by(self, index, compare)
+    }
+
+    /// Reorders the slice with a key extraction function such that the element at `index` is at
+    /// its final sorted position.
+    ///
+    /// See [`select_nth_unstable`] for the details; the only difference is that the elements are
+    /// ordered by their keys.
+    ///
+    /// # Panics
+    ///
+    /// Panics when `index >= len()`, meaning it always panics on empty slices.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(slice_select_nth_unstable)]
+    ///
+    /// let mut v = [-5i32, 4, 1, -3, 2];
+    ///
+    /// let (_, third, _) = v.select_nth_unstable_by_key(2, |k| k.abs());
+    /// assert_eq!(*third, -3);
+    /// ```
+    ///
+    /// [`select_nth_unstable`]: #method.select_nth_unstable
+    #[unstable(feature = "slice_select_nth_unstable", issue = "0")]
+    #[inline]
+    pub fn select_nth_unstable_by_key<B, F>(&mut self, index: usize, f: F)
+        -> (&mut [T], &mut T, &mut [T])
+        where F: FnMut(&T) -> B,
+              B: Ord
+    {
+        core_slice::SliceExt::select_nth_unstable_by_key(self, index, f)
+    }
+
+    /// Sorts the `k` smallest elements of the slice into its first `k` positions, leaving the
+    /// remaining elements in unspecified order.
+    ///
+    /// After the call `self[..k]` is sorted and every element in it is less than or equal to
+    /// every element of `self[k..]`. To get the `k` largest elements instead, sort in reverse
+    /// with [`partial_sort_unstable_by`].
+    ///
+    /// This sort is unstable (i.e. may reorder equal elements), in-place (i.e. does not allocate),
+    /// and `O(n + k log k)` on average. The worst-case performance is `O(n log n)`.
+    ///
+    /// # Current implementation
+    ///
+    /// The current algorithm moves the `k` smallest elements to the front with
+    /// [`select_nth_unstable`], and then sorts them with [`sort_unstable`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k > len()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(slice_partial_sort)]
+    ///
+    /// let mut v = [9, 2, 7, 4, 1, 8, 3];
+    /// v.partial_sort_unstable(3);
+    /// assert_eq!(v[..3], [1, 2, 3]);
+    /// ```
+    ///
+    /// [`partial_sort_unstable_by`]: #method.partial_sort_unstable_by
+    /// [`select_nth_unstable`]: #method.select_nth_unstable
+    /// [`sort_unstable`]: #method.sort_unstable
+    #[unstable(feature = "slice_partial_sort", issue = "0")]
+    #[inline]
+    pub fn partial_sort_unstable(&mut self, k: usize)
+        where T: Ord
+    {
+        core_slice::SliceExt::partial_sort_unstable(self, k);
+    }
+
+    /// Sorts the `k` smallest elements of the slice according to a comparator function into its
+    /// first `k` positions, leaving the remaining elements in unspecified order.
+    ///
+    /// See [`partial_sort_unstable`] for the details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k > len()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(slice_partial_sort)]
+    ///
+    /// let mut v = [9, 2, 7, 4, 1, 8, 3];
+    ///
+    /// // the three largest elements
+    /// v.partial_sort_unstable_by(3, |a, b| b.cmp(a));
+    /// assert_eq!(v[..3], [9, 8, 7]);
+    /// ```
+    ///
+    /// [`partial_sort_unstable`]: #method.partial_sort_unstable
+    #[unstable(feature = "slice_partial_sort", issue = "0")]
+    #[inline]
+    pub fn partial_sort_unstable_by<F>(&mut self, k: usize, compare: F)
+        where F: FnMut(&T, &T) -> Ordering
+    {
+        core_slice::SliceExt::partial_sort_unstable_by(self, k, compare);
+    }
+
+    /// Sorts the `k` elements of the slice with the smallest keys into its first `k` positions,
+    /// leaving the remaining elements in unspecified order.
+    ///
+    /// See [`partial_sort_unstable`] for the details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k > len()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(slice_partial_sort)]
+    ///
+    /// let mut v = [-5i32, 4, 1, -3, 2];
+    /// v.partial_sort_unstable_by_key(2, |k| k.abs());
+    /// assert_eq!(v[..2], [1, 2]);
+    /// ```
+    ///
+    /// [`partial_sort_unstable`]: #method.partial_sort_unstable
+    #[unstable(feature = "slice_partial_sort", issue = "0")]
+    #[inline]
+    pub fn partial_sort_unstable_by_key<B, F>(&mut self, k: usize, f: F)
+        where F: FnMut(&T) -> B,
+              B: Ord
+    {
+        core_slice::SliceExt::partial_sort_unstable_by_key(self, k, f);
+    }
+
+    /// Moves all consecutive repeated elements to the end of the slice according to the
+    /// [`PartialEq`] trait implementation.
+    ///
+    /// Returns two slices. The first contains no consecutive repeated elements.
+    /// The second contains all the duplicates in no specified order.
+    ///
+    /// If the slice is sorted, the first returned slice contains no duplicates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(slice_partition_dedup)]
+    ///
+    /// let mut slice = [1, 2, 2, 3, 3, 2, 1, 1];
+    ///
+    /// let (dedup, duplicates) = slice.partition_dedup();
+    ///
+    /// assert_eq!(dedup, [1, 2, 3, 2, 1]);
+    /// assert_eq!(duplicates, [2, 3, 1]);
+    /// ```
+    ///
+    /// [`PartialEq`]: ../cmp/trait.PartialEq.html
+    #[unstable(feature = "slice_partition_dedup", issue = "0")]
+    #[inline]
+    pub fn partition_dedup(&mut self) -> (&mut [T], &mut [T])
+        where T: PartialEq
+    {
+        core_slice::SliceExt::partition_dedup(self)
+    }
+
+    /// Moves all but the first of consecutive elements to the end of the slice satisfying
+    /// a given equality relation.
+    ///
+    /// Returns two slices. The first contains no consecutive repeated elements.
+    /// The second contains all the duplicates in no specified order.
+    ///
+    /// The `same_bucket` function is passed references to two elements from the slice and
+    /// must determine if the elements compare equal. The elements are passed in opposite order
+    /// from their order in the slice, so if `same_bucket(a, b)` returns `true`, `a` is moved
+    /// at the end of the slice.
+    ///
+    /// If the slice is sorted, the first returned slice contains no duplicates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(slice_partition_dedup)]
+    ///
+    /// let mut slice = ["foo", "Foo", "BAZ", "Bar", "bar", "baz", "BAZ"];
+    ///
+    /// let (dedup, duplicates) = slice.partition_dedup_by(|a, b| a.eq_ignore_ascii_case(b));
+    ///
+    /// assert_eq!(dedup, ["foo", "BAZ", "Bar", "baz"]);
+    /// assert_eq!(duplicates, ["bar", "Foo", "BAZ"]);
+    /// ```
+    #[unstable(feature = "slice_partition_dedup", issue = "0")]
+    #[inline]
+    pub fn partition_dedup_by<F>(&mut self, same_bucket: F) -> (&mut [T], &mut [T])
+        where F: FnMut(&mut T, &mut T) -> bool
+    {
+        core_slice::SliceExt::partition_dedup_by(self, same_bucket)
+    }
+
+    /// Moves all but the first of consecutive elements to the end of the slice that resolve
+    /// to the same key.
+    ///
+    /// Returns two slices. The first contains no consecutive repeated elements.
+    /// The second contains all the duplicates in no specified order.
+    ///
+    /// If the slice is sorted, the first returned slice contains no duplicates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(slice_partition_dedup)]
+    ///
+    /// let mut slice = [10, 20, 21, 30, 30, 20, 11, 13];
+    ///
+    /// let (dedup, duplicates) = slice.partition_dedup_by_key(|i| *i / 10);
+    ///
+    /// assert_eq!(dedup, [10, 20, 30, 20, 11]);
+    /// assert_eq!(duplicates, [21, 30, 13]);
+    /// ```
+    #[unstable(feature = "slice_partition_dedup", issue = "0")]
+    #[inline]
+    pub fn partition_dedup_by_key<K, F>(&mut self, key: F) -> (&mut [T], &mut [T])
+        where F: FnMut(&mut T) -> K,
+              K: PartialEq
+    {
+        core_slice::SliceExt::partition_dedup_by_key(self, key)
+    }
+
+    /// Merges this slice and `other`, which must both be sorted, into a new sorted vector.
+    ///
+    /// The merge is stable: equal elements keep their relative order, with the ones from `self`
+    /// coming before the ones from `other`. It takes `O(self.len() + other.len())` time.
+    ///
+    /// If either slice is not sorted, the result is unspecified (but it still contains all
+    /// the elements of both slices).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(slice_merge_sorted)]
+    ///
+    /// let a = [1, 3, 5, 7];
+    /// let b = [2, 3, 4, 8, 9];
+    /// assert_eq!(a.merge_sorted(&b), [1, 2, 3, 3, 4, 5, 7, 8, 9]);
+    /// ```
+    #[unstable(feature = "slice_merge_sorted", issue = "0")]
+    #[inline]
+    pub fn merge_sorted(&self, other: &[T]) -> Vec<T>
+        where T: Clone + Ord
+    {
+        self.merge_sorted_by(other, |a, b| a.cmp(b))
+    }
+
+    /// Merges this slice and `other`, which must both be sorted according to a comparator
+    /// function, into a new vector sorted the same way.
+    ///
+    /// See [`merge_sorted`] for the details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(slice_merge_sorted)]
+    ///
+    /// let a = [7, 5, 1];
+    /// let b = [8, 6, 5, 2];
+    /// assert_eq!(a.merge_sorted_by(&b, |x, y| y.cmp(x)), [8, 7, 6, 5, 5, 2, 1]);
+    /// ```
+    ///
+    /// [`merge_sorted`]: #method.merge_sorted
+    #[unstable(feature = "slice_merge_sorted", issue = "0")]
+    pub fn merge_sorted_by<F>(&self, other: &[T], mut compare: F) -> Vec<T>
+        where T: Clone,
+              F: FnMut(&T, &T) -> Ordering
+    {
+        let len = self.len().checked_add(other.len()).expect("capacity overflow");
+        let mut merged = Vec::with_capacity(len);
+        let mut left = self;
+        let mut right = other;
+        while let (Some(l), Some(r)) = (left.first(), right.first()) {
+            // Taking from `self` unless `other` is strictly less keeps the merge stable.
+            if compare(r, l) == Less {
+                merged.push(r.clone());
+                right = &right[1..];
+            } else {
+                merged.push(l.clone());
+                left = &left[1..];
+            }
+        }
+        merged.extend_from_slice(left);
+        merged.extend_from_slice(right);
+        merged
+    }
+
     /// Rotates the slice in-place such that the first `mid` elements of the
     /// slice move to the end while the last `self.len() - mid` elements move to
     /// the front. After calling `rotate_left`, the element previously at index
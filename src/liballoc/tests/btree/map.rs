@@ -332,6 +332,108 @@ fn test_range_mut() {
     }
 }
 
+#[test]
+fn test_cursor() {
+    let size = 300;
+    let map: BTreeMap<_, _> = (0..size).map(|i| (i * 2, i)).collect();
+
+    for k in 0..size * 2 + 1 {
+        let below = if k % 2 == 0 { k / 2 } else { k / 2 + 1 };
+        let next = if below < size { Some(below * 2) } else { None };
+        let prev = if below > 0 { Some(below * 2 - 2) } else { None };
+        let mut cur = map.lower_bound(Included(&k));
+        assert_eq!(cur.peek_next().map(|(&k, _)| k), next);
+        assert_eq!(cur.peek_prev().map(|(&k, _)| k), prev);
+
+        let kvs: Vec<_> = (below..size).map(|i| (i * 2, i)).collect();
+        let mut rest = vec![];
+        while let Some((&k, &v)) = cur.next() {
+            rest.push((k, v));
+        }
+        assert_eq!(rest, kvs);
+        assert_eq!(cur.prev(), map.iter().next_back());
+
+        let cur = map.upper_bound(Excluded(&k));
+        assert_eq!(cur.peek_next().map(|(&k, _)| k), next);
+    }
+
+    let cur = map.lower_bound(Unbounded);
+    assert_eq!(cur.peek_prev(), None);
+    assert_eq!(cur.peek_next(), Some((&0, &0)));
+    let cur = map.upper_bound(Unbounded);
+    assert_eq!(cur.peek_next(), None);
+    assert_eq!(cur.peek_prev(), Some((&(size * 2 - 2), &(size - 1))));
+}
+
+#[test]
+fn test_cursor_mut() {
+    let size = 300;
+    let mut map: BTreeMap<_, _> = (0..size).map(|i| (i * 2, i)).collect();
+
+    // Fill in the odd keys, walking forwards from the front.
+    {
+        let mut cur = map.lower_bound_mut(Unbounded);
+        loop {
+            let k = match cur.next() {
+                Some((&k, v)) => {
+                    *v *= 10;
+                    k
+                }
+                None => break,
+            };
+            if k + 2 < size * 2 {
+                cur.insert_after(k + 1, 0);
+                assert_eq!(cur.next().map(|(&k, _)| k), Some(k + 1));
+            }
+        }
+        assert_eq!(cur.peek_next(), None);
+        cur.insert_before(size * 2 - 1, 0);
+        assert_eq!(cur.peek_prev().map(|(&k, _)| k), Some(size * 2 - 1));
+    }
+    assert_eq!(map.len(), size as usize * 2);
+    assert!(map.keys().cloned().eq(0..size * 2));
+    assert!(map.iter().filter(|&(k, _)| k % 2 == 0).all(|(&k, &v)| v == k * 5));
+
+    // Remove them again, walking backwards from the middle.
+    {
+        let mut cur = map.upper_bound_mut(Excluded(&size));
+        assert_eq!(cur.peek_prev().map(|(&k, _)| k), Some(size - 1));
+        loop {
+            match cur.remove_prev() {
+                Some((k, _)) if k % 2 == 1 => {}
+                Some((k, v)) => cur.insert_before(k, v),
+                None => break,
+            }
+            if cur.prev().is_none() {
+                break;
+            }
+        }
+        assert_eq!(cur.peek_prev(), None);
+    }
+    assert_eq!(map.len(), size as usize * 3 / 2);
+
+    // And the rest, walking forwards from the middle.
+    {
+        let mut cur = map.lower_bound_mut(Included(&size));
+        while let Some((k, v)) = cur.remove_next() {
+            if k % 2 == 0 {
+                cur.insert_before(k, v);
+            }
+        }
+        assert_eq!(cur.peek_prev().map(|(&k, _)| k), Some(size * 2 - 2));
+    }
+    assert_eq!(map.len(), size as usize);
+    assert!(map.into_iter().eq((0..size).map(|i| (i * 2, i * 10))));
+}
+
+#[test]
+#[should_panic]
+fn test_cursor_insert_out_of_order() {
+    let mut map: BTreeMap<_, _> = (0..10).map(|i| (i * 2, ())).collect();
+    let mut cur = map.lower_bound_mut(Included(&4));
+    cur.insert_after(6, ());
+}
+
 #[test]
 fn test_borrow() {
     // make sure these compile -- using the Borrow trait
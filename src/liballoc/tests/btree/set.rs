@@ -9,6 +9,7 @@
 // except according to those terms.
 
 use std::collections::BTreeSet;
+use std::collections::Bound::{Excluded, Included};
 
 use std::iter::FromIterator;
 use super::DeterministicRng;
@@ -329,3 +330,31 @@ fn test_split_off_large_random_sorted() {
     assert!(set.into_iter().eq(data.clone().into_iter().filter(|x| *x < key)));
     assert!(right.into_iter().eq(data.into_iter().filter(|x| *x >= key)));
 }
+
+#[test]
+fn test_cursor() {
+    let mut set: BTreeSet<_> = (0..100).map(|i| i * 3).collect();
+
+    {
+        let mut cur = set.lower_bound(Included(&10));
+        assert_eq!(cur.peek_prev(), Some(&9));
+        assert_eq!(cur.next(), Some(&12));
+        assert_eq!(cur.prev(), Some(&12));
+        assert_eq!(cur.prev(), Some(&9));
+        let cur = set.upper_bound(Included(&12));
+        assert_eq!(cur.peek_prev(), Some(&12));
+        assert_eq!(cur.peek_next(), Some(&15));
+    }
+
+    {
+        let mut cur = set.lower_bound_mut(Excluded(&9));
+        assert_eq!(cur.remove_prev(), Some(9));
+        cur.insert_before(10);
+        cur.insert_after(11);
+        assert_eq!(cur.next(), Some(&11));
+        assert_eq!(cur.remove_next(), Some(12));
+        assert_eq!(cur.as_cursor().peek_next(), Some(&15));
+    }
+    assert_eq!(set.len(), 100);
+    assert!(set.range(6..16).eq(&[6, 10, 11, 15]));
+}
@@ -9,7 +9,9 @@
 // except according to those terms.
 
 use alloc_system::System;
-use std::heap::{Heap, Alloc, Layout};
+use std::cell::Cell;
+use std::heap::{Heap, Alloc, AllocErr, Layout};
+use std::rc::Rc;
 
 /// https://github.com/rust-lang/rust/issues/45955
 ///
@@ -43,3 +45,34 @@ fn check_overalign_requests<T: Alloc>(mut allocator: T) {
         }
     }
 }
+
+// An allocator that forwards to `Heap` and keeps track of how many bytes
+// it currently has handed out.
+#[derive(Clone)]
+pub struct CountingAlloc(pub Rc<Cell<usize>>);
+
+unsafe impl Alloc for CountingAlloc {
+    unsafe fn alloc(&mut self, layout: Layout) -> Result<*mut u8, AllocErr> {
+        let ptr = Heap.alloc(layout.clone())?;
+        self.0.set(self.0.get() + layout.size());
+        Ok(ptr)
+    }
+
+    unsafe fn dealloc(&mut self, ptr: *mut u8, layout: Layout) {
+        self.0.set(self.0.get() - layout.size());
+        Heap.dealloc(ptr, layout)
+    }
+}
+
+// An allocator that refuses every request.
+pub struct NoAlloc;
+
+unsafe impl Alloc for NoAlloc {
+    unsafe fn alloc(&mut self, _layout: Layout) -> Result<*mut u8, AllocErr> {
+        Err(AllocErr::Unsupported { details: "no allocations" })
+    }
+
+    unsafe fn dealloc(&mut self, _ptr: *mut u8, _layout: Layout) {
+        unreachable!()
+    }
+}
@@ -493,6 +493,34 @@ fn test_sort_stability() {
     }
 }
 
+#[test]
+fn test_merge_sorted() {
+    let mut rng = thread_rng();
+    for len1 in (0..20).chain(vec![100, 1000]) {
+        for len2 in (0..20).chain(vec![100, 1000]) {
+            let mut a: Vec<i32> = rng.gen_iter::<i32>().map(|x| x % 50).take(len1).collect();
+            let mut b: Vec<i32> = rng.gen_iter::<i32>().map(|x| x % 50).take(len2).collect();
+            a.sort();
+            b.sort();
+            let mut expected = a.clone();
+            expected.extend_from_slice(&b);
+            expected.sort();
+            assert_eq!(a.merge_sorted(&b), expected);
+
+            a.reverse();
+            b.reverse();
+            expected.reverse();
+            assert_eq!(a.merge_sorted_by(&b, |x, y| y.cmp(x)), expected);
+        }
+    }
+
+    // equal elements from the first slice come first
+    let a = [(1, 'a'), (2, 'a'), (2, 'b')];
+    let b = [(0, 'c'), (2, 'c'), (3, 'c')];
+    let merged = a.merge_sorted_by(&b, |x, y| x.0.cmp(&y.0));
+    assert_eq!(merged, [(0, 'c'), (1, 'a'), (2, 'a'), (2, 'b'), (2, 'c'), (3, 'c')]);
+}
+
 #[test]
 fn test_rotate_left() {
     let expected: Vec<_> = (0..13).collect();
@@ -1095,6 +1095,79 @@ fn test_empty_match_indices() {
     assert_eq!(vec, [(0, ""), (1, ""), (3, ""), (6, ""), (7, "")]);
 }
 
+#[test]
+fn test_str_slice_pattern() {
+    let data = "GET /a 200\nPOST /b 404\nPUT /c 200\n";
+    let methods = &["GET", "POST", "PUT"][..];
+    assert_eq!(data.find(methods), Some(0));
+    assert_eq!(data.rfind(methods), Some(23));
+    assert_eq!(data.matches(methods).collect::<Vec<_>>(), ["GET", "POST", "PUT"]);
+    assert_eq!(data.rmatch_indices(&["200", "404"][..]).collect::<Vec<_>>(),
+               [(30, "200"), (19, "404"), (7, "200")]);
+    assert_eq!(data.split(&["\n", " "][..]).collect::<Vec<_>>(),
+               ["GET", "/a", "200", "POST", "/b", "404", "PUT", "/c", "200", ""]);
+    assert_eq!(data.replace(&["GET", "PUT"][..], "X"), "X /a 200\nPOST /b 404\nX /c 200\n");
+    assert!(data.starts_with(methods));
+    assert!(!data.contains(&["DELETE", "PATCH"][..]));
+    let none: &[&str] = &[];
+    assert!(!data.contains(none));
+
+    // the first string in the slice wins where several match
+    assert_eq!("abc".matches(&["a", "ab"][..]).collect::<Vec<_>>(), ["a"]);
+    assert_eq!("abc".matches(&["ab", "a"][..]).collect::<Vec<_>>(), ["ab"]);
+
+    // many distinct first bytes, and non-ASCII ones
+    let words = &["ünï", "cödé", "x", "yz", "wv"][..];
+    assert_eq!("aünïcödéyzwvx".match_indices(words).collect::<Vec<_>>(),
+               [(1, "ünï"), (6, "cödé"), (12, "yz"), (14, "wv"), (16, "x")]);
+}
+
+#[test]
+fn test_str_slice_pattern_many_needles() {
+    // More needles than the vectorized filter has buckets, over a haystack
+    // long enough to be searched sixteen bytes at a time, with a tail left.
+    let needles = &["error", "warn", "fatal", "panic", "abort", "oom", "segv", "kill",
+                    "timeout", "ünicode", "ab", "eör"][..];
+    let line = "info: ok; warn: slow disk; errno=5 ab; ünicode ok; error: oom killed\n";
+    let log = line.repeat(5) + "panic";
+
+    let mut expected = Vec::new();
+    let mut i = 0;
+    while i < log.len() {
+        match needles.iter().find(|n| log.as_bytes()[i..].starts_with(n.as_bytes())) {
+            Some(n) => {
+                expected.push((i, *n));
+                i += n.len();
+            }
+            None => i += 1,
+        }
+    }
+    assert_eq!(log.match_indices(needles).collect::<Vec<_>>(), expected);
+    assert_eq!(log.find(needles), Some(expected[0].0));
+    assert_eq!(log.rfind(needles), Some(log.len() - "panic".len()));
+    // No two matches overlap here, so searching backwards finds the same.
+    expected.reverse();
+    assert_eq!(log.rmatch_indices(needles).collect::<Vec<_>>(), expected);
+    assert!(!log.contains(&["errors", "ünicodes", "kills"][..]));
+}
+
+#[test]
+fn test_find_after_prefilter_gives_up() {
+    // "." is the byte of "a.b" searched for first, and it is so frequent
+    // here that the prefilter turns itself off early on.
+    let dots = ".x".repeat(1000);
+    assert_eq!((dots.clone() + "a.b").find("a.b"), Some(2000));
+    assert_eq!(("a.b".to_string() + &dots).rfind("a.b"), Some(0));
+
+    let haystack = (dots.clone() + "a.b").repeat(3);
+    assert_eq!(haystack.match_indices("a.b").map(|(i, _)| i).collect::<Vec<_>>(),
+               [2000, 4003, 6006]);
+    assert_eq!(haystack.rmatch_indices("a.b").map(|(i, _)| i).collect::<Vec<_>>(),
+               [6006, 4003, 2000]);
+    assert_eq!(dots.find("a.b"), None);
+    assert_eq!(dots.rfind("a.b"), None);
+}
+
 #[test]
 fn test_bool_from_str() {
     assert_eq!("true".parse().ok(), Some(true));
@@ -1446,6 +1519,29 @@ mod pattern {
     ]);
     make_test!(str_searcher_nonempty_needle_empty_haystack, "├", "", [
     ]);
+    make_test!(str_slice_searcher_ascii_haystack, &["bb", "c"][..], "abbcbbd", [
+        Reject(0, 1),
+        Match (1, 3),
+        Match (3, 4),
+        Match (4, 6),
+        Reject(6, 7),
+    ]);
+    make_test!(str_slice_searcher_multibyte_haystack, &["─", "├"][..], "├──x", [
+        Match (0, 3),
+        Match (3, 6),
+        Match (6, 9),
+        Reject(9, 10),
+    ]);
+    make_test!(str_slice_searcher_empty_needle, &["", "b"][..], "aé", [
+        Match (0, 0),
+        Reject(0, 1),
+        Match (1, 1),
+        Reject(1, 3),
+        Match (3, 3),
+    ]);
+    make_test!(str_slice_searcher_no_needles, &[""; 0] as &[&str], "ab", [
+        Reject(0, 2),
+    ]);
     make_test!(char_searcher_ascii_haystack, 'b', "abbcbbd", [
         Reject(0, 1),
         Match (1, 2),
@@ -504,3 +504,19 @@ fn test_into_boxed_str() {
     let ys = xs.into_boxed_str();
     assert_eq!(&*ys, "hello my name is bob");
 }
+
+#[test]
+fn test_try_reserve() {
+    use std::heap::CollectionAllocErr::*;
+
+    let mut s = String::new();
+    assert_eq!(s.try_reserve(10), Ok(()));
+    assert!(s.capacity() >= 10);
+
+    s.push_str("hello");
+    assert_eq!(s.try_reserve(usize::max_value()), Err(CapacityOverflow));
+    assert_eq!(s.try_reserve_exact(usize::max_value() - 4), Err(CapacityOverflow));
+    assert_eq!(s.try_reserve_exact(20), Ok(()));
+    assert!(s.capacity() >= 25);
+    assert_eq!(s, "hello");
+}
@@ -9,10 +9,14 @@
 // except according to those terms.
 
 use std::borrow::Cow;
+use std::cell::Cell;
 use std::mem::size_of;
 use std::panic;
+use std::rc::Rc;
 use std::vec::{Drain, IntoIter};
 
+use heap::{CountingAlloc, NoAlloc};
+
 struct DropCounter<'a> {
     count: &'a mut u32,
 }
@@ -965,3 +969,64 @@ fn drain_filter_complex() {
         assert_eq!(vec, vec![1, 3, 5, 7, 9, 11, 13, 15, 17, 19]);
     }
 }
+
+#[test]
+fn test_alloc_param() {
+    let live = Rc::new(Cell::new(0));
+    {
+        let mut v = Vec::with_capacity_in(4, CountingAlloc(live.clone()));
+        assert_eq!(live.get(), 4 * size_of::<u32>());
+
+        v.extend(0..100u32);
+        assert!(live.get() >= 100 * size_of::<u32>());
+        assert_eq!(v.capacity() * size_of::<u32>(), live.get());
+
+        let w = v.split_off(50);
+        assert_eq!(w[0], 50);
+        assert_eq!(w.clone(), w);
+
+        assert_eq!(v.drain(..10).sum::<u32>(), 45);
+        assert_eq!(v.len(), 40);
+        assert_eq!(v, (10..50).collect::<Vec<_>>());
+
+        let mut iter = w.into_iter();
+        assert_eq!(iter.next(), Some(50));
+        assert_eq!(iter.clone().sum::<u32>(), (51..100).sum());
+    }
+    assert_eq!(live.get(), 0);
+
+    let mut v: Vec<String, _> = Vec::new_in(CountingAlloc(live.clone()));
+    assert_eq!(live.get(), 0);
+    v.push("a".to_string());
+    assert!(live.get() > 0);
+    drop(v);
+    assert_eq!(live.get(), 0);
+}
+
+#[test]
+fn test_try_reserve() {
+    use std::heap::CollectionAllocErr::*;
+
+    let mut v: Vec<u32> = Vec::new();
+    assert_eq!(v.try_reserve(usize::max_value()), Err(CapacityOverflow));
+    assert_eq!(v.try_reserve_exact(usize::max_value() / 2), Err(CapacityOverflow));
+    assert_eq!(v.capacity(), 0);
+
+    assert_eq!(v.try_reserve(10), Ok(()));
+    assert!(v.capacity() >= 10);
+    v.extend(0..10);
+    assert_eq!(v.try_reserve(usize::max_value() - 5), Err(CapacityOverflow));
+    assert_eq!(v.try_reserve_exact(usize::max_value() - 5), Err(CapacityOverflow));
+    assert_eq!(v, (0..10).collect::<Vec<_>>());
+
+    let mut v: Vec<u8, _> = Vec::new_in(NoAlloc);
+    match v.try_reserve(10) {
+        Err(AllocErr(_)) => {}
+        r => panic!("expected an allocation failure, got {:?}", r),
+    }
+    match v.try_reserve_exact(10) {
+        Err(AllocErr(_)) => {}
+        r => panic!("expected an allocation failure, got {:?}", r),
+    }
+    assert_eq!(v.capacity(), 0);
+}
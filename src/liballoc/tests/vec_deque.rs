@@ -8,9 +8,14 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use std::cell::Cell;
 use std::collections::VecDeque;
 use std::fmt::Debug;
+use std::mem::size_of;
 use std::collections::vec_deque::{Drain};
+use std::rc::Rc;
+
+use heap::CountingAlloc;
 
 use self::Taggy::*;
 use self::Taggypar::*;
@@ -253,6 +258,24 @@ fn test_reserve() {
     assert!(d.capacity() >= 51);
 }
 
+#[test]
+fn test_try_reserve() {
+    use std::heap::CollectionAllocErr::*;
+
+    let mut d = VecDeque::new();
+    d.push_back(0);
+    assert_eq!(d.try_reserve(50), Ok(()));
+    assert!(d.capacity() >= 51);
+    assert_eq!(d.try_reserve_exact(100), Ok(()));
+    assert!(d.capacity() >= 101);
+
+    let cap = d.capacity();
+    assert_eq!(d.try_reserve(usize::max_value()), Err(CapacityOverflow));
+    assert_eq!(d.try_reserve_exact(usize::max_value() / 2 + 1), Err(CapacityOverflow));
+    assert_eq!(d.capacity(), cap);
+    assert_eq!(d, [0]);
+}
+
 #[test]
 fn test_swap() {
     let mut d: VecDeque<_> = (0..5).collect();
@@ -1022,3 +1045,30 @@ fn test_placement_in() {
     }
     assert_eq!(buf, [5,4,3,1,2,6]);
 }
+
+#[test]
+fn test_alloc_param() {
+    let live = Rc::new(Cell::new(0));
+    {
+        let mut d = VecDeque::with_capacity_in(4, CountingAlloc(live.clone()));
+        assert!(live.get() > 0);
+        for i in 0..50 {
+            d.push_back(i);
+            d.push_front(-i);
+        }
+        assert_eq!(d.len(), 100);
+        assert_eq!(live.get(), (d.capacity() + 1) * size_of::<i32>());
+
+        let e = d.split_off(50);
+        assert_eq!(e.front(), Some(&0));
+        assert_eq!(e.clone(), e);
+
+        assert_eq!(d.drain(..10).count(), 10);
+        assert_eq!(d, (-39..1).collect::<Vec<_>>());
+
+        let mut iter = e.into_iter();
+        assert_eq!(iter.next_back(), Some(49));
+        assert_eq!(iter.sum::<i32>(), (0..49).sum());
+    }
+    assert_eq!(live.get(), 0);
+}
@@ -0,0 +1,470 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A global allocator which counts allocations, for profiling.
+//!
+//! `Counting` forwards every request to the system allocator and keeps
+//! track of the number of allocations, deallocations and reallocations as
+//! well as the bytes moved by them, both in total and per call site.
+//!
+//! A call site is a `static` [`Site`] which is entered for the duration of
+//! a scope; while it is entered on a thread, all the requests made by that
+//! thread are attributed to it as well as to the totals. Sites nest, the
+//! innermost one wins.
+//!
+//! A site counts the operations performed inside its scope, not the blocks
+//! it owns: a deallocation or reallocation is charged to the site which is
+//! current when it happens, whichever site allocated the block. Memory
+//! allocated under one site and freed under another therefore shows up as
+//! live in the first and as freed in the second.
+//!
+//! Sites rely on native thread local storage. On targets without it
+//! entering a site does nothing and only the totals are kept.
+//!
+//! ```
+//! #![feature(global_allocator, alloc_counting)]
+//!
+//! extern crate alloc_counting;
+//!
+//! use alloc_counting::{Counting, Site};
+//!
+//! #[global_allocator]
+//! static ALLOC: Counting = Counting;
+//!
+//! static PARSER: Site = Site::new("parser");
+//!
+//! fn main() {
+//!     {
+//!         let _site = PARSER.enter();
+//!         let v = vec![0u8; 100];
+//!         drop(v);
+//!     }
+//!     assert_eq!(PARSER.stats().bytes_allocated, 100);
+//!     for site in alloc_counting::sites() {
+//!         println!("{}: {:?}", site.name(), site.stats());
+//!     }
+//! }
+//! ```
+//!
+//! [`Site`]: struct.Site.html
+
+#![no_std]
+#![deny(warnings)]
+#![unstable(feature = "alloc_counting",
+            reason = "this library is unlikely to be stabilized in its current \
+                      form or name",
+            issue = "0")]
+#![feature(alloc)]
+#![feature(alloc_system)]
+#![feature(allocator_api)]
+#![feature(cfg_target_thread_local)]
+#![feature(const_fn)]
+#![feature(staged_api)]
+#![cfg_attr(target_thread_local, feature(thread_local))]
+
+extern crate alloc;
+extern crate alloc_system;
+
+use core::ptr;
+use core::sync::atomic::{AtomicBool, AtomicPtr, AtomicUsize, Ordering};
+
+use alloc::heap::{Alloc, AllocErr, Layout, CannotReallocInPlace};
+use alloc_system::System;
+
+/// The counting allocator.
+///
+/// It is a thin wrapper around [`System`] which records every request in
+/// the totals and in the [`Site`] currently entered on the calling thread.
+///
+/// [`System`]: ../alloc_system/struct.System.html
+/// [`Site`]: struct.Site.html
+pub struct Counting;
+
+impl Counting {
+    /// Returns the counts of all the requests made so far.
+    pub fn stats(&self) -> Stats {
+        TOTAL.get()
+    }
+
+    /// Sets the totals and the counts of every registered site back to zero.
+    ///
+    /// The counters are not reset atomically with respect to each other, so
+    /// requests made concurrently by other threads may be partially lost.
+    pub fn reset(&self) {
+        TOTAL.reset();
+        for site in sites() {
+            site.counters.reset();
+        }
+    }
+}
+
+/// A snapshot of the counts kept by `Counting`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Stats {
+    /// The number of allocations, including zeroed ones.
+    pub allocations: usize,
+    /// The number of deallocations.
+    pub deallocations: usize,
+    /// The number of reallocations, including ones done in place.
+    pub reallocations: usize,
+    /// The bytes handed out by allocations and reallocations.
+    pub bytes_allocated: usize,
+    /// The bytes given back by deallocations and reallocations.
+    pub bytes_deallocated: usize,
+}
+
+impl Stats {
+    /// Returns the number of bytes allocated but not yet deallocated.
+    ///
+    /// For a site this is negative when it freed more memory than it
+    /// allocated, which happens when the memory was allocated elsewhere.
+    pub fn live_bytes(&self) -> isize {
+        self.bytes_allocated.wrapping_sub(self.bytes_deallocated) as isize
+    }
+}
+
+struct Counters {
+    allocations: AtomicUsize,
+    deallocations: AtomicUsize,
+    reallocations: AtomicUsize,
+    bytes_allocated: AtomicUsize,
+    bytes_deallocated: AtomicUsize,
+}
+
+impl Counters {
+    const fn new() -> Counters {
+        Counters {
+            allocations: AtomicUsize::new(0),
+            deallocations: AtomicUsize::new(0),
+            reallocations: AtomicUsize::new(0),
+            bytes_allocated: AtomicUsize::new(0),
+            bytes_deallocated: AtomicUsize::new(0),
+        }
+    }
+
+    fn alloc(&self, size: usize) {
+        self.allocations.fetch_add(1, Ordering::Relaxed);
+        self.bytes_allocated.fetch_add(size, Ordering::Relaxed);
+    }
+
+    fn dealloc(&self, size: usize) {
+        self.deallocations.fetch_add(1, Ordering::Relaxed);
+        self.bytes_deallocated.fetch_add(size, Ordering::Relaxed);
+    }
+
+    fn realloc(&self, old_size: usize, new_size: usize) {
+        self.reallocations.fetch_add(1, Ordering::Relaxed);
+        self.bytes_deallocated.fetch_add(old_size, Ordering::Relaxed);
+        self.bytes_allocated.fetch_add(new_size, Ordering::Relaxed);
+    }
+
+    fn get(&self) -> Stats {
+        Stats {
+            allocations: self.allocations.load(Ordering::Relaxed),
+            deallocations: self.deallocations.load(Ordering::Relaxed),
+            reallocations: self.reallocations.load(Ordering::Relaxed),
+            bytes_allocated: self.bytes_allocated.load(Ordering::Relaxed),
+            bytes_deallocated: self.bytes_deallocated.load(Ordering::Relaxed),
+        }
+    }
+
+    fn reset(&self) {
+        self.allocations.store(0, Ordering::Relaxed);
+        self.deallocations.store(0, Ordering::Relaxed);
+        self.reallocations.store(0, Ordering::Relaxed);
+        self.bytes_allocated.store(0, Ordering::Relaxed);
+        self.bytes_deallocated.store(0, Ordering::Relaxed);
+    }
+}
+
+static TOTAL: Counters = Counters::new();
+
+/// Applies `f` to the totals and to the counters of the current site.
+fn record<F: Fn(&Counters)>(f: F) {
+    f(&TOTAL);
+    let site = current::get();
+    if !site.is_null() {
+        unsafe { f(&(*site).counters) }
+    }
+}
+
+/// A call site whose requests are counted separately.
+///
+/// Sites are meant to be `static`s. A site is registered, and shows up in
+/// [`sites`], the first time it is entered.
+///
+/// Deallocations are attributed to the site which is current when they
+/// happen, which is not necessarily the one that made the allocation.
+///
+/// On targets without native thread local storage entering a site has no
+/// effect, and only the totals are kept.
+///
+/// [`sites`]: fn.sites.html
+pub struct Site {
+    name: &'static str,
+    counters: Counters,
+    registered: AtomicBool,
+    next: AtomicPtr<Site>,
+}
+
+impl Site {
+    /// Creates a new site with the given name.
+    pub const fn new(name: &'static str) -> Site {
+        Site {
+            name,
+            counters: Counters::new(),
+            registered: AtomicBool::new(false),
+            next: AtomicPtr::new(ptr::null_mut()),
+        }
+    }
+
+    /// Returns the name this site was created with.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns the counts of the requests attributed to this site so far.
+    pub fn stats(&self) -> Stats {
+        self.counters.get()
+    }
+
+    /// Makes this site the current one on this thread until the returned
+    /// guard is dropped.
+    ///
+    /// Every allocation, deallocation and reallocation made by this thread
+    /// in the meantime is charged to this site, regardless of which site
+    /// allocated the memory involved.
+    ///
+    /// On targets without native thread local storage this is a no-op: the
+    /// site is registered but no requests are ever attributed to it.
+    pub fn enter(&'static self) -> SiteGuard {
+        self.register();
+        let prev = current::get();
+        current::set(self);
+        SiteGuard { prev }
+    }
+
+    fn register(&'static self) {
+        if self.registered.swap(true, Ordering::AcqRel) {
+            return;
+        }
+        let this = self as *const Site as *mut Site;
+        let mut head = SITES.load(Ordering::Acquire);
+        loop {
+            self.next.store(head, Ordering::Relaxed);
+            match SITES.compare_exchange_weak(head, this, Ordering::AcqRel, Ordering::Acquire) {
+                Ok(_) => return,
+                Err(actual) => head = actual,
+            }
+        }
+    }
+}
+
+/// A guard which makes the previously current site current again when
+/// dropped, returned by [`Site::enter`].
+///
+/// [`Site::enter`]: struct.Site.html#method.enter
+#[must_use]
+pub struct SiteGuard {
+    prev: *const Site,
+}
+
+impl Drop for SiteGuard {
+    fn drop(&mut self) {
+        current::set(self.prev);
+    }
+}
+
+// The registered sites, as an intrusive list threaded through `Site::next`.
+static SITES: AtomicPtr<Site> = AtomicPtr::new(ptr::null_mut());
+
+/// Returns an iterator over the sites entered so far, most recently
+/// registered first.
+pub fn sites() -> Sites {
+    Sites { next: SITES.load(Ordering::Acquire) }
+}
+
+/// An iterator over the registered sites, created by [`sites`].
+///
+/// [`sites`]: fn.sites.html
+pub struct Sites {
+    next: *const Site,
+}
+
+impl Iterator for Sites {
+    type Item = &'static Site;
+
+    fn next(&mut self) -> Option<&'static Site> {
+        if self.next.is_null() {
+            return None;
+        }
+        let site = unsafe { &*self.next };
+        self.next = site.next.load(Ordering::Acquire);
+        Some(site)
+    }
+}
+
+#[cfg(target_thread_local)]
+mod current {
+    use core::cell::Cell;
+    use core::ptr;
+
+    use Site;
+
+    #[thread_local]
+    static CURRENT: Cell<*const Site> = Cell::new(ptr::null());
+
+    pub fn get() -> *const Site {
+        CURRENT.get()
+    }
+
+    pub fn set(site: *const Site) {
+        CURRENT.set(site)
+    }
+}
+
+#[cfg(not(target_thread_local))]
+mod current {
+    use core::ptr;
+
+    use Site;
+
+    pub fn get() -> *const Site {
+        ptr::null()
+    }
+
+    pub fn set(_site: *const Site) {}
+}
+
+unsafe impl Alloc for Counting {
+    #[inline]
+    unsafe fn alloc(&mut self, layout: Layout) -> Result<*mut u8, AllocErr> {
+        (&*self).alloc(layout)
+    }
+
+    #[inline]
+    unsafe fn alloc_zeroed(&mut self, layout: Layout) -> Result<*mut u8, AllocErr> {
+        (&*self).alloc_zeroed(layout)
+    }
+
+    #[inline]
+    unsafe fn dealloc(&mut self, ptr: *mut u8, layout: Layout) {
+        (&*self).dealloc(ptr, layout)
+    }
+
+    #[inline]
+    unsafe fn realloc(&mut self,
+                      ptr: *mut u8,
+                      old_layout: Layout,
+                      new_layout: Layout) -> Result<*mut u8, AllocErr> {
+        (&*self).realloc(ptr, old_layout, new_layout)
+    }
+
+    fn oom(&mut self, err: AllocErr) -> ! {
+        (&*self).oom(err)
+    }
+
+    #[inline]
+    fn usable_size(&self, layout: &Layout) -> (usize, usize) {
+        (&self).usable_size(layout)
+    }
+
+    #[inline]
+    unsafe fn grow_in_place(&mut self,
+                            ptr: *mut u8,
+                            layout: Layout,
+                            new_layout: Layout) -> Result<(), CannotReallocInPlace> {
+        (&*self).grow_in_place(ptr, layout, new_layout)
+    }
+
+    #[inline]
+    unsafe fn shrink_in_place(&mut self,
+                              ptr: *mut u8,
+                              layout: Layout,
+                              new_layout: Layout) -> Result<(), CannotReallocInPlace> {
+        (&*self).shrink_in_place(ptr, layout, new_layout)
+    }
+}
+
+unsafe impl<'a> Alloc for &'a Counting {
+    #[inline]
+    unsafe fn alloc(&mut self, layout: Layout) -> Result<*mut u8, AllocErr> {
+        let size = layout.size();
+        let ret = (&System).alloc(layout);
+        if ret.is_ok() {
+            record(|c| c.alloc(size));
+        }
+        ret
+    }
+
+    #[inline]
+    unsafe fn alloc_zeroed(&mut self, layout: Layout) -> Result<*mut u8, AllocErr> {
+        let size = layout.size();
+        let ret = (&System).alloc_zeroed(layout);
+        if ret.is_ok() {
+            record(|c| c.alloc(size));
+        }
+        ret
+    }
+
+    #[inline]
+    unsafe fn dealloc(&mut self, ptr: *mut u8, layout: Layout) {
+        record(|c| c.dealloc(layout.size()));
+        (&System).dealloc(ptr, layout)
+    }
+
+    #[inline]
+    unsafe fn realloc(&mut self,
+                      ptr: *mut u8,
+                      old_layout: Layout,
+                      new_layout: Layout) -> Result<*mut u8, AllocErr> {
+        let (old_size, new_size) = (old_layout.size(), new_layout.size());
+        let ret = (&System).realloc(ptr, old_layout, new_layout);
+        if ret.is_ok() {
+            record(|c| c.realloc(old_size, new_size));
+        }
+        ret
+    }
+
+    fn oom(&mut self, err: AllocErr) -> ! {
+        (&System).oom(err)
+    }
+
+    #[inline]
+    fn usable_size(&self, layout: &Layout) -> (usize, usize) {
+        (&System).usable_size(layout)
+    }
+
+    #[inline]
+    unsafe fn grow_in_place(&mut self,
+                            ptr: *mut u8,
+                            layout: Layout,
+                            new_layout: Layout) -> Result<(), CannotReallocInPlace> {
+        let (old_size, new_size) = (layout.size(), new_layout.size());
+        let ret = (&System).grow_in_place(ptr, layout, new_layout);
+        if ret.is_ok() {
+            record(|c| c.realloc(old_size, new_size));
+        }
+        ret
+    }
+
+    #[inline]
+    unsafe fn shrink_in_place(&mut self,
+                              ptr: *mut u8,
+                              layout: Layout,
+                              new_layout: Layout) -> Result<(), CannotReallocInPlace> {
+        let (old_size, new_size) = (layout.size(), new_layout.size());
+        let ret = (&System).shrink_in_place(ptr, layout, new_layout);
+        if ret.is_ok() {
+            record(|c| c.realloc(old_size, new_size));
+        }
+        ret
+    }
+}
@@ -0,0 +1,246 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A global allocator which poisons memory and guards against overflows,
+//! for debugging.
+//!
+//! `Poison` forwards every request to the system allocator, but surrounds
+//! each block with `GUARD_SIZE` bytes of `GUARD_BYTE` on both sides. Fresh
+//! memory is filled with `ALLOC_BYTE` (unless zeroed memory was requested)
+//! and freed memory with `FREED_BYTE`, so reads of uninitialized or freed
+//! memory stand out in a debugger.
+//!
+//! The guards are checked whenever a block is deallocated or reallocated.
+//! If any of them was overwritten, the process prints the address and
+//! layout of the block and aborts. Reallocation always moves the block, so
+//! stale pointers into the old one end up pointing at poisoned memory.
+//!
+//! ```no_run
+//! #![feature(global_allocator, alloc_poison)]
+//!
+//! extern crate alloc_poison;
+//!
+//! use alloc_poison::Poison;
+//!
+//! #[global_allocator]
+//! static ALLOC: Poison = Poison;
+//!
+//! fn main() {
+//!     let mut v = vec![1, 2, 3];
+//!     // writing past the end is reported when `v` is freed
+//!     unsafe { *v.as_mut_ptr().offset(3) = 4; }
+//! }
+//! ```
+
+#![no_std]
+#![deny(warnings)]
+#![unstable(feature = "alloc_poison",
+            reason = "this library is unlikely to be stabilized in its current \
+                      form or name",
+            issue = "0")]
+#![feature(alloc)]
+#![feature(alloc_system)]
+#![feature(allocator_api)]
+#![feature(core_intrinsics)]
+#![feature(staged_api)]
+#![cfg_attr(unix, feature(libc))]
+
+extern crate alloc;
+extern crate alloc_system;
+#[cfg(unix)]
+extern crate libc;
+
+use core::cmp;
+use core::ptr;
+
+use alloc::heap::{Alloc, AllocErr, Layout};
+use alloc_system::System;
+
+/// The number of guard bytes on either side of a block.
+pub const GUARD_SIZE: usize = 16;
+
+/// The value of the guard bytes.
+pub const GUARD_BYTE: u8 = 0xfd;
+
+/// The value fresh memory is filled with.
+pub const ALLOC_BYTE: u8 = 0xcd;
+
+/// The value freed memory is filled with.
+pub const FREED_BYTE: u8 = 0xdd;
+
+/// The poisoning allocator.
+///
+/// It is a wrapper around [`System`], see the crate documentation for
+/// what it does with the memory.
+///
+/// [`System`]: ../alloc_system/struct.System.html
+pub struct Poison;
+
+impl Poison {
+    /// Checks whether the guard bytes around a block are intact.
+    ///
+    /// This is the same check which is done on deallocation, but without
+    /// aborting, so it can be used to find the culprit earlier.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must denote a block currently allocated by this allocator with
+    /// the given `layout`.
+    pub unsafe fn check(&self, ptr: *mut u8, layout: &Layout) -> bool {
+        first_bad_guard(ptr, layout).is_none()
+    }
+}
+
+// The front guard also serves to keep the block aligned, so it takes up at
+// least `layout.align()` bytes.
+fn front_size(layout: &Layout) -> usize {
+    cmp::max(GUARD_SIZE, layout.align())
+}
+
+// The layout of the block actually requested from `System`.
+fn outer_layout(layout: &Layout) -> Option<Layout> {
+    let size = front_size(layout).checked_add(layout.size())?.checked_add(GUARD_SIZE)?;
+    Layout::from_size_align(size, layout.align())
+}
+
+// Returns the offset of the first overwritten guard byte relative to `ptr`.
+unsafe fn first_bad_guard(ptr: *mut u8, layout: &Layout) -> Option<isize> {
+    let front = front_size(layout);
+    for i in 1..front + 1 {
+        if *ptr.offset(-(i as isize)) != GUARD_BYTE {
+            return Some(-(i as isize));
+        }
+    }
+    for i in layout.size()..layout.size() + GUARD_SIZE {
+        if *ptr.offset(i as isize) != GUARD_BYTE {
+            return Some(i as isize);
+        }
+    }
+    None
+}
+
+unsafe impl Alloc for Poison {
+    #[inline]
+    unsafe fn alloc(&mut self, layout: Layout) -> Result<*mut u8, AllocErr> {
+        (&*self).alloc(layout)
+    }
+
+    #[inline]
+    unsafe fn alloc_zeroed(&mut self, layout: Layout) -> Result<*mut u8, AllocErr> {
+        (&*self).alloc_zeroed(layout)
+    }
+
+    #[inline]
+    unsafe fn dealloc(&mut self, ptr: *mut u8, layout: Layout) {
+        (&*self).dealloc(ptr, layout)
+    }
+
+    #[inline]
+    unsafe fn realloc(&mut self,
+                      ptr: *mut u8,
+                      old_layout: Layout,
+                      new_layout: Layout) -> Result<*mut u8, AllocErr> {
+        (&*self).realloc(ptr, old_layout, new_layout)
+    }
+
+    fn oom(&mut self, err: AllocErr) -> ! {
+        (&*self).oom(err)
+    }
+}
+
+unsafe impl<'a> Alloc for &'a Poison {
+    unsafe fn alloc(&mut self, layout: Layout) -> Result<*mut u8, AllocErr> {
+        let outer = match outer_layout(&layout) {
+            Some(outer) => outer,
+            None => return Err(AllocErr::Exhausted { request: layout }),
+        };
+        let base = (&System).alloc(outer)?;
+        let front = front_size(&layout);
+        let ptr = base.offset(front as isize);
+        ptr::write_bytes(base, GUARD_BYTE, front);
+        ptr::write_bytes(ptr, ALLOC_BYTE, layout.size());
+        ptr::write_bytes(ptr.offset(layout.size() as isize), GUARD_BYTE, GUARD_SIZE);
+        Ok(ptr)
+    }
+
+    unsafe fn alloc_zeroed(&mut self, layout: Layout) -> Result<*mut u8, AllocErr> {
+        let size = layout.size();
+        let ptr = self.alloc(layout)?;
+        ptr::write_bytes(ptr, 0, size);
+        Ok(ptr)
+    }
+
+    unsafe fn dealloc(&mut self, ptr: *mut u8, layout: Layout) {
+        if let Some(offset) = first_bad_guard(ptr, &layout) {
+            corrupted(ptr, &layout, offset);
+        }
+        let outer = outer_layout(&layout).unwrap();
+        let base = ptr.offset(-(front_size(&layout) as isize));
+        ptr::write_bytes(base, FREED_BYTE, outer.size());
+        (&System).dealloc(base, outer)
+    }
+
+    unsafe fn realloc(&mut self,
+                      ptr: *mut u8,
+                      old_layout: Layout,
+                      new_layout: Layout) -> Result<*mut u8, AllocErr> {
+        if old_layout.align() != new_layout.align() {
+            return Err(AllocErr::Unsupported {
+                details: "cannot change alignment on `realloc`",
+            })
+        }
+
+        let size = cmp::min(old_layout.size(), new_layout.size());
+        let new_ptr = self.alloc(new_layout)?;
+        ptr::copy_nonoverlapping(ptr, new_ptr, size);
+        self.dealloc(ptr, old_layout);
+        Ok(new_ptr)
+    }
+
+    fn oom(&mut self, err: AllocErr) -> ! {
+        (&System).oom(err)
+    }
+}
+
+fn corrupted(ptr: *mut u8, layout: &Layout, offset: isize) -> ! {
+    use core::fmt::{self, Write};
+
+    // Like the out of memory handler of `System`, this must not allocate, as
+    // the heap can no longer be trusted. Errors are ignored, we are about to
+    // abort anyway.
+    drop(writeln!(Stderr,
+                  "fatal runtime error: heap corruption detected: guard byte at offset {} \
+                   of the block at {:p} (size {}, align {}) was overwritten",
+                  offset, ptr, layout.size(), layout.align()));
+    unsafe {
+        ::core::intrinsics::abort();
+    }
+
+    struct Stderr;
+
+    impl Write for Stderr {
+        #[cfg(unix)]
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            unsafe {
+                libc::write(libc::STDERR_FILENO,
+                            s.as_ptr() as *const libc::c_void,
+                            s.len());
+            }
+            Ok(())
+        }
+
+        #[cfg(not(unix))]
+        fn write_str(&mut self, _: &str) -> fmt::Result {
+            // There is no portable way to reach the standard error stream
+            // here without allocating, so only the abort is left.
+            Ok(())
+        }
+    }
+}
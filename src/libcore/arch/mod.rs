@@ -0,0 +1,61 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Architecture-specific intrinsics.
+//!
+//! Each submodule exposes the vendor intrinsics of one architecture under
+//! their vendor names, as `unsafe` functions compiled with the target
+//! feature they need. Calling one on a CPU without that feature is
+//! undefined behavior, so code which cannot assume the feature at compile
+//! time should check for it at run time first, for example with
+//! `is_x86_feature_detected!` in `std`.
+//!
+//! # Examples
+//!
+//! ```
+//! #![feature(stdsimd, target_feature)]
+//!
+//! # #[cfg(target_arch = "x86_64")]
+//! # fn main() {
+//! use std::arch::x86_64::*;
+//!
+//! fn count_zero_bytes(data: &[u8]) -> usize {
+//!     if is_x86_feature_detected!("avx2") {
+//!         unsafe { count_zero_bytes_avx2(data) }
+//!     } else {
+//!         data.iter().filter(|&&b| b == 0).count()
+//!     }
+//! }
+//!
+//! #[target_feature(enable = "avx2")]
+//! unsafe fn count_zero_bytes_avx2(data: &[u8]) -> usize {
+//!     let mut count = 0;
+//!     for chunk in data.chunks(32) {
+//!         if chunk.len() == 32 {
+//!             let v = _mm256_loadu_si256(chunk.as_ptr() as *const __m256i);
+//!             let zeros = _mm256_cmpeq_epi8(v, _mm256_setzero_si256());
+//!             count += (_mm256_movemask_epi8(zeros) as u32).count_ones() as usize;
+//!         } else {
+//!             count += chunk.iter().filter(|&&b| b == 0).count();
+//!         }
+//!     }
+//!     count
+//! }
+//!
+//! assert_eq!(count_zero_bytes(&[0; 100]), 100);
+//! # }
+//! # #[cfg(not(target_arch = "x86_64"))]
+//! # fn main() {}
+//! ```
+
+#![unstable(feature = "stdsimd", issue = "0")]
+
+#[cfg(target_arch = "x86_64")]
+pub mod x86_64;
@@ -0,0 +1,43 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Population count (POPCNT) and leading zero count (LZCNT).
+
+use intrinsics;
+
+/// Returns the number of set bits in `x`.
+#[inline]
+#[target_feature(enable = "popcnt")]
+pub unsafe fn _popcnt32(x: i32) -> i32 {
+    intrinsics::ctpop(x as u32) as i32
+}
+
+/// Returns the number of set bits in `x`.
+#[inline]
+#[target_feature(enable = "popcnt")]
+pub unsafe fn _popcnt64(x: i64) -> i32 {
+    intrinsics::ctpop(x as u64) as i32
+}
+
+/// Returns the number of leading zero bits in `x`, which is 32 if `x` is
+/// zero.
+#[inline]
+#[target_feature(enable = "lzcnt")]
+pub unsafe fn _lzcnt_u32(x: u32) -> u32 {
+    intrinsics::ctlz(x)
+}
+
+/// Returns the number of leading zero bits in `x`, which is 64 if `x` is
+/// zero.
+#[inline]
+#[target_feature(enable = "lzcnt")]
+pub unsafe fn _lzcnt_u64(x: u64) -> u64 {
+    intrinsics::ctlz(x)
+}
@@ -0,0 +1,176 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Advanced Vector Extensions (AVX).
+
+use mem;
+use ptr;
+use super::*;
+
+extern "platform-intrinsic" {
+    fn x86_mm256_min_ps(a: __m256, b: __m256) -> __m256;
+    fn x86_mm256_max_ps(a: __m256, b: __m256) -> __m256;
+    fn x86_mm256_sqrt_ps(a: __m256) -> __m256;
+    fn x86_mm256_movemask_ps(a: __m256) -> i32;
+    fn x86_mm256_testz_si256(a: u64x4, b: u64x4) -> i32;
+    fn x86_mm256_zeroupper();
+}
+
+/// Returns a vector with all bits set to zero.
+#[inline]
+#[target_feature(enable = "avx")]
+pub unsafe fn _mm256_setzero_si256() -> __m256i {
+    __m256i(0, 0, 0, 0)
+}
+
+/// Returns a vector with all 8-bit lanes set to `a`.
+#[inline]
+#[target_feature(enable = "avx")]
+pub unsafe fn _mm256_set1_epi8(a: i8) -> __m256i {
+    mem::transmute(i8x32(a, a, a, a, a, a, a, a, a, a, a, a, a, a, a, a,
+                         a, a, a, a, a, a, a, a, a, a, a, a, a, a, a, a))
+}
+
+/// Returns a vector with all 16-bit lanes set to `a`.
+#[inline]
+#[target_feature(enable = "avx")]
+pub unsafe fn _mm256_set1_epi16(a: i16) -> __m256i {
+    mem::transmute(i16x16(a, a, a, a, a, a, a, a, a, a, a, a, a, a, a, a))
+}
+
+/// Returns a vector with all 32-bit lanes set to `a`.
+#[inline]
+#[target_feature(enable = "avx")]
+pub unsafe fn _mm256_set1_epi32(a: i32) -> __m256i {
+    mem::transmute(i32x8(a, a, a, a, a, a, a, a))
+}
+
+/// Returns a vector with all 64-bit lanes set to `a`.
+#[inline]
+#[target_feature(enable = "avx")]
+pub unsafe fn _mm256_set1_epi64x(a: i64) -> __m256i {
+    __m256i(a, a, a, a)
+}
+
+/// Loads 256 bits from `mem_addr`, which need not be aligned.
+#[inline]
+#[target_feature(enable = "avx")]
+pub unsafe fn _mm256_loadu_si256(mem_addr: *const __m256i) -> __m256i {
+    ptr::read_unaligned(mem_addr)
+}
+
+/// Stores 256 bits to `mem_addr`, which need not be aligned.
+#[inline]
+#[target_feature(enable = "avx")]
+pub unsafe fn _mm256_storeu_si256(mem_addr: *mut __m256i, a: __m256i) {
+    ptr::write_unaligned(mem_addr, a)
+}
+
+/// Returns 1 if the bitwise AND of `a` and `b` is zero, and 0 otherwise.
+#[inline]
+#[target_feature(enable = "avx")]
+pub unsafe fn _mm256_testz_si256(a: __m256i, b: __m256i) -> i32 {
+    x86_mm256_testz_si256(a.as_u64x4(), b.as_u64x4())
+}
+
+/// Returns a vector with all lanes set to zero.
+#[inline]
+#[target_feature(enable = "avx")]
+pub unsafe fn _mm256_setzero_ps() -> __m256 {
+    __m256(0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0)
+}
+
+/// Returns a vector with all lanes set to `a`.
+#[inline]
+#[target_feature(enable = "avx")]
+pub unsafe fn _mm256_set1_ps(a: f32) -> __m256 {
+    __m256(a, a, a, a, a, a, a, a)
+}
+
+/// Loads eight `f32`s from `mem_addr`, which need not be aligned.
+#[inline]
+#[target_feature(enable = "avx")]
+pub unsafe fn _mm256_loadu_ps(mem_addr: *const f32) -> __m256 {
+    ptr::read_unaligned(mem_addr as *const __m256)
+}
+
+/// Stores eight `f32`s to `mem_addr`, which need not be aligned.
+#[inline]
+#[target_feature(enable = "avx")]
+pub unsafe fn _mm256_storeu_ps(mem_addr: *mut f32, a: __m256) {
+    ptr::write_unaligned(mem_addr as *mut __m256, a)
+}
+
+/// Adds the lanes of `a` and `b`.
+#[inline]
+#[target_feature(enable = "avx")]
+pub unsafe fn _mm256_add_ps(a: __m256, b: __m256) -> __m256 {
+    simd_add(a, b)
+}
+
+/// Subtracts the lanes of `b` from those of `a`.
+#[inline]
+#[target_feature(enable = "avx")]
+pub unsafe fn _mm256_sub_ps(a: __m256, b: __m256) -> __m256 {
+    simd_sub(a, b)
+}
+
+/// Multiplies the lanes of `a` and `b`.
+#[inline]
+#[target_feature(enable = "avx")]
+pub unsafe fn _mm256_mul_ps(a: __m256, b: __m256) -> __m256 {
+    simd_mul(a, b)
+}
+
+/// Divides the lanes of `a` by those of `b`.
+#[inline]
+#[target_feature(enable = "avx")]
+pub unsafe fn _mm256_div_ps(a: __m256, b: __m256) -> __m256 {
+    simd_div(a, b)
+}
+
+/// Returns the lane-wise minimum of `a` and `b`. Where either lane is NaN,
+/// the lane of `b` is returned.
+#[inline]
+#[target_feature(enable = "avx")]
+pub unsafe fn _mm256_min_ps(a: __m256, b: __m256) -> __m256 {
+    x86_mm256_min_ps(a, b)
+}
+
+/// Returns the lane-wise maximum of `a` and `b`. Where either lane is NaN,
+/// the lane of `b` is returned.
+#[inline]
+#[target_feature(enable = "avx")]
+pub unsafe fn _mm256_max_ps(a: __m256, b: __m256) -> __m256 {
+    x86_mm256_max_ps(a, b)
+}
+
+/// Returns the square roots of the lanes of `a`.
+#[inline]
+#[target_feature(enable = "avx")]
+pub unsafe fn _mm256_sqrt_ps(a: __m256) -> __m256 {
+    x86_mm256_sqrt_ps(a)
+}
+
+/// Returns a mask of the sign bits of the lanes of `a`, with lane 0 in the
+/// lowest bit.
+#[inline]
+#[target_feature(enable = "avx")]
+pub unsafe fn _mm256_movemask_ps(a: __m256) -> i32 {
+    x86_mm256_movemask_ps(a)
+}
+
+/// Zeroes the upper 128 bits of all YMM registers, which avoids a penalty
+/// when switching from AVX code to SSE code.
+#[inline]
+#[target_feature(enable = "avx")]
+pub unsafe fn _mm256_zeroupper() {
+    x86_mm256_zeroupper()
+}
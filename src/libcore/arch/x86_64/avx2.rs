@@ -0,0 +1,261 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Advanced Vector Extensions 2 (AVX2).
+
+use mem;
+use super::*;
+
+extern "platform-intrinsic" {
+    fn x86_mm256_abs_epi8(a: i8x32) -> i8x32;
+    fn x86_mm256_abs_epi16(a: i16x16) -> i16x16;
+    fn x86_mm256_abs_epi32(a: i32x8) -> i32x8;
+    fn x86_mm256_adds_epu8(a: u8x32, b: u8x32) -> u8x32;
+    fn x86_mm256_subs_epu8(a: u8x32, b: u8x32) -> u8x32;
+    fn x86_mm256_avg_epu8(a: u8x32, b: u8x32) -> u8x32;
+    fn x86_mm256_min_epu8(a: u8x32, b: u8x32) -> u8x32;
+    fn x86_mm256_max_epu8(a: u8x32, b: u8x32) -> u8x32;
+    fn x86_mm256_sad_epu8(a: u8x32, b: u8x32) -> u64x4;
+    fn x86_mm256_shuffle_epi8(a: i8x32, b: i8x32) -> i8x32;
+    fn x86_mm256_movemask_epi8(a: i8x32) -> i32;
+}
+
+/// Adds the 8-bit lanes of `a` and `b`, wrapping around on overflow.
+#[inline]
+#[target_feature(enable = "avx2")]
+pub unsafe fn _mm256_add_epi8(a: __m256i, b: __m256i) -> __m256i {
+    mem::transmute(simd_add(a.as_i8x32(), b.as_i8x32()))
+}
+
+/// Adds the 16-bit lanes of `a` and `b`, wrapping around on overflow.
+#[inline]
+#[target_feature(enable = "avx2")]
+pub unsafe fn _mm256_add_epi16(a: __m256i, b: __m256i) -> __m256i {
+    mem::transmute(simd_add(a.as_i16x16(), b.as_i16x16()))
+}
+
+/// Adds the 32-bit lanes of `a` and `b`, wrapping around on overflow.
+#[inline]
+#[target_feature(enable = "avx2")]
+pub unsafe fn _mm256_add_epi32(a: __m256i, b: __m256i) -> __m256i {
+    mem::transmute(simd_add(a.as_i32x8(), b.as_i32x8()))
+}
+
+/// Adds the 64-bit lanes of `a` and `b`, wrapping around on overflow.
+#[inline]
+#[target_feature(enable = "avx2")]
+pub unsafe fn _mm256_add_epi64(a: __m256i, b: __m256i) -> __m256i {
+    simd_add(a, b)
+}
+
+/// Subtracts the 8-bit lanes of `b` from those of `a`, wrapping around on
+/// overflow.
+#[inline]
+#[target_feature(enable = "avx2")]
+pub unsafe fn _mm256_sub_epi8(a: __m256i, b: __m256i) -> __m256i {
+    mem::transmute(simd_sub(a.as_i8x32(), b.as_i8x32()))
+}
+
+/// Subtracts the 16-bit lanes of `b` from those of `a`, wrapping around on
+/// overflow.
+#[inline]
+#[target_feature(enable = "avx2")]
+pub unsafe fn _mm256_sub_epi16(a: __m256i, b: __m256i) -> __m256i {
+    mem::transmute(simd_sub(a.as_i16x16(), b.as_i16x16()))
+}
+
+/// Subtracts the 32-bit lanes of `b` from those of `a`, wrapping around on
+/// overflow.
+#[inline]
+#[target_feature(enable = "avx2")]
+pub unsafe fn _mm256_sub_epi32(a: __m256i, b: __m256i) -> __m256i {
+    mem::transmute(simd_sub(a.as_i32x8(), b.as_i32x8()))
+}
+
+/// Subtracts the 64-bit lanes of `b` from those of `a`, wrapping around on
+/// overflow.
+#[inline]
+#[target_feature(enable = "avx2")]
+pub unsafe fn _mm256_sub_epi64(a: __m256i, b: __m256i) -> __m256i {
+    simd_sub(a, b)
+}
+
+/// Adds the unsigned 8-bit lanes of `a` and `b`, saturating at `u8::MAX`.
+#[inline]
+#[target_feature(enable = "avx2")]
+pub unsafe fn _mm256_adds_epu8(a: __m256i, b: __m256i) -> __m256i {
+    mem::transmute(x86_mm256_adds_epu8(a.as_u8x32(), b.as_u8x32()))
+}
+
+/// Subtracts the unsigned 8-bit lanes of `b` from those of `a`, saturating
+/// at zero.
+#[inline]
+#[target_feature(enable = "avx2")]
+pub unsafe fn _mm256_subs_epu8(a: __m256i, b: __m256i) -> __m256i {
+    mem::transmute(x86_mm256_subs_epu8(a.as_u8x32(), b.as_u8x32()))
+}
+
+/// Returns the rounded-up averages of the unsigned 8-bit lanes of `a` and
+/// `b`.
+#[inline]
+#[target_feature(enable = "avx2")]
+pub unsafe fn _mm256_avg_epu8(a: __m256i, b: __m256i) -> __m256i {
+    mem::transmute(x86_mm256_avg_epu8(a.as_u8x32(), b.as_u8x32()))
+}
+
+/// Returns the lane-wise minimum of the unsigned 8-bit lanes of `a` and `b`.
+#[inline]
+#[target_feature(enable = "avx2")]
+pub unsafe fn _mm256_min_epu8(a: __m256i, b: __m256i) -> __m256i {
+    mem::transmute(x86_mm256_min_epu8(a.as_u8x32(), b.as_u8x32()))
+}
+
+/// Returns the lane-wise maximum of the unsigned 8-bit lanes of `a` and `b`.
+#[inline]
+#[target_feature(enable = "avx2")]
+pub unsafe fn _mm256_max_epu8(a: __m256i, b: __m256i) -> __m256i {
+    mem::transmute(x86_mm256_max_epu8(a.as_u8x32(), b.as_u8x32()))
+}
+
+/// Sums the absolute differences of the unsigned 8-bit lanes of `a` and
+/// `b` over each 64-bit quarter, leaving the four sums in the low 16 bits
+/// of the 64-bit lanes of the result.
+#[inline]
+#[target_feature(enable = "avx2")]
+pub unsafe fn _mm256_sad_epu8(a: __m256i, b: __m256i) -> __m256i {
+    mem::transmute(x86_mm256_sad_epu8(a.as_u8x32(), b.as_u8x32()))
+}
+
+/// Returns the absolute values of the signed 8-bit lanes of `a`. The
+/// absolute value of `i8::MIN` is returned as `i8::MIN`.
+#[inline]
+#[target_feature(enable = "avx2")]
+pub unsafe fn _mm256_abs_epi8(a: __m256i) -> __m256i {
+    mem::transmute(x86_mm256_abs_epi8(a.as_i8x32()))
+}
+
+/// Returns the absolute values of the signed 16-bit lanes of `a`.
+#[inline]
+#[target_feature(enable = "avx2")]
+pub unsafe fn _mm256_abs_epi16(a: __m256i) -> __m256i {
+    mem::transmute(x86_mm256_abs_epi16(a.as_i16x16()))
+}
+
+/// Returns the absolute values of the signed 32-bit lanes of `a`.
+#[inline]
+#[target_feature(enable = "avx2")]
+pub unsafe fn _mm256_abs_epi32(a: __m256i) -> __m256i {
+    mem::transmute(x86_mm256_abs_epi32(a.as_i32x8()))
+}
+
+/// Returns the bitwise AND of `a` and `b`.
+#[inline]
+#[target_feature(enable = "avx2")]
+pub unsafe fn _mm256_and_si256(a: __m256i, b: __m256i) -> __m256i {
+    simd_and(a, b)
+}
+
+/// Returns the bitwise AND of the complement of `a` and `b`.
+#[inline]
+#[target_feature(enable = "avx2")]
+pub unsafe fn _mm256_andnot_si256(a: __m256i, b: __m256i) -> __m256i {
+    simd_and(simd_xor(a, __m256i(!0, !0, !0, !0)), b)
+}
+
+/// Returns the bitwise OR of `a` and `b`.
+#[inline]
+#[target_feature(enable = "avx2")]
+pub unsafe fn _mm256_or_si256(a: __m256i, b: __m256i) -> __m256i {
+    simd_or(a, b)
+}
+
+/// Returns the bitwise XOR of `a` and `b`.
+#[inline]
+#[target_feature(enable = "avx2")]
+pub unsafe fn _mm256_xor_si256(a: __m256i, b: __m256i) -> __m256i {
+    simd_xor(a, b)
+}
+
+/// Compares the 8-bit lanes of `a` and `b` for equality, setting each lane
+/// of the result to all ones if they are equal and to all zeros otherwise.
+#[inline]
+#[target_feature(enable = "avx2")]
+pub unsafe fn _mm256_cmpeq_epi8(a: __m256i, b: __m256i) -> __m256i {
+    mem::transmute::<i8x32, _>(simd_eq(a.as_i8x32(), b.as_i8x32()))
+}
+
+/// Compares the 16-bit lanes of `a` and `b` for equality.
+#[inline]
+#[target_feature(enable = "avx2")]
+pub unsafe fn _mm256_cmpeq_epi16(a: __m256i, b: __m256i) -> __m256i {
+    mem::transmute::<i16x16, _>(simd_eq(a.as_i16x16(), b.as_i16x16()))
+}
+
+/// Compares the 32-bit lanes of `a` and `b` for equality.
+#[inline]
+#[target_feature(enable = "avx2")]
+pub unsafe fn _mm256_cmpeq_epi32(a: __m256i, b: __m256i) -> __m256i {
+    mem::transmute::<i32x8, _>(simd_eq(a.as_i32x8(), b.as_i32x8()))
+}
+
+/// Compares the 64-bit lanes of `a` and `b` for equality.
+#[inline]
+#[target_feature(enable = "avx2")]
+pub unsafe fn _mm256_cmpeq_epi64(a: __m256i, b: __m256i) -> __m256i {
+    simd_eq(a, b)
+}
+
+/// Compares the signed 8-bit lanes of `a` and `b`, setting each lane of the
+/// result to all ones if the lane of `a` is greater.
+#[inline]
+#[target_feature(enable = "avx2")]
+pub unsafe fn _mm256_cmpgt_epi8(a: __m256i, b: __m256i) -> __m256i {
+    mem::transmute::<i8x32, _>(simd_gt(a.as_i8x32(), b.as_i8x32()))
+}
+
+/// Compares the signed 16-bit lanes of `a` and `b`, setting each lane of
+/// the result to all ones if the lane of `a` is greater.
+#[inline]
+#[target_feature(enable = "avx2")]
+pub unsafe fn _mm256_cmpgt_epi16(a: __m256i, b: __m256i) -> __m256i {
+    mem::transmute::<i16x16, _>(simd_gt(a.as_i16x16(), b.as_i16x16()))
+}
+
+/// Compares the signed 32-bit lanes of `a` and `b`, setting each lane of
+/// the result to all ones if the lane of `a` is greater.
+#[inline]
+#[target_feature(enable = "avx2")]
+pub unsafe fn _mm256_cmpgt_epi32(a: __m256i, b: __m256i) -> __m256i {
+    mem::transmute::<i32x8, _>(simd_gt(a.as_i32x8(), b.as_i32x8()))
+}
+
+/// Compares the signed 64-bit lanes of `a` and `b`, setting each lane of
+/// the result to all ones if the lane of `a` is greater.
+#[inline]
+#[target_feature(enable = "avx2")]
+pub unsafe fn _mm256_cmpgt_epi64(a: __m256i, b: __m256i) -> __m256i {
+    simd_gt(a, b)
+}
+
+/// Shuffles the bytes within each 128-bit half of `a` according to `b`, as
+/// `_mm_shuffle_epi8` does for each half.
+#[inline]
+#[target_feature(enable = "avx2")]
+pub unsafe fn _mm256_shuffle_epi8(a: __m256i, b: __m256i) -> __m256i {
+    mem::transmute(x86_mm256_shuffle_epi8(a.as_i8x32(), b.as_i8x32()))
+}
+
+/// Returns a mask of the high bits of the 8-bit lanes of `a`, with lane 0
+/// in the lowest bit.
+#[inline]
+#[target_feature(enable = "avx2")]
+pub unsafe fn _mm256_movemask_epi8(a: __m256i) -> i32 {
+    x86_mm256_movemask_epi8(a.as_i8x32())
+}
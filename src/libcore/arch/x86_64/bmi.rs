@@ -0,0 +1,62 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Bit manipulation instructions (BMI1 and BMI2).
+
+use intrinsics;
+
+extern {
+    #[link_name = "llvm.x86.bmi.bextr.64"]
+    fn bextr_u64(a: u64, control: u64) -> u64;
+    #[link_name = "llvm.x86.bmi.pdep.64"]
+    fn pdep_u64(a: u64, mask: u64) -> u64;
+    #[link_name = "llvm.x86.bmi.pext.64"]
+    fn pext_u64(a: u64, mask: u64) -> u64;
+}
+
+/// Returns the number of trailing zero bits in `x`, which is 32 if `x` is
+/// zero.
+#[inline]
+#[target_feature(enable = "bmi")]
+pub unsafe fn _tzcnt_u32(x: u32) -> u32 {
+    intrinsics::cttz(x)
+}
+
+/// Returns the number of trailing zero bits in `x`, which is 64 if `x` is
+/// zero.
+#[inline]
+#[target_feature(enable = "bmi")]
+pub unsafe fn _tzcnt_u64(x: u64) -> u64 {
+    intrinsics::cttz(x)
+}
+
+/// Returns the bits of `a` from bit `start` onwards, up to `len` of them,
+/// where `start` and `len` are the low two bytes of `control`.
+#[inline]
+#[target_feature(enable = "bmi")]
+pub unsafe fn _bextr2_u64(a: u64, control: u64) -> u64 {
+    bextr_u64(a, control)
+}
+
+/// Deposits the low bits of `a` at the positions of the set bits of `mask`,
+/// in order from the lowest, and clears the other bits.
+#[inline]
+#[target_feature(enable = "bmi2")]
+pub unsafe fn _pdep_u64(a: u64, mask: u64) -> u64 {
+    pdep_u64(a, mask)
+}
+
+/// Gathers the bits of `a` at the positions of the set bits of `mask` into
+/// the low bits of the result, in order from the lowest.
+#[inline]
+#[target_feature(enable = "bmi2")]
+pub unsafe fn _pext_u64(a: u64, mask: u64) -> u64 {
+    pext_u64(a, mask)
+}
@@ -0,0 +1,48 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Fused multiply-add (FMA).
+
+use super::*;
+
+extern "platform-intrinsic" {
+    fn x86_mm_fmadd_ps(a: __m128, b: __m128, c: __m128) -> __m128;
+    fn x86_mm_fmadd_pd(a: __m128d, b: __m128d, c: __m128d) -> __m128d;
+    fn x86_mm256_fmadd_ps(a: __m256, b: __m256, c: __m256) -> __m256;
+    fn x86_mm256_fmadd_pd(a: __m256d, b: __m256d, c: __m256d) -> __m256d;
+}
+
+/// Returns `a * b + c` for each lane, rounded once.
+#[inline]
+#[target_feature(enable = "fma")]
+pub unsafe fn _mm_fmadd_ps(a: __m128, b: __m128, c: __m128) -> __m128 {
+    x86_mm_fmadd_ps(a, b, c)
+}
+
+/// Returns `a * b + c` for each lane, rounded once.
+#[inline]
+#[target_feature(enable = "fma")]
+pub unsafe fn _mm_fmadd_pd(a: __m128d, b: __m128d, c: __m128d) -> __m128d {
+    x86_mm_fmadd_pd(a, b, c)
+}
+
+/// Returns `a * b + c` for each lane, rounded once.
+#[inline]
+#[target_feature(enable = "fma")]
+pub unsafe fn _mm256_fmadd_ps(a: __m256, b: __m256, c: __m256) -> __m256 {
+    x86_mm256_fmadd_ps(a, b, c)
+}
+
+/// Returns `a * b + c` for each lane, rounded once.
+#[inline]
+#[target_feature(enable = "fma")]
+pub unsafe fn _mm256_fmadd_pd(a: __m256d, b: __m256d, c: __m256d) -> __m256d {
+    x86_mm256_fmadd_pd(a, b, c)
+}
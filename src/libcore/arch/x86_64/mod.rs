@@ -0,0 +1,208 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Intrinsics for the x86-64 architecture.
+//!
+//! The intrinsics are named and typed as in Intel's intrinsics guide. Each
+//! one is compiled with the target feature it belongs to, so it can be
+//! used from a function which was not, but must only be called once that
+//! feature is known to be available. SSE and SSE2 are part of the x86-64
+//! baseline and always are.
+//!
+//! Most of them are thin wrappers around the `x86_*` platform intrinsics
+//! known to the compiler, or around the generic `simd_*` ones where a plain
+//! lane-wise operation is all that is needed.
+
+use mem;
+
+#[macro_use]
+mod macros;
+
+mod abm;
+mod avx;
+mod avx2;
+mod bmi;
+mod fma;
+mod sse;
+mod sse2;
+mod sse41;
+mod sse42;
+mod ssse3;
+
+pub use self::abm::*;
+pub use self::avx::*;
+pub use self::avx2::*;
+pub use self::bmi::*;
+pub use self::fma::*;
+pub use self::sse::*;
+pub use self::sse2::*;
+pub use self::sse41::*;
+pub use self::sse42::*;
+pub use self::ssse3::*;
+
+/// 128-bit vector of integers, of any lane width.
+#[repr(simd)]
+#[derive(Copy, Clone, Debug)]
+#[allow(non_camel_case_types)]
+pub struct __m128i(i64, i64);
+
+/// 128-bit vector of four `f32`s.
+#[repr(simd)]
+#[derive(Copy, Clone, Debug)]
+#[allow(non_camel_case_types)]
+pub struct __m128(f32, f32, f32, f32);
+
+/// 128-bit vector of two `f64`s.
+#[repr(simd)]
+#[derive(Copy, Clone, Debug)]
+#[allow(non_camel_case_types)]
+pub struct __m128d(f64, f64);
+
+/// 256-bit vector of integers, of any lane width.
+#[repr(simd)]
+#[derive(Copy, Clone, Debug)]
+#[allow(non_camel_case_types)]
+pub struct __m256i(i64, i64, i64, i64);
+
+/// 256-bit vector of eight `f32`s.
+#[repr(simd)]
+#[derive(Copy, Clone, Debug)]
+#[allow(non_camel_case_types)]
+pub struct __m256(f32, f32, f32, f32, f32, f32, f32, f32);
+
+/// 256-bit vector of four `f64`s.
+#[repr(simd)]
+#[derive(Copy, Clone, Debug)]
+#[allow(non_camel_case_types)]
+pub struct __m256d(f64, f64, f64, f64);
+
+/// The registers written by the `cpuid` instruction.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct CpuidResult {
+    /// EAX register.
+    pub eax: u32,
+    /// EBX register.
+    pub ebx: u32,
+    /// ECX register.
+    pub ecx: u32,
+    /// EDX register.
+    pub edx: u32,
+}
+
+/// Executes `cpuid` for the given `leaf` and `sub_leaf`.
+///
+/// Leaves above the maximum reported by `__get_cpuid_max` return
+/// unspecified values.
+#[inline]
+pub unsafe fn __cpuid_count(leaf: u32, sub_leaf: u32) -> CpuidResult {
+    let eax;
+    let ebx;
+    let ecx;
+    let edx;
+    asm!("cpuid"
+         : "={eax}"(eax), "={ebx}"(ebx), "={ecx}"(ecx), "={edx}"(edx)
+         : "{eax}"(leaf), "{ecx}"(sub_leaf)
+         :
+         : "volatile");
+    CpuidResult { eax, ebx, ecx, edx }
+}
+
+/// Executes `cpuid` for the given `leaf`, with a sub-leaf of zero.
+#[inline]
+pub unsafe fn __cpuid(leaf: u32) -> CpuidResult {
+    __cpuid_count(leaf, 0)
+}
+
+/// Returns the highest supported `leaf` in the range `leaf` belongs to
+/// (`0` for the basic leaves, `0x8000_0000` for the extended ones), and the
+/// highest sub-leaf of that leaf.
+#[inline]
+pub unsafe fn __get_cpuid_max(leaf: u32) -> (u32, u32) {
+    let CpuidResult { eax, ebx, .. } = __cpuid(leaf);
+    (eax, ebx)
+}
+
+/// Reads the extended control register `xcr_no`, which is how the operating
+/// system reports which register states it saves on context switches.
+#[inline]
+#[target_feature(enable = "xsave")]
+pub unsafe fn _xgetbv(xcr_no: u32) -> u64 {
+    let eax: u32;
+    let edx: u32;
+    asm!("xgetbv" : "={eax}"(eax), "={edx}"(edx) : "{ecx}"(xcr_no) : : "volatile");
+    ((edx as u64) << 32) | eax as u64
+}
+
+// Typed views of the vendor vectors, as expected by the platform
+// intrinsics. The vendor types themselves only fix the total width.
+
+#[repr(simd)] #[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct i8x16(i8, i8, i8, i8, i8, i8, i8, i8, i8, i8, i8, i8, i8, i8, i8, i8);
+#[repr(simd)] #[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct u8x16(u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8);
+#[repr(simd)] #[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct i16x8(i16, i16, i16, i16, i16, i16, i16, i16);
+#[repr(simd)] #[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct u16x8(u16, u16, u16, u16, u16, u16, u16, u16);
+#[repr(simd)] #[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct i32x4(i32, i32, i32, i32);
+#[repr(simd)] #[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct u64x2(u64, u64);
+
+#[repr(simd)] #[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct i8x32(i8, i8, i8, i8, i8, i8, i8, i8, i8, i8, i8, i8, i8, i8, i8, i8,
+             i8, i8, i8, i8, i8, i8, i8, i8, i8, i8, i8, i8, i8, i8, i8, i8);
+#[repr(simd)] #[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct u8x32(u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8,
+             u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8);
+#[repr(simd)] #[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct i16x16(i16, i16, i16, i16, i16, i16, i16, i16,
+              i16, i16, i16, i16, i16, i16, i16, i16);
+#[repr(simd)] #[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct i32x8(i32, i32, i32, i32, i32, i32, i32, i32);
+#[repr(simd)] #[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct u64x4(u64, u64, u64, u64);
+
+macro_rules! lane_views {
+    ($vendor:ident { $($method:ident -> $lanes:ident),* }) => (
+        impl $vendor {
+            $(
+                #[inline]
+                fn $method(self) -> $lanes {
+                    unsafe { mem::transmute(self) }
+                }
+            )*
+        }
+    )
+}
+
+lane_views!(__m128i {
+    as_i8x16 -> i8x16, as_u8x16 -> u8x16, as_i16x8 -> i16x8, as_u16x8 -> u16x8,
+    as_i32x4 -> i32x4, as_u64x2 -> u64x2
+});
+lane_views!(__m256i {
+    as_i8x32 -> i8x32, as_u8x32 -> u8x32, as_i16x16 -> i16x16, as_i32x8 -> i32x8,
+    as_u64x4 -> u64x4
+});
+
+extern "platform-intrinsic" {
+    fn simd_add<T>(x: T, y: T) -> T;
+    fn simd_sub<T>(x: T, y: T) -> T;
+    fn simd_mul<T>(x: T, y: T) -> T;
+    fn simd_div<T>(x: T, y: T) -> T;
+    fn simd_and<T>(x: T, y: T) -> T;
+    fn simd_or<T>(x: T, y: T) -> T;
+    fn simd_xor<T>(x: T, y: T) -> T;
+    fn simd_shr<T>(x: T, y: T) -> T;
+    fn simd_eq<T, U>(x: T, y: T) -> U;
+    fn simd_lt<T, U>(x: T, y: T) -> U;
+    fn simd_gt<T, U>(x: T, y: T) -> U;
+    fn simd_extract<T, U>(x: T, idx: u32) -> U;
+}
@@ -0,0 +1,157 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Streaming SIMD Extensions (SSE).
+
+use mem;
+use ptr;
+use super::*;
+
+extern "platform-intrinsic" {
+    fn x86_mm_min_ps(a: __m128, b: __m128) -> __m128;
+    fn x86_mm_max_ps(a: __m128, b: __m128) -> __m128;
+    fn x86_mm_sqrt_ps(a: __m128) -> __m128;
+    fn x86_mm_rcp_ps(a: __m128) -> __m128;
+    fn x86_mm_rsqrt_ps(a: __m128) -> __m128;
+    fn x86_mm_movemask_ps(a: __m128) -> i32;
+}
+
+/// Returns a vector with all lanes set to zero.
+#[inline]
+#[target_feature(enable = "sse")]
+pub unsafe fn _mm_setzero_ps() -> __m128 {
+    __m128(0.0, 0.0, 0.0, 0.0)
+}
+
+/// Returns a vector with all lanes set to `a`.
+#[inline]
+#[target_feature(enable = "sse")]
+pub unsafe fn _mm_set1_ps(a: f32) -> __m128 {
+    __m128(a, a, a, a)
+}
+
+/// Returns a vector with the given lanes, from the highest to the lowest.
+#[inline]
+#[target_feature(enable = "sse")]
+pub unsafe fn _mm_set_ps(a: f32, b: f32, c: f32, d: f32) -> __m128 {
+    __m128(d, c, b, a)
+}
+
+/// Returns a vector with the given lanes, from the lowest to the highest.
+#[inline]
+#[target_feature(enable = "sse")]
+pub unsafe fn _mm_setr_ps(a: f32, b: f32, c: f32, d: f32) -> __m128 {
+    __m128(a, b, c, d)
+}
+
+/// Loads four `f32`s from `mem_addr`, which need not be aligned.
+#[inline]
+#[target_feature(enable = "sse")]
+pub unsafe fn _mm_loadu_ps(mem_addr: *const f32) -> __m128 {
+    ptr::read_unaligned(mem_addr as *const __m128)
+}
+
+/// Stores four `f32`s to `mem_addr`, which need not be aligned.
+#[inline]
+#[target_feature(enable = "sse")]
+pub unsafe fn _mm_storeu_ps(mem_addr: *mut f32, a: __m128) {
+    ptr::write_unaligned(mem_addr as *mut __m128, a)
+}
+
+/// Adds the lanes of `a` and `b`.
+#[inline]
+#[target_feature(enable = "sse")]
+pub unsafe fn _mm_add_ps(a: __m128, b: __m128) -> __m128 {
+    simd_add(a, b)
+}
+
+/// Subtracts the lanes of `b` from those of `a`.
+#[inline]
+#[target_feature(enable = "sse")]
+pub unsafe fn _mm_sub_ps(a: __m128, b: __m128) -> __m128 {
+    simd_sub(a, b)
+}
+
+/// Multiplies the lanes of `a` and `b`.
+#[inline]
+#[target_feature(enable = "sse")]
+pub unsafe fn _mm_mul_ps(a: __m128, b: __m128) -> __m128 {
+    simd_mul(a, b)
+}
+
+/// Divides the lanes of `a` by those of `b`.
+#[inline]
+#[target_feature(enable = "sse")]
+pub unsafe fn _mm_div_ps(a: __m128, b: __m128) -> __m128 {
+    simd_div(a, b)
+}
+
+/// Returns the lane-wise minimum of `a` and `b`. Where either lane is NaN,
+/// the lane of `b` is returned.
+#[inline]
+#[target_feature(enable = "sse")]
+pub unsafe fn _mm_min_ps(a: __m128, b: __m128) -> __m128 {
+    x86_mm_min_ps(a, b)
+}
+
+/// Returns the lane-wise maximum of `a` and `b`. Where either lane is NaN,
+/// the lane of `b` is returned.
+#[inline]
+#[target_feature(enable = "sse")]
+pub unsafe fn _mm_max_ps(a: __m128, b: __m128) -> __m128 {
+    x86_mm_max_ps(a, b)
+}
+
+/// Returns the square roots of the lanes of `a`.
+#[inline]
+#[target_feature(enable = "sse")]
+pub unsafe fn _mm_sqrt_ps(a: __m128) -> __m128 {
+    x86_mm_sqrt_ps(a)
+}
+
+/// Returns approximations of the reciprocals of the lanes of `a`, with a
+/// relative error of at most 1.5 * 2^-12.
+#[inline]
+#[target_feature(enable = "sse")]
+pub unsafe fn _mm_rcp_ps(a: __m128) -> __m128 {
+    x86_mm_rcp_ps(a)
+}
+
+/// Returns approximations of the reciprocals of the square roots of the
+/// lanes of `a`, with a relative error of at most 1.5 * 2^-12.
+#[inline]
+#[target_feature(enable = "sse")]
+pub unsafe fn _mm_rsqrt_ps(a: __m128) -> __m128 {
+    x86_mm_rsqrt_ps(a)
+}
+
+/// Compares the lanes of `a` and `b` for equality, setting each lane of the
+/// result to all ones if they are equal and to all zeros otherwise.
+#[inline]
+#[target_feature(enable = "sse")]
+pub unsafe fn _mm_cmpeq_ps(a: __m128, b: __m128) -> __m128 {
+    mem::transmute::<i32x4, _>(simd_eq(a, b))
+}
+
+/// Compares the lanes of `a` and `b`, setting each lane of the result to
+/// all ones if the lane of `a` is less than that of `b`.
+#[inline]
+#[target_feature(enable = "sse")]
+pub unsafe fn _mm_cmplt_ps(a: __m128, b: __m128) -> __m128 {
+    mem::transmute::<i32x4, _>(simd_lt(a, b))
+}
+
+/// Returns a mask of the sign bits of the lanes of `a`, with lane 0 in the
+/// lowest bit.
+#[inline]
+#[target_feature(enable = "sse")]
+pub unsafe fn _mm_movemask_ps(a: __m128) -> i32 {
+    x86_mm_movemask_ps(a)
+}
@@ -0,0 +1,402 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Streaming SIMD Extensions 2 (SSE2).
+
+use mem;
+use ptr;
+use super::*;
+
+extern "platform-intrinsic" {
+    fn x86_mm_adds_epu8(a: u8x16, b: u8x16) -> u8x16;
+    fn x86_mm_subs_epu8(a: u8x16, b: u8x16) -> u8x16;
+    fn x86_mm_avg_epu8(a: u8x16, b: u8x16) -> u8x16;
+    fn x86_mm_min_epu8(a: u8x16, b: u8x16) -> u8x16;
+    fn x86_mm_max_epu8(a: u8x16, b: u8x16) -> u8x16;
+    fn x86_mm_min_epi16(a: i16x8, b: i16x8) -> i16x8;
+    fn x86_mm_max_epi16(a: i16x8, b: i16x8) -> i16x8;
+    fn x86_mm_sad_epu8(a: u8x16, b: u8x16) -> u64x2;
+    fn x86_mm_movemask_epi8(a: i8x16) -> i32;
+    fn x86_mm_sqrt_pd(a: __m128d) -> __m128d;
+    fn x86_mm_movemask_pd(a: __m128d) -> i32;
+}
+
+/// Returns a vector with all bits set to zero.
+#[inline]
+#[target_feature(enable = "sse2")]
+pub unsafe fn _mm_setzero_si128() -> __m128i {
+    __m128i(0, 0)
+}
+
+/// Returns a vector with all 8-bit lanes set to `a`.
+#[inline]
+#[target_feature(enable = "sse2")]
+pub unsafe fn _mm_set1_epi8(a: i8) -> __m128i {
+    mem::transmute(i8x16(a, a, a, a, a, a, a, a, a, a, a, a, a, a, a, a))
+}
+
+/// Returns a vector with all 16-bit lanes set to `a`.
+#[inline]
+#[target_feature(enable = "sse2")]
+pub unsafe fn _mm_set1_epi16(a: i16) -> __m128i {
+    mem::transmute(i16x8(a, a, a, a, a, a, a, a))
+}
+
+/// Returns a vector with all 32-bit lanes set to `a`.
+#[inline]
+#[target_feature(enable = "sse2")]
+pub unsafe fn _mm_set1_epi32(a: i32) -> __m128i {
+    mem::transmute(i32x4(a, a, a, a))
+}
+
+/// Returns a vector with both 64-bit lanes set to `a`.
+#[inline]
+#[target_feature(enable = "sse2")]
+pub unsafe fn _mm_set1_epi64x(a: i64) -> __m128i {
+    __m128i(a, a)
+}
+
+/// Returns a vector with the given 32-bit lanes, from the highest to the
+/// lowest.
+#[inline]
+#[target_feature(enable = "sse2")]
+pub unsafe fn _mm_set_epi32(e3: i32, e2: i32, e1: i32, e0: i32) -> __m128i {
+    mem::transmute(i32x4(e0, e1, e2, e3))
+}
+
+/// Returns a vector with the given 32-bit lanes, from the lowest to the
+/// highest.
+#[inline]
+#[target_feature(enable = "sse2")]
+pub unsafe fn _mm_setr_epi32(e0: i32, e1: i32, e2: i32, e3: i32) -> __m128i {
+    mem::transmute(i32x4(e0, e1, e2, e3))
+}
+
+/// Returns a vector whose low 32 bits are `a` and whose other bits are zero.
+#[inline]
+#[target_feature(enable = "sse2")]
+pub unsafe fn _mm_cvtsi32_si128(a: i32) -> __m128i {
+    mem::transmute(i32x4(a, 0, 0, 0))
+}
+
+/// Returns the low 32 bits of `a`.
+#[inline]
+#[target_feature(enable = "sse2")]
+pub unsafe fn _mm_cvtsi128_si32(a: __m128i) -> i32 {
+    simd_extract(a.as_i32x4(), 0)
+}
+
+/// Loads 128 bits from `mem_addr`, which need not be aligned.
+#[inline]
+#[target_feature(enable = "sse2")]
+pub unsafe fn _mm_loadu_si128(mem_addr: *const __m128i) -> __m128i {
+    ptr::read_unaligned(mem_addr)
+}
+
+/// Stores 128 bits to `mem_addr`, which need not be aligned.
+#[inline]
+#[target_feature(enable = "sse2")]
+pub unsafe fn _mm_storeu_si128(mem_addr: *mut __m128i, a: __m128i) {
+    ptr::write_unaligned(mem_addr, a)
+}
+
+/// Adds the 8-bit lanes of `a` and `b`, wrapping around on overflow.
+#[inline]
+#[target_feature(enable = "sse2")]
+pub unsafe fn _mm_add_epi8(a: __m128i, b: __m128i) -> __m128i {
+    mem::transmute(simd_add(a.as_i8x16(), b.as_i8x16()))
+}
+
+/// Adds the 16-bit lanes of `a` and `b`, wrapping around on overflow.
+#[inline]
+#[target_feature(enable = "sse2")]
+pub unsafe fn _mm_add_epi16(a: __m128i, b: __m128i) -> __m128i {
+    mem::transmute(simd_add(a.as_i16x8(), b.as_i16x8()))
+}
+
+/// Adds the 32-bit lanes of `a` and `b`, wrapping around on overflow.
+#[inline]
+#[target_feature(enable = "sse2")]
+pub unsafe fn _mm_add_epi32(a: __m128i, b: __m128i) -> __m128i {
+    mem::transmute(simd_add(a.as_i32x4(), b.as_i32x4()))
+}
+
+/// Adds the 64-bit lanes of `a` and `b`, wrapping around on overflow.
+#[inline]
+#[target_feature(enable = "sse2")]
+pub unsafe fn _mm_add_epi64(a: __m128i, b: __m128i) -> __m128i {
+    simd_add(a, b)
+}
+
+/// Subtracts the 8-bit lanes of `b` from those of `a`, wrapping around on
+/// overflow.
+#[inline]
+#[target_feature(enable = "sse2")]
+pub unsafe fn _mm_sub_epi8(a: __m128i, b: __m128i) -> __m128i {
+    mem::transmute(simd_sub(a.as_i8x16(), b.as_i8x16()))
+}
+
+/// Subtracts the 16-bit lanes of `b` from those of `a`, wrapping around on
+/// overflow.
+#[inline]
+#[target_feature(enable = "sse2")]
+pub unsafe fn _mm_sub_epi16(a: __m128i, b: __m128i) -> __m128i {
+    mem::transmute(simd_sub(a.as_i16x8(), b.as_i16x8()))
+}
+
+/// Subtracts the 32-bit lanes of `b` from those of `a`, wrapping around on
+/// overflow.
+#[inline]
+#[target_feature(enable = "sse2")]
+pub unsafe fn _mm_sub_epi32(a: __m128i, b: __m128i) -> __m128i {
+    mem::transmute(simd_sub(a.as_i32x4(), b.as_i32x4()))
+}
+
+/// Subtracts the 64-bit lanes of `b` from those of `a`, wrapping around on
+/// overflow.
+#[inline]
+#[target_feature(enable = "sse2")]
+pub unsafe fn _mm_sub_epi64(a: __m128i, b: __m128i) -> __m128i {
+    simd_sub(a, b)
+}
+
+/// Adds the unsigned 8-bit lanes of `a` and `b`, saturating at `u8::MAX`.
+#[inline]
+#[target_feature(enable = "sse2")]
+pub unsafe fn _mm_adds_epu8(a: __m128i, b: __m128i) -> __m128i {
+    mem::transmute(x86_mm_adds_epu8(a.as_u8x16(), b.as_u8x16()))
+}
+
+/// Subtracts the unsigned 8-bit lanes of `b` from those of `a`, saturating
+/// at zero.
+#[inline]
+#[target_feature(enable = "sse2")]
+pub unsafe fn _mm_subs_epu8(a: __m128i, b: __m128i) -> __m128i {
+    mem::transmute(x86_mm_subs_epu8(a.as_u8x16(), b.as_u8x16()))
+}
+
+/// Returns the rounded-up averages of the unsigned 8-bit lanes of `a` and
+/// `b`.
+#[inline]
+#[target_feature(enable = "sse2")]
+pub unsafe fn _mm_avg_epu8(a: __m128i, b: __m128i) -> __m128i {
+    mem::transmute(x86_mm_avg_epu8(a.as_u8x16(), b.as_u8x16()))
+}
+
+/// Returns the lane-wise minimum of the unsigned 8-bit lanes of `a` and `b`.
+#[inline]
+#[target_feature(enable = "sse2")]
+pub unsafe fn _mm_min_epu8(a: __m128i, b: __m128i) -> __m128i {
+    mem::transmute(x86_mm_min_epu8(a.as_u8x16(), b.as_u8x16()))
+}
+
+/// Returns the lane-wise maximum of the unsigned 8-bit lanes of `a` and `b`.
+#[inline]
+#[target_feature(enable = "sse2")]
+pub unsafe fn _mm_max_epu8(a: __m128i, b: __m128i) -> __m128i {
+    mem::transmute(x86_mm_max_epu8(a.as_u8x16(), b.as_u8x16()))
+}
+
+/// Returns the lane-wise minimum of the signed 16-bit lanes of `a` and `b`.
+#[inline]
+#[target_feature(enable = "sse2")]
+pub unsafe fn _mm_min_epi16(a: __m128i, b: __m128i) -> __m128i {
+    mem::transmute(x86_mm_min_epi16(a.as_i16x8(), b.as_i16x8()))
+}
+
+/// Returns the lane-wise maximum of the signed 16-bit lanes of `a` and `b`.
+#[inline]
+#[target_feature(enable = "sse2")]
+pub unsafe fn _mm_max_epi16(a: __m128i, b: __m128i) -> __m128i {
+    mem::transmute(x86_mm_max_epi16(a.as_i16x8(), b.as_i16x8()))
+}
+
+/// Sums the absolute differences of the unsigned 8-bit lanes of `a` and
+/// `b` over each 64-bit half, leaving the two sums in the low 16 bits of
+/// the 64-bit lanes of the result.
+#[inline]
+#[target_feature(enable = "sse2")]
+pub unsafe fn _mm_sad_epu8(a: __m128i, b: __m128i) -> __m128i {
+    mem::transmute(x86_mm_sad_epu8(a.as_u8x16(), b.as_u8x16()))
+}
+
+/// Returns the bitwise AND of `a` and `b`.
+#[inline]
+#[target_feature(enable = "sse2")]
+pub unsafe fn _mm_and_si128(a: __m128i, b: __m128i) -> __m128i {
+    simd_and(a, b)
+}
+
+/// Returns the bitwise AND of the complement of `a` and `b`.
+#[inline]
+#[target_feature(enable = "sse2")]
+pub unsafe fn _mm_andnot_si128(a: __m128i, b: __m128i) -> __m128i {
+    simd_and(simd_xor(a, __m128i(!0, !0)), b)
+}
+
+/// Returns the bitwise OR of `a` and `b`.
+#[inline]
+#[target_feature(enable = "sse2")]
+pub unsafe fn _mm_or_si128(a: __m128i, b: __m128i) -> __m128i {
+    simd_or(a, b)
+}
+
+/// Returns the bitwise XOR of `a` and `b`.
+#[inline]
+#[target_feature(enable = "sse2")]
+pub unsafe fn _mm_xor_si128(a: __m128i, b: __m128i) -> __m128i {
+    simd_xor(a, b)
+}
+
+/// Shifts the 16-bit lanes of `a` right by `imm8` bits, shifting in zeros.
+/// Shifts by more than 15 bits set every lane to zero.
+#[inline]
+#[target_feature(enable = "sse2")]
+pub unsafe fn _mm_srli_epi16(a: __m128i, imm8: i32) -> __m128i {
+    if imm8 < 0 || imm8 > 15 {
+        return _mm_setzero_si128();
+    }
+    let shift = imm8 as u16;
+    let shifts = u16x8(shift, shift, shift, shift, shift, shift, shift, shift);
+    mem::transmute(simd_shr(a.as_u16x8(), shifts))
+}
+
+/// Compares the 8-bit lanes of `a` and `b` for equality, setting each lane
+/// of the result to all ones if they are equal and to all zeros otherwise.
+#[inline]
+#[target_feature(enable = "sse2")]
+pub unsafe fn _mm_cmpeq_epi8(a: __m128i, b: __m128i) -> __m128i {
+    mem::transmute::<i8x16, _>(simd_eq(a.as_i8x16(), b.as_i8x16()))
+}
+
+/// Compares the 16-bit lanes of `a` and `b` for equality.
+#[inline]
+#[target_feature(enable = "sse2")]
+pub unsafe fn _mm_cmpeq_epi16(a: __m128i, b: __m128i) -> __m128i {
+    mem::transmute::<i16x8, _>(simd_eq(a.as_i16x8(), b.as_i16x8()))
+}
+
+/// Compares the 32-bit lanes of `a` and `b` for equality.
+#[inline]
+#[target_feature(enable = "sse2")]
+pub unsafe fn _mm_cmpeq_epi32(a: __m128i, b: __m128i) -> __m128i {
+    mem::transmute::<i32x4, _>(simd_eq(a.as_i32x4(), b.as_i32x4()))
+}
+
+/// Compares the signed 8-bit lanes of `a` and `b`, setting each lane of the
+/// result to all ones if the lane of `a` is greater.
+#[inline]
+#[target_feature(enable = "sse2")]
+pub unsafe fn _mm_cmpgt_epi8(a: __m128i, b: __m128i) -> __m128i {
+    mem::transmute::<i8x16, _>(simd_gt(a.as_i8x16(), b.as_i8x16()))
+}
+
+/// Compares the signed 16-bit lanes of `a` and `b`, setting each lane of
+/// the result to all ones if the lane of `a` is greater.
+#[inline]
+#[target_feature(enable = "sse2")]
+pub unsafe fn _mm_cmpgt_epi16(a: __m128i, b: __m128i) -> __m128i {
+    mem::transmute::<i16x8, _>(simd_gt(a.as_i16x8(), b.as_i16x8()))
+}
+
+/// Compares the signed 32-bit lanes of `a` and `b`, setting each lane of
+/// the result to all ones if the lane of `a` is greater.
+#[inline]
+#[target_feature(enable = "sse2")]
+pub unsafe fn _mm_cmpgt_epi32(a: __m128i, b: __m128i) -> __m128i {
+    mem::transmute::<i32x4, _>(simd_gt(a.as_i32x4(), b.as_i32x4()))
+}
+
+/// Compares the signed 8-bit lanes of `a` and `b`, setting each lane of the
+/// result to all ones if the lane of `a` is less.
+#[inline]
+#[target_feature(enable = "sse2")]
+pub unsafe fn _mm_cmplt_epi8(a: __m128i, b: __m128i) -> __m128i {
+    mem::transmute::<i8x16, _>(simd_lt(a.as_i8x16(), b.as_i8x16()))
+}
+
+/// Returns a mask of the high bits of the 8-bit lanes of `a`, with lane 0
+/// in the lowest bit.
+#[inline]
+#[target_feature(enable = "sse2")]
+pub unsafe fn _mm_movemask_epi8(a: __m128i) -> i32 {
+    x86_mm_movemask_epi8(a.as_i8x16())
+}
+
+/// Returns a vector with all lanes set to zero.
+#[inline]
+#[target_feature(enable = "sse2")]
+pub unsafe fn _mm_setzero_pd() -> __m128d {
+    __m128d(0.0, 0.0)
+}
+
+/// Returns a vector with both lanes set to `a`.
+#[inline]
+#[target_feature(enable = "sse2")]
+pub unsafe fn _mm_set1_pd(a: f64) -> __m128d {
+    __m128d(a, a)
+}
+
+/// Loads two `f64`s from `mem_addr`, which need not be aligned.
+#[inline]
+#[target_feature(enable = "sse2")]
+pub unsafe fn _mm_loadu_pd(mem_addr: *const f64) -> __m128d {
+    ptr::read_unaligned(mem_addr as *const __m128d)
+}
+
+/// Stores two `f64`s to `mem_addr`, which need not be aligned.
+#[inline]
+#[target_feature(enable = "sse2")]
+pub unsafe fn _mm_storeu_pd(mem_addr: *mut f64, a: __m128d) {
+    ptr::write_unaligned(mem_addr as *mut __m128d, a)
+}
+
+/// Adds the lanes of `a` and `b`.
+#[inline]
+#[target_feature(enable = "sse2")]
+pub unsafe fn _mm_add_pd(a: __m128d, b: __m128d) -> __m128d {
+    simd_add(a, b)
+}
+
+/// Subtracts the lanes of `b` from those of `a`.
+#[inline]
+#[target_feature(enable = "sse2")]
+pub unsafe fn _mm_sub_pd(a: __m128d, b: __m128d) -> __m128d {
+    simd_sub(a, b)
+}
+
+/// Multiplies the lanes of `a` and `b`.
+#[inline]
+#[target_feature(enable = "sse2")]
+pub unsafe fn _mm_mul_pd(a: __m128d, b: __m128d) -> __m128d {
+    simd_mul(a, b)
+}
+
+/// Divides the lanes of `a` by those of `b`.
+#[inline]
+#[target_feature(enable = "sse2")]
+pub unsafe fn _mm_div_pd(a: __m128d, b: __m128d) -> __m128d {
+    simd_div(a, b)
+}
+
+/// Returns the square roots of the lanes of `a`.
+#[inline]
+#[target_feature(enable = "sse2")]
+pub unsafe fn _mm_sqrt_pd(a: __m128d) -> __m128d {
+    x86_mm_sqrt_pd(a)
+}
+
+/// Returns a mask of the sign bits of the lanes of `a`, with lane 0 in the
+/// lowest bit.
+#[inline]
+#[target_feature(enable = "sse2")]
+pub unsafe fn _mm_movemask_pd(a: __m128d) -> i32 {
+    x86_mm_movemask_pd(a)
+}
@@ -0,0 +1,64 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Streaming SIMD Extensions 4.1 (SSE4.1).
+
+use mem;
+use super::*;
+
+extern "platform-intrinsic" {
+    fn x86_mm_min_epi8(a: i8x16, b: i8x16) -> i8x16;
+    fn x86_mm_max_epi8(a: i8x16, b: i8x16) -> i8x16;
+    fn x86_mm_min_epi32(a: i32x4, b: i32x4) -> i32x4;
+    fn x86_mm_max_epi32(a: i32x4, b: i32x4) -> i32x4;
+    fn x86_mm_testz_si128(a: u64x2, b: u64x2) -> i32;
+}
+
+/// Returns the lane-wise minimum of the signed 8-bit lanes of `a` and `b`.
+#[inline]
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn _mm_min_epi8(a: __m128i, b: __m128i) -> __m128i {
+    mem::transmute(x86_mm_min_epi8(a.as_i8x16(), b.as_i8x16()))
+}
+
+/// Returns the lane-wise maximum of the signed 8-bit lanes of `a` and `b`.
+#[inline]
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn _mm_max_epi8(a: __m128i, b: __m128i) -> __m128i {
+    mem::transmute(x86_mm_max_epi8(a.as_i8x16(), b.as_i8x16()))
+}
+
+/// Returns the lane-wise minimum of the signed 32-bit lanes of `a` and `b`.
+#[inline]
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn _mm_min_epi32(a: __m128i, b: __m128i) -> __m128i {
+    mem::transmute(x86_mm_min_epi32(a.as_i32x4(), b.as_i32x4()))
+}
+
+/// Returns the lane-wise maximum of the signed 32-bit lanes of `a` and `b`.
+#[inline]
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn _mm_max_epi32(a: __m128i, b: __m128i) -> __m128i {
+    mem::transmute(x86_mm_max_epi32(a.as_i32x4(), b.as_i32x4()))
+}
+
+/// Compares the 64-bit lanes of `a` and `b` for equality.
+#[inline]
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn _mm_cmpeq_epi64(a: __m128i, b: __m128i) -> __m128i {
+    simd_eq(a, b)
+}
+
+/// Returns 1 if the bitwise AND of `a` and `b` is zero, and 0 otherwise.
+#[inline]
+#[target_feature(enable = "sse4.1")]
+pub unsafe fn _mm_testz_si128(a: __m128i, b: __m128i) -> i32 {
+    x86_mm_testz_si128(a.as_u64x2(), b.as_u64x2())
+}
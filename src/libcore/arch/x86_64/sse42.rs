@@ -0,0 +1,218 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Streaming SIMD Extensions 4.2 (SSE4.2).
+
+use mem;
+use super::*;
+
+/// The strings hold unsigned 8-bit characters.
+pub const _SIDD_UBYTE_OPS: i32 = 0b0000_0000;
+/// The strings hold unsigned 16-bit characters.
+pub const _SIDD_UWORD_OPS: i32 = 0b0000_0001;
+/// The strings hold signed 8-bit characters.
+pub const _SIDD_SBYTE_OPS: i32 = 0b0000_0010;
+/// The strings hold signed 16-bit characters.
+pub const _SIDD_SWORD_OPS: i32 = 0b0000_0011;
+
+/// For each character of `b`, tests whether it equals any character of `a`.
+pub const _SIDD_CMP_EQUAL_ANY: i32 = 0b0000_0000;
+/// For each character of `b`, tests whether it lies in any of the inclusive
+/// ranges given by consecutive pairs of characters of `a`.
+pub const _SIDD_CMP_RANGES: i32 = 0b0000_0100;
+/// Tests the characters of `a` and `b` at the same positions for equality.
+pub const _SIDD_CMP_EQUAL_EACH: i32 = 0b0000_1000;
+/// For each position of `b`, tests whether `a` occurs as a substring there.
+pub const _SIDD_CMP_EQUAL_ORDERED: i32 = 0b0000_1100;
+
+/// Leaves the result bits as they are.
+pub const _SIDD_POSITIVE_POLARITY: i32 = 0b0000_0000;
+/// Negates all the result bits.
+pub const _SIDD_NEGATIVE_POLARITY: i32 = 0b0001_0000;
+/// Leaves the result bits as they are; equivalent to
+/// `_SIDD_POSITIVE_POLARITY`.
+pub const _SIDD_MASKED_POSITIVE_POLARITY: i32 = 0b0010_0000;
+/// Negates the result bits of the characters before the end of `b` only.
+pub const _SIDD_MASKED_NEGATIVE_POLARITY: i32 = 0b0011_0000;
+
+/// Index functions return the position of the lowest set result bit.
+pub const _SIDD_LEAST_SIGNIFICANT: i32 = 0b0000_0000;
+/// Index functions return the position of the highest set result bit.
+pub const _SIDD_MOST_SIGNIFICANT: i32 = 0b0100_0000;
+/// Mask functions return the result bits packed in the low bits.
+pub const _SIDD_BIT_MASK: i32 = 0b0000_0000;
+/// Mask functions expand each result bit to a whole character.
+pub const _SIDD_UNIT_MASK: i32 = 0b0100_0000;
+
+extern "platform-intrinsic" {
+    fn x86_mm_cmpistri(a: i8x16, b: i8x16, imm8: i32) -> i32;
+    fn x86_mm_cmpistrm(a: i8x16, b: i8x16, imm8: i32) -> i8x16;
+    fn x86_mm_cmpistrc(a: i8x16, b: i8x16, imm8: i32) -> i32;
+    fn x86_mm_cmpistrz(a: i8x16, b: i8x16, imm8: i32) -> i32;
+    fn x86_mm_cmpestri(a: i8x16, la: i32, b: i8x16, lb: i32, imm8: i32) -> i32;
+    fn x86_mm_cmpestrm(a: i8x16, la: i32, b: i8x16, lb: i32, imm8: i32) -> i8x16;
+    fn x86_mm_cmpestrc(a: i8x16, la: i32, b: i8x16, lb: i32, imm8: i32) -> i32;
+    fn x86_mm_cmpestrz(a: i8x16, la: i32, b: i8x16, lb: i32, imm8: i32) -> i32;
+}
+
+extern {
+    #[link_name = "llvm.x86.sse42.crc32.32.8"]
+    fn crc32_32_8(crc: u32, v: u8) -> u32;
+    #[link_name = "llvm.x86.sse42.crc32.32.16"]
+    fn crc32_32_16(crc: u32, v: u16) -> u32;
+    #[link_name = "llvm.x86.sse42.crc32.32.32"]
+    fn crc32_32_32(crc: u32, v: u32) -> u32;
+    #[link_name = "llvm.x86.sse42.crc32.64.64"]
+    fn crc32_64_64(crc: u64, v: u64) -> u64;
+}
+
+/// Compares the implicit-length strings `a` and `b`, which end at their
+/// first zero character or after 16 bytes, as configured by the `_SIDD_*`
+/// flags in `imm8`, and returns the index selected by those flags. If no
+/// result bit is set the number of characters in a vector is returned.
+#[inline]
+#[target_feature(enable = "sse4.2")]
+#[rustc_args_required_const(2)]
+pub unsafe fn _mm_cmpistri(a: __m128i, b: __m128i, imm8: i32) -> i32 {
+    let (a, b) = (a.as_i8x16(), b.as_i8x16());
+    macro_rules! call {
+        ($imm8:expr) => (x86_mm_cmpistri(a, b, $imm8))
+    }
+    constify_imm8!(imm8, call)
+}
+
+/// Compares the implicit-length strings `a` and `b` as `_mm_cmpistri` does,
+/// and returns the result bits as a mask.
+#[inline]
+#[target_feature(enable = "sse4.2")]
+#[rustc_args_required_const(2)]
+pub unsafe fn _mm_cmpistrm(a: __m128i, b: __m128i, imm8: i32) -> __m128i {
+    let (a, b) = (a.as_i8x16(), b.as_i8x16());
+    macro_rules! call {
+        ($imm8:expr) => (x86_mm_cmpistrm(a, b, $imm8))
+    }
+    mem::transmute(constify_imm8!(imm8, call))
+}
+
+/// Compares the implicit-length strings `a` and `b` as `_mm_cmpistri` does,
+/// and returns 1 if any result bit is set.
+#[inline]
+#[target_feature(enable = "sse4.2")]
+#[rustc_args_required_const(2)]
+pub unsafe fn _mm_cmpistrc(a: __m128i, b: __m128i, imm8: i32) -> i32 {
+    let (a, b) = (a.as_i8x16(), b.as_i8x16());
+    macro_rules! call {
+        ($imm8:expr) => (x86_mm_cmpistrc(a, b, $imm8))
+    }
+    constify_imm8!(imm8, call)
+}
+
+/// Compares the implicit-length strings `a` and `b` as `_mm_cmpistri` does,
+/// and returns 1 if `b` ends within the vector.
+#[inline]
+#[target_feature(enable = "sse4.2")]
+#[rustc_args_required_const(2)]
+pub unsafe fn _mm_cmpistrz(a: __m128i, b: __m128i, imm8: i32) -> i32 {
+    let (a, b) = (a.as_i8x16(), b.as_i8x16());
+    macro_rules! call {
+        ($imm8:expr) => (x86_mm_cmpistrz(a, b, $imm8))
+    }
+    constify_imm8!(imm8, call)
+}
+
+/// Compares the explicit-length strings `a`, of `la` characters, and `b`,
+/// of `lb` characters, as configured by the `_SIDD_*` flags in `imm8`, and
+/// returns the index selected by those flags. If no result bit is set the
+/// number of characters in a vector is returned.
+#[inline]
+#[target_feature(enable = "sse4.2")]
+#[rustc_args_required_const(4)]
+pub unsafe fn _mm_cmpestri(a: __m128i, la: i32, b: __m128i, lb: i32, imm8: i32) -> i32 {
+    let (a, b) = (a.as_i8x16(), b.as_i8x16());
+    macro_rules! call {
+        ($imm8:expr) => (x86_mm_cmpestri(a, la, b, lb, $imm8))
+    }
+    constify_imm8!(imm8, call)
+}
+
+/// Compares the explicit-length strings `a` and `b` as `_mm_cmpestri` does,
+/// and returns the result bits as a mask.
+#[inline]
+#[target_feature(enable = "sse4.2")]
+#[rustc_args_required_const(4)]
+pub unsafe fn _mm_cmpestrm(a: __m128i, la: i32, b: __m128i, lb: i32, imm8: i32) -> __m128i {
+    let (a, b) = (a.as_i8x16(), b.as_i8x16());
+    macro_rules! call {
+        ($imm8:expr) => (x86_mm_cmpestrm(a, la, b, lb, $imm8))
+    }
+    mem::transmute(constify_imm8!(imm8, call))
+}
+
+/// Compares the explicit-length strings `a` and `b` as `_mm_cmpestri` does,
+/// and returns 1 if any result bit is set.
+#[inline]
+#[target_feature(enable = "sse4.2")]
+#[rustc_args_required_const(4)]
+pub unsafe fn _mm_cmpestrc(a: __m128i, la: i32, b: __m128i, lb: i32, imm8: i32) -> i32 {
+    let (a, b) = (a.as_i8x16(), b.as_i8x16());
+    macro_rules! call {
+        ($imm8:expr) => (x86_mm_cmpestrc(a, la, b, lb, $imm8))
+    }
+    constify_imm8!(imm8, call)
+}
+
+/// Compares the explicit-length strings `a` and `b` as `_mm_cmpestri` does,
+/// and returns 1 if `b` is shorter than a vector.
+#[inline]
+#[target_feature(enable = "sse4.2")]
+#[rustc_args_required_const(4)]
+pub unsafe fn _mm_cmpestrz(a: __m128i, la: i32, b: __m128i, lb: i32, imm8: i32) -> i32 {
+    let (a, b) = (a.as_i8x16(), b.as_i8x16());
+    macro_rules! call {
+        ($imm8:expr) => (x86_mm_cmpestrz(a, la, b, lb, $imm8))
+    }
+    constify_imm8!(imm8, call)
+}
+
+/// Compares the signed 64-bit lanes of `a` and `b`, setting each lane of
+/// the result to all ones if the lane of `a` is greater.
+#[inline]
+#[target_feature(enable = "sse4.2")]
+pub unsafe fn _mm_cmpgt_epi64(a: __m128i, b: __m128i) -> __m128i {
+    simd_gt(a, b)
+}
+
+/// Accumulates the byte `v` into the CRC-32C checksum `crc`.
+#[inline]
+#[target_feature(enable = "sse4.2")]
+pub unsafe fn _mm_crc32_u8(crc: u32, v: u8) -> u32 {
+    crc32_32_8(crc, v)
+}
+
+/// Accumulates the 16-bit value `v` into the CRC-32C checksum `crc`.
+#[inline]
+#[target_feature(enable = "sse4.2")]
+pub unsafe fn _mm_crc32_u16(crc: u32, v: u16) -> u32 {
+    crc32_32_16(crc, v)
+}
+
+/// Accumulates the 32-bit value `v` into the CRC-32C checksum `crc`.
+#[inline]
+#[target_feature(enable = "sse4.2")]
+pub unsafe fn _mm_crc32_u32(crc: u32, v: u32) -> u32 {
+    crc32_32_32(crc, v)
+}
+
+/// Accumulates the 64-bit value `v` into the CRC-32C checksum `crc`.
+#[inline]
+#[target_feature(enable = "sse4.2")]
+pub unsafe fn _mm_crc32_u64(crc: u64, v: u64) -> u64 {
+    crc32_64_64(crc, v)
+}
@@ -0,0 +1,52 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Supplemental Streaming SIMD Extensions 3 (SSSE3).
+
+use mem;
+use super::*;
+
+extern "platform-intrinsic" {
+    fn x86_mm_abs_epi8(a: i8x16) -> i8x16;
+    fn x86_mm_abs_epi16(a: i16x8) -> i16x8;
+    fn x86_mm_abs_epi32(a: i32x4) -> i32x4;
+    fn x86_mm_shuffle_epi8(a: i8x16, b: i8x16) -> i8x16;
+}
+
+/// Returns the absolute values of the signed 8-bit lanes of `a`. The
+/// absolute value of `i8::MIN` is returned as `i8::MIN`.
+#[inline]
+#[target_feature(enable = "ssse3")]
+pub unsafe fn _mm_abs_epi8(a: __m128i) -> __m128i {
+    mem::transmute(x86_mm_abs_epi8(a.as_i8x16()))
+}
+
+/// Returns the absolute values of the signed 16-bit lanes of `a`.
+#[inline]
+#[target_feature(enable = "ssse3")]
+pub unsafe fn _mm_abs_epi16(a: __m128i) -> __m128i {
+    mem::transmute(x86_mm_abs_epi16(a.as_i16x8()))
+}
+
+/// Returns the absolute values of the signed 32-bit lanes of `a`.
+#[inline]
+#[target_feature(enable = "ssse3")]
+pub unsafe fn _mm_abs_epi32(a: __m128i) -> __m128i {
+    mem::transmute(x86_mm_abs_epi32(a.as_i32x4()))
+}
+
+/// Shuffles the bytes of `a` according to `b`. Each byte of the result is
+/// the byte of `a` indexed by the low four bits of the corresponding byte of
+/// `b`, or zero if that byte of `b` has its high bit set.
+#[inline]
+#[target_feature(enable = "ssse3")]
+pub unsafe fn _mm_shuffle_epi8(a: __m128i, b: __m128i) -> __m128i {
+    mem::transmute(x86_mm_shuffle_epi8(a.as_i8x16(), b.as_i8x16()))
+}
@@ -11,6 +11,7 @@
 mod strategy {
     mod dragon;
     mod grisu;
+    mod ryu;
 }
 
 use std::f64;
@@ -1154,6 +1154,227 @@ impl<'a, T: ?Sized + fmt::Display> fmt::Display for RefMut<'a, T> {
     }
 }
 
+/// A cell which can be written to only once.
+///
+/// Unlike `Cell<T>` and `RefCell<T>`, a `OnceCell<T>` hands out plain `&T`
+/// references to its contents, because once it has been initialized the value
+/// is never changed or moved again. This makes it a good fit for values which
+/// are computed lazily and then only read.
+///
+/// `OnceCell<T>` is not `Sync`. See `std::sync::OnceCell` for a thread-safe
+/// version.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(once_cell)]
+///
+/// use std::cell::OnceCell;
+///
+/// let cell = OnceCell::new();
+/// assert!(cell.get().is_none());
+///
+/// let value: &String = cell.get_or_init(|| "Hello, World!".to_string());
+/// assert_eq!(value, "Hello, World!");
+/// assert!(cell.get().is_some());
+/// ```
+#[unstable(feature = "once_cell", issue = "0")]
+pub struct OnceCell<T> {
+    // Invariant: written to at most once, and never while a reference to the
+    // contents is live.
+    inner: UnsafeCell<Option<T>>,
+}
+
+#[unstable(feature = "once_cell", issue = "0")]
+impl<T> !Sync for OnceCell<T> {}
+
+#[unstable(feature = "once_cell", issue = "0")]
+impl<T> Default for OnceCell<T> {
+    fn default() -> OnceCell<T> {
+        OnceCell::new()
+    }
+}
+
+#[unstable(feature = "once_cell", issue = "0")]
+impl<T: Clone> Clone for OnceCell<T> {
+    fn clone(&self) -> OnceCell<T> {
+        let res = OnceCell::new();
+        if let Some(value) = self.get() {
+            let _ = res.set(value.clone());
+        }
+        res
+    }
+}
+
+#[unstable(feature = "once_cell", issue = "0")]
+impl<T: PartialEq> PartialEq for OnceCell<T> {
+    fn eq(&self, other: &OnceCell<T>) -> bool {
+        self.get() == other.get()
+    }
+}
+
+#[unstable(feature = "once_cell", issue = "0")]
+impl<T: Eq> Eq for OnceCell<T> {}
+
+#[unstable(feature = "once_cell", issue = "0")]
+impl<T> From<T> for OnceCell<T> {
+    fn from(value: T) -> OnceCell<T> {
+        OnceCell { inner: UnsafeCell::new(Some(value)) }
+    }
+}
+
+impl<T> OnceCell<T> {
+    /// Creates a new empty cell.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(once_cell)]
+    ///
+    /// use std::cell::OnceCell;
+    ///
+    /// let cell: OnceCell<i32> = OnceCell::new();
+    /// assert_eq!(cell.get(), None);
+    /// ```
+    #[unstable(feature = "once_cell", issue = "0")]
+    #[inline]
+    pub const fn new() -> OnceCell<T> {
+        OnceCell { inner: UnsafeCell::new(None) }
+    }
+
+    /// Returns a reference to the contents of the cell, or `None` if it has
+    /// not been initialized yet.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(once_cell)]
+    ///
+    /// use std::cell::OnceCell;
+    ///
+    /// let cell = OnceCell::new();
+    /// assert_eq!(cell.get(), None);
+    /// cell.set(92).unwrap();
+    /// assert_eq!(cell.get(), Some(&92));
+    /// ```
+    #[unstable(feature = "once_cell", issue = "0")]
+    #[inline]
+    pub fn get(&self) -> Option<&T> {
+        // Safe because the contents are never changed once they are `Some`.
+        unsafe { (*self.inner.get()).as_ref() }
+    }
+
+    /// Returns a mutable reference to the contents of the cell, or `None` if
+    /// it has not been initialized yet.
+    ///
+    /// This call borrows `OnceCell` mutably (at compile-time) which
+    /// guarantees that we possess the only reference.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(once_cell)]
+    ///
+    /// use std::cell::OnceCell;
+    ///
+    /// let mut cell = OnceCell::from(1);
+    /// *cell.get_mut().unwrap() += 1;
+    /// assert_eq!(cell.get(), Some(&2));
+    /// ```
+    #[unstable(feature = "once_cell", issue = "0")]
+    #[inline]
+    pub fn get_mut(&mut self) -> Option<&mut T> {
+        unsafe { (*self.inner.get()).as_mut() }
+    }
+
+    /// Initializes the contents of the cell to `value`.
+    ///
+    /// # Errors
+    ///
+    /// If the cell has already been initialized, it is left unchanged and
+    /// `value` is handed back in the `Err` variant.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(once_cell)]
+    ///
+    /// use std::cell::OnceCell;
+    ///
+    /// let cell = OnceCell::new();
+    /// assert_eq!(cell.set(92), Ok(()));
+    /// assert_eq!(cell.set(62), Err(62));
+    /// assert_eq!(cell.get(), Some(&92));
+    /// ```
+    #[unstable(feature = "once_cell", issue = "0")]
+    pub fn set(&self, value: T) -> Result<(), T> {
+        if self.get().is_some() {
+            return Err(value);
+        }
+        // Nobody can hold a reference into an empty cell, so this write
+        // can't invalidate one.
+        unsafe {
+            *self.inner.get() = Some(value);
+        }
+        Ok(())
+    }
+
+    /// Returns the contents of the cell, initializing it with `f` first if
+    /// it is empty.
+    ///
+    /// # Panics
+    ///
+    /// If `f` panics, the panic is propagated to the caller and the cell
+    /// remains uninitialized.
+    ///
+    /// It is an error to reentrantly initialize the cell from `f`. Doing so
+    /// results in a panic.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(once_cell)]
+    ///
+    /// use std::cell::OnceCell;
+    ///
+    /// let cell = OnceCell::new();
+    /// assert_eq!(*cell.get_or_init(|| 92), 92);
+    /// assert_eq!(*cell.get_or_init(|| unreachable!()), 92);
+    /// ```
+    #[unstable(feature = "once_cell", issue = "0")]
+    pub fn get_or_init<F>(&self, f: F) -> &T where F: FnOnce() -> T {
+        if let Some(value) = self.get() {
+            return value;
+        }
+        let value = f();
+        if self.set(value).is_err() {
+            panic!("OnceCell initialized reentrantly");
+        }
+        self.get().unwrap()
+    }
+
+    /// Consumes the cell, returning its contents if it was initialized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(once_cell)]
+    ///
+    /// use std::cell::OnceCell;
+    ///
+    /// let cell: OnceCell<String> = OnceCell::new();
+    /// assert_eq!(cell.into_inner(), None);
+    ///
+    /// let cell = OnceCell::from("hello".to_string());
+    /// assert_eq!(cell.into_inner(), Some("hello".to_string()));
+    /// ```
+    #[unstable(feature = "once_cell", issue = "0")]
+    #[inline]
+    pub fn into_inner(self) -> Option<T> {
+        self.inner.into_inner()
+    }
+}
+
 /// The core primitive for interior mutability in Rust.
 ///
 /// `UnsafeCell<T>` is a type that wraps some `T` and indicates unsafe interior operations on the
@@ -40,7 +40,7 @@ fn float_to_decimal_common_shortest<T>(fmt: &mut Formatter, num: &T,
         // enough for f32 and f64
         let mut buf: [u8; flt2dec::MAX_SIG_DIGITS] = mem::uninitialized();
         let mut parts: [flt2dec::Part; 4] = mem::uninitialized();
-        let formatted = flt2dec::to_shortest_str(flt2dec::strategy::grisu::format_shortest, *num,
+        let formatted = flt2dec::to_shortest_str(flt2dec::strategy::ryu::format_shortest, *num,
                                                  sign, precision, false, &mut buf, &mut parts);
         fmt.pad_formatted_parts(&formatted)
     }
@@ -96,7 +96,7 @@ fn float_to_exponential_common_shortest<T>(fmt: &mut Formatter,
         // enough for f32 and f64
         let mut buf: [u8; flt2dec::MAX_SIG_DIGITS] = mem::uninitialized();
         let mut parts: [flt2dec::Part; 6] = mem::uninitialized();
-        let formatted = flt2dec::to_shortest_exp_str(flt2dec::strategy::grisu::format_shortest,
+        let formatted = flt2dec::to_shortest_exp_str(flt2dec::strategy::ryu::format_shortest,
                                                      *num, sign, (0, 0), upper,
                                                      &mut buf, &mut parts);
         fmt.pad_formatted_parts(&formatted)
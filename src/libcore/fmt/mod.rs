@@ -12,7 +12,7 @@
 
 #![stable(feature = "rust1", since = "1.0.0")]
 
-use cell::{UnsafeCell, Cell, RefCell, Ref, RefMut};
+use cell::{UnsafeCell, Cell, RefCell, Ref, RefMut, OnceCell};
 use marker::PhantomData;
 use mem;
 use num::flt2dec;
@@ -42,6 +42,9 @@ pub enum Alignment {
 #[stable(feature = "debug_builders", since = "1.2.0")]
 pub use self::builders::{DebugStruct, DebugTuple, DebugSet, DebugList, DebugMap};
 
+#[unstable(feature = "fmt_num_buffer", issue = "0")]
+pub use self::num::{NumBuffer, FormatNum};
+
 #[unstable(feature = "fmt_internals", reason = "internal to format_args!",
            issue = "0")]
 #[doc(hidden)]
@@ -1296,7 +1299,14 @@ impl<'a> Formatter<'a> {
             self.align = old_align;
             ret
         } else {
-            // this is the common case and we take a shortcut
+            // this is the common case and we take a shortcut: short results
+            // are assembled on the stack and written in one go.
+            let mut buf: [u8; 64] = unsafe { mem::uninitialized() };
+            if formatted.len() <= buf.len() {
+                if let Some(len) = formatted.write(&mut buf) {
+                    return self.buf.write_str(unsafe { str::from_utf8_unchecked(&buf[..len]) });
+                }
+            }
             self.write_formatted_parts(formatted)
         }
     }
@@ -1803,6 +1813,32 @@ impl<T: ?Sized + Debug> Debug for RefCell<T> {
     }
 }
 
+#[unstable(feature = "once_cell", issue = "0")]
+impl<T: Debug> Debug for OnceCell<T> {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self.get() {
+            Some(value) => {
+                f.debug_struct("OnceCell")
+                    .field("value", value)
+                    .finish()
+            }
+            None => {
+                struct UninitPlaceholder;
+
+                impl Debug for UninitPlaceholder {
+                    fn fmt(&self, f: &mut Formatter) -> Result {
+                        f.write_str("<uninit>")
+                    }
+                }
+
+                f.debug_struct("OnceCell")
+                    .field("value", &UninitPlaceholder)
+                    .finish()
+            }
+        }
+    }
+}
+
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<'b, T: ?Sized + Debug> Debug for Ref<'b, T> {
     fn fmt(&self, f: &mut Formatter) -> Result {
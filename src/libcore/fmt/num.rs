@@ -16,7 +16,6 @@
 use fmt;
 use ops::{Div, Rem, Sub};
 use str;
-use slice;
 use ptr;
 use mem;
 
@@ -194,77 +193,214 @@ const DEC_DIGITS_LUT: &'static[u8] =
       6061626364656667686970717273747576777879\
       8081828384858687888990919293949596979899";
 
+macro_rules! write_digits {
+    ($($name:ident: $u:ident),*) => ($(
+    /// Writes the decimal digits of `n` to the end of `buf` and returns the index
+    /// of the first digit. `buf` has to hold at least 39 bytes.
+    fn $name(mut n: $u, buf: &mut [u8]) -> usize {
+        assert!(buf.len() >= 39);
+        let mut curr = buf.len() as isize;
+        let buf_ptr = buf.as_mut_ptr();
+        let lut_ptr = DEC_DIGITS_LUT.as_ptr();
+
+        unsafe {
+            // eagerly decode 4 characters at a time
+            while n >= 10000 {
+                let rem = (n % 10000) as isize;
+                n /= 10000;
+
+                let d1 = (rem / 100) << 1;
+                let d2 = (rem % 100) << 1;
+                curr -= 4;
+                ptr::copy_nonoverlapping(lut_ptr.offset(d1), buf_ptr.offset(curr), 2);
+                ptr::copy_nonoverlapping(lut_ptr.offset(d2), buf_ptr.offset(curr + 2), 2);
+            }
+
+            // if we reach here numbers are <= 9999, so at most 4 chars long
+            let mut n = n as isize; // possibly reduce 64bit math
+
+            // decode 2 more chars, if > 2 chars
+            if n >= 100 {
+                let d1 = (n % 100) << 1;
+                n /= 100;
+                curr -= 2;
+                ptr::copy_nonoverlapping(lut_ptr.offset(d1), buf_ptr.offset(curr), 2);
+            }
+
+            // decode last 1 or 2 chars
+            if n < 10 {
+                curr -= 1;
+                *buf_ptr.offset(curr) = (n as u8) + b'0';
+            } else {
+                let d1 = n << 1;
+                curr -= 2;
+                ptr::copy_nonoverlapping(lut_ptr.offset(d1), buf_ptr.offset(curr), 2);
+            }
+        }
+
+        curr as usize
+    }
+    )*);
+}
+
+write_digits!(write_u32: u32, write_u64: u64, write_u128: u128);
+
 macro_rules! impl_Display {
-    ($($t:ident),*: $conv_fn:ident) => ($(
+    ($($t:ident),*: $conv_fn:ident via $write_fn:ident) => ($(
     #[stable(feature = "rust1", since = "1.0.0")]
     impl fmt::Display for $t {
         #[allow(unused_comparisons)]
         fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
             let is_nonnegative = *self >= 0;
-            let mut n = if is_nonnegative {
+            let n = if is_nonnegative {
                 self.$conv_fn()
             } else {
                 // convert the negative num to positive by summing 1 to it's 2 complement
                 (!self.$conv_fn()).wrapping_add(1)
             };
-            let mut buf: [u8; 39] = unsafe { mem::uninitialized() };
-            let mut curr = buf.len() as isize;
-            let buf_ptr = buf.as_mut_ptr();
-            let lut_ptr = DEC_DIGITS_LUT.as_ptr();
-
-            unsafe {
-                // need at least 16 bits for the 4-characters-at-a-time to work.
-                if ::mem::size_of::<$t>() >= 2 {
-                    // eagerly decode 4 characters at a time
-                    while n >= 10000 {
-                        let rem = (n % 10000) as isize;
-                        n /= 10000;
-
-                        let d1 = (rem / 100) << 1;
-                        let d2 = (rem % 100) << 1;
-                        curr -= 4;
-                        ptr::copy_nonoverlapping(lut_ptr.offset(d1), buf_ptr.offset(curr), 2);
-                        ptr::copy_nonoverlapping(lut_ptr.offset(d2), buf_ptr.offset(curr + 2), 2);
-                    }
-                }
-
-                // if we reach here numbers are <= 9999, so at most 4 chars long
-                let mut n = n as isize; // possibly reduce 64bit math
+            // one more byte than the digits of `u128::MAX`, for the sign
+            let mut buf: [u8; 40] = unsafe { mem::uninitialized() };
+            let mut curr = $write_fn(n, &mut buf);
 
-                // decode 2 more chars, if > 2 chars
-                if n >= 100 {
-                    let d1 = (n % 100) << 1;
-                    n /= 100;
-                    curr -= 2;
-                    ptr::copy_nonoverlapping(lut_ptr.offset(d1), buf_ptr.offset(curr), 2);
-                }
-
-                // decode last 1 or 2 chars
-                if n < 10 {
+            // without a width or a `+` flag there is no padding to do,
+            // so the sign and the digits can be written at once.
+            if f.width.is_none() && !f.sign_plus() {
+                if !is_nonnegative {
                     curr -= 1;
-                    *buf_ptr.offset(curr) = (n as u8) + b'0';
-                } else {
-                    let d1 = n << 1;
-                    curr -= 2;
-                    ptr::copy_nonoverlapping(lut_ptr.offset(d1), buf_ptr.offset(curr), 2);
+                    buf[curr] = b'-';
                 }
+                return f.buf.write_str(unsafe { str::from_utf8_unchecked(&buf[curr..]) });
             }
+            f.pad_integral(is_nonnegative, "", unsafe { str::from_utf8_unchecked(&buf[curr..]) })
+        }
+    }
 
-            let buf_slice = unsafe {
-                str::from_utf8_unchecked(
-                    slice::from_raw_parts(buf_ptr.offset(curr), buf.len() - curr as usize))
+    #[unstable(feature = "fmt_num_buffer", issue = "0")]
+    impl FormatNum for $t {
+        #[allow(unused_comparisons)]
+        fn write_to(self, buf: &mut [u8; NUM_BUFFER_LEN]) -> &str {
+            let is_nonnegative = self >= 0;
+            let n = if is_nonnegative {
+                self.$conv_fn()
+            } else {
+                (!self.$conv_fn()).wrapping_add(1)
             };
-            f.pad_integral(is_nonnegative, "", buf_slice)
+            let buf = &mut buf[..40];
+            let mut curr = $write_fn(n, buf);
+            if !is_nonnegative {
+                curr -= 1;
+                buf[curr] = b'-';
+            }
+            unsafe { str::from_utf8_unchecked(&buf[curr..]) }
         }
-    })*);
+    }
+    )*);
 }
 
-impl_Display!(i8, u8, i16, u16, i32, u32: to_u32);
-impl_Display!(i64, u64: to_u64);
-impl_Display!(i128, u128: to_u128);
-#[cfg(target_pointer_width = "16")]
-impl_Display!(isize, usize: to_u16);
-#[cfg(target_pointer_width = "32")]
-impl_Display!(isize, usize: to_u32);
+impl_Display!(i8, u8, i16, u16, i32, u32: to_u32 via write_u32);
+impl_Display!(i64, u64: to_u64 via write_u64);
+impl_Display!(i128, u128: to_u128 via write_u128);
+#[cfg(any(target_pointer_width = "16", target_pointer_width = "32"))]
+impl_Display!(isize, usize: to_u32 via write_u32);
 #[cfg(target_pointer_width = "64")]
-impl_Display!(isize, usize: to_u64);
+impl_Display!(isize, usize: to_u64 via write_u64);
+
+macro_rules! impl_FormatNum_float {
+    ($($t:ident)*) => ($(
+    #[unstable(feature = "fmt_num_buffer", issue = "0")]
+    impl FormatNum for $t {
+        fn write_to(self, buf: &mut [u8; NUM_BUFFER_LEN]) -> &str {
+            // the same as `Display`, which uses `flt2dec::Sign::Minus` and
+            // no minimum number of fractional digits.
+            let mut digits = [0; flt2dec::MAX_SIG_DIGITS];
+            let mut parts = [flt2dec::Part::Zero(0); 4];
+            let formatted = flt2dec::to_shortest_str(flt2dec::strategy::ryu::format_shortest,
+                                                     self, flt2dec::Sign::Minus, 0, false,
+                                                     &mut digits, &mut parts);
+            let len = formatted.write(buf).expect("NUM_BUFFER_LEN is too small");
+            unsafe { str::from_utf8_unchecked(&buf[..len]) }
+        }
+    }
+    )*);
+}
+
+impl_FormatNum_float! { f32 f64 }
+
+/// The length of `NumBuffer`, long enough for the `Display` form of any `f64`.
+/// The longest ones are the negative subnormals written out in full, such as
+/// `-1.2567434849616886e-308` at 327 bytes.
+const NUM_BUFFER_LEN: usize = 327;
+
+/// A stack buffer for formatting numbers without allocating and without going
+/// through a `Formatter`.
+///
+/// The output is the same as that of the `Display` implementation of the number
+/// with no width, precision or flags, i.e. `format!("{}", n)`.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(fmt_num_buffer)]
+///
+/// use std::fmt::NumBuffer;
+///
+/// let mut buf = NumBuffer::new();
+/// assert_eq!(buf.format(-1234), "-1234");
+/// assert_eq!(buf.format(u64::max_value()), "18446744073709551615");
+/// assert_eq!(buf.format(0.3), "0.3");
+/// assert_eq!(buf.format(1e21f64), "1000000000000000000000");
+/// ```
+#[unstable(feature = "fmt_num_buffer", issue = "0")]
+#[derive(Copy)]
+pub struct NumBuffer {
+    buf: [u8; NUM_BUFFER_LEN],
+}
+
+#[unstable(feature = "fmt_num_buffer", issue = "0")]
+impl Clone for NumBuffer {
+    fn clone(&self) -> NumBuffer {
+        *self
+    }
+}
+
+#[unstable(feature = "fmt_num_buffer", issue = "0")]
+impl fmt::Debug for NumBuffer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad("NumBuffer { .. }")
+    }
+}
+
+#[unstable(feature = "fmt_num_buffer", issue = "0")]
+impl Default for NumBuffer {
+    fn default() -> NumBuffer {
+        NumBuffer::new()
+    }
+}
+
+impl NumBuffer {
+    /// Creates a new buffer. This is cheap, as the contents are not initialized.
+    #[unstable(feature = "fmt_num_buffer", issue = "0")]
+    #[inline]
+    pub fn new() -> NumBuffer {
+        NumBuffer { buf: unsafe { mem::uninitialized() } }
+    }
+
+    /// Formats `n` into the buffer and returns the formatted string, which
+    /// borrows the buffer until it is dropped.
+    #[unstable(feature = "fmt_num_buffer", issue = "0")]
+    #[inline]
+    pub fn format<N: FormatNum>(&mut self, n: N) -> &str {
+        n.write_to(&mut self.buf)
+    }
+}
+
+/// A number which can be formatted into a [`NumBuffer`].
+///
+/// This is implemented for all primitive integer and floating point types.
+///
+/// [`NumBuffer`]: struct.NumBuffer.html
+#[unstable(feature = "fmt_num_buffer", issue = "0")]
+pub trait FormatNum: Copy {
+    #[doc(hidden)]
+    fn write_to(self, buf: &mut [u8; NUM_BUFFER_LEN]) -> &str;
+}
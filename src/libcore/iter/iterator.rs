@@ -8,6 +8,7 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use array::FixedSizeArray;
 use cmp::Ordering;
 use ops::Try;
 
@@ -15,6 +16,7 @@ use super::{AlwaysOk, LoopState};
 use super::{Chain, Cycle, Cloned, Enumerate, Filter, FilterMap, FlatMap, Fuse};
 use super::{Inspect, Map, Peekable, Scan, Skip, SkipWhile, StepBy, Take, TakeWhile, Rev};
 use super::{Zip, Sum, Product};
+use super::{ArrayChunks, ArrayWindows, DedupByKey, GroupByKey, Interleave, Intersperse};
 use super::{ChainState, FromIterator, ZipImpl};
 
 fn _assert_is_object_safe(_: &Iterator<Item=()>) {}
@@ -1139,6 +1141,275 @@ pub trait Iterator {
         Inspect{iter: self, f: f}
     }
 
+    /// Creates an iterator which yields the elements in chunks of `N`, as
+    /// arrays of type `[T; N]`.
+    ///
+    /// The chunks don't overlap. If the number of elements is not a multiple
+    /// of `N`, the elements left over at the end are not yielded, but can be
+    /// retrieved with [`remainder`].
+    ///
+    /// If the iterator is both a [`DoubleEndedIterator`] and an
+    /// [`ExactSizeIterator`], the chunks can also be taken from the back, and
+    /// they are the same as from the front.
+    ///
+    /// [`remainder`]: struct.ArrayChunks.html#method.remainder
+    /// [`DoubleEndedIterator`]: trait.DoubleEndedIterator.html
+    /// [`ExactSizeIterator`]: trait.ExactSizeIterator.html
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is 0.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// #![feature(iter_array_chunks)]
+    ///
+    /// let mut iter = (1..8).array_chunks::<[_; 3]>();
+    ///
+    /// assert_eq!(iter.next(), Some([1, 2, 3]));
+    /// assert_eq!(iter.next(), Some([4, 5, 6]));
+    /// assert_eq!(iter.next(), None);
+    /// assert_eq!(iter.remainder(), &[7]);
+    /// ```
+    ///
+    /// Taking chunks from the back:
+    ///
+    /// ```
+    /// #![feature(iter_array_chunks)]
+    ///
+    /// let mut iter = (1..8).array_chunks::<[_; 3]>();
+    ///
+    /// assert_eq!(iter.next_back(), Some([4, 5, 6]));
+    /// assert_eq!(iter.remainder(), &[7]);
+    /// assert_eq!(iter.next_back(), Some([1, 2, 3]));
+    /// assert_eq!(iter.next_back(), None);
+    /// ```
+    #[inline]
+    #[unstable(feature = "iter_array_chunks", issue = "0")]
+    fn array_chunks<A>(self) -> ArrayChunks<Self, A> where
+        Self: Sized, A: FixedSizeArray<Self::Item>,
+    {
+        ArrayChunks::new(self)
+    }
+
+    /// Creates an iterator which yields all windows of `N` consecutive
+    /// elements, as arrays of type `[T; N]`.
+    ///
+    /// The windows overlap, so each one is a clone of the previous window,
+    /// shifted by one element. If there are fewer than `N` elements, no
+    /// window is yielded.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is 0.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// #![feature(iter_array_windows)]
+    ///
+    /// let mut iter = "rust".chars().array_windows::<[_; 2]>();
+    ///
+    /// assert_eq!(iter.next(), Some(['r', 'u']));
+    /// assert_eq!(iter.next(), Some(['u', 's']));
+    /// assert_eq!(iter.next(), Some(['s', 't']));
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    ///
+    /// Finding the differences between consecutive elements:
+    ///
+    /// ```
+    /// #![feature(iter_array_windows)]
+    ///
+    /// let a = [1, 4, 9, 16];
+    ///
+    /// let diffs: Vec<_> = a.iter().array_windows().map(|w: [&i32; 2]| w[1] - w[0]).collect();
+    ///
+    /// assert_eq!(diffs, [3, 5, 7]);
+    /// ```
+    #[inline]
+    #[unstable(feature = "iter_array_windows", issue = "0")]
+    fn array_windows<A>(self) -> ArrayWindows<Self, A> where
+        Self: Sized, A: FixedSizeArray<Self::Item> + Clone,
+    {
+        ArrayWindows::new(self)
+    }
+
+    /// Creates an iterator which alternates between the elements of this
+    /// iterator and another one.
+    ///
+    /// The first element comes from `self`. Once one of the iterators runs
+    /// out, the remaining elements of the other one follow.
+    ///
+    /// If both iterators are [`DoubleEndedIterator`]s and
+    /// [`ExactSizeIterator`]s, so is the new one.
+    ///
+    /// [`DoubleEndedIterator`]: trait.DoubleEndedIterator.html
+    /// [`ExactSizeIterator`]: trait.ExactSizeIterator.html
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// #![feature(iter_interleave)]
+    ///
+    /// let a = [1, 2, 3];
+    /// let b = [10, 20];
+    ///
+    /// let mut iter = a.iter().interleave(&b);
+    ///
+    /// assert_eq!(iter.next(), Some(&1));
+    /// assert_eq!(iter.next(), Some(&10));
+    /// assert_eq!(iter.next(), Some(&2));
+    /// assert_eq!(iter.next(), Some(&20));
+    /// assert_eq!(iter.next(), Some(&3));
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    #[inline]
+    #[unstable(feature = "iter_interleave", issue = "0")]
+    fn interleave<U>(self, other: U) -> Interleave<Self, U::IntoIter> where
+        Self: Sized, U: IntoIterator<Item=Self::Item>,
+    {
+        Interleave{a: self.fuse(), b: other.into_iter().fuse(), a_next: true}
+    }
+
+    /// Creates an iterator which places a clone of `separator` between
+    /// adjacent elements.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// #![feature(iter_intersperse)]
+    ///
+    /// let mut iter = [1, 2, 3].iter().intersperse(&0);
+    ///
+    /// assert_eq!(iter.next(), Some(&1));
+    /// assert_eq!(iter.next(), Some(&0));
+    /// assert_eq!(iter.next(), Some(&2));
+    /// assert_eq!(iter.next(), Some(&0));
+    /// assert_eq!(iter.next(), Some(&3));
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    ///
+    /// Joining words:
+    ///
+    /// ```
+    /// #![feature(iter_intersperse)]
+    ///
+    /// let s: String = ["one", "two", "three"].iter().cloned().intersperse(", ").collect();
+    ///
+    /// assert_eq!(s, "one, two, three");
+    /// ```
+    #[inline]
+    #[unstable(feature = "iter_intersperse", issue = "0")]
+    fn intersperse(self, separator: Self::Item) -> Intersperse<Self> where
+        Self: Sized, Self::Item: Clone,
+    {
+        Intersperse {
+            iter: self,
+            separator: separator,
+            front: None,
+            back: None,
+            front_sep: false,
+            back_sep: false,
+        }
+    }
+
+    /// Creates an iterator which removes consecutive elements that resolve
+    /// to the same key.
+    ///
+    /// Of each run of elements with equal keys, only the first one is
+    /// yielded, also when iterating from the back. If the keys of all equal
+    /// elements are adjacent, for example because the iterator is sorted by
+    /// them, this removes all duplicates.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// #![feature(iter_dedup_by_key)]
+    ///
+    /// let a = [10, 20, 21, 30, 11, 12];
+    ///
+    /// let v: Vec<_> = a.iter().dedup_by_key(|&&x| x / 10).collect();
+    ///
+    /// assert_eq!(v, [&10, &20, &30, &11]);
+    /// ```
+    #[inline]
+    #[unstable(feature = "iter_dedup_by_key", issue = "0")]
+    fn dedup_by_key<K, F>(self, f: F) -> DedupByKey<Self, F, K> where
+        Self: Sized, F: FnMut(&Self::Item) -> K, K: PartialEq,
+    {
+        DedupByKey{iter: self, f: f, front: None, back: None}
+    }
+
+    /// Splits an iterator into runs of consecutive elements that resolve to
+    /// the same key.
+    ///
+    /// The returned [`GroupByKey`] is iterated over by reference, yielding
+    /// each key along with an iterator over the elements of its run. The
+    /// elements are read from this iterator only as they are needed, and
+    /// the key function is called once for each of them. A run which isn't
+    /// iterated to the end is skipped when the next one is requested, and
+    /// yields nothing more afterwards, except for its first element.
+    ///
+    /// [`GroupByKey`]: struct.GroupByKey.html
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// #![feature(iter_group_by_key)]
+    ///
+    /// let a = [1, 3, 2, 4, 6, 5];
+    ///
+    /// let groups = a.iter().group_by_key(|&&x| x % 2 == 0);
+    /// let mut iter = groups.into_iter();
+    ///
+    /// let (key, group) = iter.next().unwrap();
+    /// assert_eq!(key, false);
+    /// assert_eq!(group.collect::<Vec<_>>(), [&1, &3]);
+    ///
+    /// let (key, group) = iter.next().unwrap();
+    /// assert_eq!(key, true);
+    /// assert_eq!(group.collect::<Vec<_>>(), [&2, &4, &6]);
+    ///
+    /// let (key, group) = iter.next().unwrap();
+    /// assert_eq!(key, false);
+    /// assert_eq!(group.collect::<Vec<_>>(), [&5]);
+    ///
+    /// assert!(iter.next().is_none());
+    /// ```
+    ///
+    /// Counting the length of each run:
+    ///
+    /// ```
+    /// #![feature(iter_group_by_key)]
+    ///
+    /// let mut runs = Vec::new();
+    /// for (c, run) in &"aabccc".chars().group_by_key(|&c| c) {
+    ///     runs.push((c, run.count()));
+    /// }
+    /// assert_eq!(runs, [('a', 2), ('b', 1), ('c', 3)]);
+    /// ```
+    #[inline]
+    #[unstable(feature = "iter_group_by_key", issue = "0")]
+    fn group_by_key<K, F>(self, f: F) -> GroupByKey<Self, F, K> where
+        Self: Sized, F: FnMut(&Self::Item) -> K, K: PartialEq + Clone,
+    {
+        GroupByKey::new(self, f)
+    }
+
     /// Borrows an iterator, rather than consuming it.
     ///
     /// This is useful to allow applying iterator adaptors while still
@@ -302,10 +302,15 @@
 
 #![stable(feature = "rust1", since = "1.0.0")]
 
+use array::FixedSizeArray;
+use cell::RefCell;
 use cmp;
 use fmt;
 use iter_private::TrustedRandomAccess;
+use marker::PhantomData;
+use mem::{self, ManuallyDrop};
 use ops::Try;
+use ptr;
 use usize;
 use intrinsics;
 
@@ -2888,3 +2893,844 @@ impl<I: ExactSizeIterator, F> ExactSizeIterator for Inspect<I, F>
 #[unstable(feature = "fused", issue = "35602")]
 impl<I: FusedIterator, F> FusedIterator for Inspect<I, F>
     where F: FnMut(&I::Item) {}
+
+/// A partially initialized array, used to collect the elements for
+/// [`ArrayChunks`] and [`ArrayWindows`].
+///
+/// Only the elements in `start..end` are initialized, and only those are
+/// dropped with the buffer.
+///
+/// [`ArrayChunks`]: struct.ArrayChunks.html
+/// [`ArrayWindows`]: struct.ArrayWindows.html
+struct ArrayBuf<T, A: FixedSizeArray<T>> {
+    array: ManuallyDrop<A>,
+    start: usize,
+    end: usize,
+    marker: PhantomData<T>,
+}
+
+impl<T, A: FixedSizeArray<T>> ArrayBuf<T, A> {
+    // Creates an empty buffer which grows in both directions from `pos`.
+    fn new(pos: usize) -> ArrayBuf<T, A> {
+        let buf = ArrayBuf {
+            array: ManuallyDrop::new(unsafe { mem::uninitialized() }),
+            start: pos,
+            end: pos,
+            marker: PhantomData,
+        };
+        debug_assert!(pos <= buf.capacity());
+        buf
+    }
+
+    fn capacity(&self) -> usize {
+        self.array.as_slice().len()
+    }
+
+    fn as_slice(&self) -> &[T] {
+        &self.array.as_slice()[self.start..self.end]
+    }
+
+    fn push_back(&mut self, elt: T) {
+        debug_assert!(self.end < self.capacity());
+        unsafe {
+            ptr::write(self.array.as_mut_slice().as_mut_ptr().offset(self.end as isize), elt);
+        }
+        self.end += 1;
+    }
+
+    fn push_front(&mut self, elt: T) {
+        debug_assert!(self.start > 0);
+        self.start -= 1;
+        unsafe {
+            ptr::write(self.array.as_mut_slice().as_mut_ptr().offset(self.start as isize), elt);
+        }
+    }
+
+    // Fills the back of the buffer from the front of `iter`, returning
+    // whether there were enough elements.
+    fn fill<I: Iterator<Item = T>>(&mut self, iter: &mut I) -> bool {
+        while self.end < self.capacity() {
+            match iter.next() {
+                Some(elt) => self.push_back(elt),
+                None => return false,
+            }
+        }
+        true
+    }
+
+    // Fills the front of the buffer from the back of `iter`, returning
+    // whether there were enough elements.
+    fn fill_back<I: DoubleEndedIterator<Item = T>>(&mut self, iter: &mut I) -> bool {
+        while self.start > 0 {
+            match iter.next_back() {
+                Some(elt) => self.push_front(elt),
+                None => return false,
+            }
+        }
+        true
+    }
+
+    fn into_array(self) -> A {
+        debug_assert!(self.start == 0 && self.end == self.capacity());
+        unsafe {
+            let array = ptr::read(&*self.array);
+            mem::forget(self);
+            array
+        }
+    }
+}
+
+impl<T: Clone, A: FixedSizeArray<T>> Clone for ArrayBuf<T, A> {
+    fn clone(&self) -> ArrayBuf<T, A> {
+        let mut buf = ArrayBuf::new(self.start);
+        for elt in self.as_slice() {
+            buf.push_back(elt.clone());
+        }
+        buf
+    }
+}
+
+impl<T, A: FixedSizeArray<T>> Drop for ArrayBuf<T, A> {
+    fn drop(&mut self) {
+        unsafe {
+            ptr::drop_in_place(&mut self.array.as_mut_slice()[self.start..self.end]);
+        }
+    }
+}
+
+// The length of the array type `A`.
+fn array_len<T, A: FixedSizeArray<T>>() -> usize {
+    ArrayBuf::<T, A>::new(0).capacity()
+}
+
+/// An iterator over non-overlapping chunks of the elements of another
+/// iterator, as arrays.
+///
+/// This `struct` is created by the [`array_chunks`] method on [`Iterator`].
+/// See its documentation for more.
+///
+/// [`array_chunks`]: trait.Iterator.html#method.array_chunks
+/// [`Iterator`]: trait.Iterator.html
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[unstable(feature = "iter_array_chunks", issue = "0")]
+pub struct ArrayChunks<I: Iterator, A: FixedSizeArray<I::Item>> {
+    iter: I,
+    remainder: Option<ArrayBuf<I::Item, A>>,
+}
+
+impl<I: Iterator, A: FixedSizeArray<I::Item>> ArrayChunks<I, A> {
+    fn new(iter: I) -> ArrayChunks<I, A> {
+        assert!(array_len::<I::Item, A>() != 0, "chunk size must be non-zero");
+        ArrayChunks { iter: iter, remainder: None }
+    }
+
+    /// Returns the elements at the end of the iterator which were too few
+    /// to make up a whole chunk.
+    ///
+    /// This is empty until the iterator has returned `None` from [`next`],
+    /// or an element from [`next_back`].
+    ///
+    /// [`next`]: trait.Iterator.html#tymethod.next
+    /// [`next_back`]: trait.DoubleEndedIterator.html#tymethod.next_back
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(iter_array_chunks)]
+    ///
+    /// let mut iter = "hello".chars().array_chunks::<[_; 2]>();
+    /// assert_eq!(iter.next(), Some(['h', 'e']));
+    /// assert_eq!(iter.next(), Some(['l', 'l']));
+    /// assert_eq!(iter.remainder(), &[]);
+    /// assert_eq!(iter.next(), None);
+    /// assert_eq!(iter.remainder(), &['o']);
+    /// ```
+    #[unstable(feature = "iter_array_chunks", issue = "0")]
+    pub fn remainder(&self) -> &[I::Item] {
+        match self.remainder {
+            Some(ref buf) => buf.as_slice(),
+            None => &[],
+        }
+    }
+}
+
+#[unstable(feature = "iter_array_chunks", issue = "0")]
+impl<I, A> Clone for ArrayChunks<I, A>
+    where I: Iterator + Clone, I::Item: Clone, A: FixedSizeArray<I::Item>
+{
+    fn clone(&self) -> ArrayChunks<I, A> {
+        ArrayChunks { iter: self.iter.clone(), remainder: self.remainder.clone() }
+    }
+}
+
+#[unstable(feature = "iter_array_chunks", issue = "0")]
+impl<I, A> fmt::Debug for ArrayChunks<I, A>
+    where I: Iterator + fmt::Debug, I::Item: fmt::Debug, A: FixedSizeArray<I::Item>
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ArrayChunks")
+            .field("iter", &self.iter)
+            .field("remainder", &self.remainder())
+            .finish()
+    }
+}
+
+#[unstable(feature = "iter_array_chunks", issue = "0")]
+impl<I: Iterator, A: FixedSizeArray<I::Item>> Iterator for ArrayChunks<I, A> {
+    type Item = A;
+
+    #[inline]
+    fn next(&mut self) -> Option<A> {
+        let mut buf = ArrayBuf::new(0);
+        if buf.fill(&mut self.iter) {
+            return Some(buf.into_array());
+        }
+        // Don't let an empty buffer replace the remainder when `next` is
+        // called again.
+        if buf.end != 0 {
+            self.remainder = Some(buf);
+        }
+        None
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = array_len::<I::Item, A>();
+        let (lower, upper) = self.iter.size_hint();
+        (lower / n, upper.map(|upper| upper / n))
+    }
+}
+
+#[unstable(feature = "iter_array_chunks", issue = "0")]
+impl<I, A> DoubleEndedIterator for ArrayChunks<I, A>
+    where I: DoubleEndedIterator + ExactSizeIterator, A: FixedSizeArray<I::Item>
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<A> {
+        let n = array_len::<I::Item, A>();
+        // The elements which don't make up a whole chunk are at the back, so
+        // they have to be set aside first.
+        let rem = self.iter.len() % n;
+        if rem != 0 {
+            let mut remainder = ArrayBuf::new(rem);
+            remainder.fill_back(&mut self.iter);
+            self.remainder = Some(remainder);
+        }
+        let mut buf = ArrayBuf::new(n);
+        if buf.fill_back(&mut self.iter) {
+            Some(buf.into_array())
+        } else {
+            None
+        }
+    }
+}
+
+#[unstable(feature = "iter_array_chunks", issue = "0")]
+impl<I, A> ExactSizeIterator for ArrayChunks<I, A>
+    where I: ExactSizeIterator, A: FixedSizeArray<I::Item> {}
+
+#[unstable(feature = "fused", issue = "35602")]
+impl<I, A> FusedIterator for ArrayChunks<I, A>
+    where I: FusedIterator, A: FixedSizeArray<I::Item> {}
+
+/// An iterator over overlapping windows of the elements of another iterator,
+/// as arrays.
+///
+/// This `struct` is created by the [`array_windows`] method on [`Iterator`].
+/// See its documentation for more.
+///
+/// [`array_windows`]: trait.Iterator.html#method.array_windows
+/// [`Iterator`]: trait.Iterator.html
+#[derive(Clone, Debug)]
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[unstable(feature = "iter_array_windows", issue = "0")]
+pub struct ArrayWindows<I, A> {
+    iter: I,
+    window: Option<A>,
+}
+
+impl<I: Iterator, A: FixedSizeArray<I::Item>> ArrayWindows<I, A> {
+    fn new(iter: I) -> ArrayWindows<I, A> {
+        assert!(array_len::<I::Item, A>() != 0, "window size must be non-zero");
+        ArrayWindows { iter: iter, window: None }
+    }
+}
+
+#[unstable(feature = "iter_array_windows", issue = "0")]
+impl<I, A> Iterator for ArrayWindows<I, A>
+    where I: Iterator, A: FixedSizeArray<I::Item> + Clone
+{
+    type Item = A;
+
+    #[inline]
+    fn next(&mut self) -> Option<A> {
+        match self.window {
+            None => {
+                let mut buf = ArrayBuf::new(0);
+                if !buf.fill(&mut self.iter) {
+                    return None;
+                }
+                self.window = Some(buf.into_array());
+            }
+            Some(ref mut window) => {
+                let elt = self.iter.next()?;
+                let window = window.as_mut_slice();
+                window.rotate_left(1);
+                *window.last_mut().unwrap() = elt;
+            }
+        }
+        self.window.clone()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        if self.window.is_some() {
+            return (lower, upper);
+        }
+        // The first window takes up `n` elements, every further one only one.
+        let n = array_len::<I::Item, A>() - 1;
+        (lower.saturating_sub(n), upper.map(|upper| upper.saturating_sub(n)))
+    }
+}
+
+#[unstable(feature = "iter_array_windows", issue = "0")]
+impl<I, A> ExactSizeIterator for ArrayWindows<I, A>
+    where I: ExactSizeIterator, A: FixedSizeArray<I::Item> + Clone {}
+
+#[unstable(feature = "fused", issue = "35602")]
+impl<I, A> FusedIterator for ArrayWindows<I, A>
+    where I: FusedIterator, A: FixedSizeArray<I::Item> + Clone {}
+
+/// An iterator that alternates between the elements of two other iterators.
+///
+/// This `struct` is created by the [`interleave`] method on [`Iterator`]. See
+/// its documentation for more.
+///
+/// [`interleave`]: trait.Iterator.html#method.interleave
+/// [`Iterator`]: trait.Iterator.html
+#[derive(Clone, Debug)]
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[unstable(feature = "iter_interleave", issue = "0")]
+pub struct Interleave<A, B> {
+    a: Fuse<A>,
+    b: Fuse<B>,
+    // Whether it is `a`'s turn at the front.
+    a_next: bool,
+}
+
+#[unstable(feature = "iter_interleave", issue = "0")]
+impl<A, B> Iterator for Interleave<A, B> where
+    A: Iterator,
+    B: Iterator<Item = A::Item>
+{
+    type Item = A::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<A::Item> {
+        if self.a_next {
+            self.a_next = false;
+            match self.a.next() {
+                None => self.b.next(),
+                elt => elt,
+            }
+        } else {
+            self.a_next = true;
+            match self.b.next() {
+                None => self.a.next(),
+                elt => elt,
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (a_lower, a_upper) = self.a.size_hint();
+        let (b_lower, b_upper) = self.b.size_hint();
+
+        let lower = a_lower.saturating_add(b_lower);
+
+        let upper = match (a_upper, b_upper) {
+            (Some(x), Some(y)) => x.checked_add(y),
+            _ => None
+        };
+
+        (lower, upper)
+    }
+}
+
+#[unstable(feature = "iter_interleave", issue = "0")]
+impl<A, B> DoubleEndedIterator for Interleave<A, B> where
+    A: DoubleEndedIterator + ExactSizeIterator,
+    B: DoubleEndedIterator<Item = A::Item> + ExactSizeIterator,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<A::Item> {
+        // The iterator whose turn it is at the front also provides the last
+        // element, unless the other one is longer.
+        let (a_len, b_len) = (self.a.len(), self.b.len());
+        let from_a = if self.a_next { a_len > b_len } else { a_len >= b_len };
+        if from_a {
+            self.a.next_back()
+        } else {
+            self.b.next_back()
+        }
+    }
+}
+
+#[unstable(feature = "fused", issue = "35602")]
+impl<A, B> FusedIterator for Interleave<A, B>
+    where A: Iterator,
+          B: Iterator<Item=A::Item>,
+{}
+
+#[unstable(feature = "trusted_len", issue = "37572")]
+unsafe impl<A, B> TrustedLen for Interleave<A, B>
+    where A: TrustedLen, B: TrustedLen<Item=A::Item>,
+{}
+
+/// An iterator that places a separator between the elements of another
+/// iterator.
+///
+/// This `struct` is created by the [`intersperse`] method on [`Iterator`].
+/// See its documentation for more.
+///
+/// [`intersperse`]: trait.Iterator.html#method.intersperse
+/// [`Iterator`]: trait.Iterator.html
+#[derive(Clone, Debug)]
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[unstable(feature = "iter_intersperse", issue = "0")]
+pub struct Intersperse<I: Iterator> {
+    iter: I,
+    separator: I::Item,
+    // Elements taken from either end of `iter` to find out whether there are
+    // any left.
+    front: Option<I::Item>,
+    back: Option<I::Item>,
+    // Whether an element has been yielded from the front (back), so that a
+    // separator has to come next from that end.
+    front_sep: bool,
+    back_sep: bool,
+}
+
+impl<I: Iterator> Intersperse<I> where I::Item: Clone {
+    // Once all elements are gone, there is still one separator owed between
+    // the elements yielded from the front and those yielded from the back.
+    fn last_separator(&mut self) -> Option<I::Item> {
+        if self.front_sep && self.back_sep {
+            self.front_sep = false;
+            self.back_sep = false;
+            Some(self.separator.clone())
+        } else {
+            None
+        }
+    }
+}
+
+#[unstable(feature = "iter_intersperse", issue = "0")]
+impl<I: Iterator> Iterator for Intersperse<I> where I::Item: Clone {
+    type Item = I::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<I::Item> {
+        if self.front.is_none() {
+            self.front = match self.iter.next() {
+                None => self.back.take(),
+                elt => elt,
+            };
+            if self.front.is_none() {
+                return self.last_separator();
+            }
+        }
+        if self.front_sep {
+            self.front_sep = false;
+            Some(self.separator.clone())
+        } else {
+            self.front_sep = true;
+            self.front.take()
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        let peeked = self.front.is_some() as usize + self.back.is_some() as usize;
+        let seps = self.front_sep as usize + self.back_sep as usize;
+        // `n` elements need `n - 1` separators between them, and one more for
+        // each end which has already yielded an element.
+        let len = |n: usize| {
+            if n == 0 {
+                Some((self.front_sep && self.back_sep) as usize)
+            } else {
+                n.checked_mul(2).and_then(|len| (len - 1).checked_add(seps))
+            }
+        };
+        let lower = lower.checked_add(peeked).and_then(&len).unwrap_or(usize::MAX);
+        let upper = upper.and_then(|upper| upper.checked_add(peeked)).and_then(len);
+        (lower, upper)
+    }
+}
+
+#[unstable(feature = "iter_intersperse", issue = "0")]
+impl<I: DoubleEndedIterator> DoubleEndedIterator for Intersperse<I> where I::Item: Clone {
+    #[inline]
+    fn next_back(&mut self) -> Option<I::Item> {
+        if self.back.is_none() {
+            self.back = match self.iter.next_back() {
+                None => self.front.take(),
+                elt => elt,
+            };
+            if self.back.is_none() {
+                return self.last_separator();
+            }
+        }
+        if self.back_sep {
+            self.back_sep = false;
+            Some(self.separator.clone())
+        } else {
+            self.back_sep = true;
+            self.back.take()
+        }
+    }
+}
+
+#[unstable(feature = "fused", issue = "35602")]
+impl<I: FusedIterator> FusedIterator for Intersperse<I> where I::Item: Clone {}
+
+#[unstable(feature = "trusted_len", issue = "37572")]
+unsafe impl<I: TrustedLen> TrustedLen for Intersperse<I> where I::Item: Clone {}
+
+/// An iterator that removes consecutive elements with the same key from
+/// another iterator.
+///
+/// This `struct` is created by the [`dedup_by_key`] method on [`Iterator`].
+/// See its documentation for more.
+///
+/// [`dedup_by_key`]: trait.Iterator.html#method.dedup_by_key
+/// [`Iterator`]: trait.Iterator.html
+#[derive(Clone)]
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[unstable(feature = "iter_dedup_by_key", issue = "0")]
+pub struct DedupByKey<I: Iterator, F, K> {
+    iter: I,
+    f: F,
+    // The element at the front is the first of its run. The one at the back
+    // is the last of its run, and the run may extend further to the front.
+    front: Option<(K, I::Item)>,
+    back: Option<(K, I::Item)>,
+}
+
+#[unstable(feature = "iter_dedup_by_key", issue = "0")]
+impl<I, F, K> fmt::Debug for DedupByKey<I, F, K>
+    where I: Iterator + fmt::Debug, I::Item: fmt::Debug, K: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("DedupByKey")
+            .field("iter", &self.iter)
+            .field("front", &self.front)
+            .field("back", &self.back)
+            .finish()
+    }
+}
+
+#[unstable(feature = "iter_dedup_by_key", issue = "0")]
+impl<I, F, K> Iterator for DedupByKey<I, F, K>
+    where I: Iterator, F: FnMut(&I::Item) -> K, K: PartialEq
+{
+    type Item = I::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<I::Item> {
+        let (key, first) = match self.front.take() {
+            Some(front) => front,
+            None => match self.iter.next() {
+                Some(elt) => ((self.f)(&elt), elt),
+                None => return self.back.take().map(|(_, elt)| elt),
+            },
+        };
+        // Skip the rest of the run.
+        loop {
+            match self.iter.next() {
+                Some(elt) => {
+                    let k = (self.f)(&elt);
+                    if k != key {
+                        self.front = Some((k, elt));
+                        break;
+                    }
+                }
+                None => {
+                    if let Some((k, elt)) = self.back.take() {
+                        if k != key {
+                            self.back = Some((k, elt));
+                        }
+                    }
+                    break;
+                }
+            }
+        }
+        Some(first)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        let peeked = self.front.is_some() as usize + self.back.is_some() as usize;
+        // Any number of elements can be removed, except for the first one.
+        let lower = (lower != 0 || peeked != 0) as usize;
+        (lower, upper.and_then(|upper| upper.checked_add(peeked)))
+    }
+}
+
+#[unstable(feature = "iter_dedup_by_key", issue = "0")]
+impl<I, F, K> DoubleEndedIterator for DedupByKey<I, F, K>
+    where I: DoubleEndedIterator, F: FnMut(&I::Item) -> K, K: PartialEq
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<I::Item> {
+        let (key, mut first) = match self.back.take() {
+            Some(back) => back,
+            None => match self.iter.next_back() {
+                Some(elt) => ((self.f)(&elt), elt),
+                None => return self.front.take().map(|(_, elt)| elt),
+            },
+        };
+        // Walk back to the start of the run, as its first element is the one
+        // to keep.
+        loop {
+            match self.iter.next_back() {
+                Some(elt) => {
+                    let k = (self.f)(&elt);
+                    if k != key {
+                        self.back = Some((k, elt));
+                        break;
+                    }
+                    first = elt;
+                }
+                None => {
+                    if let Some((k, elt)) = self.front.take() {
+                        if k == key {
+                            first = elt;
+                        } else {
+                            self.front = Some((k, elt));
+                        }
+                    }
+                    break;
+                }
+            }
+        }
+        Some(first)
+    }
+}
+
+#[unstable(feature = "fused", issue = "35602")]
+impl<I, F, K> FusedIterator for DedupByKey<I, F, K>
+    where I: FusedIterator, F: FnMut(&I::Item) -> K, K: PartialEq {}
+
+/// Runs of consecutive elements with the same key in another iterator.
+///
+/// This `struct` is created by the [`group_by_key`] method on [`Iterator`].
+/// See its documentation for more.
+///
+/// It is iterated over by reference, which yields a key and a [`Group`]
+/// for each run.
+///
+/// [`group_by_key`]: trait.Iterator.html#method.group_by_key
+/// [`Iterator`]: trait.Iterator.html
+/// [`Group`]: struct.Group.html
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[unstable(feature = "iter_group_by_key", issue = "0")]
+pub struct GroupByKey<I: Iterator, F, K> {
+    inner: RefCell<GroupInner<I, F, K>>,
+}
+
+/// The state shared by a `GroupByKey` and its groups.
+struct GroupInner<I: Iterator, F, K> {
+    iter: I,
+    f: F,
+    /// The key of the group being read from `iter`, until an element with
+    /// another key or the end of `iter` is reached.
+    key: Option<K>,
+    /// The first element of the next group and its key, which had to be
+    /// read to find the end of the current one.
+    next: Option<(K, I::Item)>,
+    /// The number of groups started so far. The latest one is the only one
+    /// still reading from `iter`.
+    groups: usize,
+}
+
+#[unstable(feature = "iter_group_by_key", issue = "0")]
+impl<I: Iterator + fmt::Debug, F, K> fmt::Debug for GroupByKey<I, F, K> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.inner.try_borrow() {
+            Ok(inner) => f.debug_struct("GroupByKey").field("iter", &inner.iter).finish(),
+            Err(_) => f.debug_struct("GroupByKey").field("iter", &"<borrowed>").finish(),
+        }
+    }
+}
+
+impl<I: Iterator, F, K> GroupByKey<I, F, K>
+    where F: FnMut(&I::Item) -> K, K: PartialEq
+{
+    fn new(iter: I, f: F) -> GroupByKey<I, F, K> {
+        GroupByKey {
+            inner: RefCell::new(GroupInner { iter, f, key: None, next: None, groups: 0 }),
+        }
+    }
+}
+
+impl<I: Iterator, F, K> GroupInner<I, F, K>
+    where F: FnMut(&I::Item) -> K, K: PartialEq
+{
+    /// Reads the next element of the current group, if there is one.
+    fn next_in_group(&mut self) -> Option<I::Item> {
+        let elt = match self.key {
+            Some(_) => self.iter.next(),
+            None => return None,
+        };
+        match elt {
+            Some(elt) => {
+                let key = (self.f)(&elt);
+                if Some(&key) == self.key.as_ref() {
+                    Some(elt)
+                } else {
+                    self.key = None;
+                    self.next = Some((key, elt));
+                    None
+                }
+            }
+            None => {
+                self.key = None;
+                None
+            }
+        }
+    }
+}
+
+#[unstable(feature = "iter_group_by_key", issue = "0")]
+impl<'a, I: Iterator, F, K> IntoIterator for &'a GroupByKey<I, F, K>
+    where F: FnMut(&I::Item) -> K, K: PartialEq + Clone
+{
+    type Item = (K, Group<'a, I, F, K>);
+    type IntoIter = Groups<'a, I, F, K>;
+
+    fn into_iter(self) -> Groups<'a, I, F, K> {
+        Groups { parent: self }
+    }
+}
+
+/// An iterator over the groups of a [`GroupByKey`].
+///
+/// This `struct` is created by iterating over a reference to a
+/// [`GroupByKey`].
+///
+/// [`GroupByKey`]: struct.GroupByKey.html
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[unstable(feature = "iter_group_by_key", issue = "0")]
+pub struct Groups<'a, I: Iterator + 'a, F: 'a, K: 'a> {
+    parent: &'a GroupByKey<I, F, K>,
+}
+
+#[unstable(feature = "iter_group_by_key", issue = "0")]
+impl<'a, I: Iterator + fmt::Debug + 'a, F: 'a, K: 'a> fmt::Debug for Groups<'a, I, F, K> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Groups")
+            .field("parent", self.parent)
+            .finish()
+    }
+}
+
+#[unstable(feature = "iter_group_by_key", issue = "0")]
+impl<'a, I: Iterator + 'a, F: 'a, K: 'a> Iterator for Groups<'a, I, F, K>
+    where F: FnMut(&I::Item) -> K, K: PartialEq + Clone
+{
+    type Item = (K, Group<'a, I, F, K>);
+
+    fn next(&mut self) -> Option<(K, Group<'a, I, F, K>)> {
+        let mut inner = self.parent.inner.borrow_mut();
+        // skip what is left of the current group
+        while let Some(_) = inner.next_in_group() {}
+
+        let (key, first) = match inner.next.take() {
+            Some(next) => next,
+            None => {
+                let first = inner.iter.next()?;
+                ((inner.f)(&first), first)
+            }
+        };
+        inner.key = Some(key.clone());
+        inner.groups += 1;
+        Some((key, Group { parent: self.parent, index: inner.groups, first: Some(first) }))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let inner = self.parent.inner.borrow();
+        let (lower, upper) = inner.iter.size_hint();
+        if inner.next.is_some() {
+            (1, upper.and_then(|upper| upper.checked_add(1)))
+        } else if inner.key.is_some() {
+            // the rest may all belong to the current group
+            (0, upper)
+        } else {
+            (cmp::min(lower, 1), upper)
+        }
+    }
+}
+
+/// An iterator over one run of consecutive elements with the same key.
+///
+/// This `struct` is yielded with the key of the run when iterating over a
+/// [`GroupByKey`]. It reads the elements of the run from the underlying
+/// iterator as they are needed, so it yields nothing more once the next
+/// group has been requested, except for the first element of the run.
+///
+/// [`GroupByKey`]: struct.GroupByKey.html
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+#[unstable(feature = "iter_group_by_key", issue = "0")]
+pub struct Group<'a, I: Iterator + 'a, F: 'a, K: 'a> {
+    parent: &'a GroupByKey<I, F, K>,
+    index: usize,
+    first: Option<I::Item>,
+}
+
+#[unstable(feature = "iter_group_by_key", issue = "0")]
+impl<'a, I: Iterator + 'a, F: 'a, K: 'a> fmt::Debug for Group<'a, I, F, K>
+    where I::Item: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Group")
+            .field("index", &self.index)
+            .field("first", &self.first)
+            .finish()
+    }
+}
+
+#[unstable(feature = "iter_group_by_key", issue = "0")]
+impl<'a, I: Iterator + 'a, F: 'a, K: 'a> Iterator for Group<'a, I, F, K>
+    where F: FnMut(&I::Item) -> K, K: PartialEq
+{
+    type Item = I::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<I::Item> {
+        if let Some(first) = self.first.take() {
+            return Some(first);
+        }
+        let mut inner = self.parent.inner.borrow_mut();
+        if inner.groups != self.index {
+            return None;
+        }
+        inner.next_in_group()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let first = self.first.is_some() as usize;
+        let inner = self.parent.inner.borrow();
+        if inner.groups != self.index || inner.key.is_none() {
+            return (first, Some(first));
+        }
+        let (_, upper) = inner.iter.size_hint();
+        (first, upper.and_then(|upper| upper.checked_add(first)))
+    }
+}
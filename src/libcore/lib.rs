@@ -78,6 +78,7 @@
 #![feature(inclusive_range_syntax)]
 #![feature(intrinsics)]
 #![feature(lang_items)]
+#![feature(link_llvm_intrinsics)]
 #![feature(never_type)]
 #![feature(no_core)]
 #![feature(on_unimplemented)]
@@ -87,6 +88,7 @@
 #![feature(rustc_attrs)]
 #![feature(specialization)]
 #![feature(staged_api)]
+#![feature(target_feature)]
 #![feature(unboxed_closures)]
 #![feature(untagged_unions)]
 #![feature(unwind_attributes)]
@@ -170,6 +172,8 @@ pub mod str;
 pub mod hash;
 pub mod fmt;
 pub mod time;
+pub mod simd;
+pub mod arch;
 
 // note: does not need to be public
 mod char_private;
@@ -95,7 +95,7 @@
 use fmt;
 use str::FromStr;
 
-use self::parse::{parse_decimal, Decimal, Sign, ParseResult};
+use self::parse::{parse_decimal, decimal_prefix_len, Decimal, Sign, ParseResult};
 use self::num::digits_to_big;
 use self::rawfp::RawFloat;
 
@@ -228,6 +228,38 @@ fn dec2flt<T: RawFloat>(s: &str) -> Result<T, ParseFloatError> {
     }
 }
 
+/// Convert the longest prefix of a string which is a decimal float (as accepted by `FromStr`)
+/// into a floating point number, and return it together with the length of that prefix.
+pub fn dec2flt_prefix<T: RawFloat>(s: &str) -> Result<(T, usize), ParseFloatError> {
+    if s.is_empty() {
+        return Err(pfe_empty())
+    }
+    let (sign, rest) = extract_sign(s);
+    let mut len = decimal_prefix_len(rest);
+    let flt = if len > 0 {
+        match parse_decimal(&rest[..len]) {
+            ParseResult::Valid(decimal) => convert(decimal)?,
+            ParseResult::ShortcutToInf => T::INFINITY,
+            ParseResult::ShortcutToZero => T::ZERO,
+            ParseResult::Invalid => { return Err(pfe_invalid()); }
+        }
+    } else if rest.starts_with("inf") {
+        len = 3;
+        T::INFINITY
+    } else if rest.starts_with("NaN") {
+        len = 3;
+        T::NAN
+    } else {
+        return Err(pfe_invalid());
+    };
+
+    let len = s.len() - rest.len() + len;
+    match sign {
+        Sign::Positive => Ok((flt, len)),
+        Sign::Negative => Ok((-flt, len)),
+    }
+}
+
 /// The main workhorse for the decimal-to-float conversion: Orchestrate all the preprocessing
 /// and figure out which algorithm should do the actual conversion.
 fn convert<T: RawFloat>(mut decimal: Decimal) -> Result<T, ParseFloatError> {
@@ -87,6 +87,39 @@ pub fn parse_decimal(s: &str) -> ParseResult {
     }
 }
 
+/// Find the length of the longest prefix of the string which `parse_decimal` accepts,
+/// or zero if there is none. Does not handle signs.
+pub fn decimal_prefix_len(s: &str) -> usize {
+    let s = s.as_bytes();
+    let (integral, mut s) = eat_digits(s);
+    let mut len = integral.len();
+
+    if s.first() == Some(&b'.') {
+        let (fractional, rest) = eat_digits(&s[1..]);
+        if integral.is_empty() && fractional.is_empty() {
+            return 0; // A lone '.'
+        }
+        len += 1 + fractional.len();
+        s = rest;
+    }
+    if len == 0 {
+        return 0;
+    }
+
+    // The exponent is only part of the number if it has digits, otherwise the 'e' is left alone.
+    if let Some(&b'e') | Some(&b'E') = s.first() {
+        let sign_len = match s.get(1) {
+            Some(&b'-') | Some(&b'+') => 1,
+            _ => 0,
+        };
+        let (number, _) = eat_digits(&s[1 + sign_len..]);
+        if !number.is_empty() {
+            len += 1 + sign_len + number.len();
+        }
+    }
+    len
+}
+
 /// Carve off decimal digits up to the first non-digit character.
 fn eat_digits(s: &[u8]) -> (&[u8], &[u8]) {
     let mut i = 0;
@@ -76,14 +76,20 @@ There are two classes of algorithms widely known to be correct.
   representation which is at least guaranteed to be shortest. The variant,
   Grisu3, actively detects if the resulting representation is incorrect.
 
-We implement both algorithms with necessary tweaks to suit our requirements.
+For the shortest mode there is a newer algorithm as well.
+
+- The "Ryu" algorithm is described by Ulf Adams. Much like Grisu, it only
+  needs integer arithmetic and a table of powers, but the table is exact enough
+  that the shortest representation can be found without any fallback.
+
+We implement all three algorithms with necessary tweaks to suit our requirements.
 In particular, published literatures are short of the actual implementation
 difficulties like how to avoid arithmetic overflows. Each implementation,
-available in `strategy::dragon` and `strategy::grisu` respectively,
+available in `strategy::dragon`, `strategy::grisu` and `strategy::ryu` respectively,
 extensively describes all necessary justifications and many proofs for them.
 (It is still difficult to follow though. You have been warned.)
 
-Both implementations expose two public functions:
+Dragon and Grisu expose two public functions:
 
 - `format_shortest(decoded, buf)`, which always needs at least
   `MAX_SIG_DIGITS` digits of buffer. Implements the shortest mode.
@@ -94,6 +100,7 @@ Both implementations expose two public functions:
 They try to fill the `u8` buffer with digits and returns the number of digits
 written and the exponent `k`. They are total for all finite `f32` and `f64`
 inputs (Grisu internally falls back to Dragon if necessary).
+Ryu only implements `format_shortest`, which is used for the shortest mode.
 
 The rendered digits are formatted into the actual string form with
 four functions:
@@ -140,6 +147,7 @@ pub mod decoder;
 pub mod strategy {
     pub mod dragon;
     pub mod grisu;
+    pub mod ryu;
 }
 
 /// The minimum size of buffer necessary for the shortest mode.
@@ -402,7 +410,7 @@ fn determine_sign(sign: Sign, decoded: &FullDecoded, negative: bool) -> &'static
 /// (which can be an empty string if no sign is rendered).
 ///
 /// `format_shortest` should be the underlying digit-generation function.
-/// You probably would want `strategy::grisu::format_shortest` for this.
+/// You probably would want `strategy::ryu::format_shortest` for this.
 ///
 /// `frac_digits` can be less than the number of actual fractional digits in `v`;
 /// it will be ignored and full digits will be printed. It is only used to print
@@ -457,7 +465,7 @@ pub fn to_shortest_str<'a, T, F>(mut format_shortest: F, v: T,
 /// an empty string if no sign is rendered).
 ///
 /// `format_shortest` should be the underlying digit-generation function.
-/// You probably would want `strategy::grisu::format_shortest` for this.
+/// You probably would want `strategy::ryu::format_shortest` for this.
 ///
 /// The `dec_bounds` is a tuple `(lo, hi)` such that the number is formatted
 /// as decimal only when `10^lo <= V < 10^hi`. Note that this is the *apparent* `V`
@@ -0,0 +1,233 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+/*!
+Rust adaptation of the Ryu algorithm described in [1]. It computes the
+shortest representation directly with 128-bit multiplications by a table of
+truncated powers of five (about 10KB), so unlike Grisu it never has to give up
+on an input within its domain.
+
+The domain covers every finite `f32` and `f64`; other `Decoded` values (only
+ever constructed by tests) are passed on to Grisu.
+
+[1] Ulf Adams. 2018. Ryu: fast float-to-string conversion. SIGPLAN Not. 53, 4
+    (June 2018), 270-282.
+*/
+
+use num::flt2dec::{Decoded, MAX_SIG_DIGITS};
+
+use self::table::{POW5_BITCOUNT, POW5_INV_BITCOUNT, POW5_SPLIT, POW5_INV_SPLIT};
+
+mod table;
+
+// the smallest and largest binary exponents of the interval below, as used by
+// the subnormal `f64`s and `f64::MAX` respectively. the tables cover them.
+const MIN_E2: i32 = -1076;
+const MAX_E2: i32 = 969;
+
+/// Returns `ceil(log_2(5^e))` for `0 <= e <= 3528`, or 1 when `e == 0`.
+#[doc(hidden)]
+pub fn pow5bits(e: i32) -> i32 {
+    debug_assert!(0 <= e && e <= 3528);
+    (((e as u32) * 1217359) >> 19) as i32 + 1
+}
+
+/// Returns `floor(log_10(2^e))` for `0 <= e <= 1650`.
+#[doc(hidden)]
+pub fn log10_pow2(e: i32) -> u32 {
+    debug_assert!(0 <= e && e <= 1650);
+    ((e as u32) * 78913) >> 18
+}
+
+/// Returns `floor(log_10(5^e))` for `0 <= e <= 2620`.
+#[doc(hidden)]
+pub fn log10_pow5(e: i32) -> u32 {
+    debug_assert!(0 <= e && e <= 2620);
+    ((e as u32) * 732923) >> 20
+}
+
+fn pow5_factor(mut v: u64) -> u32 {
+    let mut count = 0;
+    while v % 5 == 0 {
+        v /= 5;
+        count += 1;
+    }
+    count
+}
+
+fn multiple_of_power_of_5(v: u64, p: u32) -> bool {
+    pow5_factor(v) >= p
+}
+
+fn multiple_of_power_of_2(v: u64, p: u32) -> bool {
+    debug_assert!(p < 64);
+    v & ((1 << p) - 1) == 0
+}
+
+/// Returns `floor(m * mul / 2^j)`, where `j >= 64` and the result fits in `u64`.
+fn mul_shift(m: u64, mul: u128, j: i32) -> u64 {
+    debug_assert!(j >= 64);
+    let lo = (m as u128) * (mul as u64 as u128);
+    let hi = (m as u128) * (mul >> 64);
+    (((lo >> 64) + hi) >> (j - 64)) as u64
+}
+
+/// The shortest mode implementation for Ryu.
+///
+/// It returns `None` when the input is outside of the domain of the tables.
+pub fn format_shortest_opt(d: &Decoded,
+                           buf: &mut [u8]) -> Option<(/*#digits*/ usize, /*exp*/ i16)> {
+    assert!(d.mant > 0);
+    assert!(d.minus > 0);
+    assert!(d.plus > 0);
+    assert!(d.mant.checked_add(d.plus).is_some());
+    assert!(d.mant.checked_sub(d.minus).is_some());
+    assert!(buf.len() >= MAX_SIG_DIGITS);
+
+    // the decoder gives either `(2m, 1, 1)` or `(4m, 1, 2)` for the minimum normal;
+    // the algorithm wants the interval scaled so that the boundaries to both
+    // neighbors are integers at least two apart from `mv`, except the lower one
+    // for the minimum normal. the latter is already in that form.
+    let (mv, mp, mm, e2) = if d.plus == 1 {
+        (d.mant << 1, (d.mant + d.plus) << 1, (d.mant - d.minus) << 1, d.exp as i32 - 1)
+    } else {
+        (d.mant, d.mant + d.plus, d.mant - d.minus, d.exp as i32)
+    };
+    // the error analysis of the tables assumes the 55-bit `mv` of `f64`.
+    if mp >= 1 << 55 || e2 < MIN_E2 || e2 > MAX_E2 {
+        return None;
+    }
+    let accept_bounds = d.inclusive;
+
+    // convert `mv`, `mp` and `mm` to `10^e10`-based values. `q` is chosen so that
+    // the interval still contains at least one integer, but the products fit in `u64`.
+    // besides, we keep track of whether the lower bound survived the conversion
+    // exactly, as only then it can be a candidate for the inclusive range.
+    let mut vr;
+    let mut vp;
+    let mut vm;
+    let e10;
+    let mut vm_is_trailing_zeros = false;
+    if e2 >= 0 {
+        // `v * 2^e2 / 10^q = v * 2^(e2 - q) / 5^q`, with `q = max(0, log10(2^e2) - 1)`.
+        let q = log10_pow2(e2) - (e2 > 3) as u32;
+        e10 = q as i32;
+        let k = POW5_INV_BITCOUNT + pow5bits(q as i32) - 1;
+        let i = -e2 + q as i32 + k;
+        let mul = POW5_INV_SPLIT[q as usize];
+        vr = mul_shift(mv, mul, i);
+        vp = mul_shift(mp, mul, i);
+        vm = mul_shift(mm, mul, i);
+        // only when `q <= 21` can any of the values be a multiple of `5^q`
+        // (and hence the product an integer).
+        if q <= 21 {
+            if accept_bounds {
+                vm_is_trailing_zeros = multiple_of_power_of_5(mm, q);
+            } else {
+                // an exact upper bound is not part of the interval.
+                vp -= multiple_of_power_of_5(mp, q) as u64;
+            }
+        }
+    } else {
+        // `v * 2^e2 / 10^q = v * 5^(-e2 - q) / 2^(q - e2)`, with `q = max(0, log10(5^-e2) - 1)`.
+        let q = log10_pow5(-e2) - (-e2 > 1) as u32;
+        e10 = q as i32 + e2;
+        let i = -e2 - q as i32;
+        let k = pow5bits(i) - POW5_BITCOUNT;
+        let j = q as i32 - k;
+        let mul = POW5_SPLIT[i as usize];
+        vr = mul_shift(mv, mul, j);
+        vp = mul_shift(mp, mul, j);
+        vm = mul_shift(mm, mul, j);
+        // the product is an integer when the value has at least `q` trailing zero bits.
+        if q < 64 {
+            if accept_bounds {
+                vm_is_trailing_zeros = multiple_of_power_of_2(mm, q);
+            } else {
+                vp -= multiple_of_power_of_2(mp, q) as u64;
+            }
+        }
+    }
+
+    // remove digits while the interval still has more than one candidate.
+    // `round_up` tracks whether the last removed digit calls for rounding `vr` up.
+    let mut removed = 0;
+    let output = if vm_is_trailing_zeros {
+        // the general case, where we need to know whether the removed digits of
+        // `vm` were all zeroes. this is rare (<1%).
+        let mut last_removed_digit = 0;
+        while vp / 10 > vm / 10 {
+            vm_is_trailing_zeros &= vm % 10 == 0;
+            last_removed_digit = (vr % 10) as u8;
+            vr /= 10;
+            vp /= 10;
+            vm /= 10;
+            removed += 1;
+        }
+        if vm_is_trailing_zeros {
+            // an inclusive lower bound which is exactly representable may be shorter yet.
+            while vm % 10 == 0 {
+                last_removed_digit = (vr % 10) as u8;
+                vr /= 10;
+                vm /= 10;
+                removed += 1;
+            }
+        }
+        // unlike the original algorithm, exact ties are rounded up (away from zero)
+        // rather than to even, in order to agree with Grisu and Dragon.
+        let outside = vr == vm && (!accept_bounds || !vm_is_trailing_zeros);
+        vr + (outside || last_removed_digit >= 5) as u64
+    } else {
+        // the common case, where the removed digits only decide the rounding.
+        let mut round_up = false;
+        if vp / 100 > vm / 100 {
+            // most inputs have at least two digits to remove.
+            round_up = vr % 100 >= 50;
+            vr /= 100;
+            vp /= 100;
+            vm /= 100;
+            removed += 2;
+        }
+        while vp / 10 > vm / 10 {
+            round_up = vr % 10 >= 5;
+            vr /= 10;
+            vp /= 10;
+            vm /= 10;
+            removed += 1;
+        }
+        vr + (vr == vm || round_up) as u64
+    };
+
+    // render `output * 10^(e10 + removed)` as `0.d1...dn * 10^k`.
+    let mut len = 0;
+    let mut rest = output;
+    while rest > 0 {
+        len += 1;
+        rest /= 10;
+    }
+    debug_assert!(0 < len && len <= MAX_SIG_DIGITS);
+    let mut rest = output;
+    for c in buf[..len].iter_mut().rev() {
+        *c = b'0' + (rest % 10) as u8;
+        rest /= 10;
+    }
+    Some((len, (e10 + removed + len as i32) as i16))
+}
+
+/// The shortest mode implementation for Ryu with Grisu fallback.
+///
+/// The fallback is never taken for `f32` and `f64` inputs.
+pub fn format_shortest(d: &Decoded, buf: &mut [u8]) -> (/*#digits*/ usize, /*exp*/ i16) {
+    use num::flt2dec::strategy::grisu::format_shortest as fallback;
+    match format_shortest_opt(d, buf) {
+        Some(ret) => ret,
+        None => fallback(d, buf),
+    }
+}
@@ -156,6 +156,37 @@ macro_rules! int_impl {
             from_str_radix(src, radix)
         }
 
+        /// Parses an integer in a given base from the start of a string slice,
+        /// returning it together with the number of bytes it took up.
+        ///
+        /// This accepts the same syntax as [`from_str_radix`], but stops at the
+        /// first byte which is not a digit in `radix` instead of failing on it,
+        /// so it can be used to scan numbers out of a longer input. At least
+        /// one digit is required.
+        ///
+        /// [`from_str_radix`]: #method.from_str_radix
+        ///
+        /// # Panics
+        ///
+        /// This function panics if `radix` is not in the range from 2 to 36.
+        ///
+        /// # Examples
+        ///
+        /// Basic usage:
+        ///
+        /// ```
+        /// #![feature(parse_prefix)]
+        ///
+        /// assert_eq!(i32::from_str_radix_prefix("-ff, 12", 16), Ok((-255, 3)));
+        /// assert_eq!(i32::from_str_radix_prefix("42", 10), Ok((42, 2)));
+        /// assert!(i32::from_str_radix_prefix("x1", 10).is_err());
+        /// ```
+        #[unstable(feature = "parse_prefix", issue = "0")]
+        pub fn from_str_radix_prefix(src: &str, radix: u32)
+                                     -> Result<(Self, usize), ParseIntError> {
+            from_str_radix_prefix(src, radix)
+        }
+
         /// Returns the number of ones in the binary representation of `self`.
         ///
         /// # Examples
@@ -1305,6 +1336,37 @@ macro_rules! uint_impl {
             from_str_radix(src, radix)
         }
 
+        /// Parses an integer in a given base from the start of a string slice,
+        /// returning it together with the number of bytes it took up.
+        ///
+        /// This accepts the same syntax as [`from_str_radix`], but stops at the
+        /// first byte which is not a digit in `radix` instead of failing on it,
+        /// so it can be used to scan numbers out of a longer input. At least
+        /// one digit is required.
+        ///
+        /// [`from_str_radix`]: #method.from_str_radix
+        ///
+        /// # Panics
+        ///
+        /// This function panics if `radix` is not in the range from 2 to 36.
+        ///
+        /// # Examples
+        ///
+        /// Basic usage:
+        ///
+        /// ```
+        /// #![feature(parse_prefix)]
+        ///
+        /// assert_eq!(u32::from_str_radix_prefix("0777+1", 8), Ok((511, 4)));
+        /// assert_eq!(u32::from_str_radix_prefix("42", 10), Ok((42, 2)));
+        /// assert!(u32::from_str_radix_prefix("-1", 10).is_err());
+        /// ```
+        #[unstable(feature = "parse_prefix", issue = "0")]
+        pub fn from_str_radix_prefix(src: &str, radix: u32)
+                                     -> Result<(Self, usize), ParseIntError> {
+            from_str_radix_prefix(src, radix)
+        }
+
         /// Returns the number of ones in the binary representation of `self`.
         ///
         /// # Examples
@@ -3224,6 +3286,15 @@ macro_rules! doit {
 doit! { i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize }
 
 fn from_str_radix<T: FromStrRadixHelper>(src: &str, radix: u32) -> Result<T, ParseIntError> {
+    let (result, len) = from_str_radix_prefix(src, radix)?;
+    if len < src.len() {
+        return Err(ParseIntError { kind: IntErrorKind::InvalidDigit });
+    }
+    Ok(result)
+}
+
+fn from_str_radix_prefix<T: FromStrRadixHelper>(src: &str, radix: u32)
+                                                -> Result<(T, usize), ParseIntError> {
     use self::IntErrorKind::*;
     use self::ParseIntError as PIE;
 
@@ -3254,12 +3325,13 @@ fn from_str_radix<T: FromStrRadixHelper>(src: &str, radix: u32) -> Result<T, Par
     }
 
     let mut result = T::from_u32(0);
+    let mut len = 0;
     if is_positive {
         // The number is positive
         for &c in digits {
             let x = match (c as char).to_digit(radix) {
                 Some(x) => x,
-                None => return Err(PIE { kind: InvalidDigit }),
+                None => break,
             };
             result = match result.checked_mul(radix) {
                 Some(result) => result,
@@ -3269,13 +3341,14 @@ fn from_str_radix<T: FromStrRadixHelper>(src: &str, radix: u32) -> Result<T, Par
                 Some(result) => result,
                 None => return Err(PIE { kind: Overflow }),
             };
+            len += 1;
         }
     } else {
         // The number is negative
         for &c in digits {
             let x = match (c as char).to_digit(radix) {
                 Some(x) => x,
-                None => return Err(PIE { kind: InvalidDigit }),
+                None => break,
             };
             result = match result.checked_mul(radix) {
                 Some(result) => result,
@@ -3285,9 +3358,14 @@ fn from_str_radix<T: FromStrRadixHelper>(src: &str, radix: u32) -> Result<T, Par
                 Some(result) => result,
                 None => return Err(PIE { kind: Underflow }),
             };
+            len += 1;
         }
     }
-    Ok(result)
+
+    if len == 0 {
+        return Err(PIE { kind: InvalidDigit });
+    }
+    Ok((result, src.len() - digits.len() + len))
 }
 
 /// An error which can be returned when parsing an integer.
@@ -3302,15 +3380,53 @@ pub struct ParseIntError {
     kind: IntErrorKind,
 }
 
+/// Enum to store the various types of errors that can cause parsing an integer to fail.
+///
+/// It is returned by [`ParseIntError::kind`].
+///
+/// [`ParseIntError::kind`]: struct.ParseIntError.html#method.kind
+#[unstable(feature = "int_error_matching", issue = "0")]
 #[derive(Debug, Clone, PartialEq, Eq)]
-enum IntErrorKind {
+pub enum IntErrorKind {
+    /// The value being parsed is empty, or only consists of a sign.
     Empty,
+    /// A byte which is not a digit in the given radix was found.
     InvalidDigit,
+    /// The value is too large to be stored in the target integer type.
     Overflow,
+    /// The value is too small to be stored in the target integer type.
     Underflow,
+
+    /// A marker variant that tells the compiler that users of this enum cannot
+    /// match it exhaustively.
+    #[unstable(feature = "int_error_internals",
+               reason = "better expressed through extensible enums that this \
+                         enum cannot be exhaustively matched against",
+               issue = "0")]
+    #[doc(hidden)]
+    __Nonexhaustive,
 }
 
 impl ParseIntError {
+    /// Returns the detailed cause of parsing an integer failing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(int_error_matching)]
+    ///
+    /// use std::num::IntErrorKind;
+    ///
+    /// let err = "256".parse::<u8>().unwrap_err();
+    /// assert_eq!(err.kind(), &IntErrorKind::Overflow);
+    /// let err = "12a".parse::<u8>().unwrap_err();
+    /// assert_eq!(err.kind(), &IntErrorKind::InvalidDigit);
+    /// ```
+    #[unstable(feature = "int_error_matching", issue = "0")]
+    pub fn kind(&self) -> &IntErrorKind {
+        &self.kind
+    }
+
     #[unstable(feature = "int_error_internals",
                reason = "available through Error trait and this method should \
                          not be exposed publicly",
@@ -3322,6 +3438,7 @@ impl ParseIntError {
             IntErrorKind::InvalidDigit => "invalid digit found in string",
             IntErrorKind::Overflow => "number too large to fit in target type",
             IntErrorKind::Underflow => "number too small to fit in target type",
+            IntErrorKind::__Nonexhaustive => unreachable!(),
         }
     }
 }
@@ -0,0 +1,745 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Macros generating the vector and mask types of `core::simd`.
+//!
+//! Every lane-wise operation is written twice: once in terms of the
+//! `simd_*` platform intrinsics, and once as a loop over the lanes for the
+//! targets where `repr(simd)` cannot be used. The cfg selecting between the
+//! two is the same as the one in `ptr::swap_nonoverlapping_bytes`.
+
+/// Expands to `$t` once per lane name, so that a struct or an expression
+/// can be given one entry per lane.
+macro_rules! lane {
+    ($x:ident => $t:tt) => ($t)
+}
+
+/// Applies a binary scalar operation to every pair of lanes of `$a` and
+/// `$b`, which must both be of type `Self`.
+macro_rules! map_lanes {
+    ($a:expr, $b:expr, |$x:ident, $y:ident| $scalar:expr) => ({
+        let (mut a, b) = ($a.to_array(), $b.to_array());
+        for (lane, &$y) in a.iter_mut().zip(b.iter()) {
+            let $x = *lane;
+            *lane = $scalar;
+        }
+        Self::from_array(a)
+    })
+}
+
+#[cfg(not(any(target_os = "emscripten", target_os = "redox", target_endian = "big")))]
+macro_rules! lanewise {
+    ($intrinsic:ident($a:expr, $b:expr), |$x:ident, $y:ident| $scalar:expr) => (
+        unsafe { $intrinsic($a, $b) }
+    )
+}
+
+// FIXME repr(simd) broken on emscripten and redox, and on big-endian
+// targets (#42778).
+#[cfg(any(target_os = "emscripten", target_os = "redox", target_endian = "big"))]
+macro_rules! lanewise {
+    ($intrinsic:ident($a:expr, $b:expr), |$x:ident, $y:ident| $scalar:expr) => (
+        map_lanes!($a, $b, |$x, $y| $scalar)
+    )
+}
+
+#[cfg(not(any(target_os = "emscripten", target_os = "redox", target_endian = "big")))]
+macro_rules! compare {
+    ($intrinsic:ident($a:expr, $b:expr) -> $mask:ident, |$x:ident, $y:ident| $scalar:expr) => (
+        unsafe { $intrinsic::<Self, $mask>($a, $b) }
+    )
+}
+
+#[cfg(any(target_os = "emscripten", target_os = "redox", target_endian = "big"))]
+macro_rules! compare {
+    ($intrinsic:ident($a:expr, $b:expr) -> $mask:ident, |$x:ident, $y:ident| $scalar:expr) => ({
+        let (a, b) = ($a.to_array(), $b.to_array());
+        let mut mask = $mask::splat(false);
+        for (i, (&$x, &$y)) in a.iter().zip(b.iter()).enumerate() {
+            mask = mask.replace(i, $scalar);
+        }
+        mask
+    })
+}
+
+#[cfg(not(any(target_os = "emscripten", target_os = "redox", target_endian = "big")))]
+macro_rules! shuffle {
+    ($intrinsic:ident($a:expr, $b:expr, [$($i:expr),*])) => (
+        unsafe { $intrinsic::<Self, Self>($a, $b, [$($i),*]) }
+    )
+}
+
+#[cfg(any(target_os = "emscripten", target_os = "redox", target_endian = "big"))]
+macro_rules! shuffle {
+    ($intrinsic:ident($a:expr, $b:expr, [$($i:expr),*])) => ({
+        let (a, b) = ($a.to_array(), $b.to_array());
+        let indices = [$($i),*];
+        let mut lanes = a;
+        for (lane, &i) in lanes.iter_mut().zip(indices.iter()) {
+            let i = i as usize;
+            *lane = if i < a.len() { a[i] } else { b[i - a.len()] };
+        }
+        Self::from_array(lanes)
+    })
+}
+
+/// Implements a binary operator and its assigning form in terms of `$body`,
+/// which sees the operands as `$a` and `$b`.
+macro_rules! impl_binop {
+    ($id:ident: $Trait:ident::$method:ident, $AssignTrait:ident::$assign:ident,
+     |$a:ident, $b:ident| $body:expr) => (
+        impl ops::$Trait for $id {
+            type Output = Self;
+
+            #[inline]
+            fn $method(self, rhs: Self) -> Self {
+                let ($a, $b) = (self, rhs);
+                $body
+            }
+        }
+
+        impl ops::$AssignTrait for $id {
+            #[inline]
+            fn $assign(&mut self, rhs: Self) {
+                *self = ops::$Trait::$method(*self, rhs);
+            }
+        }
+    )
+}
+
+/// Implements the bitwise operators for a vector or mask type.
+macro_rules! impl_bitops {
+    ($id:ident, $lanes:tt) => (
+        impl_binop!($id: BitAnd::bitand, BitAndAssign::bitand_assign,
+                    |a, b| lanewise!(simd_and(a, b), |x, y| x & y));
+        impl_binop!($id: BitOr::bitor, BitOrAssign::bitor_assign,
+                    |a, b| lanewise!(simd_or(a, b), |x, y| x | y));
+        impl_binop!($id: BitXor::bitxor, BitXorAssign::bitxor_assign,
+                    |a, b| lanewise!(simd_xor(a, b), |x, y| x ^ y));
+
+        impl ops::Not for $id {
+            type Output = Self;
+
+            #[inline]
+            fn not(self) -> Self {
+                self ^ Self::from_array([!0; $lanes])
+            }
+        }
+    )
+}
+
+/// Implements `reverse`, `interleave_low` and `interleave_high` with the
+/// shuffle indices for the number of lanes of `$id`.
+macro_rules! impl_shuffles {
+    ($id:ident, 2) => (
+        impl_shuffles!($id, simd_shuffle2, [1, 0], [0, 2], [1, 3]);
+    );
+    ($id:ident, 4) => (
+        impl_shuffles!($id, simd_shuffle4, [3, 2, 1, 0], [0, 4, 1, 5], [2, 6, 3, 7]);
+    );
+    ($id:ident, 8) => (
+        impl_shuffles!($id, simd_shuffle8,
+                       [7, 6, 5, 4, 3, 2, 1, 0],
+                       [0, 8, 1, 9, 2, 10, 3, 11],
+                       [4, 12, 5, 13, 6, 14, 7, 15]);
+    );
+    ($id:ident, 16) => (
+        impl_shuffles!($id, simd_shuffle16,
+                       [15, 14, 13, 12, 11, 10, 9, 8, 7, 6, 5, 4, 3, 2, 1, 0],
+                       [0, 16, 1, 17, 2, 18, 3, 19, 4, 20, 5, 21, 6, 22, 7, 23],
+                       [8, 24, 9, 25, 10, 26, 11, 27, 12, 28, 13, 29, 14, 30, 15, 31]);
+    );
+    ($id:ident, $intrinsic:ident, [$($rev:expr),*], [$($lo:expr),*], [$($hi:expr),*]) => (
+        impl $id {
+            /// Returns the vector with the order of its lanes reversed.
+            #[inline]
+            pub fn reverse(self) -> Self {
+                shuffle!($intrinsic(self, self, [$($rev),*]))
+            }
+
+            /// Interleaves the lower halves of `self` and `other`, taking
+            /// lane 0 of `self`, then lane 0 of `other`, then lane 1 of
+            /// `self`, and so on.
+            #[inline]
+            pub fn interleave_low(self, other: Self) -> Self {
+                shuffle!($intrinsic(self, other, [$($lo),*]))
+            }
+
+            /// Interleaves the upper halves of `self` and `other`, in the
+            /// same way as `interleave_low`.
+            #[inline]
+            pub fn interleave_high(self, other: Self) -> Self {
+                shuffle!($intrinsic(self, other, [$($hi),*]))
+            }
+        }
+    )
+}
+
+/// Defines a mask type with lanes of the signed integer type `$int`, each
+/// holding either all ones (`true`) or all zeros (`false`).
+macro_rules! simd_mask {
+    ($(#[$attr:meta])* $id:ident: $int:ident, $lanes:tt, ($($x:ident),*)) => (
+        $(#[$attr])*
+        #[cfg_attr(not(any(target_os = "emscripten", target_os = "redox",
+                           target_endian = "big")),
+                   repr(simd))]
+        #[cfg_attr(any(target_os = "emscripten", target_os = "redox", target_endian = "big"),
+                   repr(C))]
+        #[derive(Copy, Clone)]
+        #[allow(non_camel_case_types)]
+        pub struct $id($(lane!($x => $int)),*);
+
+        impl $id {
+            /// The number of lanes in the mask.
+            pub const LANES: usize = $lanes;
+
+            /// Creates a mask from the values of its lanes.
+            #[inline]
+            pub fn new($($x: bool),*) -> Self {
+                $id($(-($x as $int)),*)
+            }
+
+            /// Creates a mask with every lane set to `value`.
+            #[inline]
+            pub fn splat(value: bool) -> Self {
+                let lane = -(value as $int);
+                $id($(lane!($x => lane)),*)
+            }
+
+            /// Returns the value of lane `index`.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `index` is not less than `LANES`.
+            #[inline]
+            pub fn extract(self, index: usize) -> bool {
+                self.to_array()[index] != 0
+            }
+
+            /// Returns a copy of the mask with lane `index` set to `value`.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `index` is not less than `LANES`.
+            #[inline]
+            pub fn replace(self, index: usize, value: bool) -> Self {
+                let mut lanes = self.to_array();
+                lanes[index] = -(value as $int);
+                Self::from_array(lanes)
+            }
+
+            /// Returns `true` if every lane is set.
+            #[inline]
+            pub fn all(self) -> bool {
+                self.to_array().iter().all(|&lane| lane != 0)
+            }
+
+            /// Returns `true` if at least one lane is set.
+            #[inline]
+            pub fn any(self) -> bool {
+                self.to_array().iter().any(|&lane| lane != 0)
+            }
+
+            /// Returns `true` if no lane is set.
+            #[inline]
+            pub fn none(self) -> bool {
+                !self.any()
+            }
+
+            /// Builds a vector taking each lane from `if_true` where the
+            /// mask is set, and from `if_false` where it is not.
+            #[inline]
+            pub fn select<V: Select<Self>>(self, if_true: V, if_false: V) -> V {
+                V::select(self, if_true, if_false)
+            }
+
+            #[inline]
+            fn to_array(self) -> [$int; $lanes] {
+                unsafe { mem::transmute(self) }
+            }
+
+            #[inline]
+            fn from_array(lanes: [$int; $lanes]) -> Self {
+                unsafe { mem::transmute(lanes) }
+            }
+        }
+
+        impl_bitops!($id, $lanes);
+
+        impl Default for $id {
+            #[inline]
+            fn default() -> Self {
+                Self::from_array(Default::default())
+            }
+        }
+
+        impl PartialEq for $id {
+            #[inline]
+            fn eq(&self, other: &Self) -> bool {
+                self.to_array() == other.to_array()
+            }
+        }
+
+        impl Eq for $id {}
+
+        impl From<[bool; $lanes]> for $id {
+            #[inline]
+            fn from(values: [bool; $lanes]) -> Self {
+                let mut lanes = [0; $lanes];
+                for (lane, &value) in lanes.iter_mut().zip(values.iter()) {
+                    *lane = -(value as $int);
+                }
+                Self::from_array(lanes)
+            }
+        }
+
+        impl From<$id> for [bool; $lanes] {
+            #[inline]
+            fn from(mask: $id) -> Self {
+                let mut values = [false; $lanes];
+                for (value, &lane) in values.iter_mut().zip(mask.to_array().iter()) {
+                    *value = lane != 0;
+                }
+                values
+            }
+        }
+
+        impl fmt::Debug for $id {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                let mut builder = f.debug_tuple(stringify!($id));
+                for &lane in self.to_array().iter() {
+                    builder.field(&(lane != 0));
+                }
+                builder.finish()
+            }
+        }
+    )
+}
+
+/// Defines a vector type with lanes of type `$elem`, along with the
+/// operations shared by integer and floating point vectors.
+macro_rules! simd_vector {
+    ($(#[$attr:meta])* $id:ident: $elem:ident, $mask:ident, $lanes:tt, ($($x:ident),*)) => (
+        $(#[$attr])*
+        #[cfg_attr(not(any(target_os = "emscripten", target_os = "redox",
+                           target_endian = "big")),
+                   repr(simd))]
+        #[cfg_attr(any(target_os = "emscripten", target_os = "redox", target_endian = "big"),
+                   repr(C))]
+        #[derive(Copy, Clone)]
+        #[allow(non_camel_case_types)]
+        pub struct $id($(lane!($x => $elem)),*);
+
+        impl $id {
+            /// The number of lanes in the vector.
+            pub const LANES: usize = $lanes;
+
+            /// Creates a vector from the values of its lanes.
+            #[inline]
+            pub fn new($($x: $elem),*) -> Self {
+                $id($($x),*)
+            }
+
+            /// Creates a vector with every lane set to `value`.
+            #[inline]
+            pub fn splat(value: $elem) -> Self {
+                $id($(lane!($x => value)),*)
+            }
+
+            /// Returns the value of lane `index`.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `index` is not less than `LANES`.
+            #[inline]
+            pub fn extract(self, index: usize) -> $elem {
+                self.to_array()[index]
+            }
+
+            /// Returns a copy of the vector with lane `index` set to `value`.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `index` is not less than `LANES`.
+            #[inline]
+            pub fn replace(self, index: usize, value: $elem) -> Self {
+                let mut lanes = self.to_array();
+                lanes[index] = value;
+                Self::from_array(lanes)
+            }
+
+            /// Loads a vector from the first `LANES` elements of `slice`.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `slice` is shorter than `LANES`.
+            #[inline]
+            pub fn from_slice(slice: &[$elem]) -> Self {
+                assert!(slice.len() >= $lanes, "slice is shorter than the vector");
+                unsafe { Self::load(slice.as_ptr()) }
+            }
+
+            /// Stores the vector into the first `LANES` elements of `slice`.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `slice` is shorter than `LANES`.
+            #[inline]
+            pub fn write_to_slice(self, slice: &mut [$elem]) {
+                assert!(slice.len() >= $lanes, "slice is shorter than the vector");
+                unsafe { self.store(slice.as_mut_ptr()) }
+            }
+
+            /// Loads a vector from `LANES` consecutive elements starting at
+            /// `ptr`, which need not be aligned.
+            ///
+            /// # Safety
+            ///
+            /// `ptr` must be valid for reads of `LANES` elements.
+            #[inline]
+            pub unsafe fn load(ptr: *const $elem) -> Self {
+                ptr::read_unaligned(ptr as *const Self)
+            }
+
+            /// Stores the vector into `LANES` consecutive elements starting
+            /// at `ptr`, which need not be aligned.
+            ///
+            /// # Safety
+            ///
+            /// `ptr` must be valid for writes of `LANES` elements.
+            #[inline]
+            pub unsafe fn store(self, ptr: *mut $elem) {
+                ptr::write_unaligned(ptr as *mut Self, self)
+            }
+
+            /// Returns a mask of the lanes where `self` is equal to `other`.
+            #[inline]
+            pub fn lanes_eq(self, other: Self) -> $mask {
+                compare!(simd_eq(self, other) -> $mask, |x, y| x == y)
+            }
+
+            /// Returns a mask of the lanes where `self` is not equal to
+            /// `other`.
+            #[inline]
+            pub fn lanes_ne(self, other: Self) -> $mask {
+                compare!(simd_ne(self, other) -> $mask, |x, y| x != y)
+            }
+
+            /// Returns a mask of the lanes where `self` is less than `other`.
+            #[inline]
+            pub fn lanes_lt(self, other: Self) -> $mask {
+                compare!(simd_lt(self, other) -> $mask, |x, y| x < y)
+            }
+
+            /// Returns a mask of the lanes where `self` is less than or equal
+            /// to `other`.
+            #[inline]
+            pub fn lanes_le(self, other: Self) -> $mask {
+                compare!(simd_le(self, other) -> $mask, |x, y| x <= y)
+            }
+
+            /// Returns a mask of the lanes where `self` is greater than
+            /// `other`.
+            #[inline]
+            pub fn lanes_gt(self, other: Self) -> $mask {
+                compare!(simd_gt(self, other) -> $mask, |x, y| x > y)
+            }
+
+            /// Returns a mask of the lanes where `self` is greater than or
+            /// equal to `other`.
+            #[inline]
+            pub fn lanes_ge(self, other: Self) -> $mask {
+                compare!(simd_ge(self, other) -> $mask, |x, y| x >= y)
+            }
+
+            #[inline]
+            fn to_array(self) -> [$elem; $lanes] {
+                unsafe { mem::transmute(self) }
+            }
+
+            #[inline]
+            fn from_array(lanes: [$elem; $lanes]) -> Self {
+                unsafe { mem::transmute(lanes) }
+            }
+        }
+
+        impl_shuffles!($id, $lanes);
+
+        impl Default for $id {
+            #[inline]
+            fn default() -> Self {
+                Self::from_array(Default::default())
+            }
+        }
+
+        impl PartialEq for $id {
+            #[inline]
+            fn eq(&self, other: &Self) -> bool {
+                self.to_array() == other.to_array()
+            }
+        }
+
+        impl fmt::Debug for $id {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                let mut builder = f.debug_tuple(stringify!($id));
+                for lane in self.to_array().iter() {
+                    builder.field(lane);
+                }
+                builder.finish()
+            }
+        }
+
+        impl Select<$mask> for $id {
+            #[inline]
+            fn select(mask: $mask, if_true: Self, if_false: Self) -> Self {
+                // The mask has lanes of the same width, so the selection can
+                // be done on the bits of the lanes.
+                unsafe {
+                    let if_true: $mask = mem::transmute(if_true);
+                    let if_false: $mask = mem::transmute(if_false);
+                    mem::transmute((if_true & mask) | (if_false & !mask))
+                }
+            }
+        }
+
+        impl From<[$elem; $lanes]> for $id {
+            #[inline]
+            fn from(lanes: [$elem; $lanes]) -> Self {
+                Self::from_array(lanes)
+            }
+        }
+
+        impl From<$id> for [$elem; $lanes] {
+            #[inline]
+            fn from(vector: $id) -> Self {
+                vector.to_array()
+            }
+        }
+    )
+}
+
+/// Defines a vector of integer lanes. Arithmetic wraps on overflow.
+macro_rules! int_vector {
+    ($(#[$attr:meta])* $id:ident: $elem:ident, $mask:ident, $lanes:tt, ($($x:ident),*)) => (
+        simd_vector!($(#[$attr])* $id: $elem, $mask, $lanes, ($($x),*));
+
+        impl $id {
+            /// Returns the lane-wise minimum of `self` and `other`.
+            #[inline]
+            pub fn min(self, other: Self) -> Self {
+                self.lanes_lt(other).select(self, other)
+            }
+
+            /// Returns the lane-wise maximum of `self` and `other`.
+            #[inline]
+            pub fn max(self, other: Self) -> Self {
+                self.lanes_gt(other).select(self, other)
+            }
+
+            /// Returns the sum of the lanes, wrapping around on overflow.
+            #[inline]
+            pub fn wrapping_sum(self) -> $elem {
+                self.to_array().iter().fold(0, |acc, &x| acc.wrapping_add(x))
+            }
+
+            /// Returns the product of the lanes, wrapping around on overflow.
+            #[inline]
+            pub fn wrapping_product(self) -> $elem {
+                self.to_array().iter().fold(1, |acc, &x| acc.wrapping_mul(x))
+            }
+
+            /// Returns the smallest lane.
+            #[inline]
+            pub fn min_element(self) -> $elem {
+                self.to_array().iter().fold($elem::max_value(), |acc, &x| cmp::min(acc, x))
+            }
+
+            /// Returns the largest lane.
+            #[inline]
+            pub fn max_element(self) -> $elem {
+                self.to_array().iter().fold($elem::min_value(), |acc, &x| cmp::max(acc, x))
+            }
+
+            /// Returns the bitwise AND of all the lanes.
+            #[inline]
+            pub fn and(self) -> $elem {
+                self.to_array().iter().fold(!0, |acc, &x| acc & x)
+            }
+
+            /// Returns the bitwise OR of all the lanes.
+            #[inline]
+            pub fn or(self) -> $elem {
+                self.to_array().iter().fold(0, |acc, &x| acc | x)
+            }
+
+            /// Returns the bitwise XOR of all the lanes.
+            #[inline]
+            pub fn xor(self) -> $elem {
+                self.to_array().iter().fold(0, |acc, &x| acc ^ x)
+            }
+        }
+
+        impl_binop!($id: Add::add, AddAssign::add_assign,
+                    |a, b| lanewise!(simd_add(a, b), |x, y| x.wrapping_add(y)));
+        impl_binop!($id: Sub::sub, SubAssign::sub_assign,
+                    |a, b| lanewise!(simd_sub(a, b), |x, y| x.wrapping_sub(y)));
+        impl_binop!($id: Mul::mul, MulAssign::mul_assign,
+                    |a, b| lanewise!(simd_mul(a, b), |x, y| x.wrapping_mul(y)));
+        // No target has vector integer division, and dividing lane by lane
+        // gives the same panics as scalar division.
+        impl_binop!($id: Div::div, DivAssign::div_assign,
+                    |a, b| map_lanes!(a, b, |x, y| x / y));
+        impl_binop!($id: Rem::rem, RemAssign::rem_assign,
+                    |a, b| map_lanes!(a, b, |x, y| x % y));
+        impl_bitops!($id, $lanes);
+
+        impl ops::Shl<u32> for $id {
+            type Output = Self;
+
+            /// Shifts every lane left by `rhs`, which is masked to the width
+            /// of a lane as with `wrapping_shl`.
+            #[inline]
+            fn shl(self, rhs: u32) -> Self {
+                let rhs = Self::splat((rhs & (mem::size_of::<$elem>() as u32 * 8 - 1)) as $elem);
+                lanewise!(simd_shl(self, rhs), |x, y| x << y)
+            }
+        }
+
+        impl ops::Shr<u32> for $id {
+            type Output = Self;
+
+            /// Shifts every lane right by `rhs`, which is masked to the width
+            /// of a lane as with `wrapping_shr`. Signed lanes are shifted
+            /// arithmetically.
+            #[inline]
+            fn shr(self, rhs: u32) -> Self {
+                let rhs = Self::splat((rhs & (mem::size_of::<$elem>() as u32 * 8 - 1)) as $elem);
+                lanewise!(simd_shr(self, rhs), |x, y| x >> y)
+            }
+        }
+
+        impl ops::ShlAssign<u32> for $id {
+            #[inline]
+            fn shl_assign(&mut self, rhs: u32) {
+                *self = *self << rhs;
+            }
+        }
+
+        impl ops::ShrAssign<u32> for $id {
+            #[inline]
+            fn shr_assign(&mut self, rhs: u32) {
+                *self = *self >> rhs;
+            }
+        }
+    )
+}
+
+/// Defines a vector of signed integer lanes, which can also be negated.
+macro_rules! signed_vector {
+    ($(#[$attr:meta])* $id:ident: $elem:ident, $mask:ident, $lanes:tt, ($($x:ident),*)) => (
+        int_vector!($(#[$attr])* $id: $elem, $mask, $lanes, ($($x),*));
+
+        impl ops::Neg for $id {
+            type Output = Self;
+
+            #[inline]
+            fn neg(self) -> Self {
+                Self::splat(0) - self
+            }
+        }
+    )
+}
+
+/// Defines a vector of floating point lanes.
+macro_rules! float_vector {
+    ($(#[$attr:meta])* $id:ident: $elem:ident, $mask:ident, $lanes:tt, ($($x:ident),*)) => (
+        simd_vector!($(#[$attr])* $id: $elem, $mask, $lanes, ($($x),*));
+
+        impl $id {
+            /// Returns a mask of the lanes which are NaN.
+            #[inline]
+            pub fn is_nan(self) -> $mask {
+                self.lanes_ne(self)
+            }
+
+            /// Returns the lane-wise minimum of `self` and `other`. As with
+            /// the scalar `min`, a lane which is NaN in one vector takes the
+            /// value of the other.
+            #[inline]
+            pub fn min(self, other: Self) -> Self {
+                (self.lanes_lt(other) | other.is_nan()).select(self, other)
+            }
+
+            /// Returns the lane-wise maximum of `self` and `other`. As with
+            /// the scalar `max`, a lane which is NaN in one vector takes the
+            /// value of the other.
+            #[inline]
+            pub fn max(self, other: Self) -> Self {
+                (self.lanes_gt(other) | other.is_nan()).select(self, other)
+            }
+
+            /// Returns the sum of the lanes, added in order starting from
+            /// lane 0.
+            #[inline]
+            pub fn sum(self) -> $elem {
+                let lanes = self.to_array();
+                lanes[1..].iter().fold(lanes[0], |acc, &x| acc + x)
+            }
+
+            /// Returns the product of the lanes, multiplied in order starting
+            /// from lane 0.
+            #[inline]
+            pub fn product(self) -> $elem {
+                let lanes = self.to_array();
+                lanes[1..].iter().fold(lanes[0], |acc, &x| acc * x)
+            }
+
+            /// Returns the smallest lane, ignoring NaN lanes unless every lane
+            /// is NaN.
+            #[inline]
+            pub fn min_element(self) -> $elem {
+                let lanes = self.to_array();
+                lanes[1..].iter().fold(lanes[0], |acc, &x| {
+                    if x < acc || acc != acc { x } else { acc }
+                })
+            }
+
+            /// Returns the largest lane, ignoring NaN lanes unless every lane
+            /// is NaN.
+            #[inline]
+            pub fn max_element(self) -> $elem {
+                let lanes = self.to_array();
+                lanes[1..].iter().fold(lanes[0], |acc, &x| {
+                    if x > acc || acc != acc { x } else { acc }
+                })
+            }
+        }
+
+        impl_binop!($id: Add::add, AddAssign::add_assign,
+                    |a, b| lanewise!(simd_add(a, b), |x, y| x + y));
+        impl_binop!($id: Sub::sub, SubAssign::sub_assign,
+                    |a, b| lanewise!(simd_sub(a, b), |x, y| x - y));
+        impl_binop!($id: Mul::mul, MulAssign::mul_assign,
+                    |a, b| lanewise!(simd_mul(a, b), |x, y| x * y));
+        impl_binop!($id: Div::div, DivAssign::div_assign,
+                    |a, b| lanewise!(simd_div(a, b), |x, y| x / y));
+        impl_binop!($id: Rem::rem, RemAssign::rem_assign,
+                    |a, b| lanewise!(simd_rem(a, b), |x, y| x % y));
+
+        impl ops::Neg for $id {
+            type Output = Self;
+
+            #[inline]
+            fn neg(self) -> Self {
+                lanewise!(simd_sub(Self::splat(-0.0), self), |x, y| x - y)
+            }
+        }
+    )
+}
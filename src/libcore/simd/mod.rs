@@ -0,0 +1,165 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Portable SIMD vector types.
+//!
+//! Each type in this module holds a fixed number of *lanes* of a primitive
+//! type and is named after it: `f32x4` holds four `f32`s, `u8x16` holds
+//! sixteen `u8`s, and so on. All of them are 128 bits wide.
+//!
+//! Arithmetic, bitwise and shift operators apply lane by lane. Integer
+//! arithmetic wraps around on overflow, except for division and remainder
+//! which panic as their scalar counterparts do. Comparisons such as
+//! [`lanes_lt`] produce a *mask*, such as [`m32x4`], with one boolean lane
+//! per lane of the compared vectors; masks can be combined with bitwise
+//! operators, queried with [`all`] and [`any`], and used to [`select`] lanes
+//! from two vectors.
+//!
+//! The operations are lowered to the generic `simd_*` platform intrinsics,
+//! which LLVM turns into vector instructions where the target has them and
+//! splits into scalar code where it does not. On the few targets where
+//! `repr(simd)` cannot be used at all every operation is instead written as
+//! a loop over the lanes, with the same results.
+//!
+//! [`lanes_lt`]: struct.f32x4.html#method.lanes_lt
+//! [`m32x4`]: struct.m32x4.html
+//! [`all`]: struct.m32x4.html#method.all
+//! [`any`]: struct.m32x4.html#method.any
+//! [`select`]: struct.m32x4.html#method.select
+//!
+//! # Examples
+//!
+//! ```
+//! #![feature(portable_simd)]
+//!
+//! use std::simd::f32x4;
+//!
+//! let a = f32x4::new(1.0, 2.0, 3.0, 4.0);
+//! let b = f32x4::splat(10.0);
+//!
+//! let c = a * b + a;
+//! assert_eq!(c, f32x4::new(11.0, 22.0, 33.0, 44.0));
+//! assert_eq!(c.sum(), 110.0);
+//!
+//! let big = c.lanes_gt(f32x4::splat(25.0));
+//! assert!(big.any() && !big.all());
+//! assert_eq!(big.select(b, a), f32x4::new(1.0, 2.0, 10.0, 10.0));
+//! ```
+
+#![unstable(feature = "portable_simd", issue = "0")]
+
+use cmp;
+use fmt;
+use mem;
+use ops;
+use ptr;
+
+#[macro_use]
+mod macros;
+
+#[cfg(not(any(target_os = "emscripten", target_os = "redox", target_endian = "big")))]
+extern "platform-intrinsic" {
+    fn simd_add<T>(x: T, y: T) -> T;
+    fn simd_sub<T>(x: T, y: T) -> T;
+    fn simd_mul<T>(x: T, y: T) -> T;
+    fn simd_div<T>(x: T, y: T) -> T;
+    fn simd_rem<T>(x: T, y: T) -> T;
+    fn simd_shl<T>(x: T, y: T) -> T;
+    fn simd_shr<T>(x: T, y: T) -> T;
+    fn simd_and<T>(x: T, y: T) -> T;
+    fn simd_or<T>(x: T, y: T) -> T;
+    fn simd_xor<T>(x: T, y: T) -> T;
+
+    fn simd_eq<T, U>(x: T, y: T) -> U;
+    fn simd_ne<T, U>(x: T, y: T) -> U;
+    fn simd_lt<T, U>(x: T, y: T) -> U;
+    fn simd_le<T, U>(x: T, y: T) -> U;
+    fn simd_gt<T, U>(x: T, y: T) -> U;
+    fn simd_ge<T, U>(x: T, y: T) -> U;
+
+    fn simd_shuffle2<T, U>(x: T, y: T, idx: [u32; 2]) -> U;
+    fn simd_shuffle4<T, U>(x: T, y: T, idx: [u32; 4]) -> U;
+    fn simd_shuffle8<T, U>(x: T, y: T, idx: [u32; 8]) -> U;
+    fn simd_shuffle16<T, U>(x: T, y: T, idx: [u32; 16]) -> U;
+}
+
+/// Vector types whose lanes can be chosen with a mask of type `M`.
+///
+/// This is implemented for every vector with as many lanes as `M`, each as
+/// wide as a lane of `M`, and is what lets `M::select` work with all of
+/// them.
+pub trait Select<M>: Copy {
+    #[doc(hidden)]
+    fn select(mask: M, if_true: Self, if_false: Self) -> Self;
+}
+
+simd_mask! {
+    /// A mask of sixteen lanes, as produced by comparing `i8x16` or `u8x16`
+    /// vectors.
+    m8x16: i8, 16, (x0, x1, x2, x3, x4, x5, x6, x7, x8, x9, x10, x11, x12, x13, x14, x15)
+}
+simd_mask! {
+    /// A mask of eight lanes, as produced by comparing `i16x8` or `u16x8`
+    /// vectors.
+    m16x8: i16, 8, (x0, x1, x2, x3, x4, x5, x6, x7)
+}
+simd_mask! {
+    /// A mask of four lanes, as produced by comparing `i32x4`, `u32x4` or
+    /// `f32x4` vectors.
+    m32x4: i32, 4, (x0, x1, x2, x3)
+}
+simd_mask! {
+    /// A mask of two lanes, as produced by comparing `i64x2`, `u64x2` or
+    /// `f64x2` vectors.
+    m64x2: i64, 2, (x0, x1)
+}
+
+signed_vector! {
+    /// A vector of sixteen `i8` lanes.
+    i8x16: i8, m8x16, 16,
+    (x0, x1, x2, x3, x4, x5, x6, x7, x8, x9, x10, x11, x12, x13, x14, x15)
+}
+int_vector! {
+    /// A vector of sixteen `u8` lanes.
+    u8x16: u8, m8x16, 16,
+    (x0, x1, x2, x3, x4, x5, x6, x7, x8, x9, x10, x11, x12, x13, x14, x15)
+}
+signed_vector! {
+    /// A vector of eight `i16` lanes.
+    i16x8: i16, m16x8, 8, (x0, x1, x2, x3, x4, x5, x6, x7)
+}
+int_vector! {
+    /// A vector of eight `u16` lanes.
+    u16x8: u16, m16x8, 8, (x0, x1, x2, x3, x4, x5, x6, x7)
+}
+signed_vector! {
+    /// A vector of four `i32` lanes.
+    i32x4: i32, m32x4, 4, (x0, x1, x2, x3)
+}
+int_vector! {
+    /// A vector of four `u32` lanes.
+    u32x4: u32, m32x4, 4, (x0, x1, x2, x3)
+}
+signed_vector! {
+    /// A vector of two `i64` lanes.
+    i64x2: i64, m64x2, 2, (x0, x1)
+}
+int_vector! {
+    /// A vector of two `u64` lanes.
+    u64x2: u64, m64x2, 2, (x0, x1)
+}
+float_vector! {
+    /// A vector of four `f32` lanes.
+    f32x4: f32, m32x4, 4, (x0, x1, x2, x3)
+}
+float_vector! {
+    /// A vector of two `f64` lanes.
+    f64x2: f64, m64x2, 2, (x0, x1)
+}
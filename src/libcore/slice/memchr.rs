@@ -56,8 +56,155 @@ fn repeat_byte(b: u8) -> usize {
     rep
 }
 
+#[cfg(all(target_arch = "x86_64", target_feature = "sse2"))]
+use self::sse2::{forward_search, reverse_search};
+#[cfg(not(all(target_arch = "x86_64", target_feature = "sse2")))]
+use self::{forward_search_words as forward_search, reverse_search_words as reverse_search};
+
 /// Return the first index matching the byte `x` in `text`.
 pub fn memchr(x: u8, text: &[u8]) -> Option<usize> {
+    forward_search(One::new(x), text)
+}
+
+/// Return the first index matching either of the bytes `x1` or `x2` in
+/// `text`.
+pub fn memchr2(x1: u8, x2: u8, text: &[u8]) -> Option<usize> {
+    forward_search(Two::new(x1, x2), text)
+}
+
+/// Return the first index matching any of the bytes `x1`, `x2` or `x3` in
+/// `text`.
+pub fn memchr3(x1: u8, x2: u8, x3: u8, text: &[u8]) -> Option<usize> {
+    forward_search(Three::new(x1, x2, x3), text)
+}
+
+/// Return the last index matching the byte `x` in `text`.
+pub fn memrchr(x: u8, text: &[u8]) -> Option<usize> {
+    reverse_search(One::new(x), text)
+}
+
+/// Return the last index matching either of the bytes `x1` or `x2` in
+/// `text`.
+pub fn memrchr2(x1: u8, x2: u8, text: &[u8]) -> Option<usize> {
+    reverse_search(Two::new(x1, x2), text)
+}
+
+/// Return the last index matching any of the bytes `x1`, `x2` or `x3` in
+/// `text`.
+pub fn memrchr3(x1: u8, x2: u8, x3: u8, text: &[u8]) -> Option<usize> {
+    reverse_search(Three::new(x1, x2, x3), text)
+}
+
+/// The bytes searched for by one of the functions above.
+trait Needle: Copy {
+    /// Return `true` if `byte` is one of the needle bytes.
+    fn matches(self, byte: u8) -> bool;
+
+    /// Return `true` if any byte of `word` is one of the needle bytes.
+    fn matches_word(self, word: usize) -> bool;
+
+    /// Return a vector whose bytes are all ones where the byte of `chunk`
+    /// is one of the needle bytes, and zero elsewhere.
+    #[cfg(all(target_arch = "x86_64", target_feature = "sse2"))]
+    unsafe fn matches_vector(self, chunk: sse2::__m128i) -> sse2::__m128i;
+}
+
+#[derive(Copy, Clone)]
+struct One {
+    x: u8,
+    repeated_x: usize,
+}
+
+impl One {
+    #[inline]
+    fn new(x: u8) -> One {
+        One { x, repeated_x: repeat_byte(x) }
+    }
+}
+
+impl Needle for One {
+    #[inline]
+    fn matches(self, byte: u8) -> bool {
+        byte == self.x
+    }
+
+    #[inline]
+    fn matches_word(self, word: usize) -> bool {
+        contains_zero_byte(word ^ self.repeated_x)
+    }
+
+    #[cfg(all(target_arch = "x86_64", target_feature = "sse2"))]
+    #[inline]
+    unsafe fn matches_vector(self, chunk: sse2::__m128i) -> sse2::__m128i {
+        sse2::_mm_cmpeq_epi8(chunk, sse2::_mm_set1_epi8(self.x as i8))
+    }
+}
+
+#[derive(Copy, Clone)]
+struct Two {
+    one: One,
+    two: One,
+}
+
+impl Two {
+    #[inline]
+    fn new(x1: u8, x2: u8) -> Two {
+        Two { one: One::new(x1), two: One::new(x2) }
+    }
+}
+
+impl Needle for Two {
+    #[inline]
+    fn matches(self, byte: u8) -> bool {
+        self.one.matches(byte) || self.two.matches(byte)
+    }
+
+    #[inline]
+    fn matches_word(self, word: usize) -> bool {
+        self.one.matches_word(word) || self.two.matches_word(word)
+    }
+
+    #[cfg(all(target_arch = "x86_64", target_feature = "sse2"))]
+    #[inline]
+    unsafe fn matches_vector(self, chunk: sse2::__m128i) -> sse2::__m128i {
+        sse2::_mm_or_si128(self.one.matches_vector(chunk), self.two.matches_vector(chunk))
+    }
+}
+
+#[derive(Copy, Clone)]
+struct Three {
+    two: Two,
+    three: One,
+}
+
+impl Three {
+    #[inline]
+    fn new(x1: u8, x2: u8, x3: u8) -> Three {
+        Three { two: Two::new(x1, x2), three: One::new(x3) }
+    }
+}
+
+impl Needle for Three {
+    #[inline]
+    fn matches(self, byte: u8) -> bool {
+        self.two.matches(byte) || self.three.matches(byte)
+    }
+
+    #[inline]
+    fn matches_word(self, word: usize) -> bool {
+        self.two.matches_word(word) || self.three.matches_word(word)
+    }
+
+    #[cfg(all(target_arch = "x86_64", target_feature = "sse2"))]
+    #[inline]
+    unsafe fn matches_vector(self, chunk: sse2::__m128i) -> sse2::__m128i {
+        sse2::_mm_or_si128(self.two.matches_vector(chunk), self.three.matches_vector(chunk))
+    }
+}
+
+/// Return the first index in `text` of a byte matching `needle`.
+#[inline]
+fn forward_search_words<N: Needle>(needle: N, text: &[u8]) -> Option<usize> {
     // Scan for a single byte value by reading two `usize` words at a time.
     //
     // Split `text` in three parts
@@ -72,14 +219,12 @@ pub fn memchr(x: u8, text: &[u8]) -> Option<usize> {
     let mut offset = ptr.align_offset(usize_bytes);
     if offset > 0 {
         offset = cmp::min(offset, len);
-        if let Some(index) = text[..offset].iter().position(|elt| *elt == x) {
+        if let Some(index) = text[..offset].iter().position(|elt| needle.matches(*elt)) {
             return Some(index);
         }
     }
 
     // search the body of the text
-    let repeated_x = repeat_byte(x);
-
     if len >= 2 * usize_bytes {
         while offset <= len - 2 * usize_bytes {
             unsafe {
@@ -87,8 +232,8 @@ pub fn memchr(x: u8, text: &[u8]) -> Option<usize> {
                 let v = *(ptr.offset((offset + usize_bytes) as isize) as *const usize);
 
                 // break if there is a matching byte
-                let zu = contains_zero_byte(u ^ repeated_x);
-                let zv = contains_zero_byte(v ^ repeated_x);
+                let zu = needle.matches_word(u);
+                let zv = needle.matches_word(v);
                 if zu || zv {
                     break;
                 }
@@ -98,11 +243,12 @@ pub fn memchr(x: u8, text: &[u8]) -> Option<usize> {
     }
 
     // find the byte after the point the body loop stopped
-    text[offset..].iter().position(|elt| *elt == x).map(|i| offset + i)
+    text[offset..].iter().position(|elt| needle.matches(*elt)).map(|i| offset + i)
 }
 
-/// Return the last index matching the byte `x` in `text`.
-pub fn memrchr(x: u8, text: &[u8]) -> Option<usize> {
+/// Return the last index in `text` of a byte matching `needle`.
+#[inline]
+fn reverse_search_words<N: Needle>(needle: N, text: &[u8]) -> Option<usize> {
     // Scan for a single byte value by reading two `usize` words at a time.
     //
     // Split `text` in three parts
@@ -118,7 +264,7 @@ pub fn memrchr(x: u8, text: &[u8]) -> Option<usize> {
     let mut offset;
     if end_align > 0 {
         offset = if end_align >= len { 0 } else { len - end_align };
-        if let Some(index) = text[offset..].iter().rposition(|elt| *elt == x) {
+        if let Some(index) = text[offset..].iter().rposition(|elt| needle.matches(*elt)) {
             return Some(offset + index);
         }
     } else {
@@ -126,16 +272,14 @@ pub fn memrchr(x: u8, text: &[u8]) -> Option<usize> {
     }
 
     // search the body of the text
-    let repeated_x = repeat_byte(x);
-
     while offset >= 2 * usize_bytes {
         unsafe {
             let u = *(ptr.offset(offset as isize - 2 * usize_bytes as isize) as *const usize);
             let v = *(ptr.offset(offset as isize - usize_bytes as isize) as *const usize);
 
             // break if there is a matching byte
-            let zu = contains_zero_byte(u ^ repeated_x);
-            let zv = contains_zero_byte(v ^ repeated_x);
+            let zu = needle.matches_word(u);
+            let zv = needle.matches_word(v);
             if zu || zv {
                 break;
             }
@@ -144,7 +288,108 @@ pub fn memrchr(x: u8, text: &[u8]) -> Option<usize> {
     }
 
     // find the byte before the point the body loop stopped
-    text[..offset].iter().rposition(|elt| *elt == x)
+    text[..offset].iter().rposition(|elt| needle.matches(*elt))
+}
+
+/// Searches 16 bytes at a time with SSE2, which every x86-64 CPU has.
+#[cfg(all(target_arch = "x86_64", target_feature = "sse2"))]
+mod sse2 {
+    pub use arch::x86_64::{__m128i, _mm_cmpeq_epi8, _mm_or_si128, _mm_set1_epi8};
+    use arch::x86_64::{_mm_loadu_si128, _mm_movemask_epi8};
+    use super::{Needle, forward_search_words, reverse_search_words};
+
+    const VECTOR_SIZE: usize = 16;
+
+    /// Return the bitmask of the bytes matching `needle` in the 16 bytes of
+    /// `text` starting at `offset`, with bit `i` standing for byte `i`.
+    #[inline(always)]
+    unsafe fn matches_at<N: Needle>(needle: N, text: &[u8], offset: usize) -> u32 {
+        let chunk = _mm_loadu_si128(text.as_ptr().offset(offset as isize) as *const __m128i);
+        _mm_movemask_epi8(needle.matches_vector(chunk)) as u32
+    }
+
+    /// Return the first index in `text` of a byte matching `needle`.
+    #[inline]
+    pub fn forward_search<N: Needle>(needle: N, text: &[u8]) -> Option<usize> {
+        let len = text.len();
+        if len < VECTOR_SIZE {
+            return forward_search_words(needle, text);
+        }
+
+        unsafe {
+            // search the body of the text two vectors at a time
+            let mut offset = 0;
+            while offset + 2 * VECTOR_SIZE <= len {
+                let u = matches_at(needle, text, offset);
+                let v = matches_at(needle, text, offset + VECTOR_SIZE);
+                if u | v != 0 {
+                    break;
+                }
+                offset += 2 * VECTOR_SIZE;
+            }
+
+            // find the vector with the match, if there is one
+            while offset + VECTOR_SIZE <= len {
+                let mask = matches_at(needle, text, offset);
+                if mask != 0 {
+                    return Some(offset + mask.trailing_zeros() as usize);
+                }
+                offset += VECTOR_SIZE;
+            }
+
+            // the last bytes are searched with a vector overlapping the ones
+            // already searched, which are known not to match
+            if offset < len {
+                let last = len - VECTOR_SIZE;
+                let mask = matches_at(needle, text, last);
+                if mask != 0 {
+                    return Some(last + mask.trailing_zeros() as usize);
+                }
+            }
+        }
+        None
+    }
+
+    /// Return the last index in `text` of a byte matching `needle`.
+    #[inline]
+    pub fn reverse_search<N: Needle>(needle: N, text: &[u8]) -> Option<usize> {
+        let len = text.len();
+        if len < VECTOR_SIZE {
+            return reverse_search_words(needle, text);
+        }
+
+        unsafe {
+            // search the body of the text two vectors at a time, from the end
+            let mut end = len;
+            while end >= 2 * VECTOR_SIZE {
+                let u = matches_at(needle, text, end - 2 * VECTOR_SIZE);
+                let v = matches_at(needle, text, end - VECTOR_SIZE);
+                if u | v != 0 {
+                    break;
+                }
+                end -= 2 * VECTOR_SIZE;
+            }
+
+            // find the vector with the match, if there is one
+            while end >= VECTOR_SIZE {
+                let mask = matches_at(needle, text, end - VECTOR_SIZE);
+                if mask != 0 {
+                    return Some(end - VECTOR_SIZE + 31 - mask.leading_zeros() as usize);
+                }
+                end -= VECTOR_SIZE;
+            }
+
+            // the first bytes are searched with a vector overlapping the ones
+            // already searched, which are known not to match
+            if end > 0 {
+                let mask = matches_at(needle, text, 0);
+                if mask != 0 {
+                    return Some(31 - mask.leading_zeros() as usize);
+                }
+            }
+        }
+        None
+    }
 }
 
 // test fallback implementations on all platforms
@@ -228,3 +473,48 @@ fn each_alignment_reversed() {
         assert_eq!(Some(pos - start), memrchr(needle, &data[start..]));
     }
 }
+
+#[test]
+fn each_alignment() {
+    let mut data = [1u8; 64];
+    let needle = 2;
+    let pos = 40;
+    data[pos] = needle;
+    for start in 0..16 {
+        assert_eq!(Some(pos - start), memchr(needle, &data[start..]));
+    }
+}
+
+#[test]
+fn each_position() {
+    // covers every offset within and across the blocks searched at once
+    let mut buf = [0u8; 100];
+    for len in 0..buf.len() {
+        let data = &mut buf[..len];
+        assert_eq!(None, memchr(1, data));
+        assert_eq!(None, memrchr(1, data));
+        for pos in 0..len {
+            data[pos] = 1;
+            assert_eq!(Some(pos), memchr(1, data));
+            assert_eq!(Some(pos), memrchr(1, data));
+            data[pos] = 0;
+        }
+    }
+}
+
+#[test]
+fn matches_any_of_two() {
+    assert_eq!(Some(2), memchr2(b'z', b'c', b"abcz"));
+    assert_eq!(Some(3), memrchr2(b'c', b'z', b"abcz"));
+    assert_eq!(None, memchr2(b'x', b'y', b"abcz"));
+    assert_eq!(None, memrchr2(b'x', b'y', b""));
+}
+
+#[test]
+fn matches_any_of_three() {
+    let text = b"the quick brown fox jumps over the lazy dog";
+    assert_eq!(Some(12), memchr3(b'o', b'w', b'x', text));
+    assert_eq!(Some(41), memrchr3(b'o', b'w', b'x', text));
+    assert_eq!(Some(0), memchr3(b'!', b'?', b't', text));
+    assert_eq!(None, memrchr3(b'!', b'?', b'#', text));
+}
@@ -238,6 +238,48 @@ pub trait SliceExt {
     fn sort_unstable_by_key<B, F>(&mut self, f: F)
         where F: FnMut(&Self::Item) -> B,
               B: Ord;
+
+    #[unstable(feature = "slice_select_nth_unstable", issue = "0")]
+    fn select_nth_unstable(&mut self, index: usize)
+        -> (&mut [Self::Item], &mut Self::Item, &mut [Self::Item])
+        where Self::Item: Ord;
+
+    #[unstable(feature = "slice_select_nth_unstable", issue = "0")]
+    fn select_nth_unstable_by<F>(&mut self, index: usize, compare: F)
+        -> (&mut [Self::Item], &mut Self::Item, &mut [Self::Item])
+        where F: FnMut(&Self::Item, &Self::Item) -> Ordering;
+
+    #[unstable(feature = "slice_select_nth_unstable", issue = "0")]
+    fn select_nth_unstable_by_key<B, F>(&mut self, index: usize, f: F)
+        -> (&mut [Self::Item], &mut Self::Item, &mut [Self::Item])
+        where F: FnMut(&Self::Item) -> B,
+              B: Ord;
+
+    #[unstable(feature = "slice_partial_sort", issue = "0")]
+    fn partial_sort_unstable(&mut self, k: usize)
+        where Self::Item: Ord;
+
+    #[unstable(feature = "slice_partial_sort", issue = "0")]
+    fn partial_sort_unstable_by<F>(&mut self, k: usize, compare: F)
+        where F: FnMut(&Self::Item, &Self::Item) -> Ordering;
+
+    #[unstable(feature = "slice_partial_sort", issue = "0")]
+    fn partial_sort_unstable_by_key<B, F>(&mut self, k: usize, f: F)
+        where F: FnMut(&Self::Item) -> B,
+              B: Ord;
+
+    #[unstable(feature = "slice_partition_dedup", issue = "0")]
+    fn partition_dedup(&mut self) -> (&mut [Self::Item], &mut [Self::Item])
+        where Self::Item: PartialEq;
+
+    #[unstable(feature = "slice_partition_dedup", issue = "0")]
+    fn partition_dedup_by<F>(&mut self, same_bucket: F) -> (&mut [Self::Item], &mut [Self::Item])
+        where F: FnMut(&mut Self::Item, &mut Self::Item) -> bool;
+
+    #[unstable(feature = "slice_partition_dedup", issue = "0")]
+    fn partition_dedup_by_key<K, F>(&mut self, key: F) -> (&mut [Self::Item], &mut [Self::Item])
+        where F: FnMut(&mut Self::Item) -> K,
+              K: PartialEq;
 }
 
 // Use macros to be generic over const/mut
@@ -753,6 +795,164 @@ impl<T> SliceExt for [T] {
     {
         sort::quicksort(self, |a, b| f(a).lt(&f(b)));
     }
+
+    #[inline]
+    fn select_nth_unstable(&mut self, index: usize) -> (&mut [T], &mut T, &mut [T])
+        where T: Ord
+    {
+        sort::select(self, index, |a, b| a.lt(b));
+        split_at_nth(self, index)
+    }
+
+    #[inline]
+    fn select_nth_unstable_by<F>(&mut self, index: usize, mut compare: F)
+        -> (&mut [T], &mut T, &mut [T])
+        where F: FnMut(&T, &T) -> Ordering
+    {
+        sort::select(self, index, |a, b| compare(a, b) == Ordering::Less);
+        split_at_nth(self, index)
+    }
+
+    #[inline]
+    fn select_nth_unstable_by_key<B, F>(&mut self, index: usize, mut f: F)
+        -> (&mut [T], &mut T, &mut [T])
+        where F: FnMut(&T) -> B,
+              B: Ord
+    {
+        sort::select(self, index, |a, b| f(a).lt(&f(b)));
+        split_at_nth(self, index)
+    }
+
+    #[inline]
+    fn partial_sort_unstable(&mut self, k: usize)
+        where T: Ord
+    {
+        sort::partial_sort(self, k, |a, b| a.lt(b));
+    }
+
+    #[inline]
+    fn partial_sort_unstable_by<F>(&mut self, k: usize, mut compare: F)
+        where F: FnMut(&T, &T) -> Ordering
+    {
+        sort::partial_sort(self, k, |a, b| compare(a, b) == Ordering::Less);
+    }
+
+    #[inline]
+    fn partial_sort_unstable_by_key<B, F>(&mut self, k: usize, mut f: F)
+        where F: FnMut(&T) -> B,
+              B: Ord
+    {
+        sort::partial_sort(self, k, |a, b| f(a).lt(&f(b)));
+    }
+
+    #[inline]
+    fn partition_dedup(&mut self) -> (&mut [T], &mut [T])
+        where T: PartialEq
+    {
+        self.partition_dedup_by(|a, b| a == b)
+    }
+
+    fn partition_dedup_by<F>(&mut self, mut same_bucket: F) -> (&mut [T], &mut [T])
+        where F: FnMut(&mut T, &mut T) -> bool
+    {
+        // Although we have a mutable reference to `self`, we cannot make
+        // *arbitrary* changes. The `same_bucket` calls could panic, so we
+        // must ensure that the slice is in a valid state at all times.
+        //
+        // The way that we handle this is by using swaps; we iterate
+        // over all the elements, swapping as we go so that at the end
+        // the elements we wish to keep are in the front, and those we
+        // wish to reject are at the back. We can then split the slice.
+        // This operation is still O(n).
+        //
+        // Example: We start in this state, where `r` represents "next
+        // read" and `w` represents "next_write`.
+        //
+        //           r
+        //     +---+---+---+---+---+---+
+        //     | 0 | 1 | 1 | 2 | 3 | 3 |
+        //     +---+---+---+---+---+---+
+        //           w
+        //
+        // Comparing self[r] against self[w-1], this is not a duplicate, so
+        // we swap self[r] and self[w] (no effect as r==w) and then increment both
+        // r and w, leaving us with:
+        //
+        //               r
+        //     +---+---+---+---+---+---+
+        //     | 0 | 1 | 1 | 2 | 3 | 3 |
+        //     +---+---+---+---+---+---+
+        //               w
+        //
+        // Comparing self[r] against self[w-1], this value is a duplicate,
+        // so we increment `r` but leave everything else unchanged:
+        //
+        //                   r
+        //     +---+---+---+---+---+---+
+        //     | 0 | 1 | 1 | 2 | 3 | 3 |
+        //     +---+---+---+---+---+---+
+        //               w
+        //
+        // Comparing self[r] against self[w-1], this is not a duplicate,
+        // so swap self[r] and self[w] and advance r and w:
+        //
+        //                       r
+        //     +---+---+---+---+---+---+
+        //     | 0 | 1 | 2 | 1 | 3 | 3 |
+        //     +---+---+---+---+---+---+
+        //                   w
+        //
+        // Not a duplicate, repeat:
+        //
+        //                           r
+        //     +---+---+---+---+---+---+
+        //     | 0 | 1 | 2 | 3 | 1 | 3 |
+        //     +---+---+---+---+---+---+
+        //                       w
+        //
+        // Duplicate, advance r. End of slice. Split at w.
+
+        let len = self.len();
+        // The first element is always kept.
+        let mut w: usize = cmp::min(len, 1);
+
+        unsafe {
+            // Avoid bounds checks by using raw pointers.
+            let p = self.as_mut_ptr();
+            let mut r: usize = 1;
+
+            while r < len {
+                let p_r = p.offset(r as isize);
+                let p_wm1 = p.offset((w - 1) as isize);
+                if !same_bucket(&mut *p_r, &mut *p_wm1) {
+                    if r != w {
+                        let p_w = p_wm1.offset(1);
+                        mem::swap(&mut *p_r, &mut *p_w);
+                    }
+                    w += 1;
+                }
+                r += 1;
+            }
+        }
+
+        self.split_at_mut(w)
+    }
+
+    #[inline]
+    fn partition_dedup_by_key<K, F>(&mut self, mut key: F) -> (&mut [T], &mut [T])
+        where F: FnMut(&mut T) -> K,
+              K: PartialEq
+    {
+        self.partition_dedup_by(|a, b| key(a) == key(b))
+    }
+}
+
+// Splits `v` around the element at `index`, as returned by `select_nth_unstable`.
+#[inline]
+fn split_at_nth<T>(v: &mut [T], index: usize) -> (&mut [T], &mut T, &mut [T]) {
+    let (left, rest) = v.split_at_mut(index);
+    let (nth, right) = rest.split_at_mut(1);
+    (left, &mut nth[0], right)
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
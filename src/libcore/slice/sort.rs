@@ -701,3 +701,154 @@ pub fn quicksort<T, F>(v: &mut [T], mut is_less: F)
 
     recurse(v, &mut is_less, None, limit);
 }
+
+/// Reorders `v` so that the element at `index` ends up in its sorted position, using the same
+/// partitioning as `recurse`, but only continuing into the side that contains `index`.
+///
+/// `limit` has the same meaning as in `recurse`.
+fn select_loop<'a, T, F>(mut v: &'a mut [T], mut index: usize, is_less: &mut F,
+                         mut pred: Option<&'a T>, mut limit: usize)
+    where F: FnMut(&T, &T) -> bool
+{
+    // Slices of up to this length get sorted using insertion sort.
+    const MAX_INSERTION: usize = 10;
+
+    // True if the last partitioning was reasonably balanced.
+    let mut was_balanced = true;
+
+    loop {
+        let len = v.len();
+
+        // Very short slices get sorted using insertion sort.
+        if len <= MAX_INSERTION {
+            insertion_sort(v, is_less);
+            return;
+        }
+
+        // If too many bad pivot choices were made, simply fall back to heapsort in order to
+        // guarantee `O(n log n)` worst-case.
+        if limit == 0 {
+            heapsort(v, is_less);
+            return;
+        }
+
+        // If the last partitioning was imbalanced, try breaking patterns in the slice by shuffling
+        // some elements around. Hopefully we'll choose a better pivot this time.
+        if !was_balanced {
+            break_patterns(v);
+            limit -= 1;
+        }
+
+        let (pivot, _) = choose_pivot(v, is_less);
+
+        // If the chosen pivot is equal to the predecessor, then it's the smallest element in the
+        // slice. Partition the slice into elements equal to and elements greater than the pivot.
+        if let Some(p) = pred {
+            if !is_less(p, &v[pivot]) {
+                let mid = partition_equal(v, pivot, is_less);
+
+                // All the elements before `mid` are equal, so if `index` is among them we're done.
+                if index < mid {
+                    return;
+                }
+
+                // Otherwise continue with the elements greater than the pivot.
+                v = &mut {v}[mid..];
+                index -= mid;
+                continue;
+            }
+        }
+
+        // Partition the slice.
+        let (mid, _) = partition(v, pivot, is_less);
+        was_balanced = cmp::min(mid, len - mid) >= len / 8;
+
+        // Split the slice into `left`, `pivot`, and `right`.
+        let (left, right) = {v}.split_at_mut(mid);
+        let (pivot, right) = right.split_at_mut(1);
+        let pivot = &pivot[0];
+
+        // The pivot is in its final position, so only the side containing `index` is left to do.
+        if index < mid {
+            v = left;
+        } else if index > mid {
+            v = right;
+            index -= mid + 1;
+            pred = Some(pivot);
+        } else {
+            return;
+        }
+    }
+}
+
+/// Reorders `v` so that the element at `index` is the one that would be there if `v` was sorted,
+/// with all the elements before it less than or equal to it and all the elements after it greater
+/// than or equal to it.
+///
+/// This is `O(n)` on average and `O(n log n)` worst-case.
+///
+/// # Panics
+///
+/// Panics if `index >= v.len()`.
+pub fn select<T, F>(v: &mut [T], index: usize, mut is_less: F)
+    where F: FnMut(&T, &T) -> bool
+{
+    let len = v.len();
+    if index >= len {
+        panic!("selection index (is {}) should be < len (is {})", index, len);
+    }
+
+    // Selection has no meaningful behavior on zero-sized types.
+    if mem::size_of::<T>() == 0 {
+        return;
+    }
+
+    // The extremes only need a single pass to find.
+    if index == 0 || index == len - 1 {
+        let mut best = 0;
+        for i in 1..len {
+            let better = if index == 0 {
+                is_less(&v[i], &v[best])
+            } else {
+                !is_less(&v[i], &v[best])
+            };
+            if better {
+                best = i;
+            }
+        }
+        v.swap(best, index);
+        return;
+    }
+
+    // Limit the number of imbalanced partitions to `floor(log2(len)) + 1`.
+    let limit = mem::size_of::<usize>() * 8 - len.leading_zeros() as usize;
+
+    select_loop(v, index, &mut is_less, None, limit);
+}
+
+/// Sorts the `k` smallest elements of `v` into `v[..k]`, leaving the rest in unspecified order.
+///
+/// This is `O(n + k log k)` on average and `O(n log n)` worst-case.
+///
+/// # Panics
+///
+/// Panics if `k > v.len()`.
+pub fn partial_sort<T, F>(v: &mut [T], k: usize, mut is_less: F)
+    where F: FnMut(&T, &T) -> bool
+{
+    let len = v.len();
+    if k > len {
+        panic!("partial sort length (is {}) should be <= len (is {})", k, len);
+    }
+    if k == 0 {
+        return;
+    }
+
+    if k == len {
+        quicksort(v, is_less);
+    } else {
+        // Selecting the `k`-th smallest element also moves all the smaller ones in front of it.
+        select(v, k - 1, &mut is_less);
+        quicksort(&mut v[..k - 1], is_less);
+    }
+}
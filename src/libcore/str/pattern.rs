@@ -738,6 +738,508 @@ impl<'a, 'b> Pattern<'a> for &'b str {
 }
 
 
+/////////////////////////////////////////////////////////////////////////////
+// Impl for &[&str]
+/////////////////////////////////////////////////////////////////////////////
+
+/// Searches for any of the strings in the slice.
+///
+/// At each position the strings are tried in the order they are given, and
+/// the first one that matches there is reported: `["a", "ab"]` only ever
+/// matches `"a"`, while `["ab", "a"]` matches `"ab"` where it can. Like the
+/// `&str` pattern, an empty string matches between every pair of characters.
+///
+/// # Examples
+///
+/// ```
+/// let log = "GET /index.html 200\nPOST /login 403\nGET /admin 404";
+/// let methods = &["GET", "POST", "PUT"][..];
+///
+/// assert_eq!(log.find(methods), Some(0));
+/// assert_eq!(log.matches(methods).collect::<Vec<_>>(), ["GET", "POST", "GET"]);
+/// assert_eq!(log.split(&["200", "403", "404"][..]).count(), 4);
+/// ```
+impl<'a, 'b, 'c> Pattern<'a> for &'b [&'c str] {
+    type Searcher = StrSliceSearcher<'a, 'b, 'c>;
+
+    #[inline]
+    fn into_searcher(self, haystack: &'a str) -> StrSliceSearcher<'a, 'b, 'c> {
+        StrSliceSearcher::new(haystack, self)
+    }
+}
+
+/// Associated type for `<&[&str] as Pattern<'a>>::Searcher`.
+#[derive(Clone, Debug)]
+pub struct StrSliceSearcher<'a, 'b, 'c: 'b> {
+    haystack: &'a str,
+    needles: &'b [&'c str],
+
+    // constants
+    /// whether one of the needles is empty, and so matches everywhere
+    has_empty: bool,
+    /// length of the shortest needle
+    min_len: usize,
+    /// first bytes of the needles
+    first_bytes: ByteSet,
+    /// last bytes of the needles
+    last_bytes: ByteSet,
+    /// vectorized filter on the first bytes of the needles, used instead of
+    /// `first_bytes` where the CPU supports it
+    teddy: Option<Teddy>,
+    /// vectorized filter on the last bytes of the needles, used instead of
+    /// `last_bytes` where the CPU supports it
+    teddy_back: Option<Teddy>,
+
+    // variables
+    position: usize,
+    end: usize,
+    /// the next match found by `next()`, after the reject leading up to it
+    pending: Option<(usize, usize)>,
+    /// the next match found by `next_back()`, after the reject leading up
+    /// to it
+    pending_back: Option<(usize, usize)>,
+    /// whether `next()` last reported an empty match at `position`, and so
+    /// has to step over a character before searching again
+    after_empty: bool,
+    /// as `after_empty`, for `next_back()` and `end`
+    after_empty_back: bool,
+}
+
+impl<'a, 'b, 'c> StrSliceSearcher<'a, 'b, 'c> {
+    fn new(haystack: &'a str, needles: &'b [&'c str]) -> StrSliceSearcher<'a, 'b, 'c> {
+        let nonempty = || needles.iter().map(|n| n.as_bytes()).filter(|n| !n.is_empty());
+        let min_len = needles.iter().map(|n| n.len()).min().unwrap_or(0);
+        StrSliceSearcher {
+            haystack,
+            needles,
+            has_empty: needles.iter().any(|n| n.is_empty()),
+            min_len,
+            first_bytes: ByteSet::new(nonempty().map(|n| n[0])),
+            last_bytes: ByteSet::new(nonempty().map(|n| n[n.len() - 1])),
+            teddy: Teddy::new(needles, min_len, false),
+            teddy_back: Teddy::new(needles, min_len, true),
+            position: 0,
+            end: haystack.len(),
+            pending: None,
+            pending_back: None,
+            after_empty: false,
+            after_empty_back: false,
+        }
+    }
+
+    /// Returns the first needle in one of the Teddy `buckets` matching at
+    /// `position`, as a range of the haystack. All needles are tried if
+    /// `buckets` is `!0`.
+    #[inline]
+    fn match_at(&self, position: usize, buckets: u8) -> Option<(usize, usize)> {
+        let rest = &self.haystack.as_bytes()[position..];
+        self.needles.iter().enumerate()
+            .find(|&(i, n)| buckets & Teddy::bucket(i) != 0 && rest.starts_with(n.as_bytes()))
+            .map(|(_, n)| (position, position + n.len()))
+    }
+
+    /// Returns the first needle in one of the Teddy `buckets` matching up to
+    /// `end`, as a range of the haystack. All needles are tried if `buckets`
+    /// is `!0`.
+    #[inline]
+    fn match_ending_at(&self, end: usize, buckets: u8) -> Option<(usize, usize)> {
+        let rest = &self.haystack.as_bytes()[..end];
+        self.needles.iter().enumerate()
+            .find(|&(i, n)| buckets & Teddy::bucket(i) != 0 && rest.ends_with(n.as_bytes()))
+            .map(|(_, n)| (end - n.len(), end))
+    }
+
+    /// Returns the leftmost match starting at or after `position`.
+    fn find(&self, position: usize) -> Option<(usize, usize)> {
+        if self.has_empty {
+            return self.match_at(position, !0);
+        }
+        let haystack = self.haystack.as_bytes();
+        if self.needles.is_empty() || haystack.len() < self.min_len {
+            return None;
+        }
+        if let Some(ref teddy) = self.teddy {
+            // a window is the `teddy.len` bytes at which a match may start
+            let windows = haystack.len() - teddy.len + 1;
+            let mut start = position;
+            while start + 16 <= windows {
+                if let Some(lanes) = teddy.find_16(haystack, start) {
+                    for (i, &buckets) in lanes.iter().enumerate() {
+                        if buckets != 0 {
+                            if let Some(found) = self.match_at(start + i, buckets) {
+                                return Some(found);
+                            }
+                        }
+                    }
+                }
+                start += 16;
+            }
+            while start < windows {
+                let buckets = teddy.find_1(haystack, start);
+                if buckets != 0 {
+                    if let Some(found) = self.match_at(start, buckets) {
+                        return Some(found);
+                    }
+                }
+                start += 1;
+            }
+            return None;
+        }
+        // only the first bytes of matches which fit in the haystack are
+        // looked for, the rest is checked by `match_at`
+        let candidates = &haystack[..haystack.len() - self.min_len + 1];
+        let mut start = position;
+        while start < candidates.len() {
+            match self.first_bytes.find(&candidates[start..]) {
+                Some(i) => {
+                    if let Some(found) = self.match_at(start + i, !0) {
+                        return Some(found);
+                    }
+                    start += i + 1;
+                }
+                None => break,
+            }
+        }
+        None
+    }
+
+    /// Returns the rightmost match ending at or before `end`.
+    fn find_back(&self, end: usize) -> Option<(usize, usize)> {
+        if self.has_empty {
+            return self.match_ending_at(end, !0);
+        }
+        if self.needles.is_empty() || end < self.min_len {
+            return None;
+        }
+        if let Some(ref teddy) = self.teddy_back {
+            let haystack = self.haystack.as_bytes();
+            // a window is the `teddy.len` bytes at which a match may end,
+            // and only the `stop` first ones end by `end`
+            let mut stop = end - teddy.len + 1;
+            while stop >= 16 {
+                let start = stop - 16;
+                if let Some(lanes) = teddy.find_16(haystack, start) {
+                    for (i, &buckets) in lanes.iter().enumerate().rev() {
+                        if buckets != 0 {
+                            let window_end = start + i + teddy.len;
+                            if let Some(found) = self.match_ending_at(window_end, buckets) {
+                                return Some(found);
+                            }
+                        }
+                    }
+                }
+                stop = start;
+            }
+            while stop > 0 {
+                stop -= 1;
+                let buckets = teddy.find_1(haystack, stop);
+                if buckets != 0 {
+                    if let Some(found) = self.match_ending_at(stop + teddy.len, buckets) {
+                        return Some(found);
+                    }
+                }
+            }
+            return None;
+        }
+        // as in `find`, the last bytes of matches which fit are looked for
+        let first_last_byte = self.min_len - 1;
+        let candidates = &self.haystack.as_bytes()[first_last_byte..];
+        let mut stop = end - first_last_byte;
+        while stop > 0 {
+            match self.last_bytes.rfind(&candidates[..stop]) {
+                Some(i) => {
+                    if let Some(found) = self.match_ending_at(first_last_byte + i + 1, !0) {
+                        return Some(found);
+                    }
+                    stop = i;
+                }
+                None => break,
+            }
+        }
+        None
+    }
+}
+
+unsafe impl<'a, 'b, 'c> Searcher<'a> for StrSliceSearcher<'a, 'b, 'c> {
+    #[inline]
+    fn haystack(&self) -> &'a str {
+        self.haystack
+    }
+
+    fn next(&mut self) -> SearchStep {
+        let pos = self.position;
+        if self.after_empty {
+            // reject the character following an empty match
+            return match self.haystack[pos..].chars().next() {
+                None => SearchStep::Done,
+                Some(ch) => {
+                    self.after_empty = false;
+                    self.position += ch.len_utf8();
+                    SearchStep::Reject(pos, self.position)
+                }
+            };
+        }
+        let found = match self.pending.take() {
+            Some(found) => Some(found),
+            None => self.find(pos),
+        };
+        match found {
+            Some((a, b)) if a == pos => {
+                self.position = b;
+                self.after_empty = a == b;
+                SearchStep::Match(a, b)
+            }
+            Some((a, b)) => {
+                self.pending = Some((a, b));
+                self.position = a;
+                SearchStep::Reject(pos, a)
+            }
+            None if pos < self.haystack.len() => {
+                self.position = self.haystack.len();
+                SearchStep::Reject(pos, self.position)
+            }
+            None => SearchStep::Done,
+        }
+    }
+}
+
+unsafe impl<'a, 'b, 'c> ReverseSearcher<'a> for StrSliceSearcher<'a, 'b, 'c> {
+    fn next_back(&mut self) -> SearchStep {
+        let end = self.end;
+        if self.after_empty_back {
+            // reject the character preceding an empty match
+            return match self.haystack[..end].chars().next_back() {
+                None => SearchStep::Done,
+                Some(ch) => {
+                    self.after_empty_back = false;
+                    self.end -= ch.len_utf8();
+                    SearchStep::Reject(self.end, end)
+                }
+            };
+        }
+        let found = match self.pending_back.take() {
+            Some(found) => Some(found),
+            None => self.find_back(end),
+        };
+        match found {
+            Some((a, b)) if b == end => {
+                self.end = a;
+                self.after_empty_back = a == b;
+                SearchStep::Match(a, b)
+            }
+            Some((a, b)) => {
+                self.pending_back = Some((a, b));
+                self.end = b;
+                SearchStep::Reject(b, end)
+            }
+            None if end > 0 => {
+                self.end = 0;
+                SearchStep::Reject(0, end)
+            }
+            None => SearchStep::Done,
+        }
+    }
+}
+
+/// A set of bytes, searched for with `memchr` when there are few of them.
+#[derive(Copy, Clone, Debug)]
+enum ByteSet {
+    Empty,
+    One(u8),
+    Two(u8, u8),
+    Three(u8, u8, u8),
+    /// a fingerprint of more bytes, as in `TwoWaySearcher::byteset`
+    Many(u64),
+}
+
+impl ByteSet {
+    fn new<I: Iterator<Item = u8>>(bytes: I) -> ByteSet {
+        let mut set = ByteSet::Empty;
+        for byte in bytes {
+            set = match set {
+                ByteSet::Empty => ByteSet::One(byte),
+                ByteSet::One(x) if x == byte => set,
+                ByteSet::One(x) => ByteSet::Two(x, byte),
+                ByteSet::Two(x, y) if x == byte || y == byte => set,
+                ByteSet::Two(x, y) => ByteSet::Three(x, y, byte),
+                ByteSet::Three(x, y, z) if x == byte || y == byte || z == byte => set,
+                ByteSet::Three(x, y, z) => {
+                    ByteSet::Many(TwoWaySearcher::byteset_create(&[x, y, z, byte]))
+                }
+                ByteSet::Many(fingerprint) => {
+                    ByteSet::Many(TwoWaySearcher::byteset_create(&[byte]) | fingerprint)
+                }
+            };
+        }
+        set
+    }
+
+    /// Returns the index of the first byte of `bytes` which may be in the
+    /// set.
+    #[inline]
+    fn find(&self, bytes: &[u8]) -> Option<usize> {
+        match *self {
+            ByteSet::Empty => None,
+            ByteSet::One(x) => memchr::memchr(x, bytes),
+            ByteSet::Two(x, y) => memchr::memchr2(x, y, bytes),
+            ByteSet::Three(x, y, z) => memchr::memchr3(x, y, z, bytes),
+            ByteSet::Many(fingerprint) => {
+                bytes.iter().position(|&b| (fingerprint >> ((b & 0x3f) as usize)) & 1 != 0)
+            }
+        }
+    }
+
+    /// Returns the index of the last byte of `bytes` which may be in the
+    /// set.
+    #[inline]
+    fn rfind(&self, bytes: &[u8]) -> Option<usize> {
+        match *self {
+            ByteSet::Empty => None,
+            ByteSet::One(x) => memchr::memrchr(x, bytes),
+            ByteSet::Two(x, y) => memchr::memrchr2(x, y, bytes),
+            ByteSet::Three(x, y, z) => memchr::memrchr3(x, y, z, bytes),
+            ByteSet::Many(fingerprint) => {
+                bytes.iter().rposition(|&b| (fingerprint >> ((b & 0x3f) as usize)) & 1 != 0)
+            }
+        }
+    }
+}
+
+/// A vectorized filter for the needles of a `StrSliceSearcher`, after the
+/// "Teddy" algorithm of Intel's Hyperscan.
+///
+/// The needles are spread over eight buckets. For each of up to three bytes
+/// at the start (or the end) of the needles, two tables give the buckets of
+/// the needles with a given low or high nibble at that byte. Sixteen
+/// positions of the haystack are looked up at once with SSSE3's `pshufb`,
+/// and only the needles in the buckets left at a position are compared
+/// there, which rules out most positions without looking at any needle.
+#[derive(Clone, Debug)]
+struct Teddy {
+    /// number of bytes of the needles in the tables
+    len: usize,
+    /// buckets by the low nibble of each byte
+    lo: [[u8; 16]; 3],
+    /// buckets by the high nibble of each byte
+    hi: [[u8; 16]; 3],
+}
+
+impl Teddy {
+    /// Builds the tables from the first bytes of the needles, or from their
+    /// last bytes if `back` is set.
+    ///
+    /// Returns `None` if the needles are empty, or the CPU can't search the
+    /// tables with SIMD, in which case `ByteSet` is the faster filter.
+    fn new(needles: &[&str], min_len: usize, back: bool) -> Option<Teddy> {
+        #[cfg(target_arch = "x86_64")]
+        let vectorized = ssse3::is_available();
+        #[cfg(not(target_arch = "x86_64"))]
+        let vectorized = false;
+
+        if min_len == 0 || !vectorized {
+            return None;
+        }
+        let len = cmp::min(min_len, 3);
+        let mut teddy = Teddy { len, lo: [[0; 16]; 3], hi: [[0; 16]; 3] };
+        for (i, needle) in needles.iter().enumerate() {
+            let needle = needle.as_bytes();
+            let bytes = if back { &needle[needle.len() - len..] } else { &needle[..len] };
+            for (k, &byte) in bytes.iter().enumerate() {
+                teddy.lo[k][(byte & 0xf) as usize] |= Teddy::bucket(i);
+                teddy.hi[k][(byte >> 4) as usize] |= Teddy::bucket(i);
+            }
+        }
+        Some(teddy)
+    }
+
+    /// Returns the bucket of the `i`th needle, as a bit.
+    #[inline]
+    fn bucket(i: usize) -> u8 {
+        1 << (i % 8)
+    }
+
+    /// Returns the buckets of the needles which may have their bytes at
+    /// `haystack[window..window + self.len]`.
+    #[inline]
+    fn find_1(&self, haystack: &[u8], window: usize) -> u8 {
+        let mut buckets = !0;
+        for (k, &byte) in haystack[window..window + self.len].iter().enumerate() {
+            buckets &= self.lo[k][(byte & 0xf) as usize] & self.hi[k][(byte >> 4) as usize];
+        }
+        buckets
+    }
+
+    /// Returns the buckets of `find_1` for the sixteen windows from `start`
+    /// on, or `None` if they are all empty.
+    #[cfg(target_arch = "x86_64")]
+    #[inline]
+    fn find_16(&self, haystack: &[u8], start: usize) -> Option<[u8; 16]> {
+        assert!(start + 15 + self.len <= haystack.len());
+        // a `Teddy` is only built when SSSE3 is available
+        unsafe { ssse3::find_16(self, haystack, start) }
+    }
+
+    /// Returns the buckets of `find_1` for the sixteen windows from `start`
+    /// on, or `None` if they are all empty.
+    #[cfg(not(target_arch = "x86_64"))]
+    fn find_16(&self, haystack: &[u8], start: usize) -> Option<[u8; 16]> {
+        let mut lanes = [0; 16];
+        for (i, lane) in lanes.iter_mut().enumerate() {
+            *lane = self.find_1(haystack, start + i);
+        }
+        if lanes.iter().all(|&buckets| buckets == 0) { None } else { Some(lanes) }
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+mod ssse3 {
+    use arch::x86_64::*;
+    use sync::atomic::{AtomicUsize, Ordering};
+    use super::Teddy;
+
+    /// Returns whether the CPU supports SSSE3, checking only once.
+    pub fn is_available() -> bool {
+        // 0 until checked, then 1 if it isn't supported and 2 if it is
+        static SSSE3: AtomicUsize = AtomicUsize::new(0);
+
+        if cfg!(target_feature = "ssse3") {
+            return true;
+        }
+        match SSSE3.load(Ordering::Relaxed) {
+            0 => {
+                let available = unsafe { __cpuid(1).ecx & (1 << 9) != 0 };
+                SSSE3.store(if available { 2 } else { 1 }, Ordering::Relaxed);
+                available
+            }
+            checked => checked == 2,
+        }
+    }
+
+    #[target_feature(enable = "ssse3")]
+    pub unsafe fn find_16(teddy: &Teddy, haystack: &[u8], start: usize) -> Option<[u8; 16]> {
+        let low_nibbles = _mm_set1_epi8(0xf);
+        let mut buckets = _mm_set1_epi8(-1);
+        for k in 0..teddy.len {
+            let ptr = haystack.as_ptr().offset((start + k) as isize);
+            let bytes = _mm_loadu_si128(ptr as *const __m128i);
+            let lo = _mm_and_si128(bytes, low_nibbles);
+            let hi = _mm_and_si128(_mm_srli_epi16(bytes, 4), low_nibbles);
+            let lo_table = _mm_loadu_si128(teddy.lo[k].as_ptr() as *const __m128i);
+            let hi_table = _mm_loadu_si128(teddy.hi[k].as_ptr() as *const __m128i);
+            buckets = _mm_and_si128(buckets, _mm_and_si128(_mm_shuffle_epi8(lo_table, lo),
+                                                           _mm_shuffle_epi8(hi_table, hi)));
+        }
+        let empty = _mm_cmpeq_epi8(buckets, _mm_setzero_si128());
+        if _mm_movemask_epi8(empty) == 0xffff {
+            return None;
+        }
+        let mut lanes = [0; 16];
+        _mm_storeu_si128(lanes.as_mut_ptr() as *mut __m128i, buckets);
+        Some(lanes)
+    }
+}
+
+
 /////////////////////////////////////////////////////////////////////////////
 // Two Way substring searcher
 /////////////////////////////////////////////////////////////////////////////
@@ -961,6 +1463,8 @@ struct TwoWaySearcher {
     memory: usize,
     /// index into needle after which we have already matched
     memory_back: usize,
+    /// skips ahead to candidate positions with `memchr`
+    prefilter: Prefilter,
 }
 
 /*
@@ -1081,6 +1585,7 @@ impl TwoWaySearcher {
                 end,
                 memory: 0,
                 memory_back: needle.len(),
+                prefilter: Prefilter::new(needle),
             }
         } else {
             // long period case -- we have an approximation to the actual period,
@@ -1100,6 +1605,7 @@ impl TwoWaySearcher {
                 end,
                 memory: usize::MAX, // Dummy value to signify that the period is long
                 memory_back: usize::MAX,
+                prefilter: Prefilter::new(needle),
             }
         }
     }
@@ -1128,6 +1634,28 @@ impl TwoWaySearcher {
         let old_pos = self.position;
         let needle_last = needle.len() - 1;
         'search: loop {
+            // Jump to the next position where the prefilter's byte of the
+            // needle lines up, unless part of the needle is known to match
+            // at the current one. No match can start in between.
+            if self.prefilter.active && (long_period || self.memory == 0) {
+                let i = self.prefilter.index;
+                let start = self.position + i;
+                let end = (haystack.len() + i).saturating_sub(needle_last);
+                if start < end {
+                    match memchr::memchr(needle[i], &haystack[start..end]) {
+                        Some(skip) => {
+                            self.prefilter.record(skip);
+                            self.position += skip;
+                        }
+                        None => {
+                            self.prefilter.record(end - start);
+                            self.position = haystack.len();
+                            return S::rejecting(old_pos, self.position);
+                        }
+                    }
+                }
+            }
+
             // Check that we have room to search in
             // position + needle_last can not overflow if we assume slices
             // are bounded by isize's range.
@@ -1211,6 +1739,26 @@ impl TwoWaySearcher {
         // are independent.
         let old_end = self.end;
         'search: loop {
+            // Jump to the previous position where the prefilter's byte of
+            // the needle lines up, as in `next()`.
+            if self.prefilter.active && (long_period || self.memory_back == needle.len()) &&
+                self.end >= needle.len()
+            {
+                let i = self.prefilter.index;
+                let window = &haystack[i..self.end - needle.len() + i + 1];
+                match memchr::memrchr(needle[i], window) {
+                    Some(index) => {
+                        self.prefilter.record(window.len() - 1 - index);
+                        self.end = index + needle.len();
+                    }
+                    None => {
+                        self.prefilter.record(window.len());
+                        self.end = 0;
+                        return S::rejecting(0, old_end);
+                    }
+                }
+            }
+
             // Check that we have room to search in
             // end - needle.len() will wrap around when there is no more room,
             // but due to slice length limits it can never wrap all the way back
@@ -1375,6 +1923,71 @@ impl TwoWaySearcher {
     }
 }
 
+/// Finds candidate positions for `TwoWaySearcher` by looking for a single
+/// byte of the needle with `memchr`, which is vectorized on most targets.
+///
+/// This only pays off when that byte is rare in the haystack, so the byte
+/// is chosen to be unlikely in text, and the prefilter turns itself off
+/// when it keeps finding candidates close to where it started.
+#[derive(Clone, Debug)]
+struct Prefilter {
+    /// index into needle of the byte to look for
+    index: usize,
+    /// whether the prefilter is still in use
+    active: bool,
+    /// number of times the prefilter has run
+    runs: usize,
+    /// number of haystack bytes it has skipped in total
+    skipped: usize,
+}
+
+impl Prefilter {
+    /// The number of runs after which the prefilter's usefulness is judged.
+    const MIN_RUNS: usize = 32;
+    /// The average number of bytes each run must skip to stay in use.
+    const MIN_AVERAGE_SKIP: usize = 16;
+
+    fn new(needle: &[u8]) -> Prefilter {
+        // Lowercase letters and spaces are the most common bytes in text,
+        // then other letters, digits and UTF-8 continuation bytes.
+        fn rank(byte: u8) -> u8 {
+            if byte == b' ' || byte.is_ascii_lowercase() {
+                0
+            } else if byte.is_ascii_alphanumeric() || (byte >= 0x80 && byte < 0xc0) {
+                1
+            } else {
+                2
+            }
+        }
+
+        let mut index = 0;
+        for (i, &byte) in needle.iter().enumerate() {
+            if rank(byte) > rank(needle[index]) {
+                index = i;
+            }
+        }
+        Prefilter {
+            index,
+            active: true,
+            runs: 0,
+            skipped: 0,
+        }
+    }
+
+    #[inline]
+    fn record(&mut self, skipped: usize) {
+        self.runs += 1;
+        self.skipped = self.skipped.saturating_add(skipped);
+        if self.runs >= Self::MIN_RUNS {
+            if self.skipped < self.runs * Self::MIN_AVERAGE_SKIP {
+                self.active = false;
+            }
+            self.runs = 0;
+            self.skipped = 0;
+        }
+    }
+}
+
 // TwoWayStrategy allows the algorithm to either skip non-matches as quickly
 // as possible, or to work in a mode where it emits Rejects relatively quickly.
 trait TwoWayStrategy {
@@ -0,0 +1,179 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![cfg(target_arch = "x86_64")]
+
+use core::arch::x86_64::*;
+use core::mem;
+
+fn bytes(a: __m128i) -> [u8; 16] {
+    unsafe { mem::transmute(a) }
+}
+
+fn load(bytes: &[u8; 16]) -> __m128i {
+    unsafe { _mm_loadu_si128(bytes.as_ptr() as *const __m128i) }
+}
+
+#[test]
+fn test_sse2_integers() {
+    unsafe {
+        let a = _mm_setr_epi32(1, 2, 3, 4);
+        let b = _mm_set1_epi32(10);
+        let sum: [i32; 4] = mem::transmute(_mm_add_epi32(a, b));
+        assert_eq!(sum, [11, 12, 13, 14]);
+        let diff: [i32; 4] = mem::transmute(_mm_sub_epi32(a, b));
+        assert_eq!(diff, [-9, -8, -7, -6]);
+        assert_eq!(_mm_cvtsi128_si32(_mm_set_epi32(4, 3, 2, 1)), 1);
+
+        let x = _mm_set1_epi8(-1);
+        assert_eq!(bytes(_mm_adds_epu8(x, _mm_set1_epi8(1))), [255; 16]);
+        assert_eq!(bytes(_mm_subs_epu8(_mm_setzero_si128(), x)), [0; 16]);
+        assert_eq!(bytes(_mm_andnot_si128(x, x)), [0; 16]);
+        assert_eq!(bytes(_mm_xor_si128(x, _mm_set1_epi8(0x0f))), [0xf0; 16]);
+    }
+}
+
+#[test]
+fn test_sse2_compare_movemask() {
+    let mut haystack = *b"hello, world....";
+    haystack[15] = b'o';
+    unsafe {
+        let eq = _mm_cmpeq_epi8(load(&haystack), _mm_set1_epi8(b'o' as i8));
+        assert_eq!(_mm_movemask_epi8(eq), (1 << 4) | (1 << 8) | (1 << 15));
+
+        let a = _mm_setr_epi32(-1, 0, 1, 2);
+        let gt = _mm_cmpgt_epi32(a, _mm_setzero_si128());
+        let lanes: [i32; 4] = mem::transmute(gt);
+        assert_eq!(lanes, [0, 0, -1, -1]);
+    }
+}
+
+#[test]
+fn test_sse2_shift() {
+    unsafe {
+        let a = _mm_set1_epi16(0x1234);
+        let lanes: [u16; 8] = mem::transmute(_mm_srli_epi16(a, 4));
+        assert_eq!(lanes, [0x0123; 8]);
+        assert_eq!(bytes(_mm_srli_epi16(a, 16)), [0; 16]);
+    }
+}
+
+#[test]
+fn test_ssse3_shuffle() {
+    if !is_x86_feature_detected!("ssse3") {
+        return;
+    }
+    let table = *b"0123456789abcdef";
+    let mut indices = [0u8; 16];
+    for (i, index) in indices.iter_mut().enumerate() {
+        *index = 15 - i as u8;
+    }
+    indices[0] = 0x80;
+    unsafe {
+        let shuffled = bytes(_mm_shuffle_epi8(load(&table), load(&indices)));
+        assert_eq!(&shuffled, b"\0edcba9876543210");
+    }
+}
+
+#[test]
+fn test_sse2_sad() {
+    let a = [1u8; 16];
+    let b = [3u8; 16];
+    unsafe {
+        let sad: [u64; 2] = mem::transmute(_mm_sad_epu8(load(&a), load(&b)));
+        assert_eq!(sad, [16, 16]);
+    }
+}
+
+#[test]
+fn test_sse_floats() {
+    unsafe {
+        let a = _mm_setr_ps(1.0, 4.0, 9.0, 16.0);
+        let mut out = [0.0f32; 4];
+        _mm_storeu_ps(out.as_mut_ptr(), _mm_sqrt_ps(a));
+        assert_eq!(out, [1.0, 2.0, 3.0, 4.0]);
+        _mm_storeu_ps(out.as_mut_ptr(), _mm_max_ps(a, _mm_set1_ps(5.0)));
+        assert_eq!(out, [5.0, 5.0, 9.0, 16.0]);
+        assert_eq!(_mm_movemask_ps(_mm_cmplt_ps(a, _mm_set1_ps(5.0))), 0b0011);
+    }
+}
+
+#[test]
+fn test_cpuid() {
+    unsafe {
+        let (max_leaf, _) = __get_cpuid_max(0);
+        assert!(max_leaf >= 1);
+        // SSE2 is part of the x86-64 baseline.
+        assert!(__cpuid(1).edx & (1 << 26) != 0);
+    }
+}
+
+#[test]
+fn test_sse42_string_compare() {
+    if !is_x86_feature_detected!("sse4.2") {
+        return;
+    }
+    unsafe {
+        let needles = load(b"aeiou\0\0\0\0\0\0\0\0\0\0\0");
+        let haystack = load(b"rhythm and blues");
+        let idx = _mm_cmpistri(needles, haystack,
+                               _SIDD_UBYTE_OPS | _SIDD_CMP_EQUAL_ANY);
+        assert_eq!(idx, 7);
+        let none = load(b"kqx\0\0\0\0\0\0\0\0\0\0\0\0\0");
+        let idx = _mm_cmpistri(none, haystack,
+                               _SIDD_UBYTE_OPS | _SIDD_CMP_EQUAL_ANY);
+        assert_eq!(idx, 16);
+
+        // CRC-32C of "123456789" is the standard check value.
+        let crc = b"123456789".iter().fold(!0, |crc, &b| _mm_crc32_u8(crc, b));
+        assert_eq!(!crc, 0xe3069283);
+    }
+}
+
+#[test]
+fn test_avx2_integers() {
+    if !is_x86_feature_detected!("avx2") {
+        return;
+    }
+    unsafe {
+        let data: Vec<u8> = (0..32).collect();
+        let a = _mm256_loadu_si256(data.as_ptr() as *const __m256i);
+        let b = _mm256_set1_epi8(1);
+        let mut out = [0u8; 32];
+        _mm256_storeu_si256(out.as_mut_ptr() as *mut __m256i, _mm256_add_epi8(a, b));
+        assert_eq!(out[..], (1..33).collect::<Vec<u8>>()[..]);
+
+        let eq = _mm256_cmpeq_epi8(a, _mm256_set1_epi8(31));
+        assert_eq!(_mm256_movemask_epi8(eq), 1 << 31);
+        assert_eq!(_mm256_testz_si256(a, _mm256_setzero_si256()), 1);
+    }
+}
+
+#[test]
+fn test_bit_manipulation() {
+    unsafe {
+        if is_x86_feature_detected!("popcnt") {
+            assert_eq!(_popcnt64(-1), 64);
+            assert_eq!(_popcnt32(0b1011), 3);
+        }
+        if is_x86_feature_detected!("lzcnt") {
+            assert_eq!(_lzcnt_u32(0), 32);
+            assert_eq!(_lzcnt_u64(1), 63);
+        }
+        if is_x86_feature_detected!("bmi") {
+            assert_eq!(_tzcnt_u64(0), 64);
+            assert_eq!(_tzcnt_u32(0b1000), 3);
+        }
+        if is_x86_feature_detected!("bmi2") {
+            assert_eq!(_pdep_u64(0b101, 0b1111_0000), 0b0101_0000);
+            assert_eq!(_pext_u64(0b0101_0000, 0b1111_0000), 0b101);
+        }
+    }
+}
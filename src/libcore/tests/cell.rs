@@ -304,3 +304,29 @@ fn refcell_replace_borrows() {
     let _b = x.borrow();
     x.replace(1);
 }
+
+#[test]
+fn once_cell() {
+    let cell = OnceCell::new();
+    assert_eq!(cell.get(), None);
+    assert_eq!(*cell.get_or_init(|| 92), 92);
+    assert_eq!(*cell.get_or_init(|| 62), 92);
+    assert_eq!(cell.set(62), Err(62));
+    assert_eq!(cell.into_inner(), Some(92));
+
+    let mut cell = OnceCell::new();
+    assert_eq!(cell.set("Hello".to_owned()), Ok(()));
+    cell.get_mut().unwrap().push_str(" world");
+    assert_eq!(format!("{:?}", cell), "OnceCell { value: \"Hello world\" }");
+    assert_eq!(format!("{:?}", OnceCell::<i32>::new()), "OnceCell { value: <uninit> }");
+}
+
+#[test]
+#[should_panic]
+fn once_cell_reentrant_init() {
+    let cell = OnceCell::new();
+    cell.get_or_init(|| {
+        cell.set(1).unwrap();
+        2
+    });
+}
@@ -39,3 +39,37 @@ fn test_format_f32() {
     assert_eq!("0.0", format!("{:?}", 0.0f32));
     assert_eq!("1.01", format!("{:?}", 1.01f32));
 }
+
+#[test]
+fn test_format_float_no_flags() {
+    use core::f64;
+    assert_eq!(format!("{}", 0.1f64), "0.1");
+    assert_eq!(format!("{}", -1.5f64), "-1.5");
+    assert_eq!(format!("{}", 1e21f64), "1000000000000000000000");
+    assert_eq!(format!("{}", 1.0f64 / 3.0), "0.3333333333333333");
+    assert_eq!(format!("{:?}", 1e-7f32), "0.0000001");
+    assert_eq!(format!("{}", 1.0f32 / 3.0), "0.33333334");
+    assert_eq!(format!("{:e}", 1e-7f64), "1e-7");
+    assert_eq!(format!("{}", f64::MIN_POSITIVE).len(), 2 + 307 + 17);
+}
+
+#[test]
+fn test_num_buffer_float() {
+    use core::fmt::NumBuffer;
+    use core::{f32, f64};
+
+    let mut buf = NumBuffer::new();
+    assert_eq!(buf.format(0.0), "0");
+    assert_eq!(buf.format(-0.0), "0");
+    assert_eq!(buf.format(2.5f32), "2.5");
+    assert_eq!(buf.format(-0.1f64), "-0.1");
+    assert_eq!(buf.format(f64::NAN), "NaN");
+    assert_eq!(buf.format(f64::NEG_INFINITY), "-inf");
+    for &v in &[f64::MAX, f64::MIN, f64::MIN_POSITIVE, 5e-324, -1.2567434849616886e-308,
+                1e15, 123456.789, -9007199254740993.0] {
+        assert_eq!(buf.format(v), format!("{}", v));
+    }
+    for &v in &[f32::MAX, f32::MIN_POSITIVE, 1e-45, 16777217.0, -0.3] {
+        assert_eq!(buf.format(v), format!("{}", v));
+    }
+}
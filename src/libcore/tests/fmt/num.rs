@@ -150,3 +150,39 @@ fn test_format_int_twos_complement() {
     assert!(format!("{}", i32::MIN) == "-2147483648");
     assert!(format!("{}", i64::MIN) == "-9223372036854775808");
 }
+
+#[test]
+fn test_format_int_no_flags() {
+    use core::{i128, u128};
+    assert_eq!(format!("{}", -12345678i32), "-12345678");
+    assert_eq!(format!("{}", i128::MIN), "-170141183460469231731687303715884105728");
+    assert_eq!(format!("{}", u128::MAX), "340282366920938463463374607431768211455");
+    assert_eq!(format!("{:?}", -0i64), "0");
+    assert_eq!(format!("{:5}|{:<5}|{:+}", -12, 34u8, 56), "  -12|34   |+56");
+}
+
+#[test]
+fn test_num_buffer_int() {
+    use core::fmt::NumBuffer;
+    use core::{i8, i64, i128, u8, u64, u128, isize, usize};
+
+    let mut buf = NumBuffer::new();
+    assert_eq!(buf.format(0), "0");
+    assert_eq!(buf.format(-7), "-7");
+    assert_eq!(buf.format(10000u16), "10000");
+    assert_eq!(buf.format(i8::MIN), "-128");
+    assert_eq!(buf.format(u8::MAX), "255");
+    assert_eq!(buf.format(i64::MIN), "-9223372036854775808");
+    assert_eq!(buf.format(u64::MAX), "18446744073709551615");
+    assert_eq!(buf.format(i128::MIN), "-170141183460469231731687303715884105728");
+    assert_eq!(buf.format(u128::MAX), "340282366920938463463374607431768211455");
+    assert_eq!(buf.format(isize::MIN), format!("{}", isize::MIN));
+    assert_eq!(buf.format(usize::MAX), format!("{}", usize::MAX));
+
+    let mut n = 1u64;
+    for _ in 0..20 {
+        assert_eq!(buf.format(n), format!("{}", n));
+        assert_eq!(buf.format(n - 1), format!("{}", n - 1));
+        n = n.wrapping_mul(10);
+    }
+}
@@ -1890,3 +1890,195 @@ fn test_flat_map_try_folds() {
     assert_eq!(iter.try_rfold(0, i8::checked_add), None);
     assert_eq!(iter.next_back(), Some(35));
 }
+
+#[test]
+fn test_iterator_array_chunks() {
+    let mut it = (0..8).array_chunks::<[_; 3]>();
+    assert_eq!(it.size_hint(), (2, Some(2)));
+    assert_eq!(it.next(), Some([0, 1, 2]));
+    assert_eq!(it.len(), 1);
+    assert_eq!(it.next(), Some([3, 4, 5]));
+    assert_eq!(it.remainder(), &[]);
+    assert_eq!(it.next(), None);
+    assert_eq!(it.remainder(), &[6, 7]);
+    assert_eq!(it.next(), None);
+    assert_eq!(it.remainder(), &[6, 7]);
+
+    let mut it = (0..8).array_chunks::<[_; 3]>();
+    assert_eq!(it.next_back(), Some([3, 4, 5]));
+    assert_eq!(it.remainder(), &[6, 7]);
+    assert_eq!(it.next(), Some([0, 1, 2]));
+    assert_eq!(it.next_back(), None);
+    assert_eq!(it.remainder(), &[6, 7]);
+
+    let v: Vec<_> = (0..9).map(|i| i.to_string()).array_chunks::<[_; 3]>().rev().collect();
+    assert_eq!(v, [["6", "7", "8"], ["3", "4", "5"], ["0", "1", "2"]]);
+}
+
+#[test]
+#[should_panic]
+fn test_iterator_array_chunks_zero() {
+    let _ = (0..8).array_chunks::<[_; 0]>();
+}
+
+#[test]
+fn test_iterator_array_windows() {
+    let mut it = (0..5).array_windows::<[_; 3]>();
+    assert_eq!(it.len(), 3);
+    assert_eq!(it.next(), Some([0, 1, 2]));
+    assert_eq!(it.len(), 2);
+    assert_eq!(it.next(), Some([1, 2, 3]));
+    assert_eq!(it.next(), Some([2, 3, 4]));
+    assert_eq!(it.next(), None);
+
+    let mut it = (0..2).array_windows::<[_; 3]>();
+    assert_eq!(it.size_hint(), (0, Some(0)));
+    assert_eq!(it.next(), None);
+
+    let v: Vec<_> = "abc".chars().map(String::from).array_windows::<[_; 2]>().collect();
+    assert_eq!(v, [["a", "b"], ["b", "c"]]);
+}
+
+#[test]
+fn test_iterator_interleave() {
+    let v: Vec<_> = (0..5).interleave(10..12).collect();
+    assert_eq!(v, [0, 10, 1, 11, 2, 3, 4]);
+    let v: Vec<_> = (0..2).interleave(10..15).collect();
+    assert_eq!(v, [0, 10, 1, 11, 12, 13, 14]);
+    let v: Vec<_> = (0..5).interleave(10..12).rev().collect();
+    assert_eq!(v, [4, 3, 2, 11, 1, 10, 0]);
+    let v: Vec<_> = (0..2).interleave(10..12).rev().collect();
+    assert_eq!(v, [11, 1, 10, 0]);
+
+    let mut it = (0..3).interleave(10..13);
+    assert_eq!(it.size_hint(), (6, Some(6)));
+    assert_eq!(it.next_back(), Some(12));
+    assert_eq!(it.next(), Some(0));
+    assert_eq!(it.next_back(), Some(2));
+    assert_eq!(it.next(), Some(10));
+    assert_eq!(it.next_back(), Some(11));
+    assert_eq!(it.next(), Some(1));
+    assert_eq!(it.next(), None);
+    assert_eq!(it.next_back(), None);
+}
+
+#[test]
+fn test_iterator_intersperse() {
+    let v: Vec<_> = (0..4).intersperse(9).collect();
+    assert_eq!(v, [0, 9, 1, 9, 2, 9, 3]);
+    let v: Vec<_> = (0..4).intersperse(9).rev().collect();
+    assert_eq!(v, [3, 9, 2, 9, 1, 9, 0]);
+    assert_eq!((0..1).intersperse(9).collect::<Vec<_>>(), [0]);
+    assert_eq!((0..0).intersperse(9).size_hint(), (0, Some(0)));
+
+    let mut it = (0..3).intersperse(9);
+    assert_eq!(it.size_hint(), (5, Some(5)));
+    assert_eq!(it.next(), Some(0));
+    assert_eq!(it.next_back(), Some(2));
+    assert_eq!(it.size_hint(), (3, Some(3)));
+    assert_eq!(it.next_back(), Some(9));
+    assert_eq!(it.next(), Some(9));
+    assert_eq!(it.next(), Some(1));
+    assert_eq!(it.size_hint(), (0, Some(0)));
+    assert_eq!(it.next(), None);
+    assert_eq!(it.next_back(), None);
+
+    let mut it = (0..2).intersperse(9);
+    assert_eq!(it.next(), Some(0));
+    assert_eq!(it.next_back(), Some(1));
+    assert_eq!(it.next(), Some(9));
+    assert_eq!(it.next_back(), None);
+
+    let (lo, hi) = (0..usize::MAX).intersperse(0).size_hint();
+    assert_eq!((lo, hi), (usize::MAX, None));
+}
+
+#[test]
+fn test_iterator_dedup_by_key() {
+    let xs = [1, 1, 2, 3, 3, 3, 1, 4, 4];
+    let v: Vec<_> = xs.iter().dedup_by_key(|&&x| x).collect();
+    assert_eq!(v, [&1, &2, &3, &1, &4]);
+    let v: Vec<_> = xs.iter().dedup_by_key(|&&x| x).rev().collect();
+    assert_eq!(v, [&4, &1, &3, &2, &1]);
+
+    // the first element of each run is kept, from both ends
+    let pairs = [(0, 'a'), (0, 'b'), (1, 'c'), (1, 'd'), (1, 'e'), (2, 'f')];
+    let v: Vec<_> = pairs.iter().dedup_by_key(|p| p.0).map(|p| p.1).collect();
+    assert_eq!(v, ['a', 'c', 'f']);
+    let v: Vec<_> = pairs.iter().dedup_by_key(|p| p.0).map(|p| p.1).rev().collect();
+    assert_eq!(v, ['f', 'c', 'a']);
+
+    let mut it = pairs.iter().dedup_by_key(|p| p.0).map(|p| p.1);
+    assert_eq!(it.next(), Some('a'));
+    assert_eq!(it.next_back(), Some('f'));
+    assert_eq!(it.next_back(), Some('c'));
+    assert_eq!(it.next(), None);
+
+    let mut it = pairs.iter().dedup_by_key(|p| p.0).map(|p| p.1);
+    assert_eq!(it.next_back(), Some('f'));
+    assert_eq!(it.next(), Some('a'));
+    assert_eq!(it.next(), Some('c'));
+    assert_eq!(it.next_back(), None);
+
+    assert_eq!((0..0).dedup_by_key(|&x| x).size_hint(), (0, Some(0)));
+    assert_eq!((0..5).dedup_by_key(|&x| x).size_hint(), (1, Some(5)));
+}
+
+#[test]
+fn test_iterator_group_by_key() {
+    let xs = [1, 3, 2, 4, 6, 5];
+    let groups = xs.iter().group_by_key(|&&x| x % 2);
+    let mut it = groups.into_iter();
+    assert_eq!(it.size_hint(), (1, Some(6)));
+    let (key, group) = it.next().unwrap();
+    assert_eq!((key, group.collect::<Vec<_>>()), (1, vec![&1, &3]));
+    assert_eq!(it.size_hint(), (1, Some(4)));
+    let (key, group) = it.next().unwrap();
+    assert_eq!((key, group.collect::<Vec<_>>()), (0, vec![&2, &4, &6]));
+    let (key, group) = it.next().unwrap();
+    assert_eq!((key, group.collect::<Vec<_>>()), (1, vec![&5]));
+    assert!(it.next().is_none());
+
+    let mut lens = Vec::new();
+    for (c, g) in &"aabccc".chars().group_by_key(|&c| c) {
+        lens.push((c, g.count()));
+    }
+    assert_eq!(lens, [('a', 2), ('b', 1), ('c', 3)]);
+}
+
+#[test]
+fn test_iterator_group_by_key_reads_once() {
+    use core::cell::Cell;
+
+    let reads = Cell::new(0);
+    let keys = Cell::new(0);
+    let groups = (0..10).inspect(|_| reads.set(reads.get() + 1))
+                        .group_by_key(|&x| { keys.set(keys.get() + 1); x / 3 });
+    let mut it = groups.into_iter();
+
+    let (key, mut first) = it.next().unwrap();
+    assert_eq!(key, 0);
+    assert_eq!(reads.get(), 1);
+    assert_eq!(first.next(), Some(0));
+    assert_eq!(first.next(), Some(1));
+    assert_eq!(reads.get(), 2);
+
+    // the rest of the first group is skipped
+    let (key, mut second) = it.next().unwrap();
+    assert_eq!(key, 1);
+    assert_eq!(first.next(), None);
+    assert_eq!(second.size_hint(), (1, Some(7)));
+
+    // an unfinished group only keeps its first element
+    let (key, mut third) = it.next().unwrap();
+    assert_eq!(key, 2);
+    assert_eq!(second.next(), Some(3));
+    assert_eq!(second.next(), None);
+    assert_eq!(third.by_ref().collect::<Vec<_>>(), [6, 7, 8]);
+
+    let (key, last) = it.next().unwrap();
+    assert_eq!((key, last.collect::<Vec<_>>()), (3, vec![9]));
+    assert!(it.next().is_none());
+    assert_eq!(reads.get(), 10);
+    assert_eq!(keys.get(), 10);
+}
@@ -20,23 +20,39 @@
 #![feature(fixed_size_array)]
 #![feature(flt2dec)]
 #![feature(fmt_internals)]
+#![feature(fmt_num_buffer)]
 #![feature(iterator_step_by)]
 #![feature(i128_type)]
+#![feature(int_error_matching)]
 #![feature(inclusive_range)]
 #![feature(inclusive_range_syntax)]
 #![feature(iterator_try_fold)]
+#![feature(iter_array_chunks)]
+#![feature(iter_array_windows)]
+#![feature(iter_dedup_by_key)]
+#![feature(iter_group_by_key)]
+#![feature(iter_interleave)]
+#![feature(iter_intersperse)]
 #![feature(iter_rfind)]
 #![feature(iter_rfold)]
 #![feature(nonzero)]
+#![feature(once_cell)]
+#![feature(parse_prefix)]
 #![feature(pattern)]
+#![feature(portable_simd)]
 #![feature(raw)]
 #![feature(refcell_replace_swap)]
 #![feature(sip_hash_13)]
+#![feature(slice_partial_sort)]
+#![feature(slice_partition_dedup)]
 #![feature(slice_patterns)]
 #![feature(slice_rotate)]
+#![feature(slice_select_nth_unstable)]
 #![feature(sort_internals)]
 #![feature(specialization)]
 #![feature(step_trait)]
+#![feature(stdsimd)]
+#![feature(target_feature)]
 #![feature(test)]
 #![feature(trusted_len)]
 #![feature(try_from)]
@@ -47,6 +63,7 @@ extern crate core;
 extern crate test;
 
 mod any;
+mod arch;
 mod array;
 mod atomic;
 mod cell;
@@ -65,6 +82,7 @@ mod option;
 mod pattern;
 mod ptr;
 mod result;
+mod simd;
 mod slice;
 mod str;
 mod tuple;
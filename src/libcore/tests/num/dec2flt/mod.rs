@@ -11,6 +11,7 @@
 #![allow(overflowing_literals)]
 
 use std::{i64, f32, f64};
+use core::num::dec2flt::dec2flt_prefix;
 
 mod parse;
 mod rawfp;
@@ -127,6 +128,22 @@ fn inf() {
     assert_eq!("-inf".parse(), Ok(f32::NEG_INFINITY));
 }
 
+#[test]
+fn prefix() {
+    assert_eq!(dec2flt_prefix("2.5e3, 7"), Ok((2500.0f64, 5)));
+    assert_eq!(dec2flt_prefix("-.5x"), Ok((-0.5f32, 3)));
+    assert_eq!(dec2flt_prefix("+1e+"), Ok((1.0f64, 2)));
+    assert_eq!(dec2flt_prefix("1.448997445238699 "), Ok((1.448997445238699f64, 17)));
+    assert_eq!(dec2flt_prefix("1e400;"), Ok((f64::INFINITY, 5)));
+    assert_eq!(dec2flt_prefix("-infinity"), Ok((f32::NEG_INFINITY, 4)));
+    let (nan, len) = dec2flt_prefix::<f64>("NaN)").unwrap();
+    assert!(nan.is_nan());
+    assert_eq!(len, 3);
+    for &s in &["", ".", "-", "+.e1", "e5", " 1.0", "nan"] {
+        assert!(dec2flt_prefix::<f64>(s).is_err(), "accepted {:?}", s);
+    }
+}
+
 #[test]
 fn massive_exponent() {
     let max = i64::MAX;
@@ -9,7 +9,7 @@
 // except according to those terms.
 
 use std::iter;
-use core::num::dec2flt::parse::{Decimal, parse_decimal};
+use core::num::dec2flt::parse::{Decimal, parse_decimal, decimal_prefix_len};
 use core::num::dec2flt::parse::ParseResult::{Valid, Invalid};
 
 #[test]
@@ -50,3 +50,32 @@ fn valid() {
     let s = format!("1.5e{}", zeros);
     assert_eq!(parse_decimal(&s), Valid(Decimal::new(b"1", b"5", 0)));
 }
+
+#[test]
+fn prefix_len() {
+    assert_eq!(decimal_prefix_len("123.456e789"), 11);
+    assert_eq!(decimal_prefix_len("123.456e-789, 1"), 12);
+    assert_eq!(decimal_prefix_len("1.e300x"), 6);
+    assert_eq!(decimal_prefix_len(".050."), 4);
+    assert_eq!(decimal_prefix_len("999 "), 3);
+    assert_eq!(decimal_prefix_len("12.32e+"), 5);
+    assert_eq!(decimal_prefix_len("1e"), 1);
+    assert_eq!(decimal_prefix_len("321.e"), 4);
+    for &s in &["", ".", ".e", "e4", "e", "-1", "inf", "NaN", "r,?<j"] {
+        assert_eq!(decimal_prefix_len(s), 0, "accepted {:?}", s);
+    }
+}
+
+#[test]
+fn prefix_len_agrees_with_parse() {
+    let valid_strings = &["123", "666.", ".1", "5e1", "7e-3", "0.0e+1", "1.5e0000000"];
+    for s in valid_strings {
+        assert_eq!(decimal_prefix_len(s), s.len());
+        for suffix in &["", "x", ".", "e", "e+", "-1", " 2"] {
+            let input = format!("{}{}", s, suffix);
+            let len = decimal_prefix_len(&input);
+            assert!(len >= s.len(), "{:?} is only {} long", input, len);
+            assert!(parse_decimal(&input[..len]) != Invalid, "rejected prefix of {:?}", input);
+        }
+    }
+}
@@ -22,6 +22,7 @@ mod estimator;
 mod strategy {
     mod dragon;
     mod grisu;
+    mod ryu;
 }
 
 pub fn decode_finite<T: DecodableFloat>(v: T) -> Decoded {
@@ -0,0 +1,80 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::{f32, f64};
+use super::super::*;
+use core::num::flt2dec::strategy::ryu::*;
+
+#[test]
+fn test_log_approximations() {
+    let mut pow5 = 1u128;
+    let mut bits = 1;
+    for e in 0..55 {
+        while pow5 >= 1 << bits { bits += 1; }
+        if e > 0 { assert_eq!(pow5bits(e), bits, "pow5bits({})", e); }
+        pow5 *= 5;
+    }
+
+    for e in 0..1650 {
+        // 10^k <= 2^e < 10^(k+1) for `k = log10_pow2(e)`, checked with f64 logarithms
+        // away from the boundaries where they might be inaccurate.
+        let x = (e as f64) * 2f64.log10();
+        if x - x.floor() > 1e-9 {
+            assert_eq!(log10_pow2(e), x.floor() as u32, "log10_pow2({})", e);
+        }
+    }
+    for e in 0..2620 {
+        let x = (e as f64) * 5f64.log10();
+        if x - x.floor() > 1e-9 {
+            assert_eq!(log10_pow5(e), x.floor() as u32, "log10_pow5({})", e);
+        }
+    }
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "emscripten"), ignore)] // issue 42630
+#[test]
+fn shortest_sanity_test() {
+    f64_shortest_sanity_test(format_shortest);
+    f32_shortest_sanity_test(format_shortest);
+    more_shortest_sanity_test(format_shortest);
+}
+
+#[test]
+fn shortest_opt_domain_test() {
+    let mut buf = [0; MAX_SIG_DIGITS];
+    // every finite float is handled without a fallback...
+    for &v in &[f64::MAX, f64::MIN_POSITIVE, 5e-324, 1.0, 0.1, 1e23, 2.0f64.powi(-1022)] {
+        assert!(format_shortest_opt(&decode_finite(v), &mut buf).is_some(), "{:e}", v);
+    }
+    for &v in &[f32::MAX, f32::MIN_POSITIVE, 1e-45, 1.0, 0.1, 3.0e38] {
+        assert!(format_shortest_opt(&decode_finite(v), &mut buf).is_some(), "{:e}", v);
+    }
+    // ...but wider mantissas are outside of the tables.
+    let d = Decoded { mant: 99_999_999_999_999_999, minus: 1, plus: 1,
+                      exp: 0, inclusive: true };
+    assert!(format_shortest_opt(&d, &mut buf).is_none());
+}
+
+#[test]
+fn shortest_ties_test() {
+    // exact ties are rounded up like Grisu and Dragon do.
+    check_shortest!(format_shortest(2.0f64.powi(-25)) => b"29802322387695313", -7);
+    check_shortest!(format_shortest(3413806.25f32) => b"34138063", 7);
+}
+
+#[test]
+fn test_to_shortest_str() {
+    to_shortest_str_test(format_shortest);
+}
+
+#[test]
+fn test_to_shortest_exp_str() {
+    to_shortest_exp_str_test(format_shortest);
+}
@@ -12,7 +12,7 @@ use core::convert::{TryFrom, TryInto};
 use core::cmp::PartialEq;
 use core::fmt::Debug;
 use core::marker::Copy;
-use core::num::TryFromIntError;
+use core::num::{TryFromIntError, IntErrorKind};
 use core::ops::{Add, Sub, Mul, Div, Rem};
 use core::option::Option;
 use core::option::Option::{Some, None};
@@ -135,6 +135,36 @@ fn test_empty() {
     assert_eq!("".parse::<u8>().ok(), None);
 }
 
+#[test]
+fn test_error_kind() {
+    assert_eq!("".parse::<u8>().unwrap_err().kind(), &IntErrorKind::Empty);
+    assert_eq!("-".parse::<i8>().unwrap_err().kind(), &IntErrorKind::Empty);
+    assert_eq!("12a".parse::<u8>().unwrap_err().kind(), &IntErrorKind::InvalidDigit);
+    assert_eq!("-1".parse::<u8>().unwrap_err().kind(), &IntErrorKind::InvalidDigit);
+    assert_eq!("256".parse::<u8>().unwrap_err().kind(), &IntErrorKind::Overflow);
+    assert_eq!("-129".parse::<i8>().unwrap_err().kind(), &IntErrorKind::Underflow);
+}
+
+#[test]
+fn test_from_str_radix_prefix() {
+    assert_eq!(u8::from_str_radix_prefix("255", 10), Ok((255, 3)));
+    assert_eq!(u8::from_str_radix_prefix("12a", 10), Ok((12, 2)));
+    assert_eq!(u16::from_str_radix_prefix("12ag", 16), Ok((0x12a, 3)));
+    assert_eq!(i32::from_str_radix_prefix("+7 apples", 10), Ok((7, 2)));
+    assert_eq!(i32::from_str_radix_prefix("-ff, 12", 16), Ok((-255, 3)));
+    assert_eq!(i64::from_str_radix_prefix("101abc", 2), Ok((5, 3)));
+    assert_eq!(u32::from_str_radix_prefix("42Съешь", 10), Ok((42, 2)));
+
+    assert_eq!(u8::from_str_radix_prefix("", 10).unwrap_err().kind(), &IntErrorKind::Empty);
+    assert_eq!(i8::from_str_radix_prefix("+", 10).unwrap_err().kind(), &IntErrorKind::Empty);
+    assert_eq!(i8::from_str_radix_prefix("-x", 10).unwrap_err().kind(),
+               &IntErrorKind::InvalidDigit);
+    assert_eq!(u8::from_str_radix_prefix("256,", 10).unwrap_err().kind(),
+               &IntErrorKind::Overflow);
+    assert_eq!(i8::from_str_radix_prefix("-129,", 10).unwrap_err().kind(),
+               &IntErrorKind::Underflow);
+}
+
 #[test]
 fn test_infallible_try_from_int_error() {
     let func = |x: i8| -> Result<i32, TryFromIntError> { Ok(x.try_into()?) };
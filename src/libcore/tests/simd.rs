@@ -0,0 +1,185 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use core::simd::*;
+use core::f32;
+
+#[test]
+fn test_lanes() {
+    let v = i32x4::new(1, 2, 3, 4);
+    assert_eq!(i32x4::LANES, 4);
+    assert_eq!(v.extract(0), 1);
+    assert_eq!(v.extract(3), 4);
+    assert_eq!(v.replace(2, 9), i32x4::new(1, 2, 9, 4));
+    assert_eq!(i32x4::splat(7), i32x4::new(7, 7, 7, 7));
+    assert_eq!(<[i32; 4]>::from(v), [1, 2, 3, 4]);
+    assert_eq!(i32x4::from([1, 2, 3, 4]), v);
+    assert_eq!(i32x4::default(), i32x4::splat(0));
+    assert_eq!(format!("{:?}", v), "i32x4(1, 2, 3, 4)");
+}
+
+#[test]
+#[should_panic]
+fn test_extract_out_of_bounds() {
+    u64x2::splat(1).extract(2);
+}
+
+#[test]
+fn test_load_store() {
+    let data: Vec<u8> = (0..20).collect();
+    let v = u8x16::from_slice(&data[3..]);
+    assert_eq!(v.extract(0), 3);
+    assert_eq!(v.extract(15), 18);
+
+    let mut out = [0u16; 9];
+    u16x8::new(1, 2, 3, 4, 5, 6, 7, 8).write_to_slice(&mut out[1..]);
+    assert_eq!(out, [0, 1, 2, 3, 4, 5, 6, 7, 8]);
+
+    unsafe {
+        let w = f64x2::load(&[1.5, 2.5][0]);
+        assert_eq!(w, f64x2::new(1.5, 2.5));
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_from_short_slice() {
+    f32x4::from_slice(&[1.0, 2.0, 3.0]);
+}
+
+#[test]
+fn test_int_arithmetic() {
+    let a = i16x8::new(1, 2, 3, 4, 5, 6, 7, i16::max_value());
+    let b = i16x8::splat(2);
+    assert_eq!(a + b, i16x8::new(3, 4, 5, 6, 7, 8, 9, i16::min_value() + 1));
+    assert_eq!(a - b, i16x8::new(-1, 0, 1, 2, 3, 4, 5, i16::max_value() - 2));
+    assert_eq!(a * b, i16x8::new(2, 4, 6, 8, 10, 12, 14, -2));
+    assert_eq!(a / b, i16x8::new(0, 1, 1, 2, 2, 3, 3, i16::max_value() / 2));
+    assert_eq!(a % b, i16x8::new(1, 0, 1, 0, 1, 0, 1, 1));
+    assert_eq!(-b, i16x8::splat(-2));
+
+    let mut c = u32x4::new(1, 2, 3, 4);
+    c += u32x4::splat(1);
+    c *= u32x4::splat(3);
+    assert_eq!(c, u32x4::new(6, 9, 12, 15));
+}
+
+#[test]
+#[should_panic]
+fn test_int_div_by_zero() {
+    let _ = u32x4::splat(1) / u32x4::new(1, 1, 0, 1);
+}
+
+#[test]
+fn test_bitops_and_shifts() {
+    let a = u8x16::splat(0b1100);
+    let b = u8x16::splat(0b1010);
+    assert_eq!(a & b, u8x16::splat(0b1000));
+    assert_eq!(a | b, u8x16::splat(0b1110));
+    assert_eq!(a ^ b, u8x16::splat(0b0110));
+    assert_eq!(!a, u8x16::splat(0b1111_0011));
+    assert_eq!(a << 2, u8x16::splat(0b11_0000));
+    assert_eq!(a >> 2, u8x16::splat(0b11));
+    // Shift amounts are masked to the lane width.
+    assert_eq!(a << 10, u8x16::splat(0b11_0000));
+    assert_eq!(i32x4::splat(-8) >> 1, i32x4::splat(-4));
+}
+
+#[test]
+fn test_float_arithmetic() {
+    let a = f32x4::new(1.0, 2.0, 3.0, 4.0);
+    let b = f32x4::splat(2.0);
+    assert_eq!(a + b, f32x4::new(3.0, 4.0, 5.0, 6.0));
+    assert_eq!(a - b, f32x4::new(-1.0, 0.0, 1.0, 2.0));
+    assert_eq!(a * b, f32x4::new(2.0, 4.0, 6.0, 8.0));
+    assert_eq!(a / b, f32x4::new(0.5, 1.0, 1.5, 2.0));
+    assert_eq!(a % b, f32x4::new(1.0, 0.0, 1.0, 0.0));
+    assert_eq!(-a, f32x4::new(-1.0, -2.0, -3.0, -4.0));
+    assert!((-f64x2::splat(0.0)).extract(0).is_sign_negative());
+}
+
+#[test]
+fn test_comparisons_and_masks() {
+    let a = i32x4::new(1, 5, 3, 7);
+    let b = i32x4::new(4, 4, 4, 4);
+    let lt = a.lanes_lt(b);
+    assert_eq!(lt, m32x4::new(true, false, true, false));
+    assert_eq!(a.lanes_ge(b), !lt);
+    assert_eq!(a.lanes_eq(a), m32x4::splat(true));
+    assert_eq!(a.lanes_ne(a), m32x4::splat(false));
+    assert!(lt.any() && !lt.all() && !lt.none());
+    assert!((lt | !lt).all());
+    assert!((lt & !lt).none());
+    assert!(lt.extract(0) && !lt.extract(1));
+    assert_eq!(lt.replace(1, true), m32x4::new(true, true, true, false));
+    assert_eq!(<[bool; 4]>::from(lt), [true, false, true, false]);
+    assert_eq!(format!("{:?}", lt), "m32x4(true, false, true, false)");
+
+    assert_eq!(lt.select(a, b), i32x4::new(1, 4, 3, 4));
+    assert_eq!(lt.select(f32x4::splat(1.0), f32x4::splat(2.0)),
+               f32x4::new(1.0, 2.0, 1.0, 2.0));
+    assert_eq!(a.min(b), i32x4::new(1, 4, 3, 4));
+    assert_eq!(a.max(b), i32x4::new(4, 5, 4, 7));
+}
+
+#[test]
+fn test_float_nan() {
+    let a = f32x4::new(1.0, f32::NAN, 3.0, f32::NAN);
+    let b = f32x4::new(2.0, 2.0, f32::NAN, f32::NAN);
+    assert_eq!(a.is_nan(), m32x4::new(false, true, false, true));
+    assert_eq!(a.lanes_eq(a), m32x4::new(true, false, true, false));
+
+    let min = a.min(b);
+    assert_eq!((min.extract(0), min.extract(1), min.extract(2)), (1.0, 2.0, 3.0));
+    assert!(min.extract(3).is_nan());
+    let max = a.max(b);
+    assert_eq!((max.extract(0), max.extract(1), max.extract(2)), (2.0, 2.0, 3.0));
+
+    assert_eq!(a.min_element(), 1.0);
+    assert_eq!(a.max_element(), 3.0);
+    assert!(f32x4::splat(f32::NAN).max_element().is_nan());
+}
+
+#[test]
+fn test_reductions() {
+    let a = u8x16::new(1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 255);
+    assert_eq!(a.wrapping_sum(), 119);
+    assert_eq!(a.min_element(), 1);
+    assert_eq!(a.max_element(), 255);
+    assert_eq!(a.or(), 255);
+    assert_eq!(a.and(), 0);
+    assert_eq!(a.xor(), (1..16u8).fold(255, |acc, x| acc ^ x));
+
+    let b = i64x2::new(-3, 7);
+    assert_eq!(b.wrapping_sum(), 4);
+    assert_eq!(b.wrapping_product(), -21);
+    assert_eq!(b.min_element(), -3);
+
+    let c = f64x2::new(1.5, -4.0);
+    assert_eq!(c.sum(), -2.5);
+    assert_eq!(c.product(), -6.0);
+}
+
+#[test]
+fn test_shuffles() {
+    let a = u32x4::new(0, 1, 2, 3);
+    let b = u32x4::new(4, 5, 6, 7);
+    assert_eq!(a.reverse(), u32x4::new(3, 2, 1, 0));
+    assert_eq!(a.interleave_low(b), u32x4::new(0, 4, 1, 5));
+    assert_eq!(a.interleave_high(b), u32x4::new(2, 6, 3, 7));
+
+    let c = i8x16::new(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15);
+    let d = -c;
+    assert_eq!(c.reverse().extract(0), 15);
+    assert_eq!(<[i8; 16]>::from(c.interleave_high(d)),
+               [8, -8, 9, -9, 10, -10, 11, -11, 12, -12, 13, -13, 14, -14, 15, -15]);
+    assert_eq!(f64x2::new(1.0, 2.0).interleave_low(f64x2::new(3.0, 4.0)),
+               f64x2::new(1.0, 3.0));
+}
@@ -481,3 +481,120 @@ fn test_rotate_right() {
         assert_eq!(a[(i + 42) % N], i);
     }
 }
+
+// A small xorshift generator, so that the tests below are reproducible.
+fn xorshift(state: &mut u64) -> u64 {
+    *state ^= *state << 13;
+    *state ^= *state >> 7;
+    *state ^= *state << 17;
+    *state
+}
+
+#[test]
+fn test_select_nth_unstable() {
+    let mut state = 0x9e3779b97f4a7c15;
+    for len in (1..40).chain(vec![100, 1000, 5000]) {
+        for &modulus in &[2, 10, 1000, u64::max_value()] {
+            let orig: Vec<u64> = (0..len).map(|_| xorshift(&mut state) % modulus).collect();
+            let mut sorted = orig.clone();
+            sorted.sort();
+
+            for &index in &[0, len / 3, len / 2, len - 1] {
+                let mut v = orig.clone();
+                {
+                    let (left, nth, right) = v.select_nth_unstable(index);
+                    let nth = *nth;
+                    assert_eq!(left.len(), index);
+                    assert_eq!(nth, sorted[index]);
+                    assert!(left.iter().all(|&x| x <= nth));
+                    assert!(right.iter().all(|&x| x >= nth));
+                }
+                v.sort();
+                assert_eq!(v, sorted);
+            }
+
+            let mut v = orig.clone();
+            let index = len / 2;
+            let (_, nth, _) = v.select_nth_unstable_by(index, |a, b| b.cmp(a));
+            assert_eq!(*nth, sorted[len - 1 - index]);
+        }
+    }
+
+    // sorted and reverse sorted input
+    let mut v: Vec<_> = (0..10000).collect();
+    assert_eq!(*v.select_nth_unstable(1234).1, 1234);
+    v.reverse();
+    assert_eq!(*v.select_nth_unstable_by_key(5678, |&x| x).1, 5678);
+
+    // zero-sized types
+    let mut v = [(); 10];
+    v.select_nth_unstable(5);
+}
+
+#[test]
+#[should_panic]
+fn test_select_nth_unstable_out_of_bounds() {
+    let mut v = [1, 2, 3];
+    v.select_nth_unstable(3);
+}
+
+#[test]
+fn test_partial_sort_unstable() {
+    let mut state = 0x2545f4914f6cdd1d;
+    for len in (0..40).chain(vec![100, 1000, 5000]) {
+        for &modulus in &[2, 10, 1000, u64::max_value()] {
+            let orig: Vec<u64> = (0..len).map(|_| xorshift(&mut state) % modulus).collect();
+            let mut sorted = orig.clone();
+            sorted.sort();
+
+            for &k in &[0, 1, len / 3, len / 2, len.saturating_sub(1), len] {
+                let mut v = orig.clone();
+                v.partial_sort_unstable(k);
+                assert_eq!(v[..k], sorted[..k]);
+                v.sort();
+                assert_eq!(v, sorted);
+            }
+
+            let k = len / 2;
+            let mut v = orig.clone();
+            v.partial_sort_unstable_by(k, |a, b| b.cmp(a));
+            assert!(v[..k].iter().eq(sorted.iter().rev().take(k)));
+        }
+    }
+
+    let mut v = [-5i32, 4, 1, -3, 2];
+    v.partial_sort_unstable_by_key(3, |x| x.abs());
+    assert_eq!(v[..3], [1, 2, -3]);
+}
+
+#[test]
+#[should_panic]
+fn test_partial_sort_unstable_too_long() {
+    let mut v = [1, 2, 3];
+    v.partial_sort_unstable(4);
+}
+
+#[test]
+fn test_partition_dedup() {
+    let mut v: [i32; 0] = [];
+    let (dedup, duplicates) = v.partition_dedup();
+    assert!(dedup.is_empty() && duplicates.is_empty());
+
+    let mut v = [1];
+    assert_eq!(v.partition_dedup(), (&mut [1][..], &mut [][..]));
+
+    let mut v = [1, 1, 2, 3, 3, 3, 1, 4, 4];
+    {
+        let (dedup, duplicates) = v.partition_dedup();
+        assert_eq!(dedup, [1, 2, 3, 1, 4]);
+        duplicates.sort();
+        assert_eq!(duplicates, [1, 3, 3, 4]);
+    }
+
+    let mut v = [10, 20, 21, 30, 30, 20, 11, 13];
+    assert_eq!(v.partition_dedup_by_key(|i| *i / 10).0, [10, 20, 30, 20, 11]);
+
+    let mut v = ["a", "A", "b", "B", "b", "c"];
+    let (dedup, _) = v.partition_dedup_by(|a, b| a.eq_ignore_ascii_case(b));
+    assert_eq!(dedup, ["a", "b", "c"]);
+}
@@ -0,0 +1,161 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Architecture-specific intrinsics.
+//!
+//! This module re-exports `core::arch`. Whether the CPU a program runs on
+//! supports the features an intrinsic needs can be checked at run time with
+//! the [`is_x86_feature_detected!`] macro.
+//!
+//! [`is_x86_feature_detected!`]: ../macro.is_x86_feature_detected.html
+
+#![unstable(feature = "stdsimd", issue = "0")]
+
+pub use core::arch::*;
+
+/// Run-time detection of x86-64 CPU features, backing
+/// `is_x86_feature_detected!`.
+#[cfg(target_arch = "x86_64")]
+#[doc(hidden)]
+pub mod detect {
+    use arch::x86_64::{__cpuid, __cpuid_count, __get_cpuid_max, _xgetbv};
+    use mem;
+    use sync::atomic::{AtomicUsize, Ordering};
+
+    /// The features `is_x86_feature_detected!` knows about. Each one is
+    /// recorded as the bit with its discriminant's index in the cache.
+    #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+    #[allow(non_camel_case_types)]
+    #[repr(u8)]
+    pub enum Feature {
+        sse,
+        sse2,
+        sse3,
+        ssse3,
+        sse4_1,
+        sse4_2,
+        popcnt,
+        aes,
+        rdrnd,
+        xsave,
+        avx,
+        fma,
+        avx2,
+        avx512f,
+        bmi,
+        bmi2,
+        lzcnt,
+    }
+
+    /// Set in the cache once detection has run, so that a CPU with none of
+    /// the features is not detected over and over.
+    const INITIALIZED: usize = 1 << (mem::size_of::<usize>() * 8 - 1);
+
+    static CACHE: AtomicUsize = AtomicUsize::new(0);
+
+    /// Returns whether `feature` is available, detecting all the features
+    /// on the first call.
+    #[inline]
+    pub fn check(feature: Feature) -> bool {
+        let mut cache = CACHE.load(Ordering::Relaxed);
+        if cache == 0 {
+            // Racing threads compute the same value, so it does not matter
+            // which store wins.
+            cache = detect_features();
+            CACHE.store(cache, Ordering::Relaxed);
+        }
+        cache & (1 << feature as usize) != 0
+    }
+
+    fn detect_features() -> usize {
+        let mut features = 0;
+        unsafe {
+            let (max_leaf, _) = __get_cpuid_max(0);
+            if max_leaf < 1 {
+                return INITIALIZED;
+            }
+            let leaf1 = __cpuid(1);
+            let leaf7 = if max_leaf >= 7 { Some(__cpuid_count(7, 0)) } else { None };
+            let (max_extended_leaf, _) = __get_cpuid_max(0x8000_0000);
+            let extended_leaf1 = if max_extended_leaf >= 0x8000_0001 {
+                Some(__cpuid(0x8000_0001))
+            } else {
+                None
+            };
+
+            // The AVX register states are only usable if the OS saves them
+            // on context switches, which it reports in XCR0.
+            let osxsave = leaf1.ecx & (1 << 27) != 0;
+            let xcr0 = if osxsave { _xgetbv(0) } else { 0 };
+            let os_avx = xcr0 & 0b110 == 0b110;
+            let os_avx512 = xcr0 & 0b1110_0110 == 0b1110_0110;
+
+            let mut enable = |feature: Feature, enabled: bool| {
+                if enabled {
+                    features |= 1 << feature as usize;
+                }
+            };
+            let bit = |register: u32, bit: u32| register & (1 << bit) != 0;
+
+            enable(Feature::sse, bit(leaf1.edx, 25));
+            enable(Feature::sse2, bit(leaf1.edx, 26));
+            enable(Feature::sse3, bit(leaf1.ecx, 0));
+            enable(Feature::ssse3, bit(leaf1.ecx, 9));
+            enable(Feature::sse4_1, bit(leaf1.ecx, 19));
+            enable(Feature::sse4_2, bit(leaf1.ecx, 20));
+            enable(Feature::popcnt, bit(leaf1.ecx, 23));
+            enable(Feature::aes, bit(leaf1.ecx, 25));
+            enable(Feature::rdrnd, bit(leaf1.ecx, 30));
+            enable(Feature::xsave, bit(leaf1.ecx, 26) && osxsave);
+            enable(Feature::avx, bit(leaf1.ecx, 28) && os_avx);
+            enable(Feature::fma, bit(leaf1.ecx, 12) && os_avx);
+            if let Some(leaf7) = leaf7 {
+                enable(Feature::avx2, bit(leaf7.ebx, 5) && os_avx);
+                enable(Feature::avx512f, bit(leaf7.ebx, 16) && os_avx512);
+                enable(Feature::bmi, bit(leaf7.ebx, 3));
+                enable(Feature::bmi2, bit(leaf7.ebx, 8));
+            }
+            if let Some(extended_leaf1) = extended_leaf1 {
+                enable(Feature::lzcnt, bit(extended_leaf1.ecx, 5));
+            }
+        }
+        features | INITIALIZED
+    }
+}
+
+#[cfg(all(test, target_arch = "x86_64"))]
+mod tests {
+    use super::detect::{check, Feature};
+
+    #[test]
+    fn baseline_features() {
+        // SSE and SSE2 are part of x86-64 itself.
+        assert!(is_x86_feature_detected!("sse"));
+        assert!(is_x86_feature_detected!("sse2"));
+    }
+
+    #[test]
+    fn implied_features() {
+        // A feature is never reported without the ones it extends.
+        if is_x86_feature_detected!("avx2") {
+            assert!(is_x86_feature_detected!("avx"));
+        }
+        if is_x86_feature_detected!("sse4.2") {
+            assert!(is_x86_feature_detected!("sse4.1"));
+            assert!(is_x86_feature_detected!("ssse3"));
+        }
+    }
+
+    #[test]
+    fn cached() {
+        assert_eq!(check(Feature::avx2), check(Feature::avx2));
+        assert_eq!(is_x86_feature_detected!("popcnt"), check(Feature::popcnt));
+    }
+}
@@ -0,0 +1,387 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Capturing stack backtraces of the current thread.
+//!
+//! A [`Backtrace`] records the frames of the stack at the point where
+//! [`Backtrace::capture`] is called. Capturing only walks the stack and
+//! stores the instruction pointers of each frame; the comparatively
+//! expensive work of looking up symbol names and source locations is
+//! deferred until the frames are first inspected or the backtrace is
+//! formatted. This makes it reasonable to capture a backtrace whenever an
+//! error value is created and only pay for resolution if it is reported.
+//!
+//! Symbol names are demangled with the same demangler used for the
+//! backtraces printed on panic.
+//!
+//! If the platform does not support capturing backtraces, or the standard
+//! library was built without backtrace support, captured backtraces
+//! contain no frames.
+//!
+//! [`Backtrace`]: struct.Backtrace.html
+//! [`Backtrace::capture`]: struct.Backtrace.html#method.capture
+//!
+//! # Examples
+//!
+//! ```
+//! #![feature(backtrace_capture)]
+//!
+//! use std::backtrace::Backtrace;
+//!
+//! let backtrace = Backtrace::capture();
+//! for frame in backtrace.frames() {
+//!     println!("{:?} {:?}", frame.ip(), frame.symbol_name());
+//! }
+//! println!("{}", backtrace);
+//! ```
+
+#![unstable(feature = "backtrace_capture", issue = "0")]
+
+use cell::UnsafeCell;
+use fmt;
+use path::{Path, PathBuf};
+use ptr;
+use sync::Once;
+
+#[cfg(feature = "backtrace")]
+use sys_common::backtrace::{self as imp, PrintFormat};
+
+/// A captured stack backtrace of the current thread.
+///
+/// See the [module-level documentation] for more details.
+///
+/// Formatting a `Backtrace` with `{}` prints its frames in the same short
+/// format used by panics with `RUST_BACKTRACE=1`, stopping at the frame of
+/// the thread's entry point. The alternate form `{:#}` prints every frame
+/// together with its address and the full symbol name, like
+/// `RUST_BACKTRACE=full`.
+///
+/// [module-level documentation]: index.html
+pub struct Backtrace {
+    frames: UnsafeCell<Vec<BacktraceFrame>>,
+    resolved: Once,
+}
+
+// The frames are only mutated once, inside `resolved.call_once`, and only
+// shared references to them are handed out afterwards.
+unsafe impl Send for Backtrace {}
+unsafe impl Sync for Backtrace {}
+
+/// A single frame of a [`Backtrace`].
+///
+/// [`Backtrace`]: struct.Backtrace.html
+pub struct BacktraceFrame {
+    ip: *const u8,
+    symbol_address: *const u8,
+    #[cfg_attr(not(feature = "backtrace"), allow(dead_code))]
+    inline_context: u32,
+    name: Option<String>,
+    mangled_name: Option<String>,
+    locations: Vec<(PathBuf, u32)>,
+}
+
+unsafe impl Send for BacktraceFrame {}
+unsafe impl Sync for BacktraceFrame {}
+
+impl Backtrace {
+    /// Captures the stack of the current thread.
+    ///
+    /// Only the addresses of the frames are recorded here. Symbols are
+    /// resolved the first time [`frames`] is called or the backtrace is
+    /// formatted.
+    ///
+    /// [`frames`]: #method.frames
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(backtrace_capture)]
+    ///
+    /// use std::backtrace::Backtrace;
+    ///
+    /// let backtrace = Backtrace::capture();
+    /// println!("{:?}", backtrace);
+    /// ```
+    #[inline(never)]
+    pub fn capture() -> Backtrace {
+        Backtrace {
+            frames: UnsafeCell::new(capture_frames()),
+            resolved: Once::new(),
+        }
+    }
+
+    /// Returns the frames of this backtrace, innermost first.
+    ///
+    /// The first call resolves the symbol names and source locations of
+    /// every frame, which may be slow.
+    pub fn frames(&self) -> &[BacktraceFrame] {
+        self.resolve();
+        unsafe { &*self.frames.get() }
+    }
+
+    /// Resolves the symbols of all frames, if that has not happened yet.
+    ///
+    /// This is done automatically when the frames are inspected, but can be
+    /// called explicitly to move the cost of resolution to a convenient
+    /// point, for example before the backtrace is sent to another thread.
+    pub fn resolve(&self) {
+        let frames = &self.frames;
+        self.resolved.call_once(|| unsafe {
+            resolve_frames(&mut *frames.get());
+        });
+    }
+}
+
+impl BacktraceFrame {
+    /// Returns the address of the instruction being executed in this frame.
+    ///
+    /// For every frame but the innermost one this points at the call
+    /// instruction which created the next frame.
+    pub fn ip(&self) -> *const u8 {
+        self.ip
+    }
+
+    /// Returns the starting address of the function enclosing [`ip`], if it
+    /// is known, and [`ip`] otherwise.
+    ///
+    /// [`ip`]: #method.ip
+    pub fn symbol_address(&self) -> *const u8 {
+        self.symbol_address
+    }
+
+    /// Returns the demangled name of the function of this frame, if it
+    /// could be resolved.
+    pub fn symbol_name(&self) -> Option<&str> {
+        self.name.as_ref().map(|s| &s[..])
+    }
+
+    /// Returns the name of the function of this frame as it appears in the
+    /// symbol table, before demangling.
+    pub fn mangled_symbol_name(&self) -> Option<&str> {
+        self.mangled_name.as_ref().map(|s| &s[..])
+    }
+
+    /// Returns the source file of this frame, if debug information is
+    /// available.
+    ///
+    /// When functions have been inlined into this frame several locations
+    /// may be known; this returns the innermost one. See [`locations`] for
+    /// all of them.
+    ///
+    /// [`locations`]: #method.locations
+    pub fn filename(&self) -> Option<&Path> {
+        self.locations.first().map(|&(ref file, _)| &**file)
+    }
+
+    /// Returns the line number in the source file of this frame, if debug
+    /// information is available.
+    pub fn lineno(&self) -> Option<u32> {
+        self.locations.first().map(|&(_, line)| line)
+    }
+
+    /// Returns all source locations known for this frame as pairs of file
+    /// and line number, innermost inlined call first.
+    pub fn locations(&self) -> &[(PathBuf, u32)] {
+        &self.locations
+    }
+
+    #[cfg(feature = "backtrace")]
+    fn as_imp(&self) -> imp::Frame {
+        imp::Frame {
+            exact_position: self.ip,
+            symbol_addr: self.symbol_address,
+            inline_context: self.inline_context,
+        }
+    }
+}
+
+#[cfg(feature = "backtrace")]
+fn capture_frames() -> Vec<BacktraceFrame> {
+    let frames = match imp::capture() {
+        Ok(frames) => frames,
+        Err(_) => return Vec::new(),
+    };
+    frames.into_iter().map(|frame| {
+        BacktraceFrame {
+            ip: frame.exact_position,
+            symbol_address: frame.symbol_addr,
+            inline_context: frame.inline_context,
+            name: None,
+            mangled_name: None,
+            locations: Vec::new(),
+        }
+    }).collect()
+}
+
+#[cfg(not(feature = "backtrace"))]
+fn capture_frames() -> Vec<BacktraceFrame> {
+    Vec::new()
+}
+
+#[cfg(feature = "backtrace")]
+fn resolve_frames(frames: &mut [BacktraceFrame]) {
+    if frames.is_empty() {
+        return
+    }
+    imp::with_lock(|| {
+        let context = match imp::resolve_context() {
+            Ok(context) => context,
+            Err(_) => return,
+        };
+        for frame in frames.iter_mut() {
+            let raw = frame.as_imp();
+            let _ = imp::resolve_symname(raw, |symname| {
+                if let Some(mangled) = symname {
+                    frame.name = Some(demangle(mangled, PrintFormat::Full));
+                    frame.mangled_name = Some(mangled.to_string());
+                }
+                Ok(())
+            }, &context);
+            let locations = &mut frame.locations;
+            let _ = imp::foreach_symbol_fileline(raw, |file, line| {
+                locations.push((bytes2path(file), line));
+                Ok(())
+            }, &context);
+        }
+    })
+}
+
+#[cfg(not(feature = "backtrace"))]
+fn resolve_frames(_frames: &mut [BacktraceFrame]) {}
+
+#[cfg(feature = "backtrace")]
+fn demangle(mangled: &str, format: PrintFormat) -> String {
+    let mut buf = Vec::new();
+    match imp::demangle(&mut buf, mangled, format) {
+        Ok(()) => String::from_utf8(buf).unwrap_or_else(|_| mangled.to_string()),
+        Err(_) => mangled.to_string(),
+    }
+}
+
+#[cfg(all(feature = "backtrace", unix))]
+fn bytes2path(file: &[u8]) -> PathBuf {
+    use ffi::OsStr;
+    use os::unix::ffi::OsStrExt;
+
+    PathBuf::from(OsStr::from_bytes(file))
+}
+
+#[cfg(all(feature = "backtrace", not(unix)))]
+fn bytes2path(file: &[u8]) -> PathBuf {
+    PathBuf::from(String::from_utf8_lossy(file).into_owned())
+}
+
+impl fmt::Debug for Backtrace {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.frames()).finish()
+    }
+}
+
+impl fmt::Debug for BacktraceFrame {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BacktraceFrame")
+            .field("ip", &self.ip)
+            .field("symbol_name", &self.name)
+            .field("filename", &self.filename())
+            .field("lineno", &self.lineno())
+            .finish()
+    }
+}
+
+impl fmt::Display for Backtrace {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let full = f.alternate();
+        let mut frames = self.frames();
+
+        if !full {
+            // Like `RUST_BACKTRACE=1`, hide the frames of the runtime which
+            // lead up to the thread's entry point.
+            let marker = frames.iter().position(|frame| {
+                frame.mangled_symbol_name().map_or(false, |name| {
+                    name.contains("__rust_begin_short_backtrace")
+                })
+            });
+            if let Some(marker) = marker {
+                if marker > 0 {
+                    frames = &frames[..marker];
+                }
+            }
+        }
+
+        writeln!(f, "stack backtrace:")?;
+        for (index, frame) in frames.iter().enumerate() {
+            if full {
+                write!(f, "  {:2}: {:p} - ", index, frame.ip)?;
+            } else {
+                if frame.ip == ptr::null() {
+                    continue
+                }
+                write!(f, "  {:2}: ", index)?;
+            }
+            match frame.mangled_name {
+                Some(ref mangled) => f.write_str(&short_name(mangled, full))?,
+                None => f.write_str("<unknown>")?,
+            }
+            f.write_str("\n")?;
+            for &(ref file, line) in &frame.locations {
+                writeln!(f, "             at {}:{}", file.display(), line)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "backtrace")]
+fn short_name(mangled: &str, full: bool) -> String {
+    demangle(mangled, if full { PrintFormat::Full } else { PrintFormat::Short })
+}
+
+#[cfg(not(feature = "backtrace"))]
+fn short_name(mangled: &str, _full: bool) -> String {
+    mangled.to_string()
+}
+
+#[cfg(all(test, feature = "backtrace", not(target_os = "emscripten")))]
+mod tests {
+    use super::*;
+
+    #[inline(never)]
+    fn this_function_is_in_the_backtrace() -> Backtrace {
+        Backtrace::capture()
+    }
+
+    #[test]
+    fn capture_and_resolve() {
+        let backtrace = this_function_is_in_the_backtrace();
+        let frames = backtrace.frames();
+        assert!(!frames.is_empty());
+        assert!(frames.iter().any(|frame| {
+            frame.symbol_name().map_or(false, |name| {
+                name.contains("this_function_is_in_the_backtrace")
+            })
+        }));
+    }
+
+    #[test]
+    fn display() {
+        let backtrace = this_function_is_in_the_backtrace();
+        let short = format!("{}", backtrace);
+        let full = format!("{:#}", backtrace);
+        assert!(short.starts_with("stack backtrace:\n"));
+        assert!(full.lines().count() >= short.lines().count());
+        assert!(full.contains("this_function_is_in_the_backtrace"));
+    }
+
+    #[test]
+    fn send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Backtrace>();
+    }
+}
@@ -126,3 +126,37 @@ fn get_remove_insert(b: &mut Bencher) {
         k += 1;
     })
 }
+
+#[bench]
+fn iterate(b: &mut Bencher) {
+    use super::map::HashMap;
+
+    let mut m = HashMap::new();
+
+    for i in 1..1001 {
+        m.insert(i, i);
+    }
+
+    b.iter(|| {
+        let sum: i32 = m.values().sum();
+        assert_eq!(sum, 500500);
+    })
+}
+
+#[bench]
+fn find_existing_string(b: &mut Bencher) {
+    use super::map::HashMap;
+
+    let keys: Vec<String> = (1..1001).map(|i| format!("key {}", i)).collect();
+    let mut m = HashMap::new();
+
+    for key in &keys {
+        m.insert(key.clone(), ());
+    }
+
+    b.iter(|| {
+        for key in &keys {
+            assert!(m.contains_key(key));
+        }
+    });
+}
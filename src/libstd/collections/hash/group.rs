@@ -0,0 +1,304 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Groups of control bytes which are probed together.
+//!
+//! Every bucket of a `RawTable` has a control byte which is either `EMPTY`,
+//! `DELETED`, or the top 7 bits of the hash of the element stored in it. A
+//! `Group` is a run of `Group::WIDTH` consecutive control bytes loaded into a
+//! register so that all of them can be compared against a value at once. The
+//! result of such a comparison is a `BitMask` with one entry per byte.
+//!
+//! With SSE2 a group is 16 bytes wide and compared with `pcmpeqb` and
+//! `pmovmskb`. Everywhere else a group is a machine word and the comparisons
+//! are done with the usual bit tricks for finding bytes in a word.
+
+pub use self::imp::{BitMaskWord, BITMASK_MASK, BITMASK_STRIDE, Group};
+
+/// Control byte of a bucket which has never held an element.
+pub const EMPTY: u8 = 0b1111_1111;
+
+/// Control byte of a bucket whose element has been removed.
+pub const DELETED: u8 = 0b1000_0000;
+
+/// Returns whether a control byte belongs to a full bucket.
+#[inline]
+pub fn is_full(ctrl: u8) -> bool {
+    ctrl & 0x80 == 0
+}
+
+/// Returns whether a control byte is `EMPTY`, as opposed to `DELETED`. Must
+/// only be called on the control byte of a bucket which is not full.
+#[inline]
+pub fn special_is_empty(ctrl: u8) -> bool {
+    debug_assert!(!is_full(ctrl));
+    ctrl & 0x01 != 0
+}
+
+/// A set of positions in a `Group`, as produced by its `match_*` methods.
+///
+/// Iterating over a `BitMask` yields the indices of the matching bytes, in
+/// increasing order.
+#[derive(Copy, Clone)]
+pub struct BitMask(pub BitMaskWord);
+
+impl BitMask {
+    /// Returns a mask with all the matches of `self` inverted.
+    #[inline]
+    pub fn invert(self) -> BitMask {
+        BitMask(self.0 ^ BITMASK_MASK)
+    }
+
+    /// Returns a mask with the lowest match of `self` removed.
+    #[inline]
+    pub fn remove_lowest_bit(self) -> BitMask {
+        BitMask(self.0 & (self.0 - 1))
+    }
+
+    /// Returns whether the mask contains any match.
+    #[inline]
+    pub fn any_bit_set(self) -> bool {
+        self.0 != 0
+    }
+
+    /// Returns the index of the lowest match, if any.
+    #[inline]
+    pub fn lowest_set_bit(self) -> Option<usize> {
+        if self.0 == 0 {
+            None
+        } else {
+            Some(self.trailing_zeros())
+        }
+    }
+
+    /// Returns the number of bytes before the first match.
+    #[inline]
+    pub fn trailing_zeros(self) -> usize {
+        self.0.trailing_zeros() as usize / BITMASK_STRIDE
+    }
+
+    /// Returns the number of bytes after the last match.
+    #[inline]
+    pub fn leading_zeros(self) -> usize {
+        self.0.leading_zeros() as usize / BITMASK_STRIDE
+    }
+}
+
+impl Iterator for BitMask {
+    type Item = usize;
+
+    #[inline]
+    fn next(&mut self) -> Option<usize> {
+        let bit = self.lowest_set_bit()?;
+        *self = self.remove_lowest_bit();
+        Some(bit)
+    }
+}
+
+#[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse2"))]
+mod imp {
+    use ptr;
+    use super::{BitMask, EMPTY};
+
+    const GROUP_WIDTH: usize = 16;
+
+    pub type BitMaskWord = u16;
+    pub const BITMASK_STRIDE: usize = 1;
+    pub const BITMASK_MASK: BitMaskWord = 0xffff;
+
+    #[repr(simd)]
+    #[derive(Copy, Clone)]
+    #[allow(non_camel_case_types)]
+    struct i8x16(i8, i8, i8, i8, i8, i8, i8, i8,
+                 i8, i8, i8, i8, i8, i8, i8, i8);
+
+    extern "platform-intrinsic" {
+        fn simd_eq<T, U>(x: T, y: T) -> U;
+        fn x86_mm_movemask_epi8(x: i8x16) -> i32;
+    }
+
+    /// Sixteen control bytes held in an SSE2 register.
+    #[derive(Copy, Clone)]
+    pub struct Group(i8x16);
+
+    impl Group {
+        /// Number of control bytes in a group.
+        pub const WIDTH: usize = GROUP_WIDTH;
+
+        /// Returns a full group of `EMPTY` control bytes, used as the control
+        /// bytes of tables which have not allocated yet.
+        pub fn static_empty() -> &'static [u8; GROUP_WIDTH] {
+            static EMPTY_GROUP: [u8; GROUP_WIDTH] = [EMPTY; GROUP_WIDTH];
+            &EMPTY_GROUP
+        }
+
+        /// Loads a group of control bytes starting at `ptr`, which need not
+        /// be aligned.
+        #[inline]
+        pub unsafe fn load(ptr: *const u8) -> Group {
+            Group(ptr::read_unaligned(ptr as *const i8x16))
+        }
+
+        /// Returns the positions of the bytes equal to `byte`.
+        #[inline]
+        pub fn match_byte(self, byte: u8) -> BitMask {
+            let b = byte as i8;
+            let splat = i8x16(b, b, b, b, b, b, b, b, b, b, b, b, b, b, b, b);
+            unsafe {
+                let cmp: i8x16 = simd_eq(self.0, splat);
+                BitMask(x86_mm_movemask_epi8(cmp) as u16)
+            }
+        }
+
+        /// Returns the positions of the `EMPTY` bytes.
+        #[inline]
+        pub fn match_empty(self) -> BitMask {
+            self.match_byte(EMPTY)
+        }
+
+        /// Returns the positions of the `EMPTY` and `DELETED` bytes, which
+        /// are exactly the bytes with their high bit set.
+        #[inline]
+        pub fn match_empty_or_deleted(self) -> BitMask {
+            unsafe { BitMask(x86_mm_movemask_epi8(self.0) as u16) }
+        }
+
+        /// Returns the positions of the bytes of full buckets.
+        #[inline]
+        pub fn match_full(self) -> BitMask {
+            self.match_empty_or_deleted().invert()
+        }
+    }
+}
+
+#[cfg(not(all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse2")))]
+mod imp {
+    use mem;
+    use ptr;
+    use super::{BitMask, EMPTY};
+
+    const GROUP_WIDTH: usize = mem::size_of::<usize>();
+
+    pub type BitMaskWord = usize;
+    pub const BITMASK_STRIDE: usize = 8;
+    pub const BITMASK_MASK: BitMaskWord = repeat(0x80);
+
+    /// Returns a word with every byte set to `byte`.
+    const fn repeat(byte: u8) -> usize {
+        (byte as usize) * (!0 / 0xff)
+    }
+
+    /// `size_of::<usize>()` control bytes held in a general purpose register.
+    ///
+    /// The bytes are always loaded in little-endian order so that the byte
+    /// at the lowest address ends up in the least significant bits, which
+    /// keeps the positions reported by `BitMask` in memory order.
+    #[derive(Copy, Clone)]
+    pub struct Group(usize);
+
+    impl Group {
+        /// Number of control bytes in a group.
+        pub const WIDTH: usize = GROUP_WIDTH;
+
+        /// Returns a full group of `EMPTY` control bytes, used as the control
+        /// bytes of tables which have not allocated yet.
+        pub fn static_empty() -> &'static [u8; GROUP_WIDTH] {
+            static EMPTY_GROUP: [u8; GROUP_WIDTH] = [EMPTY; GROUP_WIDTH];
+            &EMPTY_GROUP
+        }
+
+        /// Loads a group of control bytes starting at `ptr`, which need not
+        /// be aligned.
+        #[inline]
+        pub unsafe fn load(ptr: *const u8) -> Group {
+            Group(usize::from_le(ptr::read_unaligned(ptr as *const usize)))
+        }
+
+        /// Returns the positions of the bytes equal to `byte`.
+        ///
+        /// This may report a false positive right after a true match when
+        /// the byte there differs from `byte` only in its lowest bit. Since
+        /// `byte` is always a hash fragment, such a byte belongs to a full
+        /// bucket as well and the caller compares the keys anyway.
+        #[inline]
+        pub fn match_byte(self, byte: u8) -> BitMask {
+            // The classic "does this word contain a zero byte" trick, see
+            // http://graphics.stanford.edu/~seander/bithacks.html##ValueInWord
+            let cmp = self.0 ^ repeat(byte);
+            BitMask(cmp.wrapping_sub(repeat(0x01)) & !cmp & repeat(0x80))
+        }
+
+        /// Returns the positions of the `EMPTY` bytes. `EMPTY` is the only
+        /// control byte with both of its two highest bits set.
+        #[inline]
+        pub fn match_empty(self) -> BitMask {
+            BitMask(self.0 & (self.0 << 1) & repeat(0x80))
+        }
+
+        /// Returns the positions of the `EMPTY` and `DELETED` bytes, which
+        /// are exactly the bytes with their high bit set.
+        #[inline]
+        pub fn match_empty_or_deleted(self) -> BitMask {
+            BitMask(self.0 & repeat(0x80))
+        }
+
+        /// Returns the positions of the bytes of full buckets.
+        #[inline]
+        pub fn match_full(self) -> BitMask {
+            self.match_empty_or_deleted().invert()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DELETED, EMPTY, Group};
+
+    fn load(bytes: &[u8]) -> Group {
+        assert_eq!(bytes.len(), Group::WIDTH);
+        unsafe { Group::load(bytes.as_ptr()) }
+    }
+
+    #[test]
+    fn match_special_bytes() {
+        let mut bytes = vec![EMPTY; Group::WIDTH];
+        bytes[1] = DELETED;
+        bytes[2] = 0x15;
+        bytes[Group::WIDTH - 1] = 0x7f;
+        let group = load(&bytes);
+
+        let empty: Vec<usize> = group.match_empty().collect();
+        let mut expected: Vec<usize> = (0..Group::WIDTH - 1).collect();
+        expected.retain(|&i| i != 1 && i != 2);
+        assert_eq!(empty, expected);
+
+        let full: Vec<usize> = group.match_full().collect();
+        assert_eq!(full, [2, Group::WIDTH - 1]);
+
+        let special: Vec<usize> = group.match_empty_or_deleted().collect();
+        assert_eq!(special.len(), Group::WIDTH - 2);
+        assert_eq!(group.match_empty().trailing_zeros(), 0);
+        assert_eq!(group.match_full().leading_zeros(), 0);
+    }
+
+    #[test]
+    fn match_hash_byte() {
+        let mut bytes = vec![EMPTY; Group::WIDTH];
+        bytes[0] = 0x23;
+        bytes[3] = 0x42;
+        bytes[Group::WIDTH - 2] = 0x42;
+        let group = load(&bytes);
+
+        let matches: Vec<usize> = group.match_byte(0x42).collect();
+        assert_eq!(matches, [3, Group::WIDTH - 2]);
+        assert!(!group.match_byte(0x11).any_bit_set());
+        assert!(!load(&vec![EMPTY; Group::WIDTH]).match_full().any_bit_set());
+    }
+}
@@ -11,6 +11,7 @@
 //! Unordered containers, implemented as hash-tables
 
 mod bench;
+mod group;
 mod table;
 pub mod map;
 pub mod set;
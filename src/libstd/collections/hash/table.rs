@@ -1,4 +1,4 @@
-// Copyright 2014-2015 The Rust Project Developers. See the COPYRIGHT
+// Copyright 2014-2018 The Rust Project Developers. See the COPYRIGHT
 // file at the top-level directory of this distribution and at
 // http://rust-lang.org/COPYRIGHT.
 //
@@ -8,203 +8,128 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-use alloc::heap::{Heap, Alloc, Layout};
+use alloc::heap::{Heap, Alloc, Layout, CollectionAllocErr};
 
 use cmp;
 use hash::{BuildHasher, Hash, Hasher};
 use marker;
-use mem::{align_of, size_of, needs_drop};
-use mem;
+use mem::needs_drop;
 use ops::{Deref, DerefMut};
 use ptr::{self, Unique, NonNull};
 
-use self::BucketState::*;
+use super::group::{self, Group, BitMask, DELETED, EMPTY};
 
-/// Integer type used for stored hash values.
+/// The raw hashtable, providing safe-ish access to an array of control bytes
+/// and an array of key-value pairs.
 ///
-/// No more than bit_width(usize) bits are needed to select a bucket.
+/// Every bucket `i` of the table has a control byte `ctrl[i]` and a slot
+/// `data[i]` for a key-value pair. The control byte is one of:
 ///
-/// The most significant bit is ours to use for tagging `SafeHash`.
+///   - `EMPTY`: the bucket has never held an element since the table was
+///     created or last cleared.
 ///
-/// (Even if we could have usize::MAX bytes allocated for buckets,
-/// each bucket stores at least a `HashUint`, so there can be no more than
-/// usize::MAX / size_of(usize) buckets.)
-type HashUint = usize;
-
-const EMPTY_BUCKET: HashUint = 0;
-const EMPTY: usize = 1;
-
-/// Special `Unique<HashUint>` that uses the lower bit of the pointer
-/// to expose a boolean tag.
-/// Note: when the pointer is initialized to EMPTY `.ptr()` will return
-/// null and the tag functions shouldn't be used.
-struct TaggedHashUintPtr(Unique<HashUint>);
-
-impl TaggedHashUintPtr {
-    #[inline]
-    unsafe fn new(ptr: *mut HashUint) -> Self {
-        debug_assert!(ptr as usize & 1 == 0 || ptr as usize == EMPTY as usize);
-        TaggedHashUintPtr(Unique::new_unchecked(ptr))
-    }
-
-    #[inline]
-    fn set_tag(&mut self, value: bool) {
-        let mut usize_ptr = self.0.as_ptr() as usize;
-        unsafe {
-            if value {
-                usize_ptr |= 1;
-            } else {
-                usize_ptr &= !1;
-            }
-            self.0 = Unique::new_unchecked(usize_ptr as *mut HashUint)
-        }
-    }
-
-    #[inline]
-    fn tag(&self) -> bool {
-        (self.0.as_ptr() as usize) & 1 == 1
-    }
-
-    #[inline]
-    fn ptr(&self) -> *mut HashUint {
-        (self.0.as_ptr() as usize & !1) as *mut HashUint
-    }
-}
-
-/// The raw hashtable, providing safe-ish access to the unzipped and highly
-/// optimized arrays of hashes, and key-value pairs.
+///   - `DELETED`: the bucket held an element which was removed. Lookups have
+///     to continue past such a bucket, but insertions may reuse it.
 ///
-/// This design is a lot faster than the naive
-/// `Vec<Option<(u64, K, V)>>`, because we don't pay for the overhead of an
-/// option on every element, and we get a generally more cache-aware design.
+///   - the top 7 bits of the hash of the element in `data[i]`, with the high
+///     bit clear. Only buckets with such a control byte are "full" and have
+///     an initialized `data[i]`.
 ///
-/// Essential invariants of this structure:
+/// Lookups start at the bucket selected by the low bits of the hash and load
+/// `Group::WIDTH` control bytes at once. All of them are compared against the
+/// top 7 bits of the hash in parallel, so that the keys of only a handful of
+/// candidate buckets are ever compared. If the group contains an `EMPTY`
+/// byte the key is not in the table; otherwise the search continues with the
+/// next group of a triangular probe sequence, which visits every group of a
+/// power-of-two sized table exactly once.
 ///
-///   - if t.hashes[i] == EMPTY_BUCKET, then `Bucket::at_index(&t, i).raw`
-///     points to 'undefined' contents. Don't read from it. This invariant is
-///     enforced outside this module with the `EmptyBucket`, `FullBucket`,
-///     and `SafeHash` types.
+/// To allow loading a group at any bucket without bounds checks, the first
+/// `Group::WIDTH` control bytes are repeated after the last one.
 ///
-///   - An `EmptyBucket` is only constructed at an index with
-///     a hash of EMPTY_BUCKET.
+/// The table is never filled above 7/8 of its buckets, counting `DELETED`
+/// buckets as filled. This guarantees that every probe sequence reaches an
+/// `EMPTY` byte.
 ///
-///   - A `FullBucket` is only constructed at an index with a
-///     non-EMPTY_BUCKET hash.
+/// Essential invariants of this structure:
 ///
-///   - A `SafeHash` is only constructed for non-`EMPTY_BUCKET` hash. We get
-///     around hashes of zero by changing them to 0x8000_0000_0000_0000,
-///     which will likely map to the same bucket, while not being confused
-///     with "empty".
+///   - `data[i]` is initialized if and only if `ctrl[i]` is full. This
+///     invariant is enforced outside this module with the `FullBucket` type,
+///     which is only ever constructed for a full bucket.
 ///
-///   - Both "arrays represented by pointers" are the same length:
-///     `capacity`. This is set at creation and never changes. The arrays
-///     are unzipped and are more cache aware (scanning through 8 hashes
-///     brings in at most 2 cache lines, since they're all right beside each
-///     other). This layout may waste space in padding such as in a map from
-///     u64 to u8, but is a more cache conscious layout as the key-value pairs
-///     are only very shortly probed and the desired value will be in the same
-///     or next cache line.
+///   - The number of buckets is zero or a power of two which is at least
+///     `Group::WIDTH`. A table without buckets does not allocate and uses a
+///     static group of `EMPTY` control bytes instead.
 ///
-/// You can kind of think of this module/data structure as a safe wrapper
-/// around just the "table" part of the hashtable. It enforces some
-/// invariants at the type level and employs some performance trickery,
-/// but in general is just a tricked out `Vec<Option<(u64, K, V)>>`.
+///   - `growth_left` is the number of `EMPTY` buckets which may still be
+///     filled without breaking the maximum load.
 ///
-/// The hashtable also exposes a special boolean tag. The tag defaults to false
-/// when the RawTable is created and is accessible with the `tag` and `set_tag`
-/// functions.
+/// The control bytes and the pairs live in a single allocation, with the
+/// pairs first. Scanning a group of control bytes brings in a single cache
+/// line, and the pair of a matching bucket is only read when its control
+/// byte already matched.
 pub struct RawTable<K, V> {
-    capacity_mask: usize,
-    size: usize,
-    hashes: TaggedHashUintPtr,
+    // The number of buckets minus one, or zero if the table has no buckets.
+    bucket_mask: usize,
+    ctrl: Unique<u8>,
+    data: Unique<(K, V)>,
+    growth_left: usize,
+    items: usize,
 
     // Because K/V do not appear directly in any of the types in the struct,
     // inform rustc that in fact instances of K and V are reachable from here.
     marker: marker::PhantomData<(K, V)>,
 }
 
-// An unsafe view of a RawTable bucket
-// Valid indexes are within [0..table_capacity)
-pub struct RawBucket<K, V> {
-    hash_start: *mut HashUint,
-    // We use *const to ensure covariance with respect to K and V
-    pair_start: *const (K, V),
-    idx: usize,
-    _marker: marker::PhantomData<(K, V)>,
-}
-
-impl<K, V> Copy for RawBucket<K, V> {}
-impl<K, V> Clone for RawBucket<K, V> {
-    fn clone(&self) -> RawBucket<K, V> {
-        *self
-    }
-}
-
-pub struct Bucket<K, V, M> {
-    raw: RawBucket<K, V>,
-    table: M,
-}
-
-impl<K, V, M: Copy> Copy for Bucket<K, V, M> {}
-impl<K, V, M: Copy> Clone for Bucket<K, V, M> {
-    fn clone(&self) -> Bucket<K, V, M> {
-        *self
-    }
-}
-
-pub struct EmptyBucket<K, V, M> {
-    raw: RawBucket<K, V>,
-    table: M,
-}
-
+/// A view of a full bucket of a table, holding on to the table through `M`.
+///
+/// `M` is a reference to the table (`&RawTable` or `&mut RawTable`), which
+/// determines whether the bucket can be modified.
 pub struct FullBucket<K, V, M> {
-    raw: RawBucket<K, V>,
+    idx: usize,
     table: M,
+    marker: marker::PhantomData<(K, V)>,
 }
 
 pub type FullBucketMut<'table, K, V> = FullBucket<K, V, &'table mut RawTable<K, V>>;
 
-pub enum BucketState<K, V, M> {
-    Empty(EmptyBucket<K, V, M>),
-    Full(FullBucket<K, V, M>),
-}
-
-// A GapThenFull encapsulates the state of two consecutive buckets at once.
-// The first bucket, called the gap, is known to be empty.
-// The second bucket is full.
-pub struct GapThenFull<K, V, M> {
-    gap: EmptyBucket<K, V, ()>,
-    full: FullBucket<K, V, M>,
-}
-
-/// A hash that is not zero, since we use a hash of zero to represent empty
-/// buckets.
+/// The hash of a key, as produced by the map's `BuildHasher`.
+///
+/// The low bits select the bucket where probing starts (`h1`), and the top 7
+/// bits are stored in the control byte of the bucket the key ends up in
+/// (`h2`). Using bits from both ends keeps the two as independent as
+/// possible.
 #[derive(PartialEq, Copy, Clone)]
 pub struct SafeHash {
-    hash: HashUint,
+    hash: u64,
 }
 
 impl SafeHash {
-    /// Peek at the hash value, which is guaranteed to be non-zero.
     #[inline(always)]
-    pub fn inspect(&self) -> HashUint {
+    pub fn new(hash: u64) -> Self {
+        SafeHash { hash }
+    }
+
+    /// The full hash, as computed by the map's `BuildHasher`.
+    #[inline(always)]
+    pub fn inspect(&self) -> u64 {
         self.hash
     }
 
+    /// The bits of the hash which select the first probed bucket.
     #[inline(always)]
-    pub fn new(hash: u64) -> Self {
-        // We need to avoid 0 in order to prevent collisions with
-        // EMPTY_HASH. We can maintain our precious uniform distribution
-        // of initial indexes by unconditionally setting the MSB,
-        // effectively reducing the hashes by one bit.
-        //
-        // Truncate hash to fit in `HashUint`.
-        let hash_bits = size_of::<HashUint>() * 8;
-        SafeHash { hash: (1 << (hash_bits - 1)) | (hash as HashUint) }
+    fn h1(&self) -> usize {
+        // Truncation is fine: the table can never have more buckets than
+        // fit in a `usize`.
+        self.hash as usize
+    }
+
+    /// The control byte of a bucket holding a key with this hash.
+    #[inline(always)]
+    fn h2(&self) -> u8 {
+        (self.hash >> 57) as u8
     }
 }
 
-/// We need to remove hashes of 0. That's reserved for empty buckets.
 /// This function wraps up `hash_keyed` to be the only way outside this
 /// module to generate a SafeHash.
 pub fn make_hash<T: ?Sized, S>(hash_state: &S, t: &T) -> SafeHash
@@ -216,362 +141,405 @@ pub fn make_hash<T: ?Sized, S>(hash_state: &S, t: &T) -> SafeHash
     SafeHash::new(state.finish())
 }
 
-// `replace` casts a `*HashUint` to a `*SafeHash`. Since we statically
-// ensure that a `FullBucket` points to an index with a non-zero hash,
-// and a `SafeHash` is just a `HashUint` with a different name, this is
-// safe.
-//
-// This test ensures that a `SafeHash` really IS the same size as a
-// `HashUint`. If you need to change the size of `SafeHash` (and
-// consequently made this test fail), `replace` needs to be
-// modified to no longer assume this.
-#[test]
-fn can_alias_safehash_as_hash() {
-    assert_eq!(size_of::<SafeHash>(), size_of::<HashUint>())
+/// Returns the number of elements a table with the given number of buckets
+/// can hold, which is 7/8 of the buckets.
+#[inline]
+pub fn usable_capacity(buckets: usize) -> usize {
+    if buckets < 8 {
+        // Always leave at least one bucket `EMPTY`.
+        buckets.saturating_sub(1)
+    } else {
+        buckets / 8 * 7
+    }
 }
 
-// RawBucket methods are unsafe as it's possible to
-// make a RawBucket point to invalid memory using safe code.
-impl<K, V> RawBucket<K, V> {
-    unsafe fn hash(&self) -> *mut HashUint {
-        self.hash_start.offset(self.idx as isize)
-    }
-    unsafe fn pair(&self) -> *mut (K, V) {
-        self.pair_start.offset(self.idx as isize) as *mut (K, V)
-    }
-    unsafe fn hash_pair(&self) -> (*mut HashUint, *mut (K, V)) {
-        (self.hash(), self.pair())
-    }
+/// Returns the layout of the allocation of a table with `buckets` buckets,
+/// together with the offset of the control bytes in it.
+fn calculate_layout<K, V>(buckets: usize) -> Option<(Layout, usize)> {
+    let data = Layout::array::<(K, V)>(buckets)?;
+    let ctrl = Layout::array::<u8>(buckets.checked_add(Group::WIDTH)?)?;
+    data.extend(ctrl)
 }
 
-// Buckets hold references to the table.
-impl<K, V, M> FullBucket<K, V, M> {
-    /// Borrow a reference to the table.
-    pub fn table(&self) -> &M {
-        &self.table
-    }
-    /// Borrow a mutable reference to the table.
-    pub fn table_mut(&mut self) -> &mut M {
-        &mut self.table
-    }
-    /// Move out the reference to the table.
-    pub fn into_table(self) -> M {
-        self.table
-    }
-    /// Get the raw index.
-    pub fn index(&self) -> usize {
-        self.raw.idx
-    }
-    /// Get the raw bucket.
-    pub fn raw(&self) -> RawBucket<K, V> {
-        self.raw
-    }
+#[test]
+fn test_layout_calculation() {
+    let (layout, ctrl_offset) = calculate_layout::<u64, u8>(32).unwrap();
+    assert_eq!(ctrl_offset, 32 * 16);
+    assert_eq!(layout.size(), 32 * 16 + 32 + Group::WIDTH);
+    assert_eq!(layout.align(), 8);
+
+    let (layout, ctrl_offset) = calculate_layout::<(), ()>(64).unwrap();
+    assert_eq!(ctrl_offset, 0);
+    assert_eq!(layout.size(), 64 + Group::WIDTH);
+
+    assert!(calculate_layout::<u64, u64>(!0 / 8).is_none());
 }
 
-impl<K, V, M> EmptyBucket<K, V, M> {
-    /// Borrow a reference to the table.
-    pub fn table(&self) -> &M {
-        &self.table
-    }
-    /// Borrow a mutable reference to the table.
-    pub fn table_mut(&mut self) -> &mut M {
-        &mut self.table
+/// The sequence of groups probed for a hash.
+///
+/// Each step moves `Group::WIDTH` buckets further than the last one. Since
+/// the number of buckets is a power of two, this visits every group of the
+/// table exactly once before repeating.
+struct ProbeSeq {
+    bucket_mask: usize,
+    pos: usize,
+    stride: usize,
+}
+
+impl ProbeSeq {
+    #[inline]
+    fn move_next(&mut self) {
+        // We should have found an `EMPTY` bucket by now and ended the probe.
+        debug_assert!(self.stride <= self.bucket_mask, "went past end of probe sequence");
+
+        self.stride += Group::WIDTH;
+        self.pos += self.stride;
+        self.pos &= self.bucket_mask;
     }
 }
 
-impl<K, V, M> Bucket<K, V, M> {
-    /// Get the raw index.
-    pub fn index(&self) -> usize {
-        self.raw.idx
+impl<K, V> RawTable<K, V> {
+    /// Creates a table without buckets which does not allocate.
+    fn new_empty() -> RawTable<K, V> {
+        RawTable {
+            bucket_mask: 0,
+            ctrl: unsafe { Unique::new_unchecked(Group::static_empty().as_ptr() as *mut u8) },
+            data: Unique::empty(),
+            growth_left: 0,
+            items: 0,
+            marker: marker::PhantomData,
+        }
     }
-    /// get the table.
-    pub fn into_table(self) -> M {
-        self.table
+
+    /// Does not initialize the control bytes. The caller should ensure they,
+    /// at the very least, set every control byte to `EMPTY`.
+    unsafe fn try_new_uninitialized(buckets: usize)
+                                    -> Result<RawTable<K, V>, CollectionAllocErr> {
+        debug_assert!(buckets.is_power_of_two());
+        let buckets = cmp::max(buckets, Group::WIDTH);
+
+        let (layout, ctrl_offset) = calculate_layout::<K, V>(buckets)
+            .ok_or(CollectionAllocErr::CapacityOverflow)?;
+        let buffer = Heap.alloc(layout)?;
+
+        Ok(RawTable {
+            bucket_mask: buckets - 1,
+            ctrl: Unique::new_unchecked(buffer.offset(ctrl_offset as isize)),
+            data: Unique::new_unchecked(buffer as *mut (K, V)),
+            growth_left: usable_capacity(buckets),
+            items: 0,
+            marker: marker::PhantomData,
+        })
     }
-}
 
-impl<K, V, M> Deref for FullBucket<K, V, M>
-    where M: Deref<Target = RawTable<K, V>>
-{
-    type Target = RawTable<K, V>;
-    fn deref(&self) -> &RawTable<K, V> {
-        &self.table
+    /// Creates a new raw table with the given number of buckets, which must
+    /// be zero or a power of two. All buckets are initially empty.
+    pub fn new(buckets: usize) -> RawTable<K, V> {
+        match RawTable::try_new(buckets) {
+            Ok(table) => table,
+            Err(CollectionAllocErr::CapacityOverflow) => panic!("capacity overflow"),
+            Err(CollectionAllocErr::AllocErr(e)) => Heap.oom(e),
+        }
     }
-}
 
-/// `Put` is implemented for types which provide access to a table and cannot be invalidated
-///  by filling a bucket. A similar implementation for `Take` is possible.
-pub trait Put<K, V> {
-    unsafe fn borrow_table_mut(&mut self) -> &mut RawTable<K, V>;
-}
+    /// Like `new`, but reports a capacity overflow or a failed allocation
+    /// to the caller instead of panicking or aborting.
+    pub fn try_new(buckets: usize) -> Result<RawTable<K, V>, CollectionAllocErr> {
+        if buckets == 0 {
+            return Ok(RawTable::new_empty());
+        }
+        unsafe {
+            let ret = RawTable::try_new_uninitialized(buckets)?;
+            ptr::write_bytes(ret.ctrl.as_ptr(), EMPTY, ret.num_ctrl_bytes());
+            Ok(ret)
+        }
+    }
 
+    #[inline]
+    fn is_empty_singleton(&self) -> bool {
+        // Allocated tables have at least `Group::WIDTH` buckets.
+        self.bucket_mask == 0
+    }
 
-impl<'t, K, V> Put<K, V> for &'t mut RawTable<K, V> {
-    unsafe fn borrow_table_mut(&mut self) -> &mut RawTable<K, V> {
-        *self
+    #[inline]
+    fn num_ctrl_bytes(&self) -> usize {
+        self.bucket_mask + 1 + Group::WIDTH
     }
-}
 
-impl<K, V, M> Put<K, V> for Bucket<K, V, M>
-    where M: Put<K, V>
-{
-    unsafe fn borrow_table_mut(&mut self) -> &mut RawTable<K, V> {
-        self.table.borrow_table_mut()
+    #[inline]
+    unsafe fn ctrl(&self, index: usize) -> *mut u8 {
+        debug_assert!(index < self.num_ctrl_bytes());
+        self.ctrl.as_ptr().offset(index as isize)
     }
-}
 
-impl<K, V, M> Put<K, V> for FullBucket<K, V, M>
-    where M: Put<K, V>
-{
-    unsafe fn borrow_table_mut(&mut self) -> &mut RawTable<K, V> {
-        self.table.borrow_table_mut()
+    #[inline]
+    unsafe fn bucket(&self, index: usize) -> *mut (K, V) {
+        debug_assert!(index <= self.bucket_mask);
+        self.data.as_ptr().offset(index as isize)
     }
-}
 
-impl<K, V, M: Deref<Target = RawTable<K, V>>> Bucket<K, V, M> {
-    pub fn new(table: M, hash: SafeHash) -> Bucket<K, V, M> {
-        Bucket::at_index(table, hash.inspect() as usize)
+    /// Sets the control byte of a bucket, and its copy after the end of the
+    /// table if it is one of the first `Group::WIDTH` buckets.
+    #[inline]
+    unsafe fn set_ctrl(&self, index: usize, ctrl: u8) {
+        // For the buckets past the first group the two indices are the same.
+        let index2 = (index.wrapping_sub(Group::WIDTH) & self.bucket_mask) + Group::WIDTH;
+
+        *self.ctrl(index) = ctrl;
+        *self.ctrl(index2) = ctrl;
     }
 
-    pub fn new_from(r: RawBucket<K, V>, t: M)
-        -> Bucket<K, V, M>
-    {
-        Bucket {
-            raw: r,
-            table: t,
+    #[inline]
+    fn probe_seq(&self, hash: SafeHash) -> ProbeSeq {
+        ProbeSeq {
+            bucket_mask: self.bucket_mask,
+            pos: hash.h1() & self.bucket_mask,
+            stride: 0,
         }
     }
 
-    pub fn at_index(table: M, ib_index: usize) -> Bucket<K, V, M> {
-        // if capacity is 0, then the RawBucket will be populated with bogus pointers.
-        // This is an uncommon case though, so avoid it in release builds.
-        debug_assert!(table.capacity() > 0,
-                      "Table should have capacity at this point");
-        let ib_index = ib_index & table.capacity_mask;
-        Bucket {
-            raw: table.raw_bucket_at(ib_index),
-            table,
+    /// The number of buckets of the hashtable. This is zero until the table
+    /// allocates.
+    pub fn capacity(&self) -> usize {
+        if self.is_empty_singleton() {
+            0
+        } else {
+            self.bucket_mask + 1
         }
     }
 
-    pub fn first(table: M) -> Bucket<K, V, M> {
-        Bucket {
-            raw: table.raw_bucket_at(0),
-            table,
-        }
+    /// The number of elements ever `insert`ed in the hashtable, minus the
+    /// number of elements ever `take`n.
+    pub fn size(&self) -> usize {
+        self.items
     }
 
-    // "So a few of the first shall be last: for many be called,
-    // but few chosen."
-    //
-    // We'll most likely encounter a few buckets at the beginning that
-    // have their initial buckets near the end of the table. They were
-    // placed at the beginning as the probe wrapped around the table
-    // during insertion. We must skip forward to a bucket that won't
-    // get reinserted too early and won't unfairly steal others spot.
-    // This eliminates the need for robin hood.
-    pub fn head_bucket(table: M) -> Bucket<K, V, M> {
-        let mut bucket = Bucket::first(table);
+    /// The number of elements which can still be inserted before the table
+    /// has to be rebuilt, either because it is full or because too many of
+    /// its buckets have been left `DELETED`.
+    pub fn growth_left(&self) -> usize {
+        self.growth_left
+    }
 
+    /// Searches for an element with the given hash for which `is_match`
+    /// returns `true`, and returns the index of its bucket.
+    #[inline]
+    pub fn find<F>(&self, hash: SafeHash, mut is_match: F) -> Option<usize>
+        where F: FnMut(&K) -> bool
+    {
+        let h2 = hash.h2();
+        let mut probe_seq = self.probe_seq(hash);
         loop {
-            bucket = match bucket.peek() {
-                Full(full) => {
-                    if full.displacement() == 0 {
-                        // This bucket occupies its ideal spot.
-                        // It indicates the start of another "cluster".
-                        bucket = full.into_bucket();
-                        break;
-                    }
-                    // Leaving this bucket in the last cluster for later.
-                    full.into_bucket()
-                }
-                Empty(b) => {
-                    // Encountered a hole between clusters.
-                    b.into_bucket()
+            let group = unsafe { Group::load(self.ctrl(probe_seq.pos)) };
+            for bit in group.match_byte(h2) {
+                let index = (probe_seq.pos + bit) & self.bucket_mask;
+                if is_match(unsafe { &(*self.bucket(index)).0 }) {
+                    return Some(index);
                 }
-            };
-            bucket.next();
-        }
-        bucket
-    }
-
-    /// Reads a bucket at a given index, returning an enum indicating whether
-    /// it's initialized or not. You need to match on this enum to get
-    /// the appropriate types to call most of the other functions in
-    /// this module.
-    pub fn peek(self) -> BucketState<K, V, M> {
-        match unsafe { *self.raw.hash() } {
-            EMPTY_BUCKET => {
-                Empty(EmptyBucket {
-                    raw: self.raw,
-                    table: self.table,
-                })
             }
-            _ => {
-                Full(FullBucket {
-                    raw: self.raw,
-                    table: self.table,
-                })
+            if group.match_empty().any_bit_set() {
+                return None;
             }
+            probe_seq.move_next();
         }
     }
 
-    /// Modifies the bucket in place to make it point to the next slot.
-    pub fn next(&mut self) {
-        self.raw.idx = self.raw.idx.wrapping_add(1) & self.table.capacity_mask;
-    }
-
-    /// Modifies the bucket in place to make it point to the previous slot.
-    pub fn prev(&mut self) {
-        self.raw.idx = self.raw.idx.wrapping_sub(1) & self.table.capacity_mask;
+    /// Returns the index of the first `EMPTY` or `DELETED` bucket in the
+    /// probe sequence of `hash`.
+    #[inline]
+    fn find_insert_slot(&self, hash: SafeHash) -> usize {
+        let mut probe_seq = self.probe_seq(hash);
+        loop {
+            let group = unsafe { Group::load(self.ctrl(probe_seq.pos)) };
+            if let Some(bit) = group.match_empty_or_deleted().lowest_set_bit() {
+                return (probe_seq.pos + bit) & self.bucket_mask;
+            }
+            probe_seq.move_next();
+        }
     }
-}
 
-impl<K, V, M: Deref<Target = RawTable<K, V>>> EmptyBucket<K, V, M> {
+    /// Marks the bucket in which an element with the given hash will be
+    /// inserted as full, and returns its index. The caller must initialize
+    /// the bucket.
     #[inline]
-    pub fn next(self) -> Bucket<K, V, M> {
-        let mut bucket = self.into_bucket();
-        bucket.next();
-        bucket
+    fn prepare_insert(&mut self, hash: SafeHash) -> usize {
+        let index = self.find_insert_slot(hash);
+        unsafe {
+            let old_ctrl = *self.ctrl(index);
+            if group::special_is_empty(old_ctrl) {
+                assert!(self.growth_left != 0, "inserting into a full table");
+                self.growth_left -= 1;
+            }
+            self.set_ctrl(index, hash.h2());
+        }
+        self.items += 1;
+        index
     }
 
-    #[inline]
-    pub fn into_bucket(self) -> Bucket<K, V, M> {
-        Bucket {
-            raw: self.raw,
-            table: self.table,
+    /// Inserts a new element without checking whether an equal key is
+    /// already present, and returns the index of its bucket.
+    ///
+    /// The caller must ensure there's space left in the table, see
+    /// `growth_left`.
+    pub fn insert(&mut self, hash: SafeHash, key: K, value: V) -> usize {
+        let index = self.prepare_insert(hash);
+        unsafe {
+            ptr::write(self.bucket(index), (key, value));
         }
+        index
     }
 
-    pub fn gap_peek(self) -> Result<GapThenFull<K, V, M>, Bucket<K, V, M>> {
-        let gap = EmptyBucket {
-            raw: self.raw,
-            table: (),
+    /// Inserts a key, leaving its value uninitialized.
+    /// It is only used for inplacement insertion.
+    pub unsafe fn insert_key(&mut self, hash: SafeHash, key: K) -> usize {
+        let index = self.prepare_insert(hash);
+        ptr::write(&mut (*self.bucket(index)).0, key);
+        index
+    }
+
+    /// Marks a full bucket as no longer holding an element. The element is
+    /// left for the caller to move out or drop.
+    unsafe fn erase(&mut self, index: usize) {
+        debug_assert!(group::is_full(*self.ctrl(index)));
+        let index_before = index.wrapping_sub(Group::WIDTH) & self.bucket_mask;
+        let empty_before = Group::load(self.ctrl(index_before)).match_empty();
+        let empty_after = Group::load(self.ctrl(index)).match_empty();
+
+        // A lookup stops at the first group containing an `EMPTY` byte. If
+        // every window of `Group::WIDTH` bytes covering this bucket has one,
+        // no lookup could ever have needed to move past this bucket, so it
+        // can be made `EMPTY` again. Otherwise it has to stay visible to
+        // lookups as `DELETED`.
+        let ctrl = if empty_before.leading_zeros() + empty_after.trailing_zeros()
+                      >= Group::WIDTH {
+            DELETED
+        } else {
+            self.growth_left += 1;
+            EMPTY
         };
+        self.set_ctrl(index, ctrl);
+        self.items -= 1;
+    }
 
-        match self.next().peek() {
-            Full(bucket) => {
-                Ok(GapThenFull {
-                    gap,
-                    full: bucket,
-                })
+    /// Removes all elements for which `f` returns `false`.
+    pub fn retain<F>(&mut self, mut f: F)
+        where F: FnMut(&K, &mut V) -> bool
+    {
+        unsafe {
+            for index in self.raw_iter() {
+                let pair = self.bucket(index);
+                if !f(&(*pair).0, &mut (*pair).1) {
+                    // Erase first so that the table stays consistent even
+                    // if the destructor panics.
+                    self.erase(index);
+                    ptr::drop_in_place(pair);
+                }
             }
-            Empty(e) => Err(e.into_bucket()),
         }
     }
-}
 
-impl<K, V, M> EmptyBucket<K, V, M>
-    where M: Put<K, V>
-{
-    /// Puts given key and value pair, along with the key's hash,
-    /// into this bucket in the hashtable. Note how `self` is 'moved' into
-    /// this function, because this slot will no longer be empty when
-    /// we return! A `FullBucket` is returned for later use, pointing to
-    /// the newly-filled slot in the hashtable.
-    ///
-    /// Use `make_hash` to construct a `SafeHash` to pass to this function.
-    pub fn put(mut self, hash: SafeHash, key: K, value: V) -> FullBucket<K, V, M> {
-        unsafe {
-            *self.raw.hash() = hash.inspect();
-            ptr::write(self.raw.pair(), (key, value));
-
-            self.table.borrow_table_mut().size += 1;
+    /// Marks every bucket as `EMPTY` without dropping the elements.
+    fn clear_no_drop(&mut self) {
+        if !self.is_empty_singleton() {
+            unsafe {
+                ptr::write_bytes(self.ctrl.as_ptr(), EMPTY, self.num_ctrl_bytes());
+            }
         }
+        self.items = 0;
+        self.growth_left = usable_capacity(self.capacity());
+    }
 
-        FullBucket {
-            raw: self.raw,
-            table: self.table,
+    // The returned iterator is not bound to the lifetime of `self`, which is
+    // up to the caller to enforce.
+    fn raw_iter<'a>(&self) -> RawIter<'a, K, V> {
+        RawIter {
+            data: self.data.as_ptr(),
+            ctrl: self.ctrl.as_ptr(),
+            group_base: 0,
+            current_group: unsafe { Group::load(self.ctrl.as_ptr()).match_full() },
+            elems_left: self.items,
+            marker: marker::PhantomData,
         }
     }
 
-    /// Puts given key, remain value uninitialized.
-    /// It is only used for inplacement insertion.
-    pub unsafe fn put_key(mut self, hash: SafeHash, key: K) -> FullBucket<K, V, M> {
-        *self.raw.hash() = hash.inspect();
-        let pair_ptr = self.raw.pair();
-        ptr::write(&mut (*pair_ptr).0, key);
-
-        self.table.borrow_table_mut().size += 1;
-
-        FullBucket {
-            raw: self.raw,
-            table: self.table,
+    pub fn iter(&self) -> Iter<K, V> {
+        Iter {
+            iter: self.raw_iter(),
         }
     }
-}
 
-impl<K, V, M: Deref<Target = RawTable<K, V>>> FullBucket<K, V, M> {
-    #[inline]
-    pub fn next(self) -> Bucket<K, V, M> {
-        let mut bucket = self.into_bucket();
-        bucket.next();
-        bucket
+    pub fn iter_mut(&mut self) -> IterMut<K, V> {
+        IterMut {
+            iter: self.raw_iter(),
+            _marker: marker::PhantomData,
+        }
     }
 
-    #[inline]
-    pub fn into_bucket(self) -> Bucket<K, V, M> {
-        Bucket {
-            raw: self.raw,
-            table: self.table,
+    pub fn into_iter(self) -> IntoIter<K, V> {
+        IntoIter {
+            iter: self.raw_iter(),
+            table: self,
         }
     }
 
-    /// Duplicates the current position. This can be useful for operations
-    /// on two or more buckets.
-    pub fn stash(self) -> FullBucket<K, V, Self> {
-        FullBucket {
-            raw: self.raw,
-            table: self,
+    pub fn drain(&mut self) -> Drain<K, V> {
+        Drain {
+            iter: self.raw_iter(),
+            table: NonNull::from(self),
+            marker: marker::PhantomData,
         }
     }
 
-    /// Get the distance between this bucket and the 'ideal' location
-    /// as determined by the key's hash stored in it.
-    ///
-    /// In the cited blog posts above, this is called the "distance to
-    /// initial bucket", or DIB. Also known as "probe count".
-    pub fn displacement(&self) -> usize {
-        // Calculates the distance one has to travel when going from
-        // `hash mod capacity` onwards to `idx mod capacity`, wrapping around
-        // if the destination is not reached before the end of the table.
-        (self.raw.idx.wrapping_sub(self.hash().inspect() as usize)) & self.table.capacity_mask
+    /// Drops the elements of the `items` last full buckets. It leaves the
+    /// table in an inconsistent state and should only be used for dropping
+    /// the table's remaining entries. It's used in the implementation of Drop.
+    unsafe fn rev_drop_buckets(&mut self) {
+        let mut index = self.capacity();
+        let mut elems_left = self.items;
+
+        while elems_left != 0 {
+            index -= 1;
+
+            if group::is_full(*self.ctrl(index)) {
+                elems_left -= 1;
+                ptr::drop_in_place(self.bucket(index));
+            }
+        }
     }
+}
 
-    #[inline]
-    pub fn hash(&self) -> SafeHash {
-        unsafe { SafeHash { hash: *self.raw.hash() } }
+impl<K, V, M: Deref<Target = RawTable<K, V>>> FullBucket<K, V, M> {
+    /// Creates a view of the full bucket at `idx`, such as returned by
+    /// `RawTable::find` or `RawTable::insert`.
+    pub fn at_index(table: M, idx: usize) -> FullBucket<K, V, M> {
+        debug_assert!(idx < table.capacity());
+        debug_assert!(group::is_full(unsafe { *table.ctrl(idx) }));
+        FullBucket {
+            idx,
+            table,
+            marker: marker::PhantomData,
+        }
     }
 
     /// Gets references to the key and value at a given index.
     pub fn read(&self) -> (&K, &V) {
         unsafe {
-            let pair_ptr = self.raw.pair();
+            let pair_ptr = self.table.bucket(self.idx);
             (&(*pair_ptr).0, &(*pair_ptr).1)
         }
     }
 }
 
 // We take a mutable reference to the table instead of accepting anything that
-// implements `DerefMut` to prevent fn `take` from being called on `stash`ed
-// buckets.
+// implements `DerefMut`, as the bucket is no longer full afterwards.
 impl<'t, K, V> FullBucket<K, V, &'t mut RawTable<K, V>> {
-    /// Removes this bucket's key and value from the hashtable.
-    ///
-    /// This works similarly to `put`, building an `EmptyBucket` out of the
-    /// taken bucket.
-    pub fn take(self) -> (EmptyBucket<K, V, &'t mut RawTable<K, V>>, K, V) {
-        self.table.size -= 1;
-
+    /// Removes this bucket's key and value from the hashtable, handing back
+    /// the reference to the table.
+    pub fn take(self) -> (K, V, &'t mut RawTable<K, V>) {
         unsafe {
-            *self.raw.hash() = EMPTY_BUCKET;
-            let (k, v) = ptr::read(self.raw.pair());
-            (EmptyBucket {
-                 raw: self.raw,
-                 table: self.table,
-             },
-            k,
-            v)
+            self.table.erase(self.idx);
+            let (k, v) = ptr::read(self.table.bucket(self.idx));
+            (k, v, self.table)
         }
     }
 
@@ -579,36 +547,19 @@ impl<'t, K, V> FullBucket<K, V, &'t mut RawTable<K, V>> {
     /// Only used for inplacement insertion.
     /// NOTE: `Value` is uninitialized when this function is called, don't try to drop the `Value`.
     pub unsafe fn remove_key(&mut self) {
-        self.table.size -= 1;
-
-        *self.raw.hash() = EMPTY_BUCKET;
-        let pair_ptr = self.raw.pair();
+        self.table.erase(self.idx);
+        let pair_ptr = self.table.bucket(self.idx);
         ptr::drop_in_place(&mut (*pair_ptr).0); // only drop key
     }
 }
 
-// This use of `Put` is misleading and restrictive, but safe and sufficient for our use cases
-// where `M` is a full bucket or table reference type with mutable access to the table.
-impl<K, V, M> FullBucket<K, V, M>
-    where M: Put<K, V>
-{
-    pub fn replace(&mut self, h: SafeHash, k: K, v: V) -> (SafeHash, K, V) {
-        unsafe {
-            let old_hash = ptr::replace(self.raw.hash() as *mut SafeHash, h);
-            let (old_key, old_val) = ptr::replace(self.raw.pair(), (k, v));
-
-            (old_hash, old_key, old_val)
-        }
-    }
-}
-
 impl<K, V, M> FullBucket<K, V, M>
     where M: Deref<Target = RawTable<K, V>> + DerefMut
 {
     /// Gets mutable references to the key and value at a given index.
     pub fn read_mut(&mut self) -> (&mut K, &mut V) {
         unsafe {
-            let pair_ptr = self.raw.pair();
+            let pair_ptr = self.table.bucket(self.idx);
             (&mut (*pair_ptr).0, &mut (*pair_ptr).1)
         }
     }
@@ -624,7 +575,7 @@ impl<'t, K, V, M> FullBucket<K, V, M>
     /// than the references returned by `read()`.
     pub fn into_refs(self) -> (&'t K, &'t V) {
         unsafe {
-            let pair_ptr = self.raw.pair();
+            let pair_ptr = self.table.bucket(self.idx);
             (&(*pair_ptr).0, &(*pair_ptr).1)
         }
     }
@@ -637,318 +588,77 @@ impl<'t, K, V, M> FullBucket<K, V, M>
     /// for mutable references into the table.
     pub fn into_mut_refs(self) -> (&'t mut K, &'t mut V) {
         unsafe {
-            let pair_ptr = self.raw.pair();
+            let pair_ptr = self.table.bucket(self.idx);
             (&mut (*pair_ptr).0, &mut (*pair_ptr).1)
         }
     }
 }
 
-impl<K, V, M> GapThenFull<K, V, M>
-    where M: Deref<Target = RawTable<K, V>>
-{
-    #[inline]
-    pub fn full(&self) -> &FullBucket<K, V, M> {
-        &self.full
-    }
-
-    pub fn into_table(self) -> M {
-        self.full.into_table()
-    }
-
-    pub fn shift(mut self) -> Result<GapThenFull<K, V, M>, Bucket<K, V, M>> {
-        unsafe {
-            let (gap_hash, gap_pair) = self.gap.raw.hash_pair();
-            let (full_hash, full_pair) = self.full.raw.hash_pair();
-            *gap_hash = mem::replace(&mut *full_hash, EMPTY_BUCKET);
-            ptr::copy_nonoverlapping(full_pair, gap_pair, 1);
-        }
-
-        let FullBucket { raw: prev_raw, .. } = self.full;
-
-        match self.full.next().peek() {
-            Full(bucket) => {
-                self.gap.raw = prev_raw;
-
-                self.full = bucket;
-
-                Ok(self)
-            }
-            Empty(b) => Err(b.into_bucket()),
-        }
-    }
-}
-
-
-/// Rounds up to a multiple of a power of two. Returns the closest multiple
-/// of `target_alignment` that is higher or equal to `unrounded`.
-///
-/// # Panics
+/// A raw iterator over the indices of the full buckets of a table. The basis
+/// for the other iterators in this module. Although this interface is safe,
+/// it's not used outside this module.
 ///
-/// Panics if `target_alignment` is not a power of two.
-#[inline]
-fn round_up_to_next(unrounded: usize, target_alignment: usize) -> usize {
-    assert!(target_alignment.is_power_of_two());
-    (unrounded + target_alignment - 1) & !(target_alignment - 1)
-}
-
-#[test]
-fn test_rounding() {
-    assert_eq!(round_up_to_next(0, 4), 0);
-    assert_eq!(round_up_to_next(1, 4), 4);
-    assert_eq!(round_up_to_next(2, 4), 4);
-    assert_eq!(round_up_to_next(3, 4), 4);
-    assert_eq!(round_up_to_next(4, 4), 4);
-    assert_eq!(round_up_to_next(5, 4), 8);
-}
-
-// Returns a tuple of (pairs_offset, end_of_pairs_offset),
-// from the start of a mallocated array.
-#[inline]
-fn calculate_offsets(hashes_size: usize,
-                     pairs_size: usize,
-                     pairs_align: usize)
-                     -> (usize, usize, bool) {
-    let pairs_offset = round_up_to_next(hashes_size, pairs_align);
-    let (end_of_pairs, oflo) = pairs_offset.overflowing_add(pairs_size);
-
-    (pairs_offset, end_of_pairs, oflo)
-}
-
-// Returns a tuple of (minimum required malloc alignment,
-// array_size), from the start of a mallocated array.
-fn calculate_allocation(hash_size: usize,
-                        hash_align: usize,
-                        pairs_size: usize,
-                        pairs_align: usize)
-                        -> (usize, usize, bool) {
-    let (_, end_of_pairs, oflo) = calculate_offsets(hash_size, pairs_size, pairs_align);
-
-    let align = cmp::max(hash_align, pairs_align);
-
-    (align, end_of_pairs, oflo)
-}
-
-#[test]
-fn test_offset_calculation() {
-    assert_eq!(calculate_allocation(128, 8, 16, 8), (8, 144, false));
-    assert_eq!(calculate_allocation(3, 1, 2, 1), (1, 5, false));
-    assert_eq!(calculate_allocation(6, 2, 12, 4), (4, 20, false));
-    assert_eq!(calculate_offsets(128, 15, 4), (128, 143, false));
-    assert_eq!(calculate_offsets(3, 2, 4), (4, 6, false));
-    assert_eq!(calculate_offsets(6, 12, 4), (8, 20, false));
-}
-
-impl<K, V> RawTable<K, V> {
-    /// Does not initialize the buckets. The caller should ensure they,
-    /// at the very least, set every hash to EMPTY_BUCKET.
-    unsafe fn new_uninitialized(capacity: usize) -> RawTable<K, V> {
-        if capacity == 0 {
-            return RawTable {
-                size: 0,
-                capacity_mask: capacity.wrapping_sub(1),
-                hashes: TaggedHashUintPtr::new(EMPTY as *mut HashUint),
-                marker: marker::PhantomData,
-            };
-        }
-
-        // No need for `checked_mul` before a more restrictive check performed
-        // later in this method.
-        let hashes_size = capacity.wrapping_mul(size_of::<HashUint>());
-        let pairs_size = capacity.wrapping_mul(size_of::<(K, V)>());
-
-        // Allocating hashmaps is a little tricky. We need to allocate two
-        // arrays, but since we know their sizes and alignments up front,
-        // we just allocate a single array, and then have the subarrays
-        // point into it.
-        //
-        // This is great in theory, but in practice getting the alignment
-        // right is a little subtle. Therefore, calculating offsets has been
-        // factored out into a different function.
-        let (alignment, size, oflo) = calculate_allocation(hashes_size,
-                                                           align_of::<HashUint>(),
-                                                           pairs_size,
-                                                           align_of::<(K, V)>());
-        assert!(!oflo, "capacity overflow");
-
-        // One check for overflow that covers calculation and rounding of size.
-        let size_of_bucket = size_of::<HashUint>().checked_add(size_of::<(K, V)>()).unwrap();
-        assert!(size >=
-                capacity.checked_mul(size_of_bucket)
-                    .expect("capacity overflow"),
-                "capacity overflow");
-
-        let buffer = Heap.alloc(Layout::from_size_align(size, alignment).unwrap())
-            .unwrap_or_else(|e| Heap.oom(e));
-
-        let hashes = buffer as *mut HashUint;
-
-        RawTable {
-            capacity_mask: capacity.wrapping_sub(1),
-            size: 0,
-            hashes: TaggedHashUintPtr::new(hashes),
-            marker: marker::PhantomData,
-        }
-    }
-
-    fn raw_bucket_at(&self, index: usize) -> RawBucket<K, V> {
-        let hashes_size = self.capacity() * size_of::<HashUint>();
-        let pairs_size = self.capacity() * size_of::<(K, V)>();
-
-        let (pairs_offset, _, oflo) =
-            calculate_offsets(hashes_size, pairs_size, align_of::<(K, V)>());
-        debug_assert!(!oflo, "capacity overflow");
-
-        let buffer = self.hashes.ptr() as *mut u8;
-        unsafe {
-            RawBucket {
-                hash_start: buffer as *mut HashUint,
-                pair_start: buffer.offset(pairs_offset as isize) as *const (K, V),
-                idx: index,
-                _marker: marker::PhantomData,
-            }
-        }
-    }
-
-    /// Creates a new raw table from a given capacity. All buckets are
-    /// initially empty.
-    pub fn new(capacity: usize) -> RawTable<K, V> {
-        unsafe {
-            let ret = RawTable::new_uninitialized(capacity);
-            ptr::write_bytes(ret.hashes.ptr(), 0, capacity);
-            ret
-        }
-    }
-
-    /// The hashtable's capacity, similar to a vector's.
-    pub fn capacity(&self) -> usize {
-        self.capacity_mask.wrapping_add(1)
-    }
-
-    /// The number of elements ever `put` in the hashtable, minus the number
-    /// of elements ever `take`n.
-    pub fn size(&self) -> usize {
-        self.size
-    }
-
-    fn raw_buckets(&self) -> RawBuckets<K, V> {
-        RawBuckets {
-            raw: self.raw_bucket_at(0),
-            elems_left: self.size,
-            marker: marker::PhantomData,
-        }
-    }
-
-    pub fn iter(&self) -> Iter<K, V> {
-        Iter {
-            iter: self.raw_buckets(),
-        }
-    }
-
-    pub fn iter_mut(&mut self) -> IterMut<K, V> {
-        IterMut {
-            iter: self.raw_buckets(),
-            _marker: marker::PhantomData,
-        }
-    }
-
-    pub fn into_iter(self) -> IntoIter<K, V> {
-        let RawBuckets { raw, elems_left, .. } = self.raw_buckets();
-        // Replace the marker regardless of lifetime bounds on parameters.
-        IntoIter {
-            iter: RawBuckets {
-                raw,
-                elems_left,
-                marker: marker::PhantomData,
-            },
-            table: self,
-        }
-    }
-
-    pub fn drain(&mut self) -> Drain<K, V> {
-        let RawBuckets { raw, elems_left, .. } = self.raw_buckets();
-        // Replace the marker regardless of lifetime bounds on parameters.
-        Drain {
-            iter: RawBuckets {
-                raw,
-                elems_left,
-                marker: marker::PhantomData,
-            },
-            table: NonNull::from(self),
-            marker: marker::PhantomData,
-        }
-    }
-
-    /// Drops buckets in reverse order. It leaves the table in an inconsistent
-    /// state and should only be used for dropping the table's remaining
-    /// entries. It's used in the implementation of Drop.
-    unsafe fn rev_drop_buckets(&mut self) {
-        // initialize the raw bucket past the end of the table
-        let mut raw = self.raw_bucket_at(self.capacity());
-        let mut elems_left = self.size;
-
-        while elems_left != 0 {
-            raw.idx -= 1;
-
-            if *raw.hash() != EMPTY_BUCKET {
-                elems_left -= 1;
-                ptr::drop_in_place(raw.pair());
-            }
-        }
-    }
-
-    /// Set the table tag
-    pub fn set_tag(&mut self, value: bool) {
-        self.hashes.set_tag(value)
-    }
-
-    /// Get the table tag
-    pub fn tag(&self) -> bool {
-        self.hashes.tag()
-    }
-}
-
-/// A raw iterator. The basis for some other iterators in this module. Although
-/// this interface is safe, it's not used outside this module.
-struct RawBuckets<'a, K, V> {
-    raw: RawBucket<K, V>,
+/// The control bytes are scanned a group at a time, in increasing order.
+struct RawIter<'a, K, V> {
+    data: *const (K, V),
+    ctrl: *const u8,
+    // Index of the first bucket of the group in `current_group`.
+    group_base: usize,
+    // The full buckets of the current group which have not been yielded yet.
+    current_group: BitMask,
     elems_left: usize,
 
     // Strictly speaking, this should be &'a (K,V), but that would
-    // require that K:'a, and we often use RawBuckets<'static...> for
+    // require that K:'a, and we often use RawIter<'static...> for
     // move iterations, so that messes up a lot of other things. So
     // just use `&'a (K,V)` as this is not a publicly exposed type
     // anyway.
     marker: marker::PhantomData<&'a ()>,
 }
 
+impl<'a, K, V> RawIter<'a, K, V> {
+    #[inline]
+    unsafe fn bucket(&self, index: usize) -> *mut (K, V) {
+        self.data.offset(index as isize) as *mut (K, V)
+    }
+}
+
 // FIXME(#26925) Remove in favor of `#[derive(Clone)]`
-impl<'a, K, V> Clone for RawBuckets<'a, K, V> {
-    fn clone(&self) -> RawBuckets<'a, K, V> {
-        RawBuckets {
-            raw: self.raw,
+impl<'a, K, V> Clone for RawIter<'a, K, V> {
+    fn clone(&self) -> RawIter<'a, K, V> {
+        RawIter {
+            data: self.data,
+            ctrl: self.ctrl,
+            group_base: self.group_base,
+            current_group: self.current_group,
             elems_left: self.elems_left,
             marker: marker::PhantomData,
         }
     }
 }
 
+impl<'a, K, V> Iterator for RawIter<'a, K, V> {
+    type Item = usize;
 
-impl<'a, K, V> Iterator for RawBuckets<'a, K, V> {
-    type Item = RawBucket<K, V>;
-
-    fn next(&mut self) -> Option<RawBucket<K, V>> {
+    #[inline]
+    fn next(&mut self) -> Option<usize> {
         if self.elems_left == 0 {
             return None;
         }
 
         loop {
+            if let Some(bit) = self.current_group.lowest_set_bit() {
+                self.current_group = self.current_group.remove_lowest_bit();
+                self.elems_left -= 1;
+                return Some(self.group_base + bit);
+            }
+
+            // There are more full buckets left, so the next group is still
+            // inside the table.
+            self.group_base += Group::WIDTH;
             unsafe {
-                let item = self.raw;
-                self.raw.idx += 1;
-                if *item.hash() != EMPTY_BUCKET {
-                    self.elems_left -= 1;
-                    return Some(item);
-                }
+                let ctrl = self.ctrl.offset(self.group_base as isize);
+                self.current_group = Group::load(ctrl).match_full();
             }
         }
     }
@@ -958,7 +668,7 @@ impl<'a, K, V> Iterator for RawBuckets<'a, K, V> {
     }
 }
 
-impl<'a, K, V> ExactSizeIterator for RawBuckets<'a, K, V> {
+impl<'a, K, V> ExactSizeIterator for RawIter<'a, K, V> {
     fn len(&self) -> usize {
         self.elems_left
     }
@@ -966,7 +676,7 @@ impl<'a, K, V> ExactSizeIterator for RawBuckets<'a, K, V> {
 
 /// Iterator over shared references to entries in a table.
 pub struct Iter<'a, K: 'a, V: 'a> {
-    iter: RawBuckets<'a, K, V>,
+    iter: RawIter<'a, K, V>,
 }
 
 unsafe impl<'a, K: Sync, V: Sync> Sync for Iter<'a, K, V> {}
@@ -983,7 +693,7 @@ impl<'a, K, V> Clone for Iter<'a, K, V> {
 
 /// Iterator over mutable references to entries in a table.
 pub struct IterMut<'a, K: 'a, V: 'a> {
-    iter: RawBuckets<'a, K, V>,
+    iter: RawIter<'a, K, V>,
     // To ensure invariance with respect to V
     _marker: marker::PhantomData<&'a mut V>,
 }
@@ -1004,7 +714,7 @@ impl<'a, K: 'a, V: 'a> IterMut<'a, K, V> {
 /// Iterator over the entries in a table, consuming the table.
 pub struct IntoIter<K, V> {
     table: RawTable<K, V>,
-    iter: RawBuckets<'static, K, V>,
+    iter: RawIter<'static, K, V>,
 }
 
 unsafe impl<K: Sync, V: Sync> Sync for IntoIter<K, V> {}
@@ -1021,7 +731,7 @@ impl<K, V> IntoIter<K, V> {
 /// Iterator over the entries in a table, clearing the table.
 pub struct Drain<'a, K: 'a, V: 'a> {
     table: NonNull<RawTable<K, V>>,
-    iter: RawBuckets<'static, K, V>,
+    iter: RawIter<'static, K, V>,
     marker: marker::PhantomData<&'a RawTable<K, V>>,
 }
 
@@ -1040,8 +750,8 @@ impl<'a, K, V> Iterator for Iter<'a, K, V> {
     type Item = (&'a K, &'a V);
 
     fn next(&mut self) -> Option<(&'a K, &'a V)> {
-        self.iter.next().map(|raw| unsafe {
-            let pair_ptr = raw.pair();
+        self.iter.next().map(|index| unsafe {
+            let pair_ptr = self.iter.bucket(index);
             (&(*pair_ptr).0, &(*pair_ptr).1)
         })
     }
@@ -1061,8 +771,8 @@ impl<'a, K, V> Iterator for IterMut<'a, K, V> {
     type Item = (&'a K, &'a mut V);
 
     fn next(&mut self) -> Option<(&'a K, &'a mut V)> {
-        self.iter.next().map(|raw| unsafe {
-            let pair_ptr = raw.pair();
+        self.iter.next().map(|index| unsafe {
+            let pair_ptr = self.iter.bucket(index);
             (&(*pair_ptr).0, &mut (*pair_ptr).1)
         })
     }
@@ -1079,15 +789,14 @@ impl<'a, K, V> ExactSizeIterator for IterMut<'a, K, V> {
 }
 
 impl<K, V> Iterator for IntoIter<K, V> {
-    type Item = (SafeHash, K, V);
-
-    fn next(&mut self) -> Option<(SafeHash, K, V)> {
-        self.iter.next().map(|raw| {
-            self.table.size -= 1;
-            unsafe {
-                let (k, v) = ptr::read(raw.pair());
-                (SafeHash { hash: *raw.hash() }, k, v)
-            }
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<(K, V)> {
+        self.iter.next().map(|index| {
+            // The buckets are consumed in increasing order, which is what
+            // `rev_drop_buckets` relies on to drop the rest.
+            self.table.items -= 1;
+            unsafe { ptr::read(self.iter.bucket(index)) }
         })
     }
 
@@ -1103,15 +812,18 @@ impl<K, V> ExactSizeIterator for IntoIter<K, V> {
 }
 
 impl<'a, K, V> Iterator for Drain<'a, K, V> {
-    type Item = (SafeHash, K, V);
+    type Item = (K, V);
 
     #[inline]
-    fn next(&mut self) -> Option<(SafeHash, K, V)> {
-        self.iter.next().map(|raw| {
+    fn next(&mut self) -> Option<(K, V)> {
+        self.iter.next().map(|index| {
             unsafe {
-                self.table.as_mut().size -= 1;
-                let (k, v) = ptr::read(raw.pair());
-                (SafeHash { hash: ptr::replace(&mut *raw.hash(), EMPTY_BUCKET) }, k, v)
+                // Leave a tombstone so that the remaining elements can still
+                // be found if the drain is leaked halfway.
+                let table = self.table.as_mut();
+                table.set_ctrl(index, DELETED);
+                table.items -= 1;
+                ptr::read(self.iter.bucket(index))
             }
         })
     }
@@ -1129,40 +841,46 @@ impl<'a, K, V> ExactSizeIterator for Drain<'a, K, V> {
 
 impl<'a, K: 'a, V: 'a> Drop for Drain<'a, K, V> {
     fn drop(&mut self) {
-        for _ in self {}
+        for _ in &mut *self {}
+        unsafe {
+            self.table.as_mut().clear_no_drop();
+        }
     }
 }
 
 impl<K: Clone, V: Clone> Clone for RawTable<K, V> {
     fn clone(&self) -> RawTable<K, V> {
+        let mut new_ht = RawTable::new(self.capacity());
+        if self.is_empty_singleton() {
+            return new_ht;
+        }
+
         unsafe {
-            let cap = self.capacity();
-            let mut new_ht = RawTable::new_uninitialized(cap);
-
-            let mut new_buckets = new_ht.raw_bucket_at(0);
-            let mut buckets = self.raw_bucket_at(0);
-            while buckets.idx < cap {
-                *new_buckets.hash() = *buckets.hash();
-                if *new_buckets.hash() != EMPTY_BUCKET {
-                    let pair_ptr = buckets.pair();
-                    let kv = ((*pair_ptr).0.clone(), (*pair_ptr).1.clone());
-                    ptr::write(new_buckets.pair(), kv);
-                }
-                buckets.idx += 1;
-                new_buckets.idx += 1;
+            // Mark each bucket as full only after its pair has been cloned,
+            // so that a panicking `clone` leaves a table that can be dropped.
+            for index in self.raw_iter() {
+                let pair_ptr = self.bucket(index);
+                let kv = ((*pair_ptr).0.clone(), (*pair_ptr).1.clone());
+                ptr::write(new_ht.bucket(index), kv);
+                new_ht.set_ctrl(index, *self.ctrl(index));
+                new_ht.items += 1;
             }
 
-            new_ht.size = self.size();
-            new_ht.set_tag(self.tag());
-
-            new_ht
+            // Copy the `DELETED` markers too, as the probe sequences of the
+            // elements may pass over them.
+            ptr::copy_nonoverlapping(self.ctrl.as_ptr(),
+                                     new_ht.ctrl.as_ptr(),
+                                     self.num_ctrl_bytes());
+            new_ht.growth_left = self.growth_left;
         }
+
+        new_ht
     }
 }
 
 unsafe impl<#[may_dangle] K, #[may_dangle] V> Drop for RawTable<K, V> {
     fn drop(&mut self) {
-        if self.capacity() == 0 {
+        if self.is_empty_singleton() {
             return;
         }
 
@@ -1178,18 +896,11 @@ unsafe impl<#[may_dangle] K, #[may_dangle] V> Drop for RawTable<K, V> {
             }
         }
 
-        let hashes_size = self.capacity() * size_of::<HashUint>();
-        let pairs_size = self.capacity() * size_of::<(K, V)>();
-        let (align, size, oflo) = calculate_allocation(hashes_size,
-                                                       align_of::<HashUint>(),
-                                                       pairs_size,
-                                                       align_of::<(K, V)>());
-
-        debug_assert!(!oflo, "should be impossible");
+        let (layout, _) = calculate_layout::<K, V>(self.capacity())
+            .expect("should be impossible");
 
         unsafe {
-            Heap.dealloc(self.hashes.ptr() as *mut u8,
-                         Layout::from_size_align(size, align).unwrap());
+            Heap.dealloc(self.data.as_ptr() as *mut u8, layout);
             // Remember how everything was allocated out of one buffer
             // during initialization? We only need one call to free here.
         }
@@ -18,8 +18,12 @@
 //! counterpart ending in `os`. Those ending in `os` will return an [`OsString`]
 //! and those without will be returning a [`String`].
 //!
+//! The arguments of the process can be parsed into flags, options taking a
+//! value, and free arguments with [`Options`].
+//!
 //! [`OsString`]: ../../std/ffi/struct.OsString.html
 //! [`String`]: ../string/struct.String.html
+//! [`Options`]: struct.Options.html
 
 #![stable(feature = "env", since = "1.0.0")]
 
@@ -31,6 +35,11 @@ use path::{Path, PathBuf};
 use sys;
 use sys::os as os_imp;
 
+#[unstable(feature = "env_options", issue = "0")]
+pub use self::options::{ArgsError, ArgsErrorKind, Matches, Options};
+
+mod options;
+
 /// Returns the current working directory as a [`PathBuf`].
 ///
 /// # Errors
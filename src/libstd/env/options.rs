@@ -0,0 +1,801 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A small parser for command-line arguments.
+//!
+//! Arguments are parsed as `OsStr`s, so values which aren't valid Unicode,
+//! such as file names, make it through unchanged. Option names are always
+//! ASCII or at least valid Unicode, so arguments are split right after them.
+//! Splitting the platform encoding of an `OsStr` at such a point yields
+//! valid `OsStr`s again, on Windows just like on Unix.
+
+use error::Error;
+use ffi::{OsStr, OsString};
+use fmt;
+use io::{self, Write};
+use process;
+use str::{self, FromStr};
+
+use super::args_os;
+
+fn os_str_as_u8_slice(s: &OsStr) -> &[u8] {
+    unsafe { &*(s as *const OsStr as *const [u8]) }
+}
+
+unsafe fn u8_slice_as_os_str(s: &[u8]) -> &OsStr {
+    &*(s as *const [u8] as *const OsStr)
+}
+
+/// A description of the command-line options of a program, which parses
+/// arguments into [`Matches`].
+///
+/// Options are defined as either flags, or options taking a value. Each of
+/// them has a short name of one character, used as in `-v`, a long name,
+/// used as in `--verbose`, or both. Options may be given any number of
+/// times, and anywhere between the free arguments.
+///
+/// The arguments are parsed as follows:
+///
+/// * `--name` is a long option. Its value is given either as in
+///   `--name=value`, or as the next argument.
+/// * `-abc` is a cluster of short options. An option taking a value takes
+///   the rest of the cluster as its value, or the next argument if nothing
+///   is left, so `-j4` and `-j 4` are the same.
+/// * `--` ends the options. All of the arguments after it are free
+///   arguments, even if they start with `-`.
+/// * `-` on its own and everything else which doesn't start with `-` is a
+///   free argument.
+///
+/// Unless the names are taken by other options, `-h` and `--help` request
+/// help. The help text is generated by [`usage`] from the descriptions of
+/// the options.
+///
+/// [`Matches`]: struct.Matches.html
+/// [`usage`]: #method.usage
+///
+/// # Examples
+///
+/// ```
+/// #![feature(env_options)]
+///
+/// use std::env::Options;
+/// use std::ffi::{OsStr, OsString};
+///
+/// let mut opts = Options::new();
+/// opts.flag("v", "verbose", "print more output")
+///     .option("j", "jobs", "N", "run N jobs in parallel")
+///     .option("o", "output", "FILE", "write the output to FILE");
+///
+/// let matches = opts.parse(&["-vj4", "input.txt", "--output=out.txt"]).unwrap();
+/// assert!(matches.flag("verbose"));
+/// assert_eq!(matches.value::<u32>("jobs"), Ok(Some(4)));
+/// assert_eq!(matches.value_os("o"), Some(OsStr::new("out.txt")));
+/// assert_eq!(matches.free(), &[OsString::from("input.txt")]);
+///
+/// let matches = opts.parse(&["--jobs", "many"]).unwrap();
+/// let err = matches.value::<u32>("jobs").unwrap_err();
+/// assert_eq!(err.to_string(),
+///            "invalid value 'many' for option '--jobs': invalid digit found in string");
+/// ```
+#[unstable(feature = "env_options", issue = "0")]
+#[derive(Clone, Debug, Default)]
+pub struct Options {
+    opts: Vec<Opt>,
+}
+
+#[derive(Clone, Debug)]
+struct Opt {
+    short: Option<char>,
+    long: Option<String>,
+    // The name of the value in the help text, for options which take one.
+    hint: Option<String>,
+    desc: String,
+}
+
+impl Opt {
+    fn is_named(&self, name: &str) -> bool {
+        let mut chars = name.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) if self.short == Some(c) => true,
+            _ => self.long.as_ref().map_or(false, |long| long == name),
+        }
+    }
+
+    fn display_name(&self) -> String {
+        match self.long {
+            Some(ref long) => format!("--{}", long),
+            None => format!("-{}", self.short.unwrap()),
+        }
+    }
+
+    fn usage_names(&self) -> String {
+        let mut names = match (self.short, &self.long) {
+            (Some(short), &Some(ref long)) => format!("-{}, --{}", short, long),
+            (None, &Some(ref long)) => format!("    --{}", long),
+            (Some(short), &None) => format!("-{}", short),
+            (None, &None) => unreachable!(),
+        };
+        if let Some(ref hint) = self.hint {
+            names.push(' ');
+            names.push_str(hint);
+        }
+        names
+    }
+}
+
+impl Options {
+    /// Creates a new set of options, which only has the options requesting
+    /// help.
+    #[unstable(feature = "env_options", issue = "0")]
+    pub fn new() -> Options {
+        Options { opts: Vec::new() }
+    }
+
+    /// Adds a flag, an option which doesn't take a value.
+    ///
+    /// Either `short` or `long` may be empty if the flag has no name of that
+    /// kind.
+    ///
+    /// # Panics
+    ///
+    /// Panics if both names are empty, if `short` is longer than one
+    /// character, if a name starts with `-` or contains `=`, or if another
+    /// option already has one of the names.
+    #[unstable(feature = "env_options", issue = "0")]
+    pub fn flag(&mut self, short: &str, long: &str, desc: &str) -> &mut Options {
+        self.add(short, long, None, desc)
+    }
+
+    /// Adds an option which takes a value. The value is referred to as
+    /// `hint` in the help text.
+    ///
+    /// Either `short` or `long` may be empty if the option has no name of
+    /// that kind.
+    ///
+    /// # Panics
+    ///
+    /// Panics if both names are empty, if `short` is longer than one
+    /// character, if a name starts with `-` or contains `=`, or if another
+    /// option already has one of the names.
+    #[unstable(feature = "env_options", issue = "0")]
+    pub fn option(&mut self, short: &str, long: &str, hint: &str, desc: &str) -> &mut Options {
+        self.add(short, long, Some(hint), desc)
+    }
+
+    fn add(&mut self, short: &str, long: &str, hint: Option<&str>, desc: &str) -> &mut Options {
+        assert!(!short.is_empty() || !long.is_empty(), "an option needs a name");
+        assert!(short.chars().count() <= 1,
+                "short option name `{}` is longer than one character", short);
+        for name in &[short, long] {
+            assert!(!name.starts_with('-') && !name.contains('='),
+                    "invalid option name `{}`", name);
+            assert!(name.is_empty() || !self.opts.iter().any(|opt| opt.is_named(name)),
+                    "option `{}` is defined twice", name);
+        }
+        self.opts.push(Opt {
+            short: short.chars().next(),
+            long: if long.is_empty() { None } else { Some(long.to_string()) },
+            hint: hint.map(|hint| hint.to_string()),
+            desc: desc.to_string(),
+        });
+        self
+    }
+
+    fn find_short(&self, c: char) -> Option<usize> {
+        self.opts.iter().position(|opt| opt.short == Some(c))
+    }
+
+    fn find_long(&self, name: &str) -> Option<usize> {
+        self.opts.iter().position(|opt| opt.long.as_ref().map_or(false, |long| long == name))
+    }
+
+    fn short_help(&self) -> bool {
+        self.find_short('h').is_none()
+    }
+
+    fn long_help(&self) -> bool {
+        self.find_long("help").is_none()
+    }
+
+    /// Parses `args` according to the options.
+    ///
+    /// The arguments shouldn't include the name of the program, so the
+    /// arguments of the current process are parsed with
+    /// `opts.parse(env::args_os().skip(1))`. See [`parse_env`] for a function
+    /// which also deals with help and errors.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an argument names an unknown option, if an option
+    /// is missing its value, or if a flag is given a value. Once help has
+    /// been requested, errors in the arguments are ignored, and the returned
+    /// [`Matches`] only report that help has been requested.
+    ///
+    /// [`parse_env`]: #method.parse_env
+    /// [`Matches`]: struct.Matches.html
+    #[unstable(feature = "env_options", issue = "0")]
+    pub fn parse<I>(&self, args: I) -> Result<Matches, ArgsError>
+        where I: IntoIterator, I::Item: AsRef<OsStr>
+    {
+        let mut help = false;
+        match self.parse_inner(args.into_iter(), &mut help) {
+            Err(_) if help => Ok(Matches {
+                opts: self.opts.clone(),
+                values: vec![Vec::new(); self.opts.len()],
+                free: Vec::new(),
+                help,
+            }),
+            result => result,
+        }
+    }
+
+    fn parse_inner<I>(&self, mut args: I, help: &mut bool) -> Result<Matches, ArgsError>
+        where I: Iterator, I::Item: AsRef<OsStr>
+    {
+        let mut values = vec![Vec::new(); self.opts.len()];
+        let mut free = Vec::new();
+        let mut only_free = false;
+
+        while let Some(arg) = args.next() {
+            let arg = arg.as_ref();
+            let bytes = os_str_as_u8_slice(arg);
+            if only_free || bytes == b"-" || !bytes.starts_with(b"-") {
+                free.push(arg.to_os_string());
+            } else if bytes == b"--" {
+                only_free = true;
+            } else if bytes.starts_with(b"--") {
+                let body = &bytes[2..];
+                let (name, inline) = match body.iter().position(|&b| b == b'=') {
+                    Some(i) => (&body[..i], Some(&body[i + 1..])),
+                    None => (body, None),
+                };
+                let given = format!("--{}", String::from_utf8_lossy(name));
+                let index = match str::from_utf8(name).ok().and_then(|n| self.find_long(n)) {
+                    Some(index) => index,
+                    None if name == b"help" && self.long_help() => {
+                        *help = true;
+                        continue;
+                    }
+                    None => return Err(ArgsError::new(ArgsErrorKind::UnrecognizedOption, given)),
+                };
+                let value = match (&self.opts[index].hint, inline) {
+                    (&Some(_), Some(value)) => {
+                        Some(unsafe { u8_slice_as_os_str(value) }.to_os_string())
+                    }
+                    (&Some(_), None) => match args.next() {
+                        Some(value) => Some(value.as_ref().to_os_string()),
+                        None => return Err(ArgsError::new(ArgsErrorKind::MissingValue, given)),
+                    },
+                    (&None, Some(_)) => {
+                        return Err(ArgsError::new(ArgsErrorKind::UnexpectedValue, given))
+                    }
+                    (&None, None) => None,
+                };
+                values[index].push(value);
+            } else {
+                let body = &bytes[1..];
+                let valid = match str::from_utf8(body) {
+                    Ok(valid) => valid,
+                    Err(e) => unsafe { str::from_utf8_unchecked(&body[..e.valid_up_to()]) },
+                };
+                // The part of the cluster which is left to look at.
+                let mut rest = body;
+                for (pos, c) in valid.char_indices() {
+                    rest = &body[pos + c.len_utf8()..];
+                    let given = format!("-{}", c);
+                    let index = match self.find_short(c) {
+                        Some(index) => index,
+                        None if c == 'h' && self.short_help() => {
+                            *help = true;
+                            continue;
+                        }
+                        None => {
+                            return Err(ArgsError::new(ArgsErrorKind::UnrecognizedOption, given))
+                        }
+                    };
+                    if self.opts[index].hint.is_none() {
+                        values[index].push(None);
+                        continue;
+                    }
+                    let value = if !rest.is_empty() {
+                        unsafe { u8_slice_as_os_str(rest) }.to_os_string()
+                    } else {
+                        match args.next() {
+                            Some(value) => value.as_ref().to_os_string(),
+                            None => {
+                                return Err(ArgsError::new(ArgsErrorKind::MissingValue, given))
+                            }
+                        }
+                    };
+                    values[index].push(Some(value));
+                    rest = &[];
+                    break;
+                }
+                if !rest.is_empty() {
+                    let given = format!("-{}", String::from_utf8_lossy(rest));
+                    return Err(ArgsError::new(ArgsErrorKind::UnrecognizedOption, given));
+                }
+            }
+        }
+
+        Ok(Matches {
+            opts: self.opts.clone(),
+            values,
+            free,
+            help: *help,
+        })
+    }
+
+    /// Returns the help text, which starts with `brief` and lists the
+    /// options with their descriptions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(env_options)]
+    ///
+    /// use std::env::Options;
+    ///
+    /// let mut opts = Options::new();
+    /// opts.flag("v", "verbose", "print more output")
+    ///     .option("o", "", "FILE", "write the output to FILE");
+    ///
+    /// assert_eq!(opts.usage("Usage: frob [OPTIONS] INPUT"),
+    ///            "Usage: frob [OPTIONS] INPUT\n\
+    ///             \n\
+    ///             Options:\n    \
+    ///             -v, --verbose  print more output\n    \
+    ///             -o FILE        write the output to FILE\n    \
+    ///             -h, --help     print this help message\n");
+    /// ```
+    #[unstable(feature = "env_options", issue = "0")]
+    pub fn usage(&self, brief: &str) -> String {
+        let mut rows: Vec<(String, &str)> = self.opts.iter()
+            .map(|opt| (opt.usage_names(), &opt.desc[..]))
+            .collect();
+        let help_names = match (self.short_help(), self.long_help()) {
+            (true, true) => Some("-h, --help"),
+            (false, true) => Some("    --help"),
+            (true, false) => Some("-h"),
+            (false, false) => None,
+        };
+        if let Some(names) = help_names {
+            rows.push((names.to_string(), "print this help message"));
+        }
+
+        let width = rows.iter().map(|&(ref names, _)| names.chars().count()).max().unwrap_or(0);
+        let mut usage = format!("{}\n\nOptions:\n", brief);
+        for (names, desc) in rows {
+            let line = format!("    {:2$}  {}", names, desc, width);
+            usage.push_str(line.trim_right());
+            usage.push('\n');
+        }
+        usage
+    }
+
+    /// Parses the arguments of the current process, and exits if that fails
+    /// or if help has been requested.
+    ///
+    /// The help text is the one returned by [`usage`], and it is printed to
+    /// the standard output before exiting with status 0. An error is printed
+    /// to the standard error, prefixed with the name of the program, before
+    /// exiting with status 2.
+    ///
+    /// [`usage`]: #method.usage
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(env_options)]
+    ///
+    /// use std::env::Options;
+    /// use std::process;
+    ///
+    /// let mut opts = Options::new();
+    /// opts.option("n", "count", "N", "print the greeting N times");
+    /// let matches = opts.parse_env("Usage: hello [-n N] NAME");
+    ///
+    /// let count = matches.value("count").unwrap_or_else(|e| {
+    ///     eprintln!("hello: {}", e);
+    ///     process::exit(2);
+    /// });
+    /// for name in matches.free() {
+    ///     for _ in 0..count.unwrap_or(1) {
+    ///         println!("Hello, {}!", name.to_string_lossy());
+    ///     }
+    /// }
+    /// ```
+    #[unstable(feature = "env_options", issue = "0")]
+    pub fn parse_env(&self, brief: &str) -> Matches {
+        let mut args = args_os();
+        let program = args.next()
+            .map(|program| program.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        match self.parse(args) {
+            Ok(matches) => {
+                if matches.help_requested() {
+                    print!("{}", self.usage(brief));
+                    let _ = io::stdout().flush();
+                    process::exit(0);
+                }
+                matches
+            }
+            Err(e) => {
+                eprintln!("{}: {}", program, e);
+                if self.long_help() {
+                    eprintln!("Try '{} --help' for more information.", program);
+                }
+                process::exit(2);
+            }
+        }
+    }
+}
+
+/// The result of parsing command-line arguments with [`Options`].
+///
+/// Options are looked up by either of their names, without the leading
+/// dashes.
+///
+/// # Panics
+///
+/// The methods looking up an option panic if no option has the name they are
+/// given.
+///
+/// [`Options`]: struct.Options.html
+#[unstable(feature = "env_options", issue = "0")]
+#[derive(Clone, Debug)]
+pub struct Matches {
+    opts: Vec<Opt>,
+    // Every occurrence of each option, with its value if it takes one.
+    values: Vec<Vec<Option<OsString>>>,
+    free: Vec<OsString>,
+    help: bool,
+}
+
+impl Matches {
+    fn index(&self, name: &str) -> usize {
+        match self.opts.iter().position(|opt| opt.is_named(name)) {
+            Some(index) => index,
+            None => panic!("no option `{}` is defined", name),
+        }
+    }
+
+    /// Returns whether the option has been given.
+    #[unstable(feature = "env_options", issue = "0")]
+    pub fn flag(&self, name: &str) -> bool {
+        self.count(name) > 0
+    }
+
+    /// Returns how many times the option has been given.
+    #[unstable(feature = "env_options", issue = "0")]
+    pub fn count(&self, name: &str) -> usize {
+        self.values[self.index(name)].len()
+    }
+
+    /// Returns the value the option has been given last, or `None` if it
+    /// hasn't been given at all.
+    #[unstable(feature = "env_options", issue = "0")]
+    pub fn value_os(&self, name: &str) -> Option<&OsStr> {
+        self.values[self.index(name)].last()
+            .and_then(|value| value.as_ref())
+            .map(|value| &**value)
+    }
+
+    /// Returns all of the values the option has been given, in order.
+    #[unstable(feature = "env_options", issue = "0")]
+    pub fn values_os(&self, name: &str) -> Vec<&OsStr> {
+        self.values[self.index(name)].iter()
+            .filter_map(|value| value.as_ref())
+            .map(|value| &**value)
+            .collect()
+    }
+
+    /// Parses the value the option has been given last, or returns `None` if
+    /// it hasn't been given at all.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error which names the option if the value isn't valid
+    /// Unicode, or if it can't be parsed into a `T`.
+    #[unstable(feature = "env_options", issue = "0")]
+    pub fn value<T>(&self, name: &str) -> Result<Option<T>, ArgsError>
+        where T: FromStr, T::Err: fmt::Display
+    {
+        let index = self.index(name);
+        match self.value_os(name) {
+            Some(value) => parse_value(&self.opts[index], value).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Parses all of the values the option has been given, in order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for the first value which isn't valid Unicode, or
+    /// which can't be parsed into a `T`.
+    #[unstable(feature = "env_options", issue = "0")]
+    pub fn values<T>(&self, name: &str) -> Result<Vec<T>, ArgsError>
+        where T: FromStr, T::Err: fmt::Display
+    {
+        let opt = &self.opts[self.index(name)];
+        self.values_os(name).into_iter().map(|value| parse_value(opt, value)).collect()
+    }
+
+    /// Returns the free arguments, which aren't options or their values.
+    #[unstable(feature = "env_options", issue = "0")]
+    pub fn free(&self) -> &[OsString] {
+        &self.free
+    }
+
+    /// Returns whether help has been requested with `-h` or `--help`.
+    #[unstable(feature = "env_options", issue = "0")]
+    pub fn help_requested(&self) -> bool {
+        self.help
+    }
+}
+
+fn parse_value<T>(opt: &Opt, value: &OsStr) -> Result<T, ArgsError>
+    where T: FromStr, T::Err: fmt::Display
+{
+    let value = match value.to_str() {
+        Some(value) => value,
+        None => return Err(ArgsError::new(ArgsErrorKind::InvalidUnicode, opt.display_name())),
+    };
+    value.parse().map_err(|e: T::Err| ArgsError {
+        kind: ArgsErrorKind::InvalidValue,
+        option: opt.display_name(),
+        invalid: Some((value.to_string(), e.to_string())),
+    })
+}
+
+/// An error which can be returned when parsing command-line arguments, or
+/// the values of options.
+#[unstable(feature = "env_options", issue = "0")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ArgsError {
+    kind: ArgsErrorKind,
+    option: String,
+    // The value and the reason why it couldn't be parsed.
+    invalid: Option<(String, String)>,
+}
+
+/// The kinds of errors which can occur when parsing command-line arguments.
+#[unstable(feature = "env_options", issue = "0")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ArgsErrorKind {
+    /// An argument named an option which isn't defined.
+    UnrecognizedOption,
+    /// An option which takes a value was given as the last argument, without
+    /// its value.
+    MissingValue,
+    /// A value was given to a flag, as in `--verbose=yes`.
+    UnexpectedValue,
+    /// The value of an option isn't valid Unicode.
+    InvalidUnicode,
+    /// The value of an option couldn't be parsed.
+    InvalidValue,
+
+    #[doc(hidden)]
+    __Nonexhaustive,
+}
+
+impl ArgsError {
+    fn new(kind: ArgsErrorKind, option: String) -> ArgsError {
+        ArgsError { kind, option, invalid: None }
+    }
+
+    /// Returns the kind of the error.
+    #[unstable(feature = "env_options", issue = "0")]
+    pub fn kind(&self) -> ArgsErrorKind {
+        self.kind
+    }
+
+    /// Returns the option the error is about, with its leading dashes, such
+    /// as `--output`.
+    #[unstable(feature = "env_options", issue = "0")]
+    pub fn option(&self) -> &str {
+        &self.option
+    }
+}
+
+#[unstable(feature = "env_options", issue = "0")]
+impl fmt::Display for ArgsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.invalid {
+            Some((ref value, ref reason)) => {
+                write!(f, "invalid value '{}' for option '{}': {}", value, self.option, reason)
+            }
+            None => match self.kind {
+                ArgsErrorKind::UnrecognizedOption => {
+                    write!(f, "unrecognized option '{}'", self.option)
+                }
+                ArgsErrorKind::MissingValue => {
+                    write!(f, "option '{}' requires a value", self.option)
+                }
+                ArgsErrorKind::UnexpectedValue => {
+                    write!(f, "option '{}' doesn't take a value", self.option)
+                }
+                ArgsErrorKind::InvalidUnicode => {
+                    write!(f, "the value of option '{}' is not valid unicode", self.option)
+                }
+                ArgsErrorKind::InvalidValue |
+                ArgsErrorKind::__Nonexhaustive => unreachable!(),
+            },
+        }
+    }
+}
+
+#[unstable(feature = "env_options", issue = "0")]
+impl Error for ArgsError {
+    fn description(&self) -> &str {
+        match self.kind {
+            ArgsErrorKind::UnrecognizedOption => "unrecognized option",
+            ArgsErrorKind::MissingValue => "option requires a value",
+            ArgsErrorKind::UnexpectedValue => "option doesn't take a value",
+            ArgsErrorKind::InvalidUnicode => "option value is not valid unicode",
+            ArgsErrorKind::InvalidValue => "invalid option value",
+            ArgsErrorKind::__Nonexhaustive => unreachable!(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ArgsErrorKind, Options};
+    use ffi::{OsStr, OsString};
+
+    fn opts() -> Options {
+        let mut opts = Options::new();
+        opts.flag("v", "verbose", "print more output")
+            .flag("q", "", "print less output")
+            .option("o", "output", "FILE", "write the output to FILE")
+            .option("", "level", "N", "set the level");
+        opts
+    }
+
+    fn free(args: &[&str]) -> Vec<OsString> {
+        args.iter().map(OsString::from).collect()
+    }
+
+    #[test]
+    fn long_options() {
+        let m = opts().parse(&["--verbose", "--output", "a", "--level=3", "--verbose"]).unwrap();
+        assert_eq!(m.count("verbose"), 2);
+        assert!(m.flag("v"));
+        assert!(!m.flag("q"));
+        assert_eq!(m.value_os("output"), Some(OsStr::new("a")));
+        assert_eq!(m.value::<u8>("level"), Ok(Some(3)));
+        assert!(m.free().is_empty());
+        assert!(!m.help_requested());
+
+        let m = opts().parse(&["--output=", "--output=b=c"]).unwrap();
+        assert_eq!(m.values_os("o"), [OsStr::new(""), OsStr::new("b=c")]);
+        assert_eq!(m.value_os("o"), Some(OsStr::new("b=c")));
+        assert_eq!(m.value::<u8>("level"), Ok(None));
+    }
+
+    #[test]
+    fn short_options() {
+        let m = opts().parse(&["-vqv", "-ofile", "-vo", "other", "-qo-v"]).unwrap();
+        assert_eq!(m.count("v"), 3);
+        assert_eq!(m.count("q"), 2);
+        assert_eq!(m.values_os("output"), [OsStr::new("file"), OsStr::new("other"),
+                                            OsStr::new("-v")]);
+        assert!(m.free().is_empty());
+    }
+
+    #[test]
+    fn free_arguments() {
+        let m = opts().parse(&["a", "-v", "-", "b", "--", "-q", "--", "--level"]).unwrap();
+        assert!(m.flag("v"));
+        assert!(!m.flag("q"));
+        assert_eq!(m.free(), &free(&["a", "-", "b", "-q", "--", "--level"])[..]);
+
+        let m = opts().parse(&["-o", "--", "c"]).unwrap();
+        assert_eq!(m.value_os("o"), Some(OsStr::new("--")));
+        assert_eq!(m.free(), &free(&["c"])[..]);
+    }
+
+    #[test]
+    fn errors() {
+        let cases: &[(&[&str], ArgsErrorKind, &str, &str)] = &[
+            (&["--frob"], ArgsErrorKind::UnrecognizedOption, "--frob",
+             "unrecognized option '--frob'"),
+            (&["-vx"], ArgsErrorKind::UnrecognizedOption, "-x",
+             "unrecognized option '-x'"),
+            (&["--output"], ArgsErrorKind::MissingValue, "--output",
+             "option '--output' requires a value"),
+            (&["-vo"], ArgsErrorKind::MissingValue, "-o",
+             "option '-o' requires a value"),
+            (&["--verbose=yes"], ArgsErrorKind::UnexpectedValue, "--verbose",
+             "option '--verbose' doesn't take a value"),
+        ];
+        for &(args, kind, option, message) in cases {
+            let err = opts().parse(args).unwrap_err();
+            assert_eq!(err.kind(), kind);
+            assert_eq!(err.option(), option);
+            assert_eq!(err.to_string(), message);
+        }
+    }
+
+    #[test]
+    fn typed_values() {
+        let m = opts().parse(&["--level", "7", "--level=x", "-o", "1.5"]).unwrap();
+        let err = m.value::<u32>("level").unwrap_err();
+        assert_eq!(err.kind(), ArgsErrorKind::InvalidValue);
+        assert_eq!(err.to_string(),
+                   "invalid value 'x' for option '--level': invalid digit found in string");
+        let err = m.values::<u32>("level").unwrap_err();
+        assert_eq!(err.kind(), ArgsErrorKind::InvalidValue);
+        assert_eq!(m.values::<String>("level"), Ok(vec!["7".to_string(), "x".to_string()]));
+        assert_eq!(m.value::<f64>("o"), Ok(Some(1.5)));
+    }
+
+    #[test]
+    fn help() {
+        let m = opts().parse(&["-vh"]).unwrap();
+        assert!(m.help_requested());
+        assert!(m.flag("v"));
+        assert!(opts().parse(&["--help"]).unwrap().help_requested());
+        assert!(opts().parse(&["--help", "--frob"]).unwrap().help_requested());
+        assert!(opts().parse(&["--frob", "-h"]).is_err());
+        assert!(!opts().parse(&["--", "--help"]).unwrap().help_requested());
+
+        let mut opts = opts();
+        opts.flag("h", "host", "connect to a host");
+        let m = opts.parse(&["-h"]).unwrap();
+        assert!(!m.help_requested());
+        assert!(m.flag("host"));
+        assert!(opts.parse(&["--help"]).unwrap().help_requested());
+        assert_eq!(opts.usage("Usage: test"),
+                   concat!("Usage: test\n",
+                           "\n",
+                           "Options:\n",
+                           "    -v, --verbose      print more output\n",
+                           "    -q                 print less output\n",
+                           "    -o, --output FILE  write the output to FILE\n",
+                           "        --level N      set the level\n",
+                           "    -h, --host         connect to a host\n",
+                           "        --help         print this help message\n"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn duplicate_option() {
+        opts().flag("", "output", "");
+    }
+
+    #[test]
+    #[should_panic]
+    fn undefined_option() {
+        opts().parse(&["-v"]).unwrap().flag("x");
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn non_utf8() {
+        use os::unix::ffi::OsStrExt;
+
+        let value = OsStr::from_bytes(b"caf\xe9");
+        let long = OsStr::from_bytes(b"--output=caf\xe9");
+        let short = OsStr::from_bytes(b"-vocaf\xe9");
+        let m = opts().parse(&[long, short, value, OsStr::new("--level"), value]).unwrap();
+        assert_eq!(m.values_os("output"), [value, value]);
+        assert_eq!(m.free(), &[value.to_os_string()][..]);
+        let err = m.value::<String>("level").unwrap_err();
+        assert_eq!(err.kind(), ArgsErrorKind::InvalidUnicode);
+        assert_eq!(err.to_string(), "the value of option '--level' is not valid unicode");
+
+        let err = opts().parse(&[OsStr::from_bytes(b"-v\xe9")]).unwrap_err();
+        assert_eq!(err.kind(), ArgsErrorKind::UnrecognizedOption);
+        assert_eq!(err.option(), "-\u{FFFD}");
+    }
+}
@@ -257,6 +257,15 @@ impl Error for allocator::CannotReallocInPlace {
     }
 }
 
+#[unstable(feature = "allocator_api",
+           reason = "the precise API and guarantees it provides may be tweaked.",
+           issue = "32838")]
+impl Error for allocator::CollectionAllocErr {
+    fn description(&self) -> &str {
+        allocator::CollectionAllocErr::description(self)
+    }
+}
+
 #[stable(feature = "rust1", since = "1.0.0")]
 impl Error for str::ParseBoolError {
     fn description(&self) -> &str { "failed to parse bool" }
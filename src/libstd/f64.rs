@@ -1016,6 +1016,33 @@ impl f64 {
     pub fn from_bits(v: u64) -> Self {
         num::Float::from_bits(v)
     }
+
+    /// Parses a decimal float from the start of a string slice, returning it
+    /// together with the number of bytes it took up.
+    ///
+    /// This accepts the same syntax as the [`FromStr`] implementation, but stops
+    /// after the longest prefix which is a valid float instead of failing on
+    /// the bytes after it, so it can be used to scan numbers out of a longer
+    /// input.
+    ///
+    /// [`FromStr`]: str/trait.FromStr.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(parse_prefix)]
+    ///
+    /// assert_eq!(f64::from_str_prefix("2.5e3, 7"), Ok((2500.0, 5)));
+    /// assert_eq!(f64::from_str_prefix("-.5x"), Ok((-0.5, 3)));
+    /// // an exponent without digits is not part of the number
+    /// assert_eq!(f64::from_str_prefix("1e+"), Ok((1.0, 1)));
+    /// assert!(f64::from_str_prefix("e5").is_err());
+    /// ```
+    #[unstable(feature = "parse_prefix", issue = "0")]
+    #[inline]
+    pub fn from_str_prefix(src: &str) -> Result<(f64, usize), num::ParseFloatError> {
+        num::dec2flt::dec2flt_prefix(src)
+    }
 }
 
 #[cfg(test)]
@@ -19,7 +19,7 @@
 
 use fmt;
 use ffi::OsString;
-use io::{self, SeekFrom, Seek, Read, Initializer, Write};
+use io::{self, SeekFrom, Seek, Read, Initializer, Write, IoVec, IoVecMut};
 use path::{Path, PathBuf};
 use sys::fs as fs_imp;
 use sys_common::{AsInnerMut, FromInner, AsInner, IntoInner};
@@ -590,6 +590,10 @@ impl Read for File {
         self.inner.read(buf)
     }
 
+    fn read_vectored(&mut self, bufs: &mut [IoVecMut]) -> io::Result<usize> {
+        self.inner.read_vectored(bufs)
+    }
+
     #[inline]
     unsafe fn initializer(&self) -> Initializer {
         Initializer::nop()
@@ -600,6 +604,9 @@ impl Write for File {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
         self.inner.write(buf)
     }
+    fn write_vectored(&mut self, bufs: &[IoVec]) -> io::Result<usize> {
+        self.inner.write_vectored(bufs)
+    }
     fn flush(&mut self) -> io::Result<()> { self.inner.flush() }
 }
 #[stable(feature = "rust1", since = "1.0.0")]
@@ -614,6 +621,10 @@ impl<'a> Read for &'a File {
         self.inner.read(buf)
     }
 
+    fn read_vectored(&mut self, bufs: &mut [IoVecMut]) -> io::Result<usize> {
+        self.inner.read_vectored(bufs)
+    }
+
     #[inline]
     unsafe fn initializer(&self) -> Initializer {
         Initializer::nop()
@@ -624,6 +635,9 @@ impl<'a> Write for &'a File {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
         self.inner.write(buf)
     }
+    fn write_vectored(&mut self, bufs: &[IoVec]) -> io::Result<usize> {
+        self.inner.write_vectored(bufs)
+    }
     fn flush(&mut self) -> io::Result<()> { self.inner.flush() }
 }
 #[stable(feature = "rust1", since = "1.0.0")]
@@ -2018,7 +2032,7 @@ mod tests {
     use io::prelude::*;
 
     use fs::{self, File, OpenOptions};
-    use io::{ErrorKind, SeekFrom};
+    use io::{ErrorKind, SeekFrom, IoVec, IoVecMut};
     use path::Path;
     use rand::{StdRng, Rng};
     use str;
@@ -2102,6 +2116,31 @@ mod tests {
         check!(fs::remove_file(filename));
     }
 
+    #[test]
+    #[cfg(unix)] // Windows only transfers the first nonempty buffer for files
+    fn file_test_io_vectored() {
+        let tmpdir = tmpdir();
+        let filename = &tmpdir.join("file_rt_io_file_test_vectored.txt");
+        {
+            let mut write_stream = check!(File::create(filename));
+            let n = check!(write_stream.write_vectored(&[IoVec::new(b"header:"),
+                                                         IoVec::new(b""),
+                                                         IoVec::new(b"body")]));
+            assert_eq!(n, 11);
+        }
+        {
+            let mut read_stream = check!(File::open(filename));
+            let mut header = [0; 7];
+            let mut body = [0; 16];
+            let n = check!(read_stream.read_vectored(&mut [IoVecMut::new(&mut header),
+                                                            IoVecMut::new(&mut body)]));
+            assert_eq!(n, 11);
+            assert_eq!(&header, b"header:");
+            assert_eq!(&body[..4], b"body");
+        }
+        check!(fs::remove_file(filename));
+    }
+
     #[test]
     fn invalid_path_raises() {
         let tmpdir = tmpdir();
@@ -15,7 +15,7 @@ use io::prelude::*;
 use cmp;
 use error;
 use fmt;
-use io::{self, Initializer, DEFAULT_BUF_SIZE, Error, ErrorKind, SeekFrom};
+use io::{self, Initializer, DEFAULT_BUF_SIZE, Error, ErrorKind, SeekFrom, IoVec};
 use memchr;
 
 /// The `BufReader` struct adds buffering to any reader.
@@ -554,6 +554,22 @@ impl<W: Write> Write for BufWriter<W> {
             Write::write(&mut self.buf, buf)
         }
     }
+
+    fn write_vectored(&mut self, bufs: &[IoVec]) -> io::Result<usize> {
+        let total_len = bufs.iter().map(|b| b.len()).sum::<usize>();
+        if self.buf.len() + total_len > self.buf.capacity() {
+            self.flush_buf()?;
+        }
+        if total_len >= self.buf.capacity() {
+            self.panicked = true;
+            let r = self.inner.as_mut().unwrap().write_vectored(bufs);
+            self.panicked = false;
+            r
+        } else {
+            Write::write_vectored(&mut self.buf, bufs)
+        }
+    }
+
     fn flush(&mut self) -> io::Result<()> {
         self.flush_buf().and_then(|()| self.get_mut().flush())
     }
@@ -910,7 +926,7 @@ impl<W: Write> fmt::Debug for LineWriter<W> where W: fmt::Debug {
 #[cfg(test)]
 mod tests {
     use io::prelude::*;
-    use io::{self, BufReader, BufWriter, LineWriter, SeekFrom};
+    use io::{self, BufReader, BufWriter, LineWriter, SeekFrom, IoVec};
     use sync::atomic::{AtomicUsize, Ordering};
     use thread;
     use test;
@@ -1045,6 +1061,21 @@ mod tests {
         assert_eq!(reader.get_ref().pos, expected);
     }
 
+    #[test]
+    fn test_buffered_writer_vectored() {
+        let inner = Vec::new();
+        let mut writer = BufWriter::with_capacity(4, inner);
+
+        assert_eq!(writer.write_vectored(&[IoVec::new(&[0]), IoVec::new(&[1, 2])]).unwrap(), 3);
+        assert_eq!(*writer.get_ref(), []);
+
+        // Too large for the remaining space: the buffer is flushed first and
+        // the bufs are written through directly.
+        assert_eq!(writer.write_vectored(&[IoVec::new(&[3, 4]),
+                                          IoVec::new(&[5, 6, 7])]).unwrap(), 5);
+        assert_eq!(*writer.get_ref(), [0, 1, 2, 3, 4, 5, 6, 7]);
+    }
+
     #[test]
     fn test_buffered_writer() {
         let inner = Vec::new();
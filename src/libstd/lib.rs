@@ -258,6 +258,7 @@
 #![feature(const_fn)]
 #![feature(core_float)]
 #![feature(core_intrinsics)]
+#![feature(dec2flt)]
 #![feature(dropck_eyepatch)]
 #![feature(exact_size_is_empty)]
 #![feature(external_doc)]
@@ -274,6 +275,7 @@
 #![feature(i128_type)]
 #![feature(inclusive_range)]
 #![feature(int_error_internals)]
+#![feature(int_error_matching)]
 #![feature(integer_atomics)]
 #![feature(into_cow)]
 #![feature(lang_items)]
@@ -293,10 +295,13 @@
 #![feature(peek)]
 #![feature(placement_in_syntax)]
 #![feature(placement_new_protocol)]
+#![feature(platform_intrinsics)]
+#![feature(portable_simd)]
 #![feature(prelude_import)]
 #![feature(ptr_internals)]
 #![feature(rand)]
 #![feature(raw)]
+#![feature(repr_simd)]
 #![feature(rustc_attrs)]
 #![feature(sip_hash_13)]
 #![feature(slice_bytes)]
@@ -304,10 +309,12 @@
 #![feature(slice_internals)]
 #![feature(slice_patterns)]
 #![feature(staged_api)]
+#![feature(stdsimd)]
 #![feature(stmt_expr_attributes)]
 #![feature(str_char)]
 #![feature(str_internals)]
 #![feature(str_utf16)]
+#![feature(target_feature)]
 #![feature(termination_trait)]
 #![feature(test, rustc_private)]
 #![feature(thread_local)]
@@ -465,6 +472,8 @@ pub use alloc::vec;
 pub use std_unicode::char;
 #[unstable(feature = "i128", issue = "35118")]
 pub use core::u128;
+#[unstable(feature = "portable_simd", issue = "0")]
+pub use core::simd;
 
 pub mod f32;
 pub mod f64;
@@ -472,6 +481,7 @@ pub mod f64;
 #[macro_use]
 pub mod thread;
 pub mod ascii;
+pub mod backtrace;
 pub mod collections;
 pub mod env;
 pub mod error;
@@ -487,6 +497,7 @@ pub mod process;
 pub mod sync;
 pub mod time;
 pub mod heap;
+pub mod arch;
 
 // Platform-abstraction modules
 #[macro_use]
@@ -263,6 +263,62 @@ macro_rules! select {
     })
 }
 
+/// Tests at run time whether an x86-64 CPU feature is available.
+///
+/// The feature is named as in `#[target_feature(enable = "...")]`, and this
+/// macro is the way to decide whether it is safe to call a function compiled
+/// with that attribute. The CPU is queried with `cpuid` the first time any
+/// feature is tested and the answer is cached for the rest of the program.
+///
+/// Naming a feature this macro does not know about is a compile-time error.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(stdsimd, target_feature)]
+///
+/// # #[cfg(target_arch = "x86_64")]
+/// # fn main() {
+/// use std::arch::x86_64::*;
+///
+/// #[target_feature(enable = "sse4.2")]
+/// unsafe fn crc32_sse42(bytes: &[u8]) -> u32 {
+///     bytes.iter().fold(!0, |crc, &b| _mm_crc32_u8(crc, b))
+/// }
+///
+/// if is_x86_feature_detected!("sse4.2") {
+///     let crc = unsafe { crc32_sse42(b"hello") };
+///     println!("crc32c: {:x}", !crc);
+/// }
+/// # }
+/// # #[cfg(not(target_arch = "x86_64"))]
+/// # fn main() {}
+/// ```
+#[macro_export]
+#[cfg(target_arch = "x86_64")]
+#[unstable(feature = "stdsimd", issue = "0")]
+#[allow_internal_unstable]
+macro_rules! is_x86_feature_detected {
+    ("sse") => ($crate::arch::detect::check($crate::arch::detect::Feature::sse));
+    ("sse2") => ($crate::arch::detect::check($crate::arch::detect::Feature::sse2));
+    ("sse3") => ($crate::arch::detect::check($crate::arch::detect::Feature::sse3));
+    ("ssse3") => ($crate::arch::detect::check($crate::arch::detect::Feature::ssse3));
+    ("sse4.1") => ($crate::arch::detect::check($crate::arch::detect::Feature::sse4_1));
+    ("sse4.2") => ($crate::arch::detect::check($crate::arch::detect::Feature::sse4_2));
+    ("popcnt") => ($crate::arch::detect::check($crate::arch::detect::Feature::popcnt));
+    ("aes") => ($crate::arch::detect::check($crate::arch::detect::Feature::aes));
+    ("rdrnd") => ($crate::arch::detect::check($crate::arch::detect::Feature::rdrnd));
+    ("xsave") => ($crate::arch::detect::check($crate::arch::detect::Feature::xsave));
+    ("avx") => ($crate::arch::detect::check($crate::arch::detect::Feature::avx));
+    ("fma") => ($crate::arch::detect::check($crate::arch::detect::Feature::fma));
+    ("avx2") => ($crate::arch::detect::check($crate::arch::detect::Feature::avx2));
+    ("avx512f") => ($crate::arch::detect::check($crate::arch::detect::Feature::avx512f));
+    ("bmi") => ($crate::arch::detect::check($crate::arch::detect::Feature::bmi));
+    ("bmi2") => ($crate::arch::detect::check($crate::arch::detect::Feature::bmi2));
+    ("lzcnt") => ($crate::arch::detect::check($crate::arch::detect::Feature::lzcnt));
+    ($t:tt) => (compile_error!(concat!("unknown x86 target feature: ", $t)));
+}
+
 #[cfg(test)]
 macro_rules! assert_approx_eq {
     ($a:expr, $b:expr) => ({
@@ -11,7 +11,7 @@
 use io::prelude::*;
 
 use fmt;
-use io::{self, Initializer};
+use io::{self, Initializer, IoVec, IoVecMut};
 use net::{ToSocketAddrs, SocketAddr, Shutdown};
 use sys_common::net as net_imp;
 use sys_common::{AsInner, FromInner, IntoInner};
@@ -105,6 +105,42 @@ pub struct TcpListener(net_imp::TcpListener);
 #[derive(Debug)]
 pub struct Incoming<'a> { listener: &'a TcpListener }
 
+/// A builder used to configure a [`TcpListener`] before it is bound.
+///
+/// Some socket options, such as `SO_REUSEPORT` or `IPV6_V6ONLY`, only have
+/// an effect when they are set before the socket is bound to an address.
+/// A `TcpListenerBuilder` records the options to apply and then creates,
+/// configures, binds and starts listening on the socket in [`bind`]. Options
+/// which are not explicitly set are left at the system's defaults.
+///
+/// [`TcpListener`]: ../../std/net/struct.TcpListener.html
+/// [`bind`]: #method.bind
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(tcp_listener_builder)]
+///
+/// use std::net::TcpListenerBuilder;
+///
+/// let listener = TcpListenerBuilder::new()
+///     .reuse_port(true)
+///     .backlog(1024)
+///     .bind("127.0.0.1:8080")
+///     .expect("couldn't bind to address");
+/// ```
+#[unstable(feature = "tcp_listener_builder", issue = "0")]
+#[derive(Clone, Debug)]
+pub struct TcpListenerBuilder {
+    backlog: u32,
+    reuse_address: Option<bool>,
+    reuse_port: Option<bool>,
+    only_v6: Option<bool>,
+    ttl: Option<u32>,
+    recv_buffer_size: Option<usize>,
+    send_buffer_size: Option<usize>,
+}
+
 impl TcpStream {
     /// Opens a TCP connection to a remote host.
     ///
@@ -476,6 +512,236 @@ impl TcpStream {
         self.0.ttl()
     }
 
+    /// Sets the value of the `SO_KEEPALIVE` option on this socket.
+    ///
+    /// If `keepalive` is `Some`, keepalive probes are enabled and the first
+    /// probe is sent after the connection has been idle for the specified
+    /// duration. If `keepalive` is `None`, keepalive probes are disabled.
+    ///
+    /// The idle time has a granularity of one second on most platforms.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(tcp_socket_options)]
+    ///
+    /// use std::net::TcpStream;
+    /// use std::time::Duration;
+    ///
+    /// let stream = TcpStream::connect("127.0.0.1:8080")
+    ///                        .expect("Couldn't connect to the server...");
+    /// stream.set_keepalive(Some(Duration::from_secs(60)))
+    ///       .expect("set_keepalive call failed");
+    /// ```
+    #[unstable(feature = "tcp_socket_options", issue = "0")]
+    pub fn set_keepalive(&self, keepalive: Option<Duration>) -> io::Result<()> {
+        self.0.set_keepalive(keepalive)
+    }
+
+    /// Returns the idle time before keepalive probes are sent, or `None` if
+    /// the `SO_KEEPALIVE` option is disabled on this socket.
+    ///
+    /// For more information about this option, see [`set_keepalive`].
+    ///
+    /// [`set_keepalive`]: #method.set_keepalive
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(tcp_socket_options)]
+    ///
+    /// use std::net::TcpStream;
+    /// use std::time::Duration;
+    ///
+    /// let stream = TcpStream::connect("127.0.0.1:8080")
+    ///                        .expect("Couldn't connect to the server...");
+    /// stream.set_keepalive(Some(Duration::from_secs(60)))
+    ///       .expect("set_keepalive call failed");
+    /// assert_eq!(stream.keepalive().unwrap(), Some(Duration::from_secs(60)));
+    /// ```
+    #[unstable(feature = "tcp_socket_options", issue = "0")]
+    pub fn keepalive(&self) -> io::Result<Option<Duration>> {
+        self.0.keepalive()
+    }
+
+    /// Sets the interval between keepalive probes which are not acknowledged
+    /// by the peer.
+    ///
+    /// This corresponds to the `TCP_KEEPINTVL` option. An error of kind
+    /// [`Other`] is returned on platforms which do not support it.
+    ///
+    /// [`Other`]: ../../std/io/enum.ErrorKind.html#variant.Other
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(tcp_socket_options)]
+    ///
+    /// use std::net::TcpStream;
+    /// use std::time::Duration;
+    ///
+    /// let stream = TcpStream::connect("127.0.0.1:8080")
+    ///                        .expect("Couldn't connect to the server...");
+    /// stream.set_keepalive_interval(Duration::from_secs(10))
+    ///       .expect("set_keepalive_interval call failed");
+    /// ```
+    #[unstable(feature = "tcp_socket_options", issue = "0")]
+    pub fn set_keepalive_interval(&self, interval: Duration) -> io::Result<()> {
+        self.0.set_keepalive_interval(interval)
+    }
+
+    /// Gets the value of the `TCP_KEEPINTVL` option on this socket.
+    ///
+    /// For more information about this option, see
+    /// [`set_keepalive_interval`][link].
+    ///
+    /// [link]: #method.set_keepalive_interval
+    #[unstable(feature = "tcp_socket_options", issue = "0")]
+    pub fn keepalive_interval(&self) -> io::Result<Duration> {
+        self.0.keepalive_interval()
+    }
+
+    /// Sets the number of unacknowledged keepalive probes to send before the
+    /// connection is considered dead.
+    ///
+    /// This corresponds to the `TCP_KEEPCNT` option. An error of kind
+    /// [`Other`] is returned on platforms which do not support it.
+    ///
+    /// [`Other`]: ../../std/io/enum.ErrorKind.html#variant.Other
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(tcp_socket_options)]
+    ///
+    /// use std::net::TcpStream;
+    ///
+    /// let stream = TcpStream::connect("127.0.0.1:8080")
+    ///                        .expect("Couldn't connect to the server...");
+    /// stream.set_keepalive_retries(5).expect("set_keepalive_retries call failed");
+    /// assert_eq!(stream.keepalive_retries().unwrap_or(0), 5);
+    /// ```
+    #[unstable(feature = "tcp_socket_options", issue = "0")]
+    pub fn set_keepalive_retries(&self, retries: u32) -> io::Result<()> {
+        self.0.set_keepalive_retries(retries)
+    }
+
+    /// Gets the value of the `TCP_KEEPCNT` option on this socket.
+    ///
+    /// For more information about this option, see
+    /// [`set_keepalive_retries`][link].
+    ///
+    /// [link]: #method.set_keepalive_retries
+    #[unstable(feature = "tcp_socket_options", issue = "0")]
+    pub fn keepalive_retries(&self) -> io::Result<u32> {
+        self.0.keepalive_retries()
+    }
+
+    /// Sets the value of the `SO_LINGER` option on this socket.
+    ///
+    /// If `linger` is `Some`, closing the socket will block until all queued
+    /// data has been sent or the timeout expires. A timeout of zero causes
+    /// the connection to be reset when the socket is closed. If `linger` is
+    /// `None`, closing returns immediately and the system sends any queued
+    /// data in the background.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(tcp_socket_options)]
+    ///
+    /// use std::net::TcpStream;
+    /// use std::time::Duration;
+    ///
+    /// let stream = TcpStream::connect("127.0.0.1:8080")
+    ///                        .expect("Couldn't connect to the server...");
+    /// stream.set_linger(Some(Duration::from_secs(0))).expect("set_linger call failed");
+    /// ```
+    #[unstable(feature = "tcp_socket_options", issue = "0")]
+    pub fn set_linger(&self, linger: Option<Duration>) -> io::Result<()> {
+        self.0.set_linger(linger)
+    }
+
+    /// Gets the value of the `SO_LINGER` option on this socket.
+    ///
+    /// For more information about this option, see [`set_linger`][link].
+    ///
+    /// [link]: #method.set_linger
+    #[unstable(feature = "tcp_socket_options", issue = "0")]
+    pub fn linger(&self) -> io::Result<Option<Duration>> {
+        self.0.linger()
+    }
+
+    /// Sets the value of the `SO_RCVBUF` option on this socket.
+    ///
+    /// This is a hint to the system about the size of the receive buffer to
+    /// allocate for the socket. The system may adjust the value, so the
+    /// size reported by [`recv_buffer_size`] can differ from the one set.
+    ///
+    /// [`recv_buffer_size`]: #method.recv_buffer_size
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(tcp_socket_options)]
+    ///
+    /// use std::net::TcpStream;
+    ///
+    /// let stream = TcpStream::connect("127.0.0.1:8080")
+    ///                        .expect("Couldn't connect to the server...");
+    /// stream.set_recv_buffer_size(64 * 1024).expect("set_recv_buffer_size call failed");
+    /// ```
+    #[unstable(feature = "tcp_socket_options", issue = "0")]
+    pub fn set_recv_buffer_size(&self, size: usize) -> io::Result<()> {
+        self.0.set_recv_buffer_size(size)
+    }
+
+    /// Gets the value of the `SO_RCVBUF` option on this socket.
+    ///
+    /// For more information about this option, see
+    /// [`set_recv_buffer_size`][link].
+    ///
+    /// [link]: #method.set_recv_buffer_size
+    #[unstable(feature = "tcp_socket_options", issue = "0")]
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        self.0.recv_buffer_size()
+    }
+
+    /// Sets the value of the `SO_SNDBUF` option on this socket.
+    ///
+    /// This is a hint to the system about the size of the send buffer to
+    /// allocate for the socket. The system may adjust the value, so the
+    /// size reported by [`send_buffer_size`] can differ from the one set.
+    ///
+    /// [`send_buffer_size`]: #method.send_buffer_size
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(tcp_socket_options)]
+    ///
+    /// use std::net::TcpStream;
+    ///
+    /// let stream = TcpStream::connect("127.0.0.1:8080")
+    ///                        .expect("Couldn't connect to the server...");
+    /// stream.set_send_buffer_size(64 * 1024).expect("set_send_buffer_size call failed");
+    /// ```
+    #[unstable(feature = "tcp_socket_options", issue = "0")]
+    pub fn set_send_buffer_size(&self, size: usize) -> io::Result<()> {
+        self.0.set_send_buffer_size(size)
+    }
+
+    /// Gets the value of the `SO_SNDBUF` option on this socket.
+    ///
+    /// For more information about this option, see
+    /// [`set_send_buffer_size`][link].
+    ///
+    /// [link]: #method.set_send_buffer_size
+    #[unstable(feature = "tcp_socket_options", issue = "0")]
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        self.0.send_buffer_size()
+    }
+
     /// Get the value of the `SO_ERROR` option on this socket.
     ///
     /// This will retrieve the stored error in the underlying socket, clearing
@@ -548,6 +814,10 @@ impl TcpStream {
 impl Read for TcpStream {
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> { self.0.read(buf) }
 
+    fn read_vectored(&mut self, bufs: &mut [IoVecMut]) -> io::Result<usize> {
+        self.0.read_vectored(bufs)
+    }
+
     #[inline]
     unsafe fn initializer(&self) -> Initializer {
         Initializer::nop()
@@ -556,12 +826,19 @@ impl Read for TcpStream {
 #[stable(feature = "rust1", since = "1.0.0")]
 impl Write for TcpStream {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> { self.0.write(buf) }
+    fn write_vectored(&mut self, bufs: &[IoVec]) -> io::Result<usize> {
+        self.0.write_vectored(bufs)
+    }
     fn flush(&mut self) -> io::Result<()> { Ok(()) }
 }
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<'a> Read for &'a TcpStream {
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> { self.0.read(buf) }
 
+    fn read_vectored(&mut self, bufs: &mut [IoVecMut]) -> io::Result<usize> {
+        self.0.read_vectored(bufs)
+    }
+
     #[inline]
     unsafe fn initializer(&self) -> Initializer {
         Initializer::nop()
@@ -570,6 +847,9 @@ impl<'a> Read for &'a TcpStream {
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<'a> Write for &'a TcpStream {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> { self.0.write(buf) }
+    fn write_vectored(&mut self, bufs: &[IoVec]) -> io::Result<usize> {
+        self.0.write_vectored(bufs)
+    }
     fn flush(&mut self) -> io::Result<()> { Ok(()) }
 }
 
@@ -771,6 +1051,110 @@ impl TcpListener {
         self.0.ttl()
     }
 
+    /// Sets the value of the `SO_REUSEPORT` option on this socket.
+    ///
+    /// When set, other sockets which also enable this option may bind to the
+    /// same address and port. The option has to be set on every socket
+    /// sharing the port; use [`TcpListenerBuilder`] to set it before this socket
+    /// is bound. An error of kind [`Other`] is returned on platforms which
+    /// do not support the option.
+    ///
+    /// [`TcpListenerBuilder`]: ../../std/net/struct.TcpListenerBuilder.html
+    /// [`Other`]: ../../std/io/enum.ErrorKind.html#variant.Other
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(tcp_socket_options)]
+    ///
+    /// use std::net::TcpListener;
+    ///
+    /// let listener = TcpListener::bind("127.0.0.1:80").unwrap();
+    /// listener.set_reuse_port(true).expect("set_reuse_port call failed");
+    /// ```
+    #[unstable(feature = "tcp_socket_options", issue = "0")]
+    pub fn set_reuse_port(&self, reuse: bool) -> io::Result<()> {
+        self.0.set_reuse_port(reuse)
+    }
+
+    /// Gets the value of the `SO_REUSEPORT` option on this socket.
+    ///
+    /// For more information about this option, see [`set_reuse_port`][link].
+    ///
+    /// [link]: #method.set_reuse_port
+    #[unstable(feature = "tcp_socket_options", issue = "0")]
+    pub fn reuse_port(&self) -> io::Result<bool> {
+        self.0.reuse_port()
+    }
+
+    /// Sets the value of the `SO_RCVBUF` option on this socket.
+    ///
+    /// This is a hint to the system about the size of the receive buffer to
+    /// allocate for the socket. The system may adjust the value, so the
+    /// size reported by [`recv_buffer_size`] can differ from the one set.
+    ///
+    /// [`recv_buffer_size`]: #method.recv_buffer_size
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(tcp_socket_options)]
+    ///
+    /// use std::net::TcpListener;
+    ///
+    /// let listener = TcpListener::bind("127.0.0.1:80").unwrap();
+    /// listener.set_recv_buffer_size(64 * 1024).expect("set_recv_buffer_size call failed");
+    /// ```
+    #[unstable(feature = "tcp_socket_options", issue = "0")]
+    pub fn set_recv_buffer_size(&self, size: usize) -> io::Result<()> {
+        self.0.set_recv_buffer_size(size)
+    }
+
+    /// Gets the value of the `SO_RCVBUF` option on this socket.
+    ///
+    /// For more information about this option, see
+    /// [`set_recv_buffer_size`][link].
+    ///
+    /// [link]: #method.set_recv_buffer_size
+    #[unstable(feature = "tcp_socket_options", issue = "0")]
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        self.0.recv_buffer_size()
+    }
+
+    /// Sets the value of the `SO_SNDBUF` option on this socket.
+    ///
+    /// This is a hint to the system about the size of the send buffer to
+    /// allocate for the socket. The system may adjust the value, so the
+    /// size reported by [`send_buffer_size`] can differ from the one set.
+    ///
+    /// [`send_buffer_size`]: #method.send_buffer_size
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(tcp_socket_options)]
+    ///
+    /// use std::net::TcpListener;
+    ///
+    /// let listener = TcpListener::bind("127.0.0.1:80").unwrap();
+    /// listener.set_send_buffer_size(64 * 1024).expect("set_send_buffer_size call failed");
+    /// ```
+    #[unstable(feature = "tcp_socket_options", issue = "0")]
+    pub fn set_send_buffer_size(&self, size: usize) -> io::Result<()> {
+        self.0.set_send_buffer_size(size)
+    }
+
+    /// Gets the value of the `SO_SNDBUF` option on this socket.
+    ///
+    /// For more information about this option, see
+    /// [`set_send_buffer_size`][link].
+    ///
+    /// [link]: #method.set_send_buffer_size
+    #[unstable(feature = "tcp_socket_options", issue = "0")]
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        self.0.send_buffer_size()
+    }
+
     #[stable(feature = "net2_mutators", since = "1.9.0")]
     #[rustc_deprecated(since = "1.16.0",
                        reason = "this option can only be set before the socket is bound")]
@@ -864,6 +1248,164 @@ impl<'a> Iterator for Incoming<'a> {
     }
 }
 
+impl TcpListenerBuilder {
+    /// Creates a new builder with the default backlog of 128 and all socket
+    /// options left at the system's defaults.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(tcp_listener_builder)]
+    ///
+    /// use std::net::TcpListenerBuilder;
+    ///
+    /// let listener = TcpListenerBuilder::new().bind("127.0.0.1:8080").unwrap();
+    /// ```
+    #[unstable(feature = "tcp_listener_builder", issue = "0")]
+    pub fn new() -> TcpListenerBuilder {
+        TcpListenerBuilder {
+            backlog: 128,
+            reuse_address: None,
+            reuse_port: None,
+            only_v6: None,
+            ttl: None,
+            recv_buffer_size: None,
+            send_buffer_size: None,
+        }
+    }
+
+    /// Sets the maximum length of the queue of pending connections passed
+    /// to `listen`.
+    ///
+    /// The system may silently clamp the value to a platform specific limit.
+    #[unstable(feature = "tcp_listener_builder", issue = "0")]
+    pub fn backlog(&mut self, backlog: u32) -> &mut TcpListenerBuilder {
+        self.backlog = backlog;
+        self
+    }
+
+    /// Sets the value of the `SO_REUSEADDR` option on the socket.
+    ///
+    /// On Unix platforms this option is enabled by default for listeners so
+    /// that a server can be restarted without waiting for connections in the
+    /// `TIME_WAIT` state to expire.
+    #[unstable(feature = "tcp_listener_builder", issue = "0")]
+    pub fn reuse_address(&mut self, reuse: bool) -> &mut TcpListenerBuilder {
+        self.reuse_address = Some(reuse);
+        self
+    }
+
+    /// Sets the value of the `SO_REUSEPORT` option on the socket, allowing
+    /// several listeners to bind to the same address and port.
+    ///
+    /// Binding fails with an error of kind [`Other`] on platforms which do
+    /// not support this option.
+    ///
+    /// [`Other`]: ../../std/io/enum.ErrorKind.html#variant.Other
+    #[unstable(feature = "tcp_listener_builder", issue = "0")]
+    pub fn reuse_port(&mut self, reuse: bool) -> &mut TcpListenerBuilder {
+        self.reuse_port = Some(reuse);
+        self
+    }
+
+    /// Sets the value of the `IPV6_V6ONLY` option on the socket.
+    ///
+    /// If this is set to `true`, a listener bound to an IPv6 address will
+    /// only accept IPv6 connections. The option is ignored when binding to
+    /// an IPv4 address.
+    #[unstable(feature = "tcp_listener_builder", issue = "0")]
+    pub fn only_v6(&mut self, only_v6: bool) -> &mut TcpListenerBuilder {
+        self.only_v6 = Some(only_v6);
+        self
+    }
+
+    /// Sets the value of the `IP_TTL` option on the socket.
+    #[unstable(feature = "tcp_listener_builder", issue = "0")]
+    pub fn ttl(&mut self, ttl: u32) -> &mut TcpListenerBuilder {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// Sets the value of the `SO_RCVBUF` option on the socket.
+    ///
+    /// Accepted connections inherit this value, and it has to be set before
+    /// `listen` for window scaling to take larger buffers into account.
+    #[unstable(feature = "tcp_listener_builder", issue = "0")]
+    pub fn recv_buffer_size(&mut self, size: usize) -> &mut TcpListenerBuilder {
+        self.recv_buffer_size = Some(size);
+        self
+    }
+
+    /// Sets the value of the `SO_SNDBUF` option on the socket.
+    ///
+    /// Accepted connections inherit this value.
+    #[unstable(feature = "tcp_listener_builder", issue = "0")]
+    pub fn send_buffer_size(&mut self, size: usize) -> &mut TcpListenerBuilder {
+        self.send_buffer_size = Some(size);
+        self
+    }
+
+    /// Creates a socket with the configured options, binds it to the
+    /// specified address and starts listening for connections.
+    ///
+    /// Like [`TcpListener::bind`], if `addr` yields multiple addresses each
+    /// of them is tried in turn until one succeeds. If none succeed, the
+    /// error from the last attempt is returned.
+    ///
+    /// [`TcpListener::bind`]: ../../std/net/struct.TcpListener.html#method.bind
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(tcp_listener_builder)]
+    ///
+    /// use std::net::TcpListenerBuilder;
+    ///
+    /// let listener = TcpListenerBuilder::new()
+    ///     .only_v6(true)
+    ///     .bind("[::1]:8080")
+    ///     .expect("couldn't bind to address");
+    /// ```
+    #[unstable(feature = "tcp_listener_builder", issue = "0")]
+    pub fn bind<A: ToSocketAddrs>(&self, addr: A) -> io::Result<TcpListener> {
+        super::each_addr(addr, |addr| {
+            net_imp::TcpListener::bind_with(addr, self.backlog, |sock| {
+                self.configure(addr, sock)
+            })
+        }).map(TcpListener)
+    }
+
+    fn configure(&self, addr: &SocketAddr, sock: &net_imp::TcpListener)
+                 -> io::Result<()> {
+        if let Some(reuse) = self.reuse_address {
+            sock.set_reuse_address(reuse)?;
+        }
+        if let Some(reuse) = self.reuse_port {
+            sock.set_reuse_port(reuse)?;
+        }
+        if let (Some(only_v6), &SocketAddr::V6(..)) = (self.only_v6, addr) {
+            sock.set_only_v6(only_v6)?;
+        }
+        if let Some(ttl) = self.ttl {
+            sock.set_ttl(ttl)?;
+        }
+        if let Some(size) = self.recv_buffer_size {
+            sock.set_recv_buffer_size(size)?;
+        }
+        if let Some(size) = self.send_buffer_size {
+            sock.set_send_buffer_size(size)?;
+        }
+        Ok(())
+    }
+}
+
+#[unstable(feature = "tcp_listener_builder", issue = "0")]
+impl Default for TcpListenerBuilder {
+    fn default() -> TcpListenerBuilder {
+        TcpListenerBuilder::new()
+    }
+}
+
 impl AsInner<net_imp::TcpListener> for TcpListener {
     fn as_inner(&self) -> &net_imp::TcpListener { &self.0 }
 }
@@ -887,7 +1429,7 @@ impl fmt::Debug for TcpListener {
 
 #[cfg(all(test, not(any(target_os = "cloudabi", target_os = "emscripten"))))]
 mod tests {
-    use io::ErrorKind;
+    use io::{ErrorKind, IoVec, IoVecMut};
     use io::prelude::*;
     use net::*;
     use net::test::{next_test_ip4, next_test_ip6};
@@ -1545,6 +2087,48 @@ mod tests {
         drop(listener);
     }
 
+    #[test]
+    fn vectored_smoke() {
+        each_ip(&mut |addr| {
+            let acceptor = t!(TcpListener::bind(&addr));
+
+            let _t = thread::spawn(move|| {
+                let mut stream = t!(TcpStream::connect(&addr));
+                t!(stream.write_all(b"header:body"));
+            });
+
+            let mut stream = t!(acceptor.accept()).0;
+            let mut header = [0; 7];
+            let mut body = [0; 4];
+            let mut nread = 0;
+            while nread < 11 {
+                let n = {
+                    let (h, b) = if nread < 7 {
+                        (&mut header[nread..], &mut body[..])
+                    } else {
+                        (&mut header[7..], &mut body[nread - 7..])
+                    };
+                    t!(stream.read_vectored(&mut [IoVecMut::new(h), IoVecMut::new(b)]))
+                };
+                assert!(n > 0);
+                nread += n;
+            }
+            assert_eq!(&header, b"header:");
+            assert_eq!(&body, b"body");
+
+            let _t = thread::spawn(move|| {
+                let mut stream = t!(TcpStream::connect(&addr));
+                let n = t!(stream.write_vectored(&[IoVec::new(b"head"),
+                                                   IoVec::new(b"tail")]));
+                assert_eq!(n, 8);
+            });
+            let mut stream = t!(acceptor.accept()).0;
+            let mut buf = Vec::new();
+            t!(stream.read_to_end(&mut buf));
+            assert_eq!(buf, b"headtail");
+        })
+    }
+
     #[test]
     fn nodelay() {
         let addr = next_test_ip4();
@@ -1659,4 +2243,92 @@ mod tests {
         let addr = listener.local_addr().unwrap();
         TcpStream::connect_timeout(&addr, Duration::from_secs(2)).unwrap();
     }
+
+    #[test]
+    #[cfg(any(target_os = "linux", target_os = "macos", target_os = "windows"))]
+    fn keepalive() {
+        let addr = next_test_ip4();
+        let _listener = t!(TcpListener::bind(&addr));
+
+        let stream = t!(TcpStream::connect(&("localhost", addr.port())));
+
+        t!(stream.set_keepalive(Some(Duration::from_secs(60))));
+        assert_eq!(Some(Duration::from_secs(60)), t!(stream.keepalive()));
+        t!(stream.set_keepalive(None));
+        assert_eq!(None, t!(stream.keepalive()));
+    }
+
+    #[test]
+    #[cfg(any(target_os = "linux", target_os = "android",
+              target_os = "fuchsia", target_os = "emscripten",
+              target_os = "freebsd", target_os = "dragonfly",
+              target_os = "netbsd", target_os = "macos", target_os = "ios"))]
+    fn keepalive_interval_retries() {
+        let addr = next_test_ip4();
+        let _listener = t!(TcpListener::bind(&addr));
+
+        let stream = t!(TcpStream::connect(&("localhost", addr.port())));
+
+        t!(stream.set_keepalive_interval(Duration::from_secs(10)));
+        assert_eq!(Duration::from_secs(10), t!(stream.keepalive_interval()));
+        t!(stream.set_keepalive_retries(5));
+        assert_eq!(5, t!(stream.keepalive_retries()));
+    }
+
+    #[test]
+    fn linger() {
+        let addr = next_test_ip4();
+        let _listener = t!(TcpListener::bind(&addr));
+
+        let stream = t!(TcpStream::connect(&("localhost", addr.port())));
+
+        t!(stream.set_linger(Some(Duration::from_secs(1))));
+        assert_eq!(Some(Duration::from_secs(1)), t!(stream.linger()));
+        t!(stream.set_linger(None));
+        assert_eq!(None, t!(stream.linger()));
+    }
+
+    #[test]
+    fn buffer_sizes() {
+        let addr = next_test_ip4();
+        let listener = t!(TcpListener::bind(&addr));
+
+        t!(listener.set_recv_buffer_size(16 * 1024));
+        assert!(t!(listener.recv_buffer_size()) >= 16 * 1024);
+
+        let stream = t!(TcpStream::connect(&("localhost", addr.port())));
+
+        t!(stream.set_send_buffer_size(16 * 1024));
+        assert!(t!(stream.send_buffer_size()) >= 16 * 1024);
+    }
+
+    #[test]
+    fn listener_builder() {
+        let addr = next_test_ip4();
+        let listener = t!(TcpListenerBuilder::new()
+            .backlog(16)
+            .ttl(100)
+            .recv_buffer_size(16 * 1024)
+            .bind(&addr));
+
+        assert_eq!(addr, t!(listener.local_addr()));
+        assert_eq!(100, t!(listener.ttl()));
+        assert!(t!(listener.recv_buffer_size()) >= 16 * 1024);
+
+        let _stream = t!(TcpStream::connect(&("localhost", addr.port())));
+        t!(listener.accept());
+    }
+
+    #[test]
+    #[cfg(any(target_os = "linux", target_os = "macos", target_os = "freebsd"))]
+    fn listener_builder_reuse_port() {
+        let addr = next_test_ip4();
+        let mut builder = TcpListenerBuilder::new();
+        builder.reuse_port(true);
+
+        let first = t!(builder.bind(&addr));
+        let second = t!(builder.bind(&addr));
+        assert!(t!(first.reuse_port()));
+        assert!(t!(second.reuse_port()));
+    }
 }
@@ -526,6 +526,108 @@ impl UdpSocket {
         self.0.ttl()
     }
 
+    /// Sets the value of the `SO_REUSEPORT` option on this socket.
+    ///
+    /// When set, other sockets which also enable this option may bind to the
+    /// same address and port. The option has to be set on every socket
+    /// sharing the port. An error of kind [`Other`] is returned on platforms
+    /// which do not support the option.
+    ///
+    /// [`Other`]: ../../std/io/enum.ErrorKind.html#variant.Other
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(udp_socket_options)]
+    ///
+    /// use std::net::UdpSocket;
+    ///
+    /// let socket = UdpSocket::bind("127.0.0.1:34254").expect("couldn't bind to address");
+    /// socket.set_reuse_port(true).expect("set_reuse_port call failed");
+    /// ```
+    #[unstable(feature = "udp_socket_options", issue = "0")]
+    pub fn set_reuse_port(&self, reuse: bool) -> io::Result<()> {
+        self.0.set_reuse_port(reuse)
+    }
+
+    /// Gets the value of the `SO_REUSEPORT` option on this socket.
+    ///
+    /// For more information about this option, see [`set_reuse_port`][link].
+    ///
+    /// [link]: #method.set_reuse_port
+    #[unstable(feature = "udp_socket_options", issue = "0")]
+    pub fn reuse_port(&self) -> io::Result<bool> {
+        self.0.reuse_port()
+    }
+
+    /// Sets the value of the `SO_RCVBUF` option on this socket.
+    ///
+    /// This is a hint to the system about the size of the receive buffer to
+    /// allocate for the socket. The system may adjust the value, so the
+    /// size reported by [`recv_buffer_size`] can differ from the one set.
+    ///
+    /// [`recv_buffer_size`]: #method.recv_buffer_size
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(udp_socket_options)]
+    ///
+    /// use std::net::UdpSocket;
+    ///
+    /// let socket = UdpSocket::bind("127.0.0.1:34254").expect("couldn't bind to address");
+    /// socket.set_recv_buffer_size(64 * 1024).expect("set_recv_buffer_size call failed");
+    /// ```
+    #[unstable(feature = "udp_socket_options", issue = "0")]
+    pub fn set_recv_buffer_size(&self, size: usize) -> io::Result<()> {
+        self.0.set_recv_buffer_size(size)
+    }
+
+    /// Gets the value of the `SO_RCVBUF` option on this socket.
+    ///
+    /// For more information about this option, see
+    /// [`set_recv_buffer_size`][link].
+    ///
+    /// [link]: #method.set_recv_buffer_size
+    #[unstable(feature = "udp_socket_options", issue = "0")]
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        self.0.recv_buffer_size()
+    }
+
+    /// Sets the value of the `SO_SNDBUF` option on this socket.
+    ///
+    /// This is a hint to the system about the size of the send buffer to
+    /// allocate for the socket. The system may adjust the value, so the
+    /// size reported by [`send_buffer_size`] can differ from the one set.
+    ///
+    /// [`send_buffer_size`]: #method.send_buffer_size
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(udp_socket_options)]
+    ///
+    /// use std::net::UdpSocket;
+    ///
+    /// let socket = UdpSocket::bind("127.0.0.1:34254").expect("couldn't bind to address");
+    /// socket.set_send_buffer_size(64 * 1024).expect("set_send_buffer_size call failed");
+    /// ```
+    #[unstable(feature = "udp_socket_options", issue = "0")]
+    pub fn set_send_buffer_size(&self, size: usize) -> io::Result<()> {
+        self.0.set_send_buffer_size(size)
+    }
+
+    /// Gets the value of the `SO_SNDBUF` option on this socket.
+    ///
+    /// For more information about this option, see
+    /// [`set_send_buffer_size`][link].
+    ///
+    /// [link]: #method.set_send_buffer_size
+    #[unstable(feature = "udp_socket_options", issue = "0")]
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        self.0.send_buffer_size()
+    }
+
     /// Executes an operation of the `IP_ADD_MEMBERSHIP` type.
     ///
     /// This function specifies a new multicast group for this socket to join.
@@ -1092,6 +1194,30 @@ mod tests {
         assert_eq!(ttl, t!(stream.ttl()));
     }
 
+    #[test]
+    fn buffer_sizes() {
+        let addr = next_test_ip4();
+
+        let socket = t!(UdpSocket::bind(&addr));
+
+        t!(socket.set_recv_buffer_size(16 * 1024));
+        assert!(t!(socket.recv_buffer_size()) >= 16 * 1024);
+        t!(socket.set_send_buffer_size(16 * 1024));
+        assert!(t!(socket.send_buffer_size()) >= 16 * 1024);
+    }
+
+    #[test]
+    #[cfg(any(target_os = "linux", target_os = "macos", target_os = "freebsd"))]
+    fn reuse_port() {
+        let addr = next_test_ip4();
+
+        let socket = t!(UdpSocket::bind(&addr));
+
+        assert_eq!(false, t!(socket.reuse_port()));
+        t!(socket.set_reuse_port(true));
+        assert_eq!(true, t!(socket.reuse_port()));
+    }
+
     #[test]
     fn set_nonblocking() {
         each_ip(&mut |addr, _| {
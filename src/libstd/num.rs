@@ -18,6 +18,8 @@
 
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use core::num::{FpCategory, ParseIntError, ParseFloatError, TryFromIntError};
+#[unstable(feature = "int_error_matching", issue = "0")]
+pub use core::num::IntErrorKind;
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use core::num::Wrapping;
 
@@ -1941,6 +1941,133 @@ impl Path {
         iter_after(self.components().rev(), child.components().rev()).is_some()
     }
 
+    /// Normalizes the path lexically, without touching the filesystem.
+    ///
+    /// Every `.` component is removed, and every `..` component removes the
+    /// component before it, unless that is a `..` itself. A `..` right after
+    /// the root directory is removed, as the parent of the root is the root
+    /// itself. Leading `..` components of relative paths are kept. An empty
+    /// result is returned as `.`.
+    ///
+    /// Unlike [`canonicalize`], this doesn't require the path to exist, and
+    /// doesn't resolve symbolic links. If `b` is a symbolic link, `a/b/..`
+    /// may well refer to another directory than `a`, so the normalized path
+    /// is only equivalent to the original if no such links are involved.
+    ///
+    /// [`canonicalize`]: #method.canonicalize
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(path_lexical)]
+    ///
+    /// use std::path::{Path, PathBuf};
+    ///
+    /// let path = Path::new("/foo/./test/../test/bar.rs");
+    /// assert_eq!(path.normalize_lexically(), PathBuf::from("/foo/test/bar.rs"));
+    ///
+    /// assert_eq!(Path::new("/../etc").normalize_lexically(), PathBuf::from("/etc"));
+    /// assert_eq!(Path::new("../a/../b").normalize_lexically(), PathBuf::from("../b"));
+    /// assert_eq!(Path::new("a/..").normalize_lexically(), PathBuf::from("."));
+    /// ```
+    #[unstable(feature = "path_lexical", issue = "0")]
+    pub fn normalize_lexically(&self) -> PathBuf {
+        let mut stack = Vec::new();
+        for component in self.components() {
+            match component {
+                Component::CurDir => {}
+                Component::ParentDir => match stack.last().cloned() {
+                    Some(Component::Normal(_)) => {
+                        stack.pop();
+                    }
+                    Some(Component::RootDir) => {}
+                    _ => stack.push(component),
+                },
+                _ => stack.push(component),
+            }
+        }
+        if stack.is_empty() {
+            return PathBuf::from(".");
+        }
+        stack.iter().collect()
+    }
+
+    /// Computes the relative path which leads from `base` to `self`.
+    ///
+    /// Both paths are [normalized lexically] first, so the filesystem isn't
+    /// consulted and symbolic links aren't resolved. Joining `base` and the
+    /// returned path and normalizing the result yields the normalized `self`.
+    /// If both paths are the same, `.` is returned.
+    ///
+    /// Returns [`None`] if there is no such path: if one of the paths is
+    /// absolute and the other one isn't, if their prefixes differ on Windows,
+    /// or if `base` has `..` components which `self` doesn't share, as the
+    /// names of the directories they lead to are unknown.
+    ///
+    /// [normalized lexically]: #method.normalize_lexically
+    /// [`None`]: ../../std/option/enum.Option.html#variant.None
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(path_lexical)]
+    ///
+    /// use std::path::{Path, PathBuf};
+    ///
+    /// let path = Path::new("/usr/share/doc");
+    /// assert_eq!(path.relative_to("/usr/lib"), Some(PathBuf::from("../share/doc")));
+    /// assert_eq!(path.relative_to("/usr"), Some(PathBuf::from("share/doc")));
+    /// assert_eq!(path.relative_to("/usr/share/doc/rust"), Some(PathBuf::from("..")));
+    ///
+    /// assert_eq!(path.relative_to("usr"), None);
+    /// assert_eq!(Path::new("a").relative_to("../b"), None);
+    /// ```
+    #[unstable(feature = "path_lexical", issue = "0")]
+    pub fn relative_to<P: AsRef<Path>>(&self, base: P) -> Option<PathBuf> {
+        self._relative_to(base.as_ref())
+    }
+
+    fn _relative_to(&self, base: &Path) -> Option<PathBuf> {
+        let path = self.normalize_lexically();
+        let base = base.normalize_lexically();
+
+        // Skip the components both paths start with, including their prefixes
+        // and root directories.
+        let mut path_rest = path.components();
+        let mut base_rest = base.components();
+        loop {
+            let (mut path_next, mut base_next) = (path_rest.clone(), base_rest.clone());
+            match (path_next.next(), base_next.next()) {
+                (Some(a), Some(b)) if a == b => {
+                    path_rest = path_next;
+                    base_rest = base_next;
+                }
+                _ => break,
+            }
+        }
+
+        // A normalized path can only contain `.` on its own.
+        let mut relative = PathBuf::new();
+        for component in base_rest {
+            match component {
+                Component::Normal(_) => relative.push(".."),
+                Component::CurDir => {}
+                _ => return None,
+            }
+        }
+        for component in path_rest {
+            match component {
+                Component::Prefix(_) | Component::RootDir => return None,
+                Component::CurDir => {}
+                _ => relative.push(component),
+            }
+        }
+        if relative.as_os_str().is_empty() {
+            relative.push(".");
+        }
+        Some(relative)
+    }
+
     /// Extracts the stem (non-extension) portion of [`self.file_name`].
     ///
     /// [`self.file_name`]: struct.Path.html#method.file_name
@@ -4021,6 +4148,95 @@ mod tests {
         assert!(path_buf.into_os_string().capacity() >= 15);
     }
 
+    #[test]
+    fn test_normalize_lexically() {
+        macro_rules! tn(
+            ($path:expr, $expected:expr) => ( {
+                // Comparing paths rather than strings lets the same
+                // expectations hold with either kind of separator.
+                let actual = Path::new($path).normalize_lexically();
+                assert!(actual == Path::new($expected),
+                        "normalizing {:?}: Expected {:?}, got {:?}",
+                        $path, $expected, actual);
+            });
+        );
+
+        tn!("", ".");
+        tn!(".", ".");
+        tn!("./", ".");
+        tn!("a/..", ".");
+        tn!("a/b/../c", "a/c");
+        tn!("a/./b/./", "a/b");
+        tn!("./a//b", "a/b");
+        tn!("..", "..");
+        tn!("../..", "../..");
+        tn!("a/../../b", "../b");
+        tn!("../a/../..", "../..");
+        tn!("/", "/");
+        tn!("/..", "/");
+        tn!("/../a/..", "/");
+        tn!("/a/b/../../../c", "/c");
+        tn!("/a/b/../..", "/");
+
+        if cfg!(windows) {
+            tn!("a\\b\\..\\c", "a\\c");
+            tn!("c:..", "C:..");
+            tn!("c:a\\..", "C:");
+            tn!("c:\\..\\a", "C:\\a");
+            tn!("\\\\server\\share\\a\\..\\..", "\\\\server\\share\\");
+            tn!("\\\\?\\C:\\a\\..\\b", "\\\\?\\C:\\b");
+        }
+    }
+
+    #[test]
+    fn test_relative_to() {
+        macro_rules! tr(
+            ($path:expr, $base:expr, $expected:expr) => ( {
+                let actual = Path::new($path).relative_to($base);
+                let expected: Option<&str> = $expected;
+                assert!(actual.as_ref().map(|p| p.as_path()) == expected.map(Path::new),
+                        "{:?} relative to {:?}: Expected {:?}, got {:?}",
+                        $path, $base, expected, actual);
+                if let Some(relative) = actual {
+                    let joined = Path::new($base).join(relative).normalize_lexically();
+                    assert_eq!(joined, Path::new($path).normalize_lexically());
+                }
+            });
+        );
+
+        tr!("a", "a", Some("."));
+        tr!("a/b", "a", Some("b"));
+        tr!("a", "a/b", Some(".."));
+        tr!("a/b", "a/c", Some("../b"));
+        tr!("a/b/c", "d/e", Some("../../a/b/c"));
+        tr!("a", ".", Some("a"));
+        tr!(".", "a/b", Some("../.."));
+        tr!("", "a", Some(".."));
+        tr!("a/./b/../c", "a/b/..", Some("c"));
+        tr!("../a", "b", Some("../../a"));
+        tr!("../a", "../b", Some("../a"));
+        tr!("a", "../b", None);
+        tr!("../../a", "../b", Some("../../a"));
+        tr!("/", "/", Some("."));
+        tr!("/a/b", "/", Some("a/b"));
+        tr!("/", "/a/b", Some("../.."));
+        tr!("/usr/share/doc", "/usr/lib", Some("../share/doc"));
+        tr!("/a", "/../a", Some("."));
+        tr!("/a", "a", None);
+        tr!("a", "/a", None);
+
+        if cfg!(windows) {
+            tr!("c:\\a\\b", "C:\\a\\c", Some("..\\b"));
+            tr!("c:\\a", "d:\\a", None);
+            tr!("c:a", "c:b", Some("..\\a"));
+            tr!("c:\\a", "c:a", None);
+            tr!("c:\\a", "\\a", None);
+            tr!("\\\\server\\share\\a", "\\\\server\\share\\b", Some("..\\a"));
+            tr!("\\\\server\\share\\a", "\\\\server\\other\\a", None);
+            tr!("\\\\?\\C:\\a", "C:\\a", None);
+        }
+    }
+
     #[test]
     fn display_format_flags() {
         assert_eq!(format!("a{:#<5}b", Path::new("").display()), "a#####b");
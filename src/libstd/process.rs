@@ -121,12 +121,13 @@ use io::prelude::*;
 use ffi::OsStr;
 use fmt;
 use fs;
-use io::{self, Initializer};
+use io::{self, Initializer, IoVec, IoVecMut};
 use path::Path;
 use str;
 use sys::pipe::{read2, AnonPipe};
 use sys::process as imp;
 use sys_common::{AsInner, AsInnerMut, FromInner, IntoInner};
+use time::Duration;
 
 /// Representation of a running or exited child process.
 ///
@@ -138,7 +139,8 @@ use sys_common::{AsInner, AsInnerMut, FromInner, IntoInner};
 /// There is no implementation of [`Drop`] for child processes,
 /// so if you do not ensure the `Child` has exited then it will continue to
 /// run, even after the `Child` handle to the child process has gone out of
-/// scope.
+/// scope. This can be changed with [`Command::kill_on_drop`], in which case
+/// the child is killed when the `Child` handle is dropped.
 ///
 /// Calling [`wait`](#method.wait) (or other functions that wrap around it) will make
 /// the parent process wait until the child has actually exited before
@@ -161,6 +163,7 @@ use sys_common::{AsInner, AsInnerMut, FromInner, IntoInner};
 /// ```
 ///
 /// [`Command`]: struct.Command.html
+/// [`Command::kill_on_drop`]: struct.Command.html#method.kill_on_drop
 /// [`Drop`]: ../../core/ops/trait.Drop.html
 /// [`wait`]: #method.wait
 #[stable(feature = "process", since = "1.0.0")]
@@ -187,6 +190,10 @@ impl AsInner<imp::Process> for Child {
     fn as_inner(&self) -> &imp::Process { &self.handle }
 }
 
+impl AsInnerMut<imp::Process> for Child {
+    fn as_inner_mut(&mut self) -> &mut imp::Process { &mut self.handle }
+}
+
 impl FromInner<(imp::Process, imp::StdioPipes)> for Child {
     fn from_inner((handle, io): (imp::Process, imp::StdioPipes)) -> Child {
         Child {
@@ -235,6 +242,10 @@ impl Write for ChildStdin {
         self.inner.write(buf)
     }
 
+    fn write_vectored(&mut self, bufs: &[IoVec]) -> io::Result<usize> {
+        self.inner.write_vectored(bufs)
+    }
+
     fn flush(&mut self) -> io::Result<()> {
         Ok(())
     }
@@ -281,6 +292,9 @@ impl Read for ChildStdout {
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
         self.inner.read(buf)
     }
+    fn read_vectored(&mut self, bufs: &mut [IoVecMut]) -> io::Result<usize> {
+        self.inner.read_vectored(bufs)
+    }
     #[inline]
     unsafe fn initializer(&self) -> Initializer {
         Initializer::nop()
@@ -328,6 +342,9 @@ impl Read for ChildStderr {
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
         self.inner.read(buf)
     }
+    fn read_vectored(&mut self, bufs: &mut [IoVecMut]) -> io::Result<usize> {
+        self.inner.read_vectored(bufs)
+    }
     #[inline]
     unsafe fn initializer(&self) -> Initializer {
         Initializer::nop()
@@ -384,6 +401,7 @@ impl fmt::Debug for ChildStderr {
 #[stable(feature = "process", since = "1.0.0")]
 pub struct Command {
     inner: imp::Command,
+    kill_on_drop: bool,
 }
 
 impl Command {
@@ -419,7 +437,7 @@ impl Command {
     /// ```
     #[stable(feature = "process", since = "1.0.0")]
     pub fn new<S: AsRef<OsStr>>(program: S) -> Command {
-        Command { inner: imp::Command::new(program.as_ref()) }
+        Command { inner: imp::Command::new(program.as_ref()), kill_on_drop: false }
     }
 
     /// Add an argument to pass to the program.
@@ -706,7 +724,7 @@ impl Command {
     /// ```
     #[stable(feature = "process", since = "1.0.0")]
     pub fn spawn(&mut self) -> io::Result<Child> {
-        self.inner.spawn(imp::Stdio::Inherit, true).map(Child::from_inner)
+        self.spawn_child(imp::Stdio::Inherit, true)
     }
 
     /// Executes the command as a child process, waiting for it to finish and
@@ -734,7 +752,7 @@ impl Command {
     /// ```
     #[stable(feature = "process", since = "1.0.0")]
     pub fn output(&mut self) -> io::Result<Output> {
-        self.inner.spawn(imp::Stdio::MakePipe, false).map(Child::from_inner)
+        self.spawn_child(imp::Stdio::MakePipe, false)
             .and_then(|p| p.wait_with_output())
     }
 
@@ -759,8 +777,71 @@ impl Command {
     /// ```
     #[stable(feature = "process", since = "1.0.0")]
     pub fn status(&mut self) -> io::Result<ExitStatus> {
-        self.inner.spawn(imp::Stdio::Inherit, true).map(Child::from_inner)
-                  .and_then(|mut p| p.wait())
+        self.spawn_child(imp::Stdio::Inherit, true)
+            .and_then(|mut p| p.wait())
+    }
+
+    /// Configures whether the spawned child process is forcibly killed when
+    /// its [`Child`] handle is dropped.
+    ///
+    /// By default a child process keeps running after its `Child` handle goes
+    /// out of scope. With this option enabled, dropping a `Child` which has
+    /// not yet been waited on will kill the process (as with [`kill`]) and
+    /// then reap it, so the child is guaranteed not to outlive the handle even
+    /// if the parent unwinds because of a panic.
+    ///
+    /// Note that only the child process itself is killed. Any processes it
+    /// has spawned in turn are left running; on Unix they can be reached by
+    /// spawning the child into its own process group and signalling that
+    /// group.
+    ///
+    /// [`Child`]: struct.Child.html
+    /// [`kill`]: struct.Child.html#method.kill
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(process_kill_on_drop)]
+    /// use std::process::Command;
+    ///
+    /// let child = Command::new("sleep")
+    ///                     .arg("1000")
+    ///                     .kill_on_drop(true)
+    ///                     .spawn()
+    ///                     .expect("sleep command failed to start");
+    ///
+    /// // `sleep` is killed here rather than left running in the background.
+    /// drop(child);
+    /// ```
+    #[unstable(feature = "process_kill_on_drop", issue = "0")]
+    pub fn kill_on_drop(&mut self, kill_on_drop: bool) -> &mut Command {
+        self.kill_on_drop = kill_on_drop;
+        self
+    }
+
+    fn spawn_child(&mut self, default: imp::Stdio, needs_stdin: bool) -> io::Result<Child> {
+        let mut child = Child::from_inner(self.inner.spawn(default, needs_stdin)?);
+        child.handle.set_kill_on_drop(self.kill_on_drop);
+        Ok(child)
+    }
+
+    // Spawns the command as `spawn` does, using the given standard input and
+    // output in place of the configured ones for this spawn only.
+    fn spawn_with_stdio(&mut self, stdin: Option<imp::Stdio>, stdout: Option<imp::Stdio>)
+                        -> io::Result<Child> {
+        let saved_stdin = stdin.map(|stdin| self.inner.replace_stdin(Some(stdin)));
+        let saved_stdout = stdout.map(|stdout| self.inner.replace_stdout(Some(stdout)));
+        let res = self.spawn();
+        // Putting the configuration back also closes our copy of the read end
+        // of the previous pipe, which would otherwise keep its writer from
+        // seeing `EPIPE` if this child exits without draining it.
+        if let Some(stdin) = saved_stdin {
+            self.inner.replace_stdin(stdin);
+        }
+        if let Some(stdout) = saved_stdout {
+            self.inner.replace_stdout(stdout);
+        }
+        res
     }
 }
 
@@ -956,6 +1037,44 @@ impl Stdio {
     /// ```
     #[stable(feature = "process", since = "1.0.0")]
     pub fn null() -> Stdio { Stdio(imp::Stdio::Null) }
+
+    /// The child's standard error should be written to the same place as its
+    /// standard output, interleaved with it in the order the child writes.
+    ///
+    /// This is only valid as the [`stderr`] of a [`Command`]; spawning a
+    /// command which uses it for stdin or stdout fails with an error of kind
+    /// `InvalidInput`. The child's stderr follows wherever its stdout goes,
+    /// including a pipe back to the parent, so combined with
+    /// [`Command::output`] it captures both streams into [`Output::stdout`],
+    /// leaving [`Output::stderr`] empty.
+    ///
+    /// On Unix this is implemented by `dup2`ing the child's stdout onto its
+    /// stderr.
+    ///
+    /// [`stderr`]: struct.Command.html#method.stderr
+    /// [`Command`]: struct.Command.html
+    /// [`Command::output`]: struct.Command.html#method.output
+    /// [`Output::stdout`]: struct.Output.html#structfield.stdout
+    /// [`Output::stderr`]: struct.Output.html#structfield.stderr
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(process_stderr_to_stdout)]
+    /// use std::process::{Command, Stdio};
+    ///
+    /// let output = Command::new("sh")
+    ///     .arg("-c")
+    ///     .arg("echo out; echo err >&2; echo out again")
+    ///     .stderr(Stdio::same_as_stdout())
+    ///     .output()
+    ///     .expect("Failed to execute command");
+    ///
+    /// assert_eq!(output.stdout, b"out\nerr\nout again\n");
+    /// assert!(output.stderr.is_empty());
+    /// ```
+    #[unstable(feature = "process_stderr_to_stdout", issue = "0")]
+    pub fn same_as_stdout() -> Stdio { Stdio(imp::Stdio::SameAsStdout) }
 }
 
 impl FromInner<imp::Stdio> for Stdio {
@@ -1193,6 +1312,52 @@ impl Child {
         Ok(self.handle.try_wait()?.map(ExitStatus))
     }
 
+    /// Waits for the child to exit for at most `dur`, returning the status
+    /// that it exited with if it did so in time.
+    ///
+    /// If the child has exited, then `Ok(Some(status))` is returned. If the
+    /// child is still running once `dur` has elapsed then `Ok(None)` is
+    /// returned and the child is left running; it may then be waited on
+    /// again, or [`kill`]ed. If an error occurs, then that error is returned.
+    ///
+    /// As with [`wait`], the stdin handle to the child process, if any, will
+    /// be closed before waiting.
+    ///
+    /// [`kill`]: #method.kill
+    /// [`wait`]: #method.wait
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// On Windows this waits on the process handle directly. On Unix there is
+    /// no portable way to wait for a child with a deadline, so the child is
+    /// polled with [`try_wait`] at increasing intervals (capped at 100
+    /// milliseconds) until it exits or `dur` elapses.
+    ///
+    /// [`try_wait`]: #method.try_wait
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(process_wait_timeout)]
+    /// use std::process::Command;
+    /// use std::time::Duration;
+    ///
+    /// let mut child = Command::new("sleep").arg("1000").spawn().unwrap();
+    ///
+    /// match child.wait_timeout(Duration::from_secs(5)).unwrap() {
+    ///     Some(status) => println!("exited with: {}", status),
+    ///     None => {
+    ///         child.kill().unwrap();
+    ///         child.wait().unwrap();
+    ///     }
+    /// }
+    /// ```
+    #[unstable(feature = "process_wait_timeout", issue = "0")]
+    pub fn wait_timeout(&mut self, dur: Duration) -> io::Result<Option<ExitStatus>> {
+        drop(self.stdin.take());
+        Ok(self.handle.wait_timeout(dur)?.map(ExitStatus))
+    }
+
     /// Simultaneously waits for the child to exit and collect all remaining
     /// output on the stdout/stderr handles, returning an `Output`
     /// instance.
@@ -1255,6 +1420,317 @@ impl Child {
     }
 }
 
+/// A builder for a pipeline of processes, each connected to the next one
+/// through an OS pipe, like `cmd1 | cmd2 | cmd3` in a shell.
+///
+/// The standard output of every [`Command`] in the pipeline but the last is
+/// connected to the standard input of the command following it. The standard
+/// input of the first command and the standard output of the last command are
+/// left as configured on those commands, as is the standard error of every
+/// command.
+///
+/// Any standard input configured on a command other than the first, and any
+/// standard output configured on a command other than the last, is ignored
+/// when the pipeline is spawned. The commands themselves are left as they
+/// were configured, so the pipeline can be run again.
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(process_pipeline)]
+/// use std::process::{Command, Pipeline};
+///
+/// let mut echo = Command::new("echo");
+/// echo.arg("Oh no, a tpyo!");
+/// let mut sed = Command::new("sed");
+/// sed.arg("s/tpyo/typo/");
+///
+/// let output = Pipeline::new()
+///     .push(echo)
+///     .push(sed)
+///     .output()
+///     .expect("failed to run pipeline");
+///
+/// assert!(output.status.success());
+/// assert_eq!(b"Oh no, a typo!\n", output.stdout.as_slice());
+/// ```
+///
+/// [`Command`]: struct.Command.html
+#[unstable(feature = "process_pipeline", issue = "0")]
+pub struct Pipeline {
+    commands: Vec<Command>,
+}
+
+impl Pipeline {
+    /// Constructs a new, empty `Pipeline`.
+    #[unstable(feature = "process_pipeline", issue = "0")]
+    pub fn new() -> Pipeline {
+        Pipeline { commands: Vec::new() }
+    }
+
+    /// Appends a command to the end of the pipeline. Its standard input will
+    /// be connected to the standard output of the command pushed before it.
+    #[unstable(feature = "process_pipeline", issue = "0")]
+    pub fn push(&mut self, command: Command) -> &mut Pipeline {
+        self.commands.push(command);
+        self
+    }
+
+    /// Spawns every command of the pipeline, connecting them together, and
+    /// returns a handle to the running pipeline.
+    ///
+    /// If any of the commands fails to spawn then the children which were
+    /// already spawned are killed and waited on, and the error is returned.
+    /// An error of kind `InvalidInput` is returned if the pipeline is empty.
+    #[unstable(feature = "process_pipeline", issue = "0")]
+    pub fn spawn(&mut self) -> io::Result<PipelineChild> {
+        self.spawn_pipeline(false)
+    }
+
+    // Spawns the pipeline, also piping the standard output of the last command
+    // back to the parent if `capture_stdout` is set.
+    fn spawn_pipeline(&mut self, capture_stdout: bool) -> io::Result<PipelineChild> {
+        if self.commands.is_empty() {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput,
+                                      "cannot spawn an empty pipeline"));
+        }
+
+        let last = self.commands.len() - 1;
+        let mut children: Vec<Child> = Vec::with_capacity(self.commands.len());
+        let mut prev_stdout: Option<ChildStdout> = None;
+        for (i, command) in self.commands.iter_mut().enumerate() {
+            let stdin = prev_stdout.take().map(|stdout| Stdio::from(stdout).0);
+            let stdout = if i != last || capture_stdout {
+                Some(imp::Stdio::MakePipe)
+            } else {
+                None
+            };
+            match command.spawn_with_stdio(stdin, stdout) {
+                Ok(mut child) => {
+                    if i != last {
+                        prev_stdout = child.stdout.take();
+                    }
+                    children.push(child);
+                }
+                Err(e) => {
+                    for child in children.iter_mut() {
+                        let _ = child.kill();
+                        let _ = child.wait();
+                    }
+                    return Err(e)
+                }
+            }
+        }
+
+        let stdin = children[0].stdin.take();
+        let stdout = children[last].stdout.take();
+        Ok(PipelineChild { children, stdin, stdout })
+    }
+
+    /// Runs the pipeline, waiting for every command in it to finish and
+    /// collecting their exit statuses.
+    ///
+    /// The standard input of the first command and the standard output of
+    /// the last one are inherited from the parent by default.
+    #[unstable(feature = "process_pipeline", issue = "0")]
+    pub fn status(&mut self) -> io::Result<PipelineStatus> {
+        self.spawn().and_then(|mut p| p.wait())
+    }
+
+    /// Runs the pipeline, waiting for every command in it to finish and
+    /// collecting the standard output of the last command.
+    ///
+    /// The standard output of the last command is always captured. The
+    /// standard input of the first command is inherited from the parent
+    /// unless configured otherwise.
+    #[unstable(feature = "process_pipeline", issue = "0")]
+    pub fn output(&mut self) -> io::Result<PipelineOutput> {
+        self.spawn_pipeline(true).and_then(|p| p.wait_with_output())
+    }
+}
+
+#[unstable(feature = "process_pipeline", issue = "0")]
+impl fmt::Debug for Pipeline {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, command) in self.commands.iter().enumerate() {
+            if i != 0 {
+                f.write_str(" | ")?;
+            }
+            command.fmt(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Representation of a running or exited pipeline of child processes.
+///
+/// This is created by [`Pipeline::spawn`]. As with [`Child`], there is no
+/// implementation of [`Drop`] which waits on the children.
+///
+/// [`Pipeline::spawn`]: struct.Pipeline.html#method.spawn
+/// [`Child`]: struct.Child.html
+/// [`Drop`]: ../../core/ops/trait.Drop.html
+#[unstable(feature = "process_pipeline", issue = "0")]
+pub struct PipelineChild {
+    children: Vec<Child>,
+
+    /// The handle for writing to the standard input of the first child, if it
+    /// has been captured.
+    #[unstable(feature = "process_pipeline", issue = "0")]
+    pub stdin: Option<ChildStdin>,
+
+    /// The handle for reading from the standard output of the last child, if
+    /// it has been captured.
+    #[unstable(feature = "process_pipeline", issue = "0")]
+    pub stdout: Option<ChildStdout>,
+}
+
+impl PipelineChild {
+    /// Returns the children of the pipeline, in the order their commands were
+    /// pushed.
+    #[unstable(feature = "process_pipeline", issue = "0")]
+    pub fn children(&mut self) -> &mut [Child] {
+        &mut self.children
+    }
+
+    /// Forces every child of the pipeline which has not yet been waited on to
+    /// exit. The first error encountered, if any, is returned once all of the
+    /// children have been killed.
+    #[unstable(feature = "process_pipeline", issue = "0")]
+    pub fn kill(&mut self) -> io::Result<()> {
+        let mut res = Ok(());
+        for child in self.children.iter_mut() {
+            if let Ok(None) = child.try_wait() {
+                if let Err(e) = child.kill() {
+                    if res.is_ok() {
+                        res = Err(e);
+                    }
+                }
+            }
+        }
+        res
+    }
+
+    /// Waits for every child of the pipeline to exit completely, returning
+    /// their exit statuses.
+    ///
+    /// The stdin handle of the pipeline, if any, is closed before waiting.
+    /// Every child is waited on even if waiting on one of them fails; the
+    /// first error encountered is then returned.
+    #[unstable(feature = "process_pipeline", issue = "0")]
+    pub fn wait(&mut self) -> io::Result<PipelineStatus> {
+        drop(self.stdin.take());
+        let mut statuses = Vec::with_capacity(self.children.len());
+        let mut err = None;
+        for child in self.children.iter_mut() {
+            match child.wait() {
+                Ok(status) => statuses.push(status),
+                Err(e) => {
+                    if err.is_none() {
+                        err = Some(e);
+                    }
+                }
+            }
+        }
+        match err {
+            Some(e) => Err(e),
+            None => Ok(PipelineStatus { statuses }),
+        }
+    }
+
+    /// Simultaneously waits for every child of the pipeline to exit and
+    /// collects all remaining output of the last child on the stdout handle.
+    ///
+    /// The stdin handle of the pipeline, if any, is closed before waiting.
+    /// Every child is waited on even if reading the output fails.
+    #[unstable(feature = "process_pipeline", issue = "0")]
+    pub fn wait_with_output(mut self) -> io::Result<PipelineOutput> {
+        drop(self.stdin.take());
+
+        let mut stdout = Vec::new();
+        let read = match self.stdout.take() {
+            Some(mut out) => out.read_to_end(&mut stdout).map(|_| ()),
+            None => Ok(()),
+        };
+
+        let status = self.wait();
+        read?;
+        Ok(PipelineOutput { status: status?, stdout })
+    }
+}
+
+#[unstable(feature = "process_pipeline", issue = "0")]
+impl fmt::Debug for PipelineChild {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("PipelineChild")
+            .field("children", &self.children)
+            .field("stdin", &self.stdin)
+            .field("stdout", &self.stdout)
+            .finish()
+    }
+}
+
+/// The exit statuses of a finished pipeline.
+///
+/// Like a shell with the `pipefail` option set, a pipeline is only considered
+/// successful if every one of its commands was.
+#[derive(PartialEq, Eq, Clone, Debug)]
+#[unstable(feature = "process_pipeline", issue = "0")]
+pub struct PipelineStatus {
+    statuses: Vec<ExitStatus>,
+}
+
+impl PipelineStatus {
+    /// Returns the exit status of every command in the pipeline, in the order
+    /// the commands were pushed.
+    #[unstable(feature = "process_pipeline", issue = "0")]
+    pub fn statuses(&self) -> &[ExitStatus] {
+        &self.statuses
+    }
+
+    /// Was termination successful for every command in the pipeline?
+    #[unstable(feature = "process_pipeline", issue = "0")]
+    pub fn success(&self) -> bool {
+        self.statuses.iter().all(|s| s.success())
+    }
+
+    /// Returns the exit status of the pipeline as a whole: that of the last
+    /// command which did not terminate successfully, or that of the last
+    /// command if all of them did.
+    #[unstable(feature = "process_pipeline", issue = "0")]
+    pub fn status(&self) -> ExitStatus {
+        *self.statuses.iter().rev().find(|s| !s.success())
+            .unwrap_or(&self.statuses[self.statuses.len() - 1])
+    }
+}
+
+#[unstable(feature = "process_pipeline", issue = "0")]
+impl fmt::Display for PipelineStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.status().fmt(f)
+    }
+}
+
+/// The output of a finished pipeline.
+///
+/// This is returned by either the [`output`] method of a [`Pipeline`], or the
+/// [`wait_with_output`] method of a [`PipelineChild`].
+///
+/// [`Pipeline`]: struct.Pipeline.html
+/// [`PipelineChild`]: struct.PipelineChild.html
+/// [`output`]: struct.Pipeline.html#method.output
+/// [`wait_with_output`]: struct.PipelineChild.html#method.wait_with_output
+#[derive(PartialEq, Eq, Clone, Debug)]
+#[unstable(feature = "process_pipeline", issue = "0")]
+pub struct PipelineOutput {
+    /// The exit statuses of the commands in the pipeline.
+    #[unstable(feature = "process_pipeline", issue = "0")]
+    pub status: PipelineStatus,
+    /// The data that the last command wrote to stdout.
+    #[unstable(feature = "process_pipeline", issue = "0")]
+    pub stdout: Vec<u8>,
+}
+
 /// Terminates the current process with the specified exit code.
 ///
 /// This function will never return and will immediately terminate the current
@@ -1844,6 +2320,176 @@ mod tests {
         assert!(events > 0);
     }
 
+    #[test]
+    #[cfg_attr(target_os = "android", ignore)]
+    fn test_wait_timeout() {
+        use time::Duration;
+
+        let mut p = if cfg!(target_os = "windows") {
+            Command::new("cmd").args(&["/C", "exit 0"]).spawn().unwrap()
+        } else {
+            Command::new("true").spawn().unwrap()
+        };
+        let status = p.wait_timeout(Duration::from_secs(60)).unwrap();
+        assert!(status.unwrap().success());
+
+        // A timeout too long to compute a deadline for doesn't overflow.
+        let mut p = if cfg!(target_os = "windows") {
+            Command::new("cmd").args(&["/C", "exit 0"]).spawn().unwrap()
+        } else {
+            Command::new("true").spawn().unwrap()
+        };
+        let status = p.wait_timeout(Duration::from_secs(u64::max_value())).unwrap();
+        assert!(status.unwrap().success());
+
+        let mut p = if cfg!(target_os = "windows") {
+            Command::new("cmd").args(&["/C", "ping -n 60 127.0.0.1 > nul"]).spawn().unwrap()
+        } else {
+            Command::new("sleep").arg("60").spawn().unwrap()
+        };
+        assert!(p.wait_timeout(Duration::from_millis(10)).unwrap().is_none());
+        p.kill().unwrap();
+        assert!(!p.wait_timeout(Duration::from_secs(60)).unwrap().unwrap().success());
+    }
+
+    #[test]
+    #[cfg_attr(target_os = "android", ignore)]
+    #[cfg(all(unix, not(target_os = "fuchsia")))]
+    fn test_kill_on_drop() {
+        use io;
+        use libc;
+
+        let p = Command::new("sleep").arg("60").kill_on_drop(true).spawn().unwrap();
+        let pid = p.id() as libc::pid_t;
+        drop(p);
+        // The child has been killed and reaped, so the pid no longer exists.
+        assert_eq!(unsafe { libc::kill(pid, 0) }, -1);
+        assert_eq!(io::Error::last_os_error().raw_os_error(), Some(libc::ESRCH));
+    }
+
+    #[test]
+    #[cfg_attr(target_os = "android", ignore)]
+    #[cfg(all(unix, not(target_os = "fuchsia")))]
+    fn test_signal_group() {
+        use os::unix::process::{ChildExt, CommandGroupExt, ExitStatusExt};
+        use libc;
+
+        let mut p = Command::new("/bin/sh")
+                            .arg("-c").arg("sleep 60 & wait")
+                            .process_group(0)
+                            .stdout(Stdio::piped())
+                            .spawn().unwrap();
+        p.signal_group(libc::SIGKILL).unwrap();
+        assert_eq!(p.wait().unwrap().signal(), Some(libc::SIGKILL));
+
+        // The backgrounded `sleep` holds the write end of the pipe, so reading
+        // to EOF only finishes once it was killed along with the shell.
+        let mut out = Vec::new();
+        p.stdout.take().unwrap().read_to_end(&mut out).unwrap();
+
+        let mut p = Command::new("true").spawn().unwrap();
+        assert_eq!(p.signal_group(libc::SIGKILL).unwrap_err().kind(),
+                   ErrorKind::InvalidInput);
+        p.wait().unwrap();
+    }
+
+    #[test]
+    #[cfg_attr(target_os = "android", ignore)]
+    #[cfg(unix)]
+    fn test_pipeline() {
+        use super::Pipeline;
+
+        let mut echo = Command::new("echo");
+        echo.arg("hello");
+        let mut tr = Command::new("tr");
+        tr.arg("a-z").arg("A-Z");
+
+        let output = Pipeline::new()
+            .push(echo)
+            .push(Command::new("cat"))
+            .push(tr)
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        assert_eq!(output.status.statuses().len(), 3);
+        assert_eq!(output.stdout, b"HELLO\n");
+    }
+
+    #[test]
+    #[cfg_attr(target_os = "android", ignore)]
+    #[cfg(unix)]
+    fn test_pipeline_failure() {
+        use super::Pipeline;
+
+        let mut exit_2 = Command::new("/bin/sh");
+        exit_2.arg("-c").arg("exit 2");
+
+        let status = Pipeline::new()
+            .push(Command::new("false"))
+            .push(exit_2)
+            .push(Command::new("true"))
+            .status()
+            .unwrap();
+        assert!(!status.success());
+        assert!(status.statuses()[2].success());
+        assert_eq!(status.status().code(), Some(2));
+    }
+
+    #[test]
+    #[cfg_attr(target_os = "android", ignore)]
+    #[cfg(unix)]
+    fn test_pipeline_rerun() {
+        use super::Pipeline;
+
+        let mut echo = Command::new("echo");
+        echo.arg("hello");
+        let mut pipeline = Pipeline::new();
+        pipeline.push(echo).push(Command::new("cat"));
+
+        for _ in 0..2 {
+            let output = pipeline.output().unwrap();
+            assert!(output.status.success());
+            assert_eq!(output.stdout, b"hello\n");
+        }
+
+        // `output` must not leave the last command's stdout captured.
+        let mut child = pipeline.spawn().unwrap();
+        assert!(child.stdout.is_none());
+        assert!(child.wait().unwrap().success());
+    }
+
+    #[test]
+    fn test_pipeline_spawn_failure() {
+        use super::Pipeline;
+
+        assert_eq!(Pipeline::new().spawn().unwrap_err().kind(), ErrorKind::InvalidInput);
+
+        let res = Pipeline::new()
+            .push(Command::new("if-this-is-a-binary-then-the-world-has-ended"))
+            .spawn();
+        assert!(res.is_err());
+    }
+
+    #[test]
+    #[cfg_attr(target_os = "android", ignore)]
+    #[cfg(unix)]
+    fn test_stderr_same_as_stdout() {
+        let output = Command::new("/bin/sh")
+            .arg("-c").arg("echo out; echo err >&2; echo out again")
+            .stderr(Stdio::same_as_stdout())
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        assert_eq!(output.stdout, b"out\nerr\nout again\n");
+        assert!(output.stderr.is_empty());
+    }
+
+    #[test]
+    fn test_stdout_same_as_stdout_is_error() {
+        let res = Command::new("echo").stdout(Stdio::same_as_stdout()).spawn();
+        assert_eq!(res.unwrap_err().kind(), ErrorKind::InvalidInput);
+    }
+
     #[test]
     fn test_command_implements_send() {
         fn take_send_type<T: Send>(_: T) {}
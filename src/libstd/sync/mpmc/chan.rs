@@ -0,0 +1,419 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The state shared by all the senders and receivers of a channel.
+//!
+//! Every channel is a queue behind a mutex. Threads which can't make progress
+//! register a `Waiter` with the channel and park until the channel notifies
+//! them about a change. The same waiter can be registered with several
+//! channels at once, which is all that `Select` needs.
+//!
+//! A rendezvous channel (one with a capacity of zero) still queues messages,
+//! but only from senders which then wait for their message to be taken.
+//! Otherwise a message is handed directly to a receiver which is blocked
+//! waiting for one, after claiming its waiter. A waiter can be claimed only
+//! once, so a receiver blocked in a `Select` gets at most one message, and
+//! the `Select` claims the waiter itself before it gives up waiting.
+
+use collections::VecDeque;
+use sync::{Arc, Mutex, MutexGuard};
+use sync::atomic::{AtomicBool, Ordering};
+use thread::{self, Thread};
+use time::Instant;
+
+use super::{RecvTimeoutError, SendTimeoutError, TryRecvError, TrySendError};
+
+/// A thread blocked on one or more channels.
+pub struct Waiter {
+    thread: Thread,
+    notified: AtomicBool,
+    // Set by the first channel to hand a message to the thread, or by the
+    // thread itself once it stops waiting, whichever comes first.
+    claimed: AtomicBool,
+    // Whether the thread is blocked in a `Select`. Such a thread may well act
+    // on another channel than the one which woke it up.
+    selecting: bool,
+}
+
+impl Waiter {
+    pub fn new(selecting: bool) -> Arc<Waiter> {
+        Arc::new(Waiter {
+            thread: thread::current(),
+            notified: AtomicBool::new(false),
+            claimed: AtomicBool::new(false),
+            selecting,
+        })
+    }
+
+    /// Prepares the waiter for being registered again.
+    pub fn reset(&self) {
+        self.notified.store(false, Ordering::SeqCst);
+        self.claimed.store(false, Ordering::SeqCst);
+    }
+
+    /// Claims the waiter, returning `false` if it has been claimed already.
+    ///
+    /// Once its thread has claimed the waiter, no channel can hand a message
+    /// to it any more. If that fails, a channel was quicker, and the message
+    /// is waiting to be taken with `Channel::take_handoff`.
+    pub fn claim(&self) -> bool {
+        !self.claimed.swap(true, Ordering::SeqCst)
+    }
+
+    fn is_claimed(&self) -> bool {
+        self.claimed.load(Ordering::SeqCst)
+    }
+
+    fn notify(&self) {
+        self.notified.store(true, Ordering::SeqCst);
+        self.thread.unpark();
+    }
+
+    /// Blocks the current thread until the waiter has been notified or the
+    /// deadline has passed. Must be called from the thread which created the
+    /// waiter.
+    pub fn wait(&self, deadline: Option<Instant>) {
+        while !self.notified.load(Ordering::SeqCst) {
+            match deadline {
+                None => thread::park(),
+                Some(deadline) => {
+                    let now = Instant::now();
+                    if now >= deadline {
+                        return
+                    }
+                    thread::park_timeout(deadline - now);
+                }
+            }
+        }
+    }
+}
+
+/// Wakes up the waiters in the order they started waiting until one has been
+/// woken which is sure to act on the notification.
+fn notify_one(waiters: &mut VecDeque<Arc<Waiter>>) {
+    while let Some(waiter) = waiters.pop_front() {
+        waiter.notify();
+        if !waiter.selecting {
+            break
+        }
+    }
+}
+
+fn notify_all(waiters: &mut VecDeque<Arc<Waiter>>) {
+    for waiter in waiters.drain(..) {
+        waiter.notify();
+    }
+}
+
+fn unregister(waiters: &mut VecDeque<Arc<Waiter>>, waiter: &Arc<Waiter>) {
+    // The waiter is already gone if it has been notified.
+    if let Some(pos) = waiters.iter().position(|w| Arc::ptr_eq(w, waiter)) {
+        waiters.remove(pos);
+    }
+}
+
+pub fn has_passed(deadline: Option<Instant>) -> bool {
+    deadline.map_or(false, |deadline| Instant::now() >= deadline)
+}
+
+pub struct Channel<T> {
+    inner: Mutex<Inner<T>>,
+}
+
+struct Inner<T> {
+    // Every message is stamped so that a sender on a rendezvous channel can
+    // tell whether its message has been taken yet.
+    queue: VecDeque<(usize, T)>,
+    next_stamp: usize,
+    cap: Option<usize>,
+    senders: usize,
+    receivers: usize,
+    // Messages handed directly to a blocked receiver on a rendezvous channel,
+    // next to the receiver's waiter.
+    handoffs: Vec<(Arc<Waiter>, T)>,
+    recv_waiters: VecDeque<Arc<Waiter>>,
+    send_waiters: VecDeque<Arc<Waiter>>,
+}
+
+impl<T> Inner<T> {
+    fn is_full(&self) -> bool {
+        match self.cap {
+            None => false,
+            Some(cap) => self.queue.len() >= cap,
+        }
+    }
+
+    fn push(&mut self, msg: T) -> usize {
+        let stamp = self.next_stamp;
+        self.next_stamp = stamp.wrapping_add(1);
+        self.queue.push_back((stamp, msg));
+        notify_one(&mut self.recv_waiters);
+        stamp
+    }
+
+    fn try_push(&mut self, msg: T) -> Result<(), TrySendError<T>> {
+        if self.receivers == 0 {
+            Err(TrySendError::Disconnected(msg))
+        } else if self.cap == Some(0) {
+            self.hand_off(msg).map_err(TrySendError::Full)
+        } else if self.is_full() {
+            Err(TrySendError::Full(msg))
+        } else {
+            self.push(msg);
+            Ok(())
+        }
+    }
+
+    fn try_pop(&mut self) -> Result<T, TryRecvError> {
+        match self.queue.pop_front() {
+            Some((_, msg)) => {
+                // Every sender on a rendezvous channel waits for its own
+                // message, so they all need to check whether this was it.
+                if self.cap == Some(0) {
+                    notify_all(&mut self.send_waiters);
+                } else {
+                    notify_one(&mut self.send_waiters);
+                }
+                Ok(msg)
+            }
+            None if self.senders == 0 => Err(TryRecvError::Disconnected),
+            None => Err(TryRecvError::Empty),
+        }
+    }
+
+    fn is_queued(&self, stamp: usize) -> bool {
+        self.queue.iter().any(|&(s, _)| s == stamp)
+    }
+
+    // Gives the message to the first blocked receiver whose waiter can still
+    // be claimed.
+    fn hand_off(&mut self, msg: T) -> Result<(), T> {
+        match self.recv_waiters.iter().position(|waiter| waiter.claim()) {
+            Some(pos) => {
+                let waiter = self.recv_waiters.remove(pos).unwrap();
+                waiter.notify();
+                self.handoffs.push((waiter, msg));
+                Ok(())
+            }
+            None => Err(msg),
+        }
+    }
+
+    fn take_handoff(&mut self, waiter: &Arc<Waiter>) -> Option<T> {
+        let pos = self.handoffs.iter().position(|&(ref w, _)| Arc::ptr_eq(w, waiter))?;
+        Some(self.handoffs.swap_remove(pos).1)
+    }
+
+    fn register_recv(&mut self, waiter: &Arc<Waiter>) {
+        self.recv_waiters.push_back(waiter.clone());
+        if self.cap == Some(0) {
+            // A sender selecting on this channel can hand off a message now.
+            notify_all(&mut self.send_waiters);
+        }
+    }
+
+}
+
+impl<T> Channel<T> {
+    pub fn new(cap: Option<usize>) -> Channel<T> {
+        Channel {
+            inner: Mutex::new(Inner {
+                queue: VecDeque::new(),
+                next_stamp: 0,
+                cap,
+                senders: 1,
+                receivers: 1,
+                handoffs: Vec::new(),
+                recv_waiters: VecDeque::new(),
+                send_waiters: VecDeque::new(),
+            }),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<Inner<T>> {
+        // Nothing panics while the lock is held, not even destructors of
+        // messages, so the mutex is never poisoned.
+        self.inner.lock().unwrap()
+    }
+
+    pub fn add_sender(&self) {
+        self.lock().senders += 1;
+    }
+
+    pub fn add_receiver(&self) {
+        self.lock().receivers += 1;
+    }
+
+    pub fn drop_sender(&self) {
+        let mut inner = self.lock();
+        inner.senders -= 1;
+        if inner.senders == 0 {
+            notify_all(&mut inner.recv_waiters);
+        }
+    }
+
+    pub fn drop_receiver(&self) {
+        // Messages still in the queue are dropped along with the channel.
+        // Until then, blocked senders on a rendezvous channel take theirs
+        // back out of it.
+        let mut inner = self.lock();
+        inner.receivers -= 1;
+        if inner.receivers == 0 {
+            notify_all(&mut inner.send_waiters);
+        }
+    }
+
+    pub fn try_send(&self, msg: T) -> Result<(), TrySendError<T>> {
+        self.lock().try_push(msg)
+    }
+
+    pub fn send(&self, msg: T, deadline: Option<Instant>) -> Result<(), SendTimeoutError<T>> {
+        let mut inner = self.lock();
+        if inner.cap == Some(0) {
+            return self.send_rendezvous(inner, msg, deadline);
+        }
+
+        let waiter = Waiter::new(false);
+        let mut msg = msg;
+        loop {
+            msg = match inner.try_push(msg) {
+                Ok(()) => return Ok(()),
+                Err(TrySendError::Disconnected(msg)) => {
+                    return Err(SendTimeoutError::Disconnected(msg))
+                }
+                Err(TrySendError::Full(msg)) => msg,
+            };
+            if has_passed(deadline) {
+                return Err(SendTimeoutError::Timeout(msg));
+            }
+
+            waiter.reset();
+            inner.send_waiters.push_back(waiter.clone());
+            drop(inner);
+            waiter.wait(deadline);
+            inner = self.lock();
+            unregister(&mut inner.send_waiters, &waiter);
+        }
+    }
+
+    // Queues the message right away, and then waits for a receiver to take
+    // it. If that doesn't happen in time the message is taken back out.
+    fn send_rendezvous<'a>(&'a self,
+                           mut inner: MutexGuard<'a, Inner<T>>,
+                           msg: T,
+                           deadline: Option<Instant>) -> Result<(), SendTimeoutError<T>> {
+        if inner.receivers == 0 {
+            return Err(SendTimeoutError::Disconnected(msg));
+        }
+
+        let msg = match inner.hand_off(msg) {
+            Ok(()) => return Ok(()),
+            Err(msg) => msg,
+        };
+        let stamp = inner.push(msg);
+        let waiter = Waiter::new(false);
+        loop {
+            if !inner.is_queued(stamp) {
+                return Ok(());
+            }
+            if inner.receivers == 0 || has_passed(deadline) {
+                let pos = inner.queue.iter().position(|&(s, _)| s == stamp).unwrap();
+                let (_, msg) = inner.queue.remove(pos).unwrap();
+                return Err(if inner.receivers == 0 {
+                    SendTimeoutError::Disconnected(msg)
+                } else {
+                    SendTimeoutError::Timeout(msg)
+                });
+            }
+
+            waiter.reset();
+            inner.send_waiters.push_back(waiter.clone());
+            drop(inner);
+            waiter.wait(deadline);
+            inner = self.lock();
+            unregister(&mut inner.send_waiters, &waiter);
+        }
+    }
+
+    pub fn try_recv(&self) -> Result<T, TryRecvError> {
+        self.lock().try_pop()
+    }
+
+    pub fn recv(&self, deadline: Option<Instant>) -> Result<T, RecvTimeoutError> {
+        let mut inner = self.lock();
+        let waiter = Waiter::new(false);
+        loop {
+            match inner.try_pop() {
+                Ok(msg) => return Ok(msg),
+                Err(TryRecvError::Disconnected) => return Err(RecvTimeoutError::Disconnected),
+                Err(TryRecvError::Empty) => {}
+            }
+            if has_passed(deadline) {
+                return Err(RecvTimeoutError::Timeout);
+            }
+
+            waiter.reset();
+            inner.register_recv(&waiter);
+            drop(inner);
+            waiter.wait(deadline);
+            inner = self.lock();
+            unregister(&mut inner.recv_waiters, &waiter);
+            if let Some(msg) = inner.take_handoff(&waiter) {
+                return Ok(msg);
+            }
+        }
+    }
+
+    /// Registers a waiter which is notified once a receive operation may be
+    /// able to make progress.
+    pub fn register_recv(&self, waiter: &Arc<Waiter>) {
+        self.lock().register_recv(waiter);
+    }
+
+    pub fn unregister_recv(&self, waiter: &Arc<Waiter>) {
+        unregister(&mut self.lock().recv_waiters, waiter);
+    }
+
+    /// Takes the message handed to a waiter which failed to claim itself.
+    pub fn take_handoff(&self, waiter: &Arc<Waiter>) -> Option<T> {
+        self.lock().take_handoff(waiter)
+    }
+
+    /// Returns whether a receive operation would make progress right now.
+    pub fn recv_ready(&self) -> bool {
+        let inner = self.lock();
+        !inner.queue.is_empty() || inner.senders == 0
+    }
+
+    /// Returns whether a send operation would make progress right now, not
+    /// counting `waiter` as a receiver to hand the message to.
+    pub fn send_ready(&self, waiter: &Arc<Waiter>) -> bool {
+        let inner = self.lock();
+        if inner.receivers == 0 {
+            return true;
+        }
+        match inner.cap {
+            Some(0) => inner.recv_waiters.iter().any(|w| {
+                !Arc::ptr_eq(w, waiter) && !w.is_claimed()
+            }),
+            _ => !inner.is_full(),
+        }
+    }
+
+    /// Registers a waiter which is notified once a send operation may be able
+    /// to make progress.
+    pub fn register_send(&self, waiter: &Arc<Waiter>) {
+        self.lock().send_waiters.push_back(waiter.clone());
+    }
+
+    pub fn unregister_send(&self, waiter: &Arc<Waiter>) {
+        unregister(&mut self.lock().send_waiters, waiter);
+    }
+}
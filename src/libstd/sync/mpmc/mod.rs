@@ -0,0 +1,804 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Multi-producer, multi-consumer FIFO queue communication primitives.
+//!
+//! This module provides channels which, unlike the ones in [`mpsc`], may
+//! have any number of receivers as well as senders. Both [`Sender`] and
+//! [`Receiver`] can be cloned and shared between threads, and every message
+//! is received by exactly one of the receivers. This makes them a good fit
+//! for distributing work among a pool of threads.
+//!
+//! These channels come in the same two flavors as the ones in [`mpsc`]:
+//!
+//! 1. An asynchronous, infinitely buffered channel. The [`channel`] function
+//!    returns a `(Sender, Receiver)` tuple where all sends will be
+//!    **asynchronous** (they never block).
+//!
+//! 2. A synchronous, bounded channel. The [`sync_channel`] function returns a
+//!    `(Sender, Receiver)` tuple where sends will block until there is buffer
+//!    space available. A bound of 0 makes the channel a "rendezvous" channel
+//!    where each sender hands off its message to a receiver directly.
+//!
+//! Every blocking operation comes with variants which give up after a
+//! timeout or at a deadline, and [`Select`] waits on operations on several
+//! channels at once.
+//!
+//! [`mpsc`]: ../mpsc/index.html
+//! [`Sender`]: struct.Sender.html
+//! [`Receiver`]: struct.Receiver.html
+//! [`channel`]: fn.channel.html
+//! [`sync_channel`]: fn.sync_channel.html
+//! [`Select`]: struct.Select.html
+//!
+//! ## Disconnection
+//!
+//! Disconnection works just like it does for [`mpsc`] channels. Once all of
+//! the senders of a channel have been dropped, receivers get the messages
+//! which are still buffered and then an error. Once all of the receivers have
+//! been dropped, sending fails and hands the message back.
+//!
+//! # Examples
+//!
+//! A pool of workers sharing a queue of jobs:
+//!
+//! ```
+//! #![feature(mpmc_channel)]
+//!
+//! use std::sync::mpmc::channel;
+//! use std::thread;
+//!
+//! let (job_tx, job_rx) = channel();
+//! let (result_tx, result_rx) = channel();
+//!
+//! for _ in 0..4 {
+//!     let job_rx = job_rx.clone();
+//!     let result_tx = result_tx.clone();
+//!     thread::spawn(move || {
+//!         for job in job_rx {
+//!             result_tx.send(job * 2).unwrap();
+//!         }
+//!     });
+//! }
+//! drop(result_tx);
+//!
+//! for job in 0..100 {
+//!     job_tx.send(job).unwrap();
+//! }
+//! // Hang up, so that the workers finish once the queue has been drained.
+//! drop(job_tx);
+//!
+//! let total: i32 = result_rx.iter().sum();
+//! assert_eq!(total, 9900);
+//! ```
+
+#![unstable(feature = "mpmc_channel", issue = "0")]
+
+use error;
+use fmt;
+use sync::Arc;
+use time::{Duration, Instant};
+
+pub use sync::mpsc::{RecvError, RecvTimeoutError, SendError, TryRecvError, TrySendError};
+pub use self::select::Select;
+
+use self::chan::Channel;
+
+mod chan;
+mod select;
+
+/// The sending half of a multi-producer, multi-consumer channel.
+///
+/// Senders can be cloned and shared between threads. Sending over a bounded
+/// channel blocks while its buffer is full.
+pub struct Sender<T> {
+    chan: Arc<Channel<T>>,
+}
+
+/// The receiving half of a multi-producer, multi-consumer channel.
+///
+/// Receivers can be cloned and shared between threads. Every message is
+/// received by only one of them.
+pub struct Receiver<T> {
+    chan: Arc<Channel<T>>,
+}
+
+/// An iterator over messages on a [`Receiver`], created by [`iter`].
+///
+/// This iterator blocks whenever [`next`] is called, waiting for a new
+/// message, and returns [`None`] once the channel is empty and has hung up.
+///
+/// [`Receiver`]: struct.Receiver.html
+/// [`iter`]: struct.Receiver.html#method.iter
+/// [`next`]: ../../../std/iter/trait.Iterator.html#tymethod.next
+/// [`None`]: ../../../std/option/enum.Option.html#variant.None
+#[derive(Debug)]
+pub struct Iter<'a, T: 'a> {
+    rx: &'a Receiver<T>,
+}
+
+/// An iterator which yields the messages which are already buffered in a
+/// [`Receiver`], created by [`try_iter`].
+///
+/// [`Receiver`]: struct.Receiver.html
+/// [`try_iter`]: struct.Receiver.html#method.try_iter
+#[derive(Debug)]
+pub struct TryIter<'a, T: 'a> {
+    rx: &'a Receiver<T>,
+}
+
+/// An owning iterator over messages on a [`Receiver`], created by
+/// **Receiver::into_iter**.
+///
+/// [`Receiver`]: struct.Receiver.html
+#[derive(Debug)]
+pub struct IntoIter<T> {
+    rx: Receiver<T>,
+}
+
+/// An error returned from [`Sender::send_timeout`].
+///
+/// Both variants hold the message which could not be sent.
+///
+/// [`Sender::send_timeout`]: struct.Sender.html#method.send_timeout
+#[derive(PartialEq, Eq, Clone, Copy)]
+pub enum SendTimeoutError<T> {
+    /// The message could not be sent before the timeout passed, because the
+    /// channel stayed full.
+    Timeout(T),
+
+    /// The message could not be sent because all of the receivers of the
+    /// channel have been dropped.
+    Disconnected(T),
+}
+
+/// An error returned from the `wait_timeout`, `wait_deadline` and `try_wait`
+/// methods of [`Select`] when none of its operations could be performed in
+/// time.
+///
+/// [`Select`]: struct.Select.html
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct SelectTimeoutError;
+
+/// Creates a new asynchronous multi-producer, multi-consumer channel.
+///
+/// Sending over this channel never blocks, because its buffer grows as
+/// needed.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(mpmc_channel)]
+///
+/// use std::sync::mpmc::channel;
+/// use std::thread;
+///
+/// let (tx, rx) = channel();
+/// let rx2 = rx.clone();
+///
+/// let handle = thread::spawn(move || rx2.recv().unwrap());
+/// tx.send(1).unwrap();
+/// tx.send(2).unwrap();
+///
+/// let first = rx.recv().unwrap();
+/// let second = handle.join().unwrap();
+/// assert_eq!(first + second, 3);
+/// ```
+pub fn channel<T>() -> (Sender<T>, Receiver<T>) {
+    new_channel(None)
+}
+
+/// Creates a new synchronous, bounded multi-producer, multi-consumer channel.
+///
+/// The channel buffers at most `bound` messages, and sending blocks while
+/// the buffer is full. With a `bound` of 0, every send blocks until a
+/// receiver has taken the message.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(mpmc_channel)]
+///
+/// use std::sync::mpmc::{sync_channel, TrySendError};
+///
+/// let (tx, rx) = sync_channel(1);
+/// tx.send(1).unwrap();
+/// assert_eq!(tx.try_send(2), Err(TrySendError::Full(2)));
+/// assert_eq!(rx.recv(), Ok(1));
+/// ```
+pub fn sync_channel<T>(bound: usize) -> (Sender<T>, Receiver<T>) {
+    new_channel(Some(bound))
+}
+
+fn new_channel<T>(cap: Option<usize>) -> (Sender<T>, Receiver<T>) {
+    let chan = Arc::new(Channel::new(cap));
+    (Sender { chan: chan.clone() }, Receiver { chan: chan })
+}
+
+impl<T> Sender<T> {
+    /// Sends a message over the channel, blocking while the channel is full.
+    ///
+    /// # Errors
+    ///
+    /// If all of the receivers have been dropped, the message is returned in
+    /// a [`SendError`].
+    ///
+    /// [`SendError`]: struct.SendError.html
+    pub fn send(&self, msg: T) -> Result<(), SendError<T>> {
+        self.chan.send(msg, None).map_err(|err| match err {
+            SendTimeoutError::Disconnected(msg) => SendError(msg),
+            SendTimeoutError::Timeout(_) => unreachable!(),
+        })
+    }
+
+    /// Attempts to send a message over the channel without blocking.
+    ///
+    /// A rendezvous channel only accepts the message if a receiver is
+    /// already waiting for it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TrySendError::Full`] if the channel is full, and
+    /// [`TrySendError::Disconnected`] if all of the receivers have been
+    /// dropped. Both hold the message.
+    ///
+    /// [`TrySendError::Full`]: enum.TrySendError.html#variant.Full
+    /// [`TrySendError::Disconnected`]: enum.TrySendError.html#variant.Disconnected
+    pub fn try_send(&self, msg: T) -> Result<(), TrySendError<T>> {
+        self.chan.try_send(msg)
+    }
+
+    /// Sends a message over the channel, blocking for at most `timeout`
+    /// while the channel is full.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SendTimeoutError::Timeout`] if the channel is still full
+    /// once the timeout has passed, and [`SendTimeoutError::Disconnected`] if
+    /// all of the receivers have been dropped. Both hold the message.
+    ///
+    /// [`SendTimeoutError::Timeout`]: enum.SendTimeoutError.html#variant.Timeout
+    /// [`SendTimeoutError::Disconnected`]: enum.SendTimeoutError.html#variant.Disconnected
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(mpmc_channel)]
+    ///
+    /// use std::sync::mpmc::{sync_channel, SendTimeoutError};
+    /// use std::time::Duration;
+    ///
+    /// let (tx, rx) = sync_channel(0);
+    ///
+    /// // Nobody is receiving.
+    /// assert_eq!(tx.send_timeout(1, Duration::from_millis(10)),
+    ///            Err(SendTimeoutError::Timeout(1)));
+    ///
+    /// drop(rx);
+    /// assert_eq!(tx.send_timeout(2, Duration::from_millis(10)),
+    ///            Err(SendTimeoutError::Disconnected(2)));
+    /// ```
+    pub fn send_timeout(&self, msg: T, timeout: Duration) -> Result<(), SendTimeoutError<T>> {
+        self.chan.send(msg, Some(Instant::now() + timeout))
+    }
+
+    /// Sends a message over the channel, blocking until `deadline` at the
+    /// latest while the channel is full.
+    ///
+    /// # Errors
+    ///
+    /// The same as for [`send_timeout`].
+    ///
+    /// [`send_timeout`]: #method.send_timeout
+    pub fn send_deadline(&self, msg: T, deadline: Instant) -> Result<(), SendTimeoutError<T>> {
+        self.chan.send(msg, Some(deadline))
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Sender<T> {
+        self.chan.add_sender();
+        Sender { chan: self.chan.clone() }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        self.chan.drop_sender();
+    }
+}
+
+impl<T> fmt::Debug for Sender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Sender").finish()
+    }
+}
+
+impl<T> Receiver<T> {
+    /// Attempts to receive a message from the channel without blocking.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TryRecvError::Empty`] if there is no message, and
+    /// [`TryRecvError::Disconnected`] if there is none and all of the senders
+    /// have been dropped.
+    ///
+    /// [`TryRecvError::Empty`]: enum.TryRecvError.html#variant.Empty
+    /// [`TryRecvError::Disconnected`]: enum.TryRecvError.html#variant.Disconnected
+    pub fn try_recv(&self) -> Result<T, TryRecvError> {
+        self.chan.try_recv()
+    }
+
+    /// Receives a message from the channel, blocking while it is empty.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RecvError`] if the channel is empty and all of the senders
+    /// have been dropped.
+    ///
+    /// [`RecvError`]: struct.RecvError.html
+    pub fn recv(&self) -> Result<T, RecvError> {
+        self.chan.recv(None).map_err(|_| RecvError)
+    }
+
+    /// Receives a message from the channel, blocking for at most `timeout`
+    /// while it is empty.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RecvTimeoutError::Timeout`] if the channel is still empty
+    /// once the timeout has passed, and [`RecvTimeoutError::Disconnected`] if
+    /// it is empty and all of the senders have been dropped.
+    ///
+    /// [`RecvTimeoutError::Timeout`]: enum.RecvTimeoutError.html#variant.Timeout
+    /// [`RecvTimeoutError::Disconnected`]: enum.RecvTimeoutError.html#variant.Disconnected
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(mpmc_channel)]
+    ///
+    /// use std::sync::mpmc::{channel, RecvTimeoutError};
+    /// use std::time::Duration;
+    ///
+    /// let (tx, rx) = channel::<i32>();
+    /// assert_eq!(rx.recv_timeout(Duration::from_millis(10)),
+    ///            Err(RecvTimeoutError::Timeout));
+    ///
+    /// drop(tx);
+    /// assert_eq!(rx.recv_timeout(Duration::from_millis(10)),
+    ///            Err(RecvTimeoutError::Disconnected));
+    /// ```
+    pub fn recv_timeout(&self, timeout: Duration) -> Result<T, RecvTimeoutError> {
+        self.chan.recv(Some(Instant::now() + timeout))
+    }
+
+    /// Receives a message from the channel, blocking until `deadline` at the
+    /// latest while it is empty.
+    ///
+    /// # Errors
+    ///
+    /// The same as for [`recv_timeout`].
+    ///
+    /// [`recv_timeout`]: #method.recv_timeout
+    pub fn recv_deadline(&self, deadline: Instant) -> Result<T, RecvTimeoutError> {
+        self.chan.recv(Some(deadline))
+    }
+
+    /// Returns an iterator which blocks waiting for messages, and ends once
+    /// the channel is empty and all of the senders have been dropped.
+    pub fn iter(&self) -> Iter<T> {
+        Iter { rx: self }
+    }
+
+    /// Returns an iterator over the messages which can be received without
+    /// blocking.
+    pub fn try_iter(&self) -> TryIter<T> {
+        TryIter { rx: self }
+    }
+}
+
+impl<T> Clone for Receiver<T> {
+    fn clone(&self) -> Receiver<T> {
+        self.chan.add_receiver();
+        Receiver { chan: self.chan.clone() }
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        self.chan.drop_receiver();
+    }
+}
+
+impl<T> fmt::Debug for Receiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Receiver").finish()
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> { self.rx.recv().ok() }
+}
+
+impl<'a, T> Iterator for TryIter<'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> { self.rx.try_recv().ok() }
+}
+
+impl<'a, T> IntoIterator for &'a Receiver<T> {
+    type Item = T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> { self.iter() }
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> { self.rx.recv().ok() }
+}
+
+impl<T> IntoIterator for Receiver<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter { rx: self }
+    }
+}
+
+impl<T> fmt::Debug for SendTimeoutError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SendTimeoutError::Timeout(..) => "Timeout(..)".fmt(f),
+            SendTimeoutError::Disconnected(..) => "Disconnected(..)".fmt(f),
+        }
+    }
+}
+
+impl<T> fmt::Display for SendTimeoutError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SendTimeoutError::Timeout(..) => "timed out waiting on send operation".fmt(f),
+            SendTimeoutError::Disconnected(..) => "sending on a closed channel".fmt(f),
+        }
+    }
+}
+
+impl<T: Send> error::Error for SendTimeoutError<T> {
+    fn description(&self) -> &str {
+        match *self {
+            SendTimeoutError::Timeout(..) => "timed out waiting on send operation",
+            SendTimeoutError::Disconnected(..) => "sending on a closed channel",
+        }
+    }
+}
+
+impl<T> From<SendError<T>> for SendTimeoutError<T> {
+    fn from(err: SendError<T>) -> SendTimeoutError<T> {
+        match err {
+            SendError(t) => SendTimeoutError::Disconnected(t),
+        }
+    }
+}
+
+impl fmt::Display for SelectTimeoutError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        "timed out waiting on select".fmt(f)
+    }
+}
+
+impl error::Error for SelectTimeoutError {
+    fn description(&self) -> &str {
+        "timed out waiting on select"
+    }
+}
+
+#[cfg(all(test, not(target_os = "emscripten")))]
+mod tests {
+    use sync::Arc;
+    use sync::atomic::{AtomicUsize, Ordering};
+    use thread;
+    use time::{Duration, Instant};
+    use super::*;
+
+    #[test]
+    fn smoke() {
+        let (tx, rx) = channel::<i32>();
+        tx.send(1).unwrap();
+        assert_eq!(rx.recv().unwrap(), 1);
+        assert_eq!(rx.try_recv(), Err(TryRecvError::Empty));
+    }
+
+    #[test]
+    fn disconnect_senders() {
+        let (tx, rx) = channel::<i32>();
+        let tx2 = tx.clone();
+        tx.send(1).unwrap();
+        drop(tx);
+        tx2.send(2).unwrap();
+        drop(tx2);
+        assert_eq!(rx.recv(), Ok(1));
+        assert_eq!(rx.recv(), Ok(2));
+        assert_eq!(rx.recv(), Err(RecvError));
+        assert_eq!(rx.try_recv(), Err(TryRecvError::Disconnected));
+    }
+
+    #[test]
+    fn disconnect_receivers() {
+        let (tx, rx) = sync_channel::<i32>(1);
+        let rx2 = rx.clone();
+        drop(rx);
+        tx.send(1).unwrap();
+        drop(rx2);
+        assert_eq!(tx.send(2), Err(SendError(2)));
+        assert_eq!(tx.try_send(3), Err(TrySendError::Disconnected(3)));
+    }
+
+    #[test]
+    fn disconnect_wakes_blocked() {
+        let (tx, rx) = channel::<i32>();
+        let t = thread::spawn(move || rx.recv());
+        thread::sleep(Duration::from_millis(10));
+        drop(tx);
+        assert_eq!(t.join().unwrap(), Err(RecvError));
+
+        let (tx, rx) = sync_channel::<i32>(0);
+        let t = thread::spawn(move || tx.send(1));
+        thread::sleep(Duration::from_millis(10));
+        drop(rx);
+        assert_eq!(t.join().unwrap(), Err(SendError(1)));
+    }
+
+    #[test]
+    fn bounded() {
+        let (tx, rx) = sync_channel(2);
+        tx.send(1).unwrap();
+        tx.try_send(2).unwrap();
+        assert_eq!(tx.try_send(3), Err(TrySendError::Full(3)));
+        assert_eq!(tx.send_timeout(3, Duration::from_millis(1)),
+                   Err(SendTimeoutError::Timeout(3)));
+
+        let t = thread::spawn(move || {
+            tx.send(3).unwrap();
+            tx.send(4).unwrap();
+        });
+        thread::sleep(Duration::from_millis(10));
+        let got: Vec<i32> = rx.iter().collect();
+        assert_eq!(got, [1, 2, 3, 4]);
+        t.join().unwrap();
+    }
+
+    #[test]
+    fn rendezvous() {
+        let (tx, rx) = sync_channel(0);
+        assert_eq!(tx.try_send(1), Err(TrySendError::Full(1)));
+
+        let t = thread::spawn(move || {
+            for i in 0..10 {
+                tx.send(i).unwrap();
+            }
+        });
+        for i in 0..10 {
+            assert_eq!(rx.recv(), Ok(i));
+        }
+        t.join().unwrap();
+        assert_eq!(rx.recv(), Err(RecvError));
+    }
+
+    #[test]
+    fn rendezvous_try_send_to_blocked_receiver() {
+        let (tx, rx) = sync_channel(0);
+        let t = thread::spawn(move || rx.recv());
+        loop {
+            match tx.try_send(1) {
+                Ok(()) => break,
+                Err(TrySendError::Full(_)) => thread::yield_now(),
+                Err(TrySendError::Disconnected(_)) => panic!(),
+            }
+        }
+        assert_eq!(t.join().unwrap(), Ok(1));
+    }
+
+    #[test]
+    fn rendezvous_send_timeout() {
+        let (tx, rx) = sync_channel(0);
+        assert_eq!(tx.send_timeout(1, Duration::from_millis(10)),
+                   Err(SendTimeoutError::Timeout(1)));
+        // The timed out message mustn't be left behind.
+        assert_eq!(rx.try_recv(), Err(TryRecvError::Empty));
+    }
+
+    #[test]
+    fn recv_timeout() {
+        let (tx, rx) = channel::<i32>();
+        let start = Instant::now();
+        assert_eq!(rx.recv_timeout(Duration::from_millis(20)),
+                   Err(RecvTimeoutError::Timeout));
+        assert!(start.elapsed() >= Duration::from_millis(20));
+
+        let t = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(10));
+            tx.send(1).unwrap();
+        });
+        assert_eq!(rx.recv_timeout(Duration::from_secs(10)), Ok(1));
+        t.join().unwrap();
+        assert_eq!(rx.recv_timeout(Duration::from_secs(10)),
+                   Err(RecvTimeoutError::Disconnected));
+    }
+
+    #[test]
+    fn many_producers_many_consumers() {
+        const PRODUCERS: usize = 4;
+        const CONSUMERS: usize = 4;
+        const MESSAGES: usize = 1000;
+
+        for &cap in &[None, Some(0), Some(1), Some(16)] {
+            let (tx, rx) = match cap {
+                None => channel(),
+                Some(cap) => sync_channel(cap),
+            };
+            let total = Arc::new(AtomicUsize::new(0));
+
+            let consumers: Vec<_> = (0..CONSUMERS).map(|_| {
+                let rx = rx.clone();
+                let total = total.clone();
+                thread::spawn(move || {
+                    let mut received = 0;
+                    for msg in rx {
+                        total.fetch_add(msg, Ordering::SeqCst);
+                        received += 1;
+                    }
+                    received
+                })
+            }).collect();
+            drop(rx);
+
+            let producers: Vec<_> = (0..PRODUCERS).map(|_| {
+                let tx = tx.clone();
+                thread::spawn(move || {
+                    for i in 0..MESSAGES {
+                        tx.send(i).unwrap();
+                    }
+                })
+            }).collect();
+            drop(tx);
+
+            for t in producers {
+                t.join().unwrap();
+            }
+            let received: usize = consumers.into_iter().map(|t| t.join().unwrap()).sum();
+            assert_eq!(received, PRODUCERS * MESSAGES);
+            assert_eq!(total.load(Ordering::SeqCst),
+                       PRODUCERS * MESSAGES * (MESSAGES - 1) / 2);
+        }
+    }
+
+    #[test]
+    fn select_recv() {
+        let (tx1, rx1) = channel::<i32>();
+        let (tx2, rx2) = channel::<&'static str>();
+
+        let t = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(10));
+            tx2.send("two").unwrap();
+        });
+
+        let mut sel = Select::new();
+        sel.recv(&rx1, |_| panic!())
+           .recv(&rx2, |msg| msg.unwrap());
+        assert_eq!(sel.wait(), "two");
+        t.join().unwrap();
+        drop(tx1);
+
+        // A disconnected channel is selected with an error.
+        let mut sel = Select::new();
+        sel.recv(&rx1, |msg| msg.is_err())
+           .recv(&rx2, |msg| msg.is_err());
+        assert_eq!(sel.try_wait(), Ok(true));
+    }
+
+    #[test]
+    fn select_send() {
+        let (tx1, rx1) = sync_channel(0);
+        let (tx2, _rx2) = sync_channel(0);
+
+        let t = thread::spawn(move || rx1.recv().unwrap());
+
+        let mut sel = Select::new();
+        sel.send(&tx1, 1, |res| { res.unwrap(); 1 })
+           .send(&tx2, 2, |res| { res.unwrap(); 2 });
+        assert_eq!(sel.wait_timeout(Duration::from_secs(10)), Ok(1));
+        assert_eq!(t.join().unwrap(), 1);
+    }
+
+    #[test]
+    fn select_rendezvous_hands_off_one_message() {
+        let (tx1, rx1) = sync_channel(0);
+        let (tx2, rx2) = sync_channel(0);
+
+        let t = thread::spawn(move || {
+            let res = {
+                let mut sel = Select::new();
+                sel.recv(&rx1, |msg| (1, msg.unwrap()))
+                   .recv(&rx2, |msg| (2, msg.unwrap()));
+                sel.wait()
+            };
+            (res, rx1, rx2)
+        });
+
+        // Once the selecting receiver is blocked, a message can be handed to
+        // it on either channel, but only one of them gets through.
+        loop {
+            match tx1.try_send(1) {
+                Ok(()) => break,
+                Err(TrySendError::Full(_)) => thread::yield_now(),
+                Err(TrySendError::Disconnected(_)) => panic!(),
+            }
+        }
+        assert_eq!(tx2.try_send(2), Err(TrySendError::Full(2)));
+
+        let (res, rx1, rx2) = t.join().unwrap();
+        assert_eq!(res, (1, 1));
+        assert_eq!(rx1.try_recv(), Err(TryRecvError::Empty));
+        assert_eq!(rx2.try_recv(), Err(TryRecvError::Empty));
+    }
+
+    #[test]
+    fn select_timeout() {
+        let (_tx1, rx1) = channel::<i32>();
+        let (tx2, _rx2) = sync_channel(0);
+
+        let mut sel = Select::new();
+        sel.recv(&rx1, |_| ())
+           .send(&tx2, 1, |_| ());
+        assert_eq!(sel.try_wait(), Err(SelectTimeoutError));
+
+        let mut sel = Select::new();
+        sel.recv(&rx1, |_| ())
+           .send(&tx2, 1, |_| ());
+        let start = Instant::now();
+        assert_eq!(sel.wait_deadline(start + Duration::from_millis(20)),
+                   Err(SelectTimeoutError));
+        assert!(start.elapsed() >= Duration::from_millis(20));
+    }
+
+    #[test]
+    fn select_stress() {
+        const MESSAGES: usize = 1000;
+
+        let (tx1, rx1) = sync_channel(1);
+        let (tx2, rx2) = sync_channel(0);
+
+        let t = thread::spawn(move || {
+            for i in 0..MESSAGES {
+                if i % 2 == 0 {
+                    tx1.send(i).unwrap();
+                } else {
+                    tx2.send(i).unwrap();
+                }
+            }
+        });
+
+        let mut sum = 0;
+        for _ in 0..MESSAGES {
+            let mut sel = Select::new();
+            sel.recv(&rx1, |msg| msg.unwrap())
+               .recv(&rx2, |msg| msg.unwrap());
+            sum += sel.wait();
+        }
+        t.join().unwrap();
+        assert_eq!(sum, MESSAGES * (MESSAGES - 1) / 2);
+    }
+}
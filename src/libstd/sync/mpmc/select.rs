@@ -0,0 +1,332 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use fmt;
+use sync::Arc;
+use sync::atomic::{AtomicUsize, Ordering};
+use time::{Duration, Instant};
+
+use super::{Receiver, Sender, RecvError, SendError, SelectTimeoutError};
+use super::{TryRecvError, TrySendError};
+use super::chan::{has_passed, Waiter};
+
+/// Waits on several send and receive operations at once, and performs the
+/// first one which can go ahead.
+///
+/// Every operation is added together with a callback which is given the
+/// outcome of the operation if it is the one which gets selected. The value
+/// returned from that callback is then returned from [`wait`] and its
+/// siblings. An operation on a channel whose other half has hung up is
+/// selected just like one which succeeds, and its callback is given the
+/// error.
+///
+/// If several operations are ready at the same time, which one of them is
+/// selected is unspecified.
+///
+/// [`wait`]: #method.wait
+///
+/// # Examples
+///
+/// ```
+/// #![feature(mpmc_channel)]
+///
+/// use std::sync::mpmc::{channel, Select};
+/// use std::thread;
+/// use std::time::Duration;
+///
+/// let (tx1, rx1) = channel();
+/// let (tx2, rx2) = channel();
+///
+/// thread::spawn(move || tx1.send("one").unwrap());
+/// thread::spawn(move || tx2.send(2).unwrap());
+///
+/// for _ in 0..2 {
+///     let mut sel = Select::new();
+///     sel.recv(&rx1, |msg| format!("rx1: {:?}", msg))
+///        .recv(&rx2, |msg| format!("rx2: {:?}", msg));
+///     match sel.wait_timeout(Duration::from_secs(10)) {
+///         Ok(line) => println!("{}", line),
+///         Err(_) => panic!("nothing arrived in time"),
+///     }
+/// }
+/// ```
+pub struct Select<'a, R> {
+    cases: Vec<Box<Case<R> + 'a>>,
+}
+
+// One of the operations of a `Select`.
+trait Case<R> {
+    // Tries to perform the operation without blocking, and returns whether it
+    // has been selected. The callback isn't run yet, because the `Select`
+    // first needs to clean up after itself.
+    fn try_select(&mut self) -> bool;
+
+    // Runs the callback of a selected operation.
+    fn complete(&mut self) -> R;
+
+    fn register(&self, waiter: &Arc<Waiter>);
+    fn unregister(&self, waiter: &Arc<Waiter>);
+
+    // Returns whether `try_select` is likely to succeed now.
+    fn is_ready(&self, waiter: &Arc<Waiter>) -> bool;
+
+    // Takes the message a channel has handed to the waiter, and returns
+    // whether the operation has been selected that way.
+    fn take_handoff(&mut self, waiter: &Arc<Waiter>) -> bool;
+}
+
+struct RecvCase<'a, T: 'a, F> {
+    rx: &'a Receiver<T>,
+    result: Option<Result<T, RecvError>>,
+    callback: Option<F>,
+}
+
+impl<'a, T, F, R> Case<R> for RecvCase<'a, T, F>
+    where F: FnOnce(Result<T, RecvError>) -> R
+{
+    fn try_select(&mut self) -> bool {
+        self.result = match self.rx.chan.try_recv() {
+            Ok(msg) => Some(Ok(msg)),
+            Err(TryRecvError::Disconnected) => Some(Err(RecvError)),
+            Err(TryRecvError::Empty) => None,
+        };
+        self.result.is_some()
+    }
+
+    fn complete(&mut self) -> R {
+        let callback = self.callback.take().unwrap();
+        callback(self.result.take().unwrap())
+    }
+
+    fn register(&self, waiter: &Arc<Waiter>) {
+        self.rx.chan.register_recv(waiter);
+    }
+
+    fn unregister(&self, waiter: &Arc<Waiter>) {
+        self.rx.chan.unregister_recv(waiter);
+    }
+
+    fn is_ready(&self, _waiter: &Arc<Waiter>) -> bool {
+        self.rx.chan.recv_ready()
+    }
+
+    fn take_handoff(&mut self, waiter: &Arc<Waiter>) -> bool {
+        self.result = self.rx.chan.take_handoff(waiter).map(Ok);
+        self.result.is_some()
+    }
+}
+
+struct SendCase<'a, T: 'a, F> {
+    tx: &'a Sender<T>,
+    // The message until it has been sent, and then the result of sending it.
+    msg: Option<T>,
+    result: Option<Result<(), SendError<T>>>,
+    callback: Option<F>,
+}
+
+impl<'a, T, F, R> Case<R> for SendCase<'a, T, F>
+    where F: FnOnce(Result<(), SendError<T>>) -> R
+{
+    fn try_select(&mut self) -> bool {
+        let msg = self.msg.take().unwrap();
+        match self.tx.chan.try_send(msg) {
+            Ok(()) => self.result = Some(Ok(())),
+            Err(TrySendError::Disconnected(msg)) => self.result = Some(Err(SendError(msg))),
+            Err(TrySendError::Full(msg)) => self.msg = Some(msg),
+        }
+        self.result.is_some()
+    }
+
+    fn complete(&mut self) -> R {
+        let callback = self.callback.take().unwrap();
+        callback(self.result.take().unwrap())
+    }
+
+    fn register(&self, waiter: &Arc<Waiter>) {
+        self.tx.chan.register_send(waiter);
+    }
+
+    fn unregister(&self, waiter: &Arc<Waiter>) {
+        self.tx.chan.unregister_send(waiter);
+    }
+
+    fn is_ready(&self, waiter: &Arc<Waiter>) -> bool {
+        self.tx.chan.send_ready(waiter)
+    }
+
+    fn take_handoff(&mut self, _waiter: &Arc<Waiter>) -> bool {
+        false
+    }
+}
+
+// Rotates the operation which is tried first, so that a busy channel doesn't
+// starve the ones added after it.
+static NEXT_START: AtomicUsize = AtomicUsize::new(0);
+
+impl<'a, R> Select<'a, R> {
+    /// Creates a new `Select` without any operations.
+    pub fn new() -> Select<'a, R> {
+        Select { cases: Vec::new() }
+    }
+
+    /// Adds an operation receiving a message from `rx`.
+    ///
+    /// If this operation gets selected, `f` is given the received message, or
+    /// [`RecvError`] if the channel is empty and all of its senders have been
+    /// dropped.
+    ///
+    /// [`RecvError`]: struct.RecvError.html
+    pub fn recv<T, F>(&mut self, rx: &'a Receiver<T>, f: F) -> &mut Select<'a, R>
+        where F: FnOnce(Result<T, RecvError>) -> R + 'a
+    {
+        self.cases.push(Box::new(RecvCase {
+            rx,
+            result: None,
+            callback: Some(f),
+        }));
+        self
+    }
+
+    /// Adds an operation sending `msg` over `tx`.
+    ///
+    /// If this operation gets selected, `f` is given `Ok(())` once the message
+    /// has been sent, or a [`SendError`] holding the message if all of the
+    /// channel's receivers have been dropped. If another operation is selected
+    /// instead, the message is dropped along with the `Select`.
+    ///
+    /// [`SendError`]: struct.SendError.html
+    pub fn send<T, F>(&mut self, tx: &'a Sender<T>, msg: T, f: F) -> &mut Select<'a, R>
+        where F: FnOnce(Result<(), SendError<T>>) -> R + 'a
+    {
+        self.cases.push(Box::new(SendCase {
+            tx,
+            msg: Some(msg),
+            result: None,
+            callback: Some(f),
+        }));
+        self
+    }
+
+    /// Blocks until one of the operations has been performed, and returns
+    /// the value returned by its callback.
+    ///
+    /// A `Select` without any operations blocks forever.
+    pub fn wait(self) -> R {
+        self.wait_inner(None).unwrap()
+    }
+
+    /// Performs one of the operations if one of them can go ahead right away,
+    /// and returns the value returned by its callback.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SelectTimeoutError`] without blocking if none of the
+    /// operations is ready.
+    ///
+    /// [`SelectTimeoutError`]: struct.SelectTimeoutError.html
+    pub fn try_wait(self) -> Result<R, SelectTimeoutError> {
+        self.wait_inner(Some(Instant::now()))
+    }
+
+    /// Waits for at most `timeout` for one of the operations to be performed,
+    /// and returns the value returned by its callback.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SelectTimeoutError`] if none of the operations has been
+    /// performed by the time the timeout has passed.
+    ///
+    /// [`SelectTimeoutError`]: struct.SelectTimeoutError.html
+    pub fn wait_timeout(self, timeout: Duration) -> Result<R, SelectTimeoutError> {
+        self.wait_inner(Some(Instant::now() + timeout))
+    }
+
+    /// Waits until `deadline` at the latest for one of the operations to be
+    /// performed, and returns the value returned by its callback.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SelectTimeoutError`] if none of the operations has been
+    /// performed by the time the deadline has passed.
+    ///
+    /// [`SelectTimeoutError`]: struct.SelectTimeoutError.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(mpmc_channel)]
+    ///
+    /// use std::sync::mpmc::{sync_channel, Select};
+    /// use std::time::{Duration, Instant};
+    ///
+    /// let (tx, rx) = sync_channel(1);
+    /// let deadline = Instant::now() + Duration::from_millis(50);
+    ///
+    /// // The first send fits into the buffer, the second one has to wait
+    /// // for a receiver which never comes.
+    /// for &expect_sent in &[true, false] {
+    ///     let mut sel = Select::new();
+    ///     sel.send(&tx, 1, |res| res.is_ok());
+    ///     assert_eq!(sel.wait_deadline(deadline).is_ok(), expect_sent);
+    /// }
+    /// assert_eq!(rx.try_recv(), Ok(1));
+    /// ```
+    pub fn wait_deadline(self, deadline: Instant) -> Result<R, SelectTimeoutError> {
+        self.wait_inner(Some(deadline))
+    }
+
+    fn wait_inner(mut self, deadline: Option<Instant>) -> Result<R, SelectTimeoutError> {
+        let len = self.cases.len();
+        let start = NEXT_START.fetch_add(1, Ordering::Relaxed);
+        let waiter = Waiter::new(true);
+
+        loop {
+            for i in 0..len {
+                let i = (start + i) % len;
+                if self.cases[i].try_select() {
+                    return Ok(self.cases[i].complete());
+                }
+            }
+            if has_passed(deadline) {
+                return Err(SelectTimeoutError);
+            }
+
+            // The operations are only tried while not registered, since a
+            // rendezvous channel may hand a message to a registered waiter at
+            // any time. Instead, check whether one of them has become ready
+            // since, so that we can't miss the notification about it.
+            waiter.reset();
+            for case in &self.cases {
+                case.register(&waiter);
+            }
+            if !self.cases.iter().any(|case| case.is_ready(&waiter)) {
+                waiter.wait(deadline);
+            }
+
+            // Stop the channels from handing messages to the waiter before
+            // leaving them. If one of them was quicker, its operation is the
+            // one which has been selected.
+            let handed_off = !waiter.claim();
+            for case in &self.cases {
+                case.unregister(&waiter);
+            }
+            if handed_off {
+                let i = self.cases.iter_mut().position(|case| case.take_handoff(&waiter)).unwrap();
+                return Ok(self.cases[i].complete());
+            }
+        }
+    }
+}
+
+impl<'a, R> fmt::Debug for Select<'a, R> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Select").field("len", &self.cases.len()).finish()
+    }
+}
@@ -148,9 +148,9 @@ struct Waiter {
 
 // Helper struct used to clean up after a closure call with a `Drop`
 // implementation to also run on panic.
-struct Finish {
+struct Finish<'a> {
     panicked: bool,
-    me: &'static Once,
+    me: &'a Once,
 }
 
 impl Once {
@@ -286,6 +286,11 @@ impl Once {
         });
     }
 
+    /// Returns whether some initialization routine has run to completion.
+    pub(crate) fn is_completed(&self) -> bool {
+        self.state.load(Ordering::SeqCst) == COMPLETE
+    }
+
     // This is a non-generic function to reduce the monomorphization cost of
     // using `call_once` (this isn't exactly a trivial or small implementation).
     //
@@ -297,10 +302,15 @@ impl Once {
     // Finally, this takes an `FnMut` instead of a `FnOnce` because there's
     // currently no way to take an `FnOnce` and call it via virtual dispatch
     // without some allocation overhead.
+    //
+    // Unlike the public entry points this doesn't require a `'static` once, so
+    // that `OnceCell` can embed a `Once` anywhere. Every thread inside
+    // `call_inner` holds a borrow of `self`, so the `Once` can't go away while
+    // anybody is still running or waiting on it.
     #[cold]
-    fn call_inner(&'static self,
-                  ignore_poisoning: bool,
-                  init: &mut FnMut(bool)) {
+    pub(crate) fn call_inner(&self,
+                             ignore_poisoning: bool,
+                             init: &mut FnMut(bool)) {
         let mut state = self.state.load(Ordering::SeqCst);
 
         'outer: loop {
@@ -389,7 +399,7 @@ impl fmt::Debug for Once {
     }
 }
 
-impl Drop for Finish {
+impl<'a> Drop for Finish<'a> {
     fn drop(&mut self) {
         // Swap out our state with however we finished. We should only ever see
         // an old state which was RUNNING.
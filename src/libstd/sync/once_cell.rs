@@ -0,0 +1,432 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use cell::{Cell, UnsafeCell};
+use fmt;
+use ops::Deref;
+use sync::Once;
+
+/// A thread-safe cell which can be written to only once.
+///
+/// A `OnceCell` holds a value which is computed the first time it is asked
+/// for and then shared by every thread. Unlike a bare [`Once`] it stores the
+/// value it was initialized with, and unlike a [`Mutex`] it hands out plain
+/// `&T` references once initialized.
+///
+/// See `std::cell::OnceCell` for a version which is not thread-safe.
+///
+/// [`Once`]: struct.Once.html
+/// [`Mutex`]: struct.Mutex.html
+///
+/// # Examples
+///
+/// ```
+/// #![feature(once_cell)]
+///
+/// use std::sync::OnceCell;
+/// use std::thread;
+///
+/// static CELL: OnceCell<String> = OnceCell::new();
+/// assert!(CELL.get().is_none());
+///
+/// thread::spawn(|| {
+///     let value: &String = CELL.get_or_init(|| "Hello, World!".to_string());
+///     assert_eq!(value, "Hello, World!");
+/// }).join().unwrap();
+///
+/// let value: Option<&String> = CELL.get();
+/// assert_eq!(value.unwrap(), "Hello, World!");
+/// ```
+#[unstable(feature = "once_cell", issue = "0")]
+pub struct OnceCell<T> {
+    once: Once,
+    // Only written to from within `once`, and only read once `once` has
+    // completed, which is what makes sharing it between threads sound.
+    value: UnsafeCell<Option<T>>,
+}
+
+// Sharing a `OnceCell` allows sending a `T` to whichever thread initializes it
+// and sharing it with all of the others, hence both bounds.
+#[unstable(feature = "once_cell", issue = "0")]
+unsafe impl<T: Send + Sync> Sync for OnceCell<T> {}
+
+#[unstable(feature = "once_cell", issue = "0")]
+impl<T> Default for OnceCell<T> {
+    fn default() -> OnceCell<T> {
+        OnceCell::new()
+    }
+}
+
+#[unstable(feature = "once_cell", issue = "0")]
+impl<T: fmt::Debug> fmt::Debug for OnceCell<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.get() {
+            Some(value) => {
+                f.debug_struct("OnceCell")
+                    .field("value", value)
+                    .finish()
+            }
+            None => {
+                struct UninitPlaceholder;
+
+                impl fmt::Debug for UninitPlaceholder {
+                    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                        f.write_str("<uninit>")
+                    }
+                }
+
+                f.debug_struct("OnceCell")
+                    .field("value", &UninitPlaceholder)
+                    .finish()
+            }
+        }
+    }
+}
+
+#[unstable(feature = "once_cell", issue = "0")]
+impl<T: Clone> Clone for OnceCell<T> {
+    fn clone(&self) -> OnceCell<T> {
+        let res = OnceCell::new();
+        if let Some(value) = self.get() {
+            let _ = res.set(value.clone());
+        }
+        res
+    }
+}
+
+#[unstable(feature = "once_cell", issue = "0")]
+impl<T: PartialEq> PartialEq for OnceCell<T> {
+    fn eq(&self, other: &OnceCell<T>) -> bool {
+        self.get() == other.get()
+    }
+}
+
+#[unstable(feature = "once_cell", issue = "0")]
+impl<T: Eq> Eq for OnceCell<T> {}
+
+#[unstable(feature = "once_cell", issue = "0")]
+impl<T> From<T> for OnceCell<T> {
+    fn from(value: T) -> OnceCell<T> {
+        let cell = OnceCell::new();
+        let _ = cell.set(value);
+        cell
+    }
+}
+
+impl<T> OnceCell<T> {
+    /// Creates a new empty cell.
+    #[unstable(feature = "once_cell", issue = "0")]
+    pub const fn new() -> OnceCell<T> {
+        OnceCell {
+            once: Once::new(),
+            value: UnsafeCell::new(None),
+        }
+    }
+
+    /// Returns a reference to the contents of the cell, or `None` if it has
+    /// not been initialized yet.
+    ///
+    /// This never blocks. While another thread is running the initializer
+    /// the cell still counts as uninitialized.
+    #[unstable(feature = "once_cell", issue = "0")]
+    pub fn get(&self) -> Option<&T> {
+        if self.once.is_completed() {
+            unsafe { (*self.value.get()).as_ref() }
+        } else {
+            None
+        }
+    }
+
+    /// Returns a mutable reference to the contents of the cell, or `None` if
+    /// it has not been initialized yet.
+    ///
+    /// This call borrows `OnceCell` mutably (at compile-time) which
+    /// guarantees that we possess the only reference.
+    #[unstable(feature = "once_cell", issue = "0")]
+    pub fn get_mut(&mut self) -> Option<&mut T> {
+        unsafe { (*self.value.get()).as_mut() }
+    }
+
+    /// Initializes the contents of the cell to `value`.
+    ///
+    /// If another thread is initializing the cell at the same time, this
+    /// blocks until it is done.
+    ///
+    /// # Errors
+    ///
+    /// If the cell has already been initialized, it is left unchanged and
+    /// `value` is handed back in the `Err` variant.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(once_cell)]
+    ///
+    /// use std::sync::OnceCell;
+    ///
+    /// static CELL: OnceCell<i32> = OnceCell::new();
+    ///
+    /// assert_eq!(CELL.set(92), Ok(()));
+    /// assert_eq!(CELL.set(62), Err(62));
+    /// assert_eq!(CELL.get(), Some(&92));
+    /// ```
+    #[unstable(feature = "once_cell", issue = "0")]
+    pub fn set(&self, value: T) -> Result<(), T> {
+        let mut value = Some(value);
+        self.get_or_init(|| value.take().unwrap());
+        match value {
+            None => Ok(()),
+            Some(value) => Err(value),
+        }
+    }
+
+    /// Returns the contents of the cell, initializing it with `f` first if
+    /// it is empty.
+    ///
+    /// Many threads may call `get_or_init` concurrently with different
+    /// initializers, but it is guaranteed that exactly one of them runs to
+    /// completion. All of the other callers block until it has, and then
+    /// return the value it produced.
+    ///
+    /// # Panics
+    ///
+    /// If `f` panics, the panic is propagated to the caller and the cell
+    /// remains uninitialized, so that the next caller runs its initializer
+    /// instead.
+    ///
+    /// It is an error to reentrantly initialize the cell from `f`. Doing so
+    /// blocks the thread forever.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(once_cell)]
+    ///
+    /// use std::sync::OnceCell;
+    ///
+    /// let cell = OnceCell::new();
+    /// assert_eq!(*cell.get_or_init(|| 92), 92);
+    /// assert_eq!(*cell.get_or_init(|| unreachable!()), 92);
+    /// ```
+    #[unstable(feature = "once_cell", issue = "0")]
+    pub fn get_or_init<F>(&self, f: F) -> &T where F: FnOnce() -> T {
+        if let Some(value) = self.get() {
+            return value;
+        }
+
+        // A panicking initializer poisons `once`, which we ignore so that the
+        // next caller gets to try again.
+        let mut f = Some(f);
+        let slot = &self.value;
+        self.once.call_inner(true, &mut |_| {
+            let value = f.take().unwrap()();
+            unsafe { *slot.get() = Some(value) }
+        });
+        self.get().unwrap()
+    }
+
+    /// Consumes the cell, returning its contents if it was initialized.
+    #[unstable(feature = "once_cell", issue = "0")]
+    pub fn into_inner(self) -> Option<T> {
+        self.value.into_inner()
+    }
+}
+
+/// A value which is initialized on its first access.
+///
+/// `Lazy` pairs a [`OnceCell`] with the function which initializes it, and
+/// runs that function the first time the value is dereferenced from any
+/// thread. This makes it a convenient replacement for `lazy_static!` style
+/// macros.
+///
+/// [`OnceCell`]: struct.OnceCell.html
+///
+/// # Examples
+///
+/// ```
+/// #![feature(once_cell)]
+///
+/// use std::collections::HashMap;
+/// use std::sync::Lazy;
+///
+/// fn make_primes() -> HashMap<u32, &'static str> {
+///     let mut map = HashMap::new();
+///     map.insert(2, "two");
+///     map.insert(3, "three");
+///     map.insert(5, "five");
+///     map
+/// }
+///
+/// static PRIMES: Lazy<HashMap<u32, &'static str>> = Lazy::new(make_primes);
+///
+/// assert_eq!(PRIMES.get(&3), Some(&"three"));
+/// assert_eq!(PRIMES.len(), 3);
+/// ```
+#[unstable(feature = "once_cell", issue = "0")]
+pub struct Lazy<T, F = fn() -> T> {
+    cell: OnceCell<T>,
+    // Taken by whichever thread initializes `cell`.
+    init: Cell<Option<F>>,
+}
+
+// `init` is only ever touched from within the `Once` of `cell`, so it's fine
+// to share as long as `F` may be sent to the initializing thread.
+#[unstable(feature = "once_cell", issue = "0")]
+unsafe impl<T, F: Send> Sync for Lazy<T, F> where OnceCell<T>: Sync {}
+
+#[unstable(feature = "once_cell", issue = "0")]
+impl<T: fmt::Debug, F> fmt::Debug for Lazy<T, F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Lazy").field("cell", &self.cell).finish()
+    }
+}
+
+impl<T, F> Lazy<T, F> {
+    /// Creates a new lazy value which is initialized by `f`.
+    #[unstable(feature = "once_cell", issue = "0")]
+    pub const fn new(f: F) -> Lazy<T, F> {
+        Lazy {
+            cell: OnceCell::new(),
+            init: Cell::new(Some(f)),
+        }
+    }
+}
+
+impl<T, F: FnOnce() -> T> Lazy<T, F> {
+    /// Forces the evaluation of this lazy value and returns a reference to
+    /// the result. This is equivalent to the `Deref` impl, but is explicit.
+    ///
+    /// # Panics
+    ///
+    /// If the initialization function panics, the panic is propagated to the
+    /// caller and the `Lazy` is poisoned: every later access panics as well.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(once_cell)]
+    ///
+    /// use std::sync::Lazy;
+    ///
+    /// let lazy = Lazy::new(|| 92);
+    ///
+    /// assert_eq!(Lazy::force(&lazy), &92);
+    /// assert_eq!(&*lazy, &92);
+    /// ```
+    #[unstable(feature = "once_cell", issue = "0")]
+    pub fn force(this: &Lazy<T, F>) -> &T {
+        this.cell.get_or_init(|| match this.init.take() {
+            Some(f) => f(),
+            None => panic!("Lazy instance has previously been poisoned"),
+        })
+    }
+}
+
+#[unstable(feature = "once_cell", issue = "0")]
+impl<T, F: FnOnce() -> T> Deref for Lazy<T, F> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        Lazy::force(self)
+    }
+}
+
+#[cfg(all(test, not(target_os = "emscripten")))]
+mod tests {
+    use panic;
+    use sync::atomic::{AtomicUsize, Ordering};
+    use sync::mpsc::channel;
+    use sync::Arc;
+    use thread;
+    use super::{Lazy, OnceCell};
+
+    #[test]
+    fn smoke_once_cell() {
+        let cell = OnceCell::new();
+        assert_eq!(cell.get(), None);
+        assert_eq!(*cell.get_or_init(|| 92), 92);
+        assert_eq!(cell.set(62), Err(62));
+        assert_eq!(cell.get(), Some(&92));
+        assert_eq!(cell.into_inner(), Some(92));
+    }
+
+    #[test]
+    fn stampede_once_cell() {
+        static CELL: OnceCell<usize> = OnceCell::new();
+        static RUNS: AtomicUsize = AtomicUsize::new(0);
+
+        let (tx, rx) = channel();
+        for i in 0..10 {
+            let tx = tx.clone();
+            thread::spawn(move|| {
+                for _ in 0..4 { thread::yield_now() }
+                let value = *CELL.get_or_init(|| {
+                    RUNS.fetch_add(1, Ordering::SeqCst);
+                    i
+                });
+                tx.send(value).unwrap();
+            });
+        }
+
+        let first = rx.recv().unwrap();
+        for _ in 1..10 {
+            assert_eq!(rx.recv().unwrap(), first);
+        }
+        assert_eq!(CELL.get(), Some(&first));
+        assert_eq!(RUNS.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn get_or_init_panic() {
+        let cell = OnceCell::new();
+
+        let t = panic::catch_unwind(|| {
+            cell.get_or_init(|| panic!());
+        });
+        assert!(t.is_err());
+        assert_eq!(cell.get(), None);
+
+        // unlike `Once`, the next initializer gets to run
+        assert_eq!(*cell.get_or_init(|| 1), 1);
+    }
+
+    #[test]
+    fn drop_once_cell() {
+        let value = Arc::new(());
+        {
+            let cell = OnceCell::new();
+            cell.set(value.clone()).unwrap();
+            assert_eq!(Arc::strong_count(&value), 2);
+        }
+        assert_eq!(Arc::strong_count(&value), 1);
+    }
+
+    #[test]
+    fn smoke_lazy() {
+        static RUNS: AtomicUsize = AtomicUsize::new(0);
+
+        let lazy = Lazy::new(|| {
+            RUNS.fetch_add(1, Ordering::SeqCst);
+            vec![1, 2, 3]
+        });
+        assert_eq!(RUNS.load(Ordering::SeqCst), 0);
+        assert_eq!(lazy.len(), 3);
+        assert_eq!(*lazy, [1, 2, 3]);
+        assert_eq!(RUNS.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn lazy_poisoned() {
+        let lazy = Lazy::new(|| -> i32 { panic!() });
+
+        assert!(panic::catch_unwind(|| *lazy).is_err());
+        assert!(panic::catch_unwind(|| *lazy).is_err());
+    }
+}
@@ -18,6 +18,10 @@ use unwind as uw;
 
 pub struct BacktraceContext;
 
+pub fn resolve_context() -> io::Result<BacktraceContext> {
+    Ok(BacktraceContext)
+}
+
 struct Context<'a> {
     idx: usize,
     frames: &'a mut [Frame],
@@ -18,6 +18,8 @@ pub mod backtrace;
 #[path = "../unix/cmath.rs"]
 pub mod cmath;
 pub mod condvar;
+#[path = "../wasm/iovec.rs"]
+pub mod iovec;
 #[path = "../unix/memchr.rs"]
 pub mod memchr;
 pub mod mutex;
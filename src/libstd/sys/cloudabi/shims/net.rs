@@ -9,7 +9,7 @@
 // except according to those terms.
 
 use fmt;
-use io;
+use io::{self, IoVec, IoVecMut};
 use net::{Ipv4Addr, Ipv6Addr, Shutdown, SocketAddr};
 use time::Duration;
 use sys::{unsupported, Void};
@@ -51,10 +51,18 @@ impl TcpStream {
         match self.0 {}
     }
 
+    pub fn read_vectored(&self, _: &mut [IoVecMut]) -> io::Result<usize> {
+        match self.0 {}
+    }
+
     pub fn write(&self, _: &[u8]) -> io::Result<usize> {
         match self.0 {}
     }
 
+    pub fn write_vectored(&self, _: &[IoVec]) -> io::Result<usize> {
+        match self.0 {}
+    }
+
     pub fn peer_addr(&self) -> io::Result<SocketAddr> {
         match self.0 {}
     }
@@ -94,6 +102,54 @@ impl TcpStream {
     pub fn set_nonblocking(&self, _: bool) -> io::Result<()> {
         match self.0 {}
     }
+
+    pub fn set_keepalive(&self, _: Option<Duration>) -> io::Result<()> {
+        match self.0 {}
+    }
+
+    pub fn keepalive(&self) -> io::Result<Option<Duration>> {
+        match self.0 {}
+    }
+
+    pub fn set_keepalive_interval(&self, _: Duration) -> io::Result<()> {
+        match self.0 {}
+    }
+
+    pub fn keepalive_interval(&self) -> io::Result<Duration> {
+        match self.0 {}
+    }
+
+    pub fn set_keepalive_retries(&self, _: u32) -> io::Result<()> {
+        match self.0 {}
+    }
+
+    pub fn keepalive_retries(&self) -> io::Result<u32> {
+        match self.0 {}
+    }
+
+    pub fn set_linger(&self, _: Option<Duration>) -> io::Result<()> {
+        match self.0 {}
+    }
+
+    pub fn linger(&self) -> io::Result<Option<Duration>> {
+        match self.0 {}
+    }
+
+    pub fn set_recv_buffer_size(&self, _: usize) -> io::Result<()> {
+        match self.0 {}
+    }
+
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        match self.0 {}
+    }
+
+    pub fn set_send_buffer_size(&self, _: usize) -> io::Result<()> {
+        match self.0 {}
+    }
+
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        match self.0 {}
+    }
 }
 
 impl fmt::Debug for TcpStream {
@@ -109,6 +165,12 @@ impl TcpListener {
         unsupported()
     }
 
+    pub fn bind_with<F>(_: &SocketAddr, _: u32, _: F) -> io::Result<TcpListener>
+        where F: FnOnce(&TcpListener) -> io::Result<()>
+    {
+        unsupported()
+    }
+
     pub fn socket_addr(&self) -> io::Result<SocketAddr> {
         match self.0 {}
     }
@@ -144,6 +206,34 @@ impl TcpListener {
     pub fn set_nonblocking(&self, _: bool) -> io::Result<()> {
         match self.0 {}
     }
+
+    pub fn set_reuse_address(&self, _: bool) -> io::Result<()> {
+        match self.0 {}
+    }
+
+    pub fn set_reuse_port(&self, _: bool) -> io::Result<()> {
+        match self.0 {}
+    }
+
+    pub fn reuse_port(&self) -> io::Result<bool> {
+        match self.0 {}
+    }
+
+    pub fn set_recv_buffer_size(&self, _: usize) -> io::Result<()> {
+        match self.0 {}
+    }
+
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        match self.0 {}
+    }
+
+    pub fn set_send_buffer_size(&self, _: usize) -> io::Result<()> {
+        match self.0 {}
+    }
+
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        match self.0 {}
+    }
 }
 
 impl fmt::Debug for TcpListener {
@@ -259,6 +349,30 @@ impl UdpSocket {
         match self.0 {}
     }
 
+    pub fn set_reuse_port(&self, _: bool) -> io::Result<()> {
+        match self.0 {}
+    }
+
+    pub fn reuse_port(&self) -> io::Result<bool> {
+        match self.0 {}
+    }
+
+    pub fn set_recv_buffer_size(&self, _: usize) -> io::Result<()> {
+        match self.0 {}
+    }
+
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        match self.0 {}
+    }
+
+    pub fn set_send_buffer_size(&self, _: usize) -> io::Result<()> {
+        match self.0 {}
+    }
+
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        match self.0 {}
+    }
+
     pub fn recv(&self, _: &mut [u8]) -> io::Result<usize> {
         match self.0 {}
     }
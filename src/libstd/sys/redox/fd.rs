@@ -10,7 +10,7 @@
 
 #![unstable(reason = "not public", issue = "0", feature = "fd")]
 
-use io::{self, Read};
+use io::{self, Read, IoVec, IoVecMut};
 use mem;
 use sys::{cvt, syscall};
 use sys_common::AsInner;
@@ -37,6 +37,12 @@ impl FileDesc {
         cvt(syscall::read(self.fd, buf))
     }
 
+    // Redox has no vectored I/O system calls, so these fall back to
+    // transferring the first nonempty buffer.
+    pub fn read_vectored(&self, bufs: &mut [IoVecMut]) -> io::Result<usize> {
+        io::default_read_vectored(|buf| self.read(buf), bufs)
+    }
+
     pub fn read_to_end(&self, buf: &mut Vec<u8>) -> io::Result<usize> {
         let mut me = self;
         (&mut me).read_to_end(buf)
@@ -46,6 +52,10 @@ impl FileDesc {
         cvt(syscall::write(self.fd, buf))
     }
 
+    pub fn write_vectored(&self, bufs: &[IoVec]) -> io::Result<usize> {
+        io::default_write_vectored(|buf| self.write(buf), bufs)
+    }
+
     pub fn duplicate(&self) -> io::Result<FileDesc> {
         let new_fd = cvt(syscall::dup(self.fd, &[]))?;
         Ok(FileDesc::new(new_fd))
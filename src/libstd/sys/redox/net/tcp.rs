@@ -9,7 +9,7 @@
 // except according to those terms.
 
 use cmp;
-use io::{Error, ErrorKind, Result};
+use io::{Error, ErrorKind, Result, IoVec, IoVecMut};
 use mem;
 use net::{SocketAddr, Shutdown};
 use path::Path;
@@ -44,10 +44,18 @@ impl TcpStream {
         self.0.read(buf)
     }
 
+    pub fn read_vectored(&self, bufs: &mut [IoVecMut]) -> Result<usize> {
+        self.0.read_vectored(bufs)
+    }
+
     pub fn write(&self, buf: &[u8]) -> Result<usize> {
         self.0.write(buf)
     }
 
+    pub fn write_vectored(&self, bufs: &[IoVec]) -> Result<usize> {
+        self.0.write_vectored(bufs)
+    }
+
     pub fn take_error(&self) -> Result<Option<Error>> {
         Ok(None)
     }
@@ -152,6 +160,54 @@ impl TcpStream {
         }
         Ok(())
     }
+
+    pub fn set_keepalive(&self, _keepalive: Option<Duration>) -> Result<()> {
+        Err(Error::new(ErrorKind::Other, "TcpStream::set_keepalive not implemented"))
+    }
+
+    pub fn keepalive(&self) -> Result<Option<Duration>> {
+        Err(Error::new(ErrorKind::Other, "TcpStream::keepalive not implemented"))
+    }
+
+    pub fn set_keepalive_interval(&self, _interval: Duration) -> Result<()> {
+        Err(Error::new(ErrorKind::Other, "TcpStream::set_keepalive_interval not implemented"))
+    }
+
+    pub fn keepalive_interval(&self) -> Result<Duration> {
+        Err(Error::new(ErrorKind::Other, "TcpStream::keepalive_interval not implemented"))
+    }
+
+    pub fn set_keepalive_retries(&self, _retries: u32) -> Result<()> {
+        Err(Error::new(ErrorKind::Other, "TcpStream::set_keepalive_retries not implemented"))
+    }
+
+    pub fn keepalive_retries(&self) -> Result<u32> {
+        Err(Error::new(ErrorKind::Other, "TcpStream::keepalive_retries not implemented"))
+    }
+
+    pub fn set_linger(&self, _linger: Option<Duration>) -> Result<()> {
+        Err(Error::new(ErrorKind::Other, "TcpStream::set_linger not implemented"))
+    }
+
+    pub fn linger(&self) -> Result<Option<Duration>> {
+        Err(Error::new(ErrorKind::Other, "TcpStream::linger not implemented"))
+    }
+
+    pub fn set_recv_buffer_size(&self, _size: usize) -> Result<()> {
+        Err(Error::new(ErrorKind::Other, "TcpStream::set_recv_buffer_size not implemented"))
+    }
+
+    pub fn recv_buffer_size(&self) -> Result<usize> {
+        Err(Error::new(ErrorKind::Other, "TcpStream::recv_buffer_size not implemented"))
+    }
+
+    pub fn set_send_buffer_size(&self, _size: usize) -> Result<()> {
+        Err(Error::new(ErrorKind::Other, "TcpStream::set_send_buffer_size not implemented"))
+    }
+
+    pub fn send_buffer_size(&self) -> Result<usize> {
+        Err(Error::new(ErrorKind::Other, "TcpStream::send_buffer_size not implemented"))
+    }
 }
 
 impl AsInner<File> for TcpStream {
@@ -180,6 +236,16 @@ impl TcpListener {
         Ok(TcpListener(File::open(Path::new(path.as_str()), &options)?))
     }
 
+    pub fn bind_with<F>(addr: &SocketAddr, _backlog: u32, configure: F) -> Result<TcpListener>
+        where F: FnOnce(&TcpListener) -> Result<()>
+    {
+        // Opening the socket binds it right away, so options can only be set
+        // afterwards.
+        let listener = TcpListener::bind(addr)?;
+        configure(&listener)?;
+        Ok(listener)
+    }
+
     pub fn accept(&self) -> Result<(TcpStream, SocketAddr)> {
         let file = self.0.dup(b"listen")?;
         let path = file.path()?;
@@ -228,6 +294,34 @@ impl TcpListener {
         file.write(&[cmp::min(ttl, 255) as u8])?;
         Ok(())
     }
+
+    pub fn set_reuse_address(&self, _reuse: bool) -> Result<()> {
+        Err(Error::new(ErrorKind::Other, "TcpListener::set_reuse_address not implemented"))
+    }
+
+    pub fn set_reuse_port(&self, _reuse: bool) -> Result<()> {
+        Err(Error::new(ErrorKind::Other, "TcpListener::set_reuse_port not implemented"))
+    }
+
+    pub fn reuse_port(&self) -> Result<bool> {
+        Err(Error::new(ErrorKind::Other, "TcpListener::reuse_port not implemented"))
+    }
+
+    pub fn set_recv_buffer_size(&self, _size: usize) -> Result<()> {
+        Err(Error::new(ErrorKind::Other, "TcpListener::set_recv_buffer_size not implemented"))
+    }
+
+    pub fn recv_buffer_size(&self) -> Result<usize> {
+        Err(Error::new(ErrorKind::Other, "TcpListener::recv_buffer_size not implemented"))
+    }
+
+    pub fn set_send_buffer_size(&self, _size: usize) -> Result<()> {
+        Err(Error::new(ErrorKind::Other, "TcpListener::set_send_buffer_size not implemented"))
+    }
+
+    pub fn send_buffer_size(&self) -> Result<usize> {
+        Err(Error::new(ErrorKind::Other, "TcpListener::send_buffer_size not implemented"))
+    }
 }
 
 impl AsInner<File> for TcpListener {
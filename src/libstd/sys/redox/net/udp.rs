@@ -176,6 +176,30 @@ impl UdpSocket {
         Ok(())
     }
 
+    pub fn set_reuse_port(&self, _reuse: bool) -> Result<()> {
+        Err(Error::new(ErrorKind::Other, "UdpSocket::set_reuse_port not implemented"))
+    }
+
+    pub fn reuse_port(&self) -> Result<bool> {
+        Err(Error::new(ErrorKind::Other, "UdpSocket::reuse_port not implemented"))
+    }
+
+    pub fn set_recv_buffer_size(&self, _size: usize) -> Result<()> {
+        Err(Error::new(ErrorKind::Other, "UdpSocket::set_recv_buffer_size not implemented"))
+    }
+
+    pub fn recv_buffer_size(&self) -> Result<usize> {
+        Err(Error::new(ErrorKind::Other, "UdpSocket::recv_buffer_size not implemented"))
+    }
+
+    pub fn set_send_buffer_size(&self, _size: usize) -> Result<()> {
+        Err(Error::new(ErrorKind::Other, "UdpSocket::set_send_buffer_size not implemented"))
+    }
+
+    pub fn send_buffer_size(&self) -> Result<usize> {
+        Err(Error::new(ErrorKind::Other, "UdpSocket::send_buffer_size not implemented"))
+    }
+
     pub fn set_read_timeout(&self, duration_option: Option<Duration>) -> Result<()> {
         let file = self.0.dup(b"read_timeout")?;
         if let Some(duration) = duration_option {
@@ -8,17 +8,22 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use cmp;
 use env::{split_paths};
 use ffi::OsStr;
 use os::unix::ffi::OsStrExt;
 use fmt;
 use io::{self, Error, ErrorKind};
+use mem;
 use path::{Path, PathBuf};
 use sys::fd::FileDesc;
 use sys::fs::{File, OpenOptions};
 use sys::pipe::{self, AnonPipe};
+use sys::time::Instant;
 use sys::{cvt, syscall};
 use sys_common::process::{CommandEnv, DefaultEnvKey};
+use thread;
+use time::Duration;
 
 ////////////////////////////////////////////////////////////////////////////////
 // Command
@@ -76,6 +81,7 @@ enum ChildStdio {
     Inherit,
     Explicit(usize),
     Owned(FileDesc),
+    SameAsStdout,
 }
 
 pub enum Stdio {
@@ -83,6 +89,7 @@ pub enum Stdio {
     Null,
     MakePipe,
     Fd(FileDesc),
+    SameAsStdout,
 }
 
 impl Command {
@@ -135,6 +142,14 @@ impl Command {
         self.stderr = Some(stderr);
     }
 
+    pub fn replace_stdin(&mut self, stdin: Option<Stdio>) -> Option<Stdio> {
+        mem::replace(&mut self.stdin, stdin)
+    }
+
+    pub fn replace_stdout(&mut self, stdout: Option<Stdio>) -> Option<Stdio> {
+        mem::replace(&mut self.stdout, stdout)
+    }
+
     pub fn spawn(&mut self, default: Stdio, needs_stdin: bool)
                  -> io::Result<(Process, StdioPipes)> {
          const CLOEXEC_MSG_FOOTER: &'static [u8] = b"NOEX";
@@ -172,7 +187,7 @@ impl Command {
              }
          };
 
-         let mut p = Process { pid: pid, status: None };
+         let mut p = Process { pid: pid, status: None, kill_on_drop: false };
          drop(output);
          let mut bytes = [0; 8];
 
@@ -274,6 +289,9 @@ impl Command {
             flags &= ! syscall::O_CLOEXEC;
             t!(cvt(syscall::fcntl(1, syscall::F_SETFD, flags)));
         }
+        if let ChildStdio::SameAsStdout = stdio.stderr {
+            t!(cvt(syscall::dup2(1, 2, &[])));
+        }
         if let Some(fd) = stdio.stdin.fd() {
             t!(cvt(syscall::dup2(fd, 0, &[])));
             let mut flags = t!(cvt(syscall::fcntl(0, syscall::F_GETFD, 0)));
@@ -338,6 +356,10 @@ impl Command {
         let stdin = self.stdin.as_ref().unwrap_or(default_stdin);
         let stdout = self.stdout.as_ref().unwrap_or(&default);
         let stderr = self.stderr.as_ref().unwrap_or(&default);
+        if stdin.is_same_as_stdout() || stdout.is_same_as_stdout() {
+            return Err(io::Error::new(ErrorKind::InvalidInput,
+                                      "only stderr can be redirected to stdout"));
+        }
         let (their_stdin, our_stdin) = stdin.to_child_stdio(true)?;
         let (their_stdout, our_stdout) = stdout.to_child_stdio(false)?;
         let (their_stderr, our_stderr) = stderr.to_child_stdio(false)?;
@@ -393,6 +415,15 @@ impl Stdio {
                 let fd = File::open(Path::new("null:"), &opts)?;
                 Ok((ChildStdio::Owned(fd.into_fd()), None))
             }
+
+            Stdio::SameAsStdout => Ok((ChildStdio::SameAsStdout, None)),
+        }
+    }
+
+    fn is_same_as_stdout(&self) -> bool {
+        match *self {
+            Stdio::SameAsStdout => true,
+            _ => false,
         }
     }
 }
@@ -415,6 +446,7 @@ impl ChildStdio {
             ChildStdio::Inherit => None,
             ChildStdio::Explicit(fd) => Some(fd),
             ChildStdio::Owned(ref fd) => Some(fd.raw()),
+            ChildStdio::SameAsStdout => None,
         }
     }
 }
@@ -484,6 +516,7 @@ impl fmt::Display for ExitStatus {
 pub struct Process {
     pid: usize,
     status: Option<ExitStatus>,
+    kill_on_drop: bool,
 }
 
 impl Process {
@@ -527,4 +560,38 @@ impl Process {
             Ok(Some(ExitStatus(status as i32)))
         }
     }
+
+    pub fn wait_timeout(&mut self, dur: Duration) -> io::Result<Option<ExitStatus>> {
+        // Poll with an exponential backoff, as `waitpid` can't time out.
+        let deadline = match Instant::now().checked_add_duration(&dur) {
+            Some(deadline) => deadline,
+            // Too far in the future to ever be reached.
+            None => return self.wait().map(Some),
+        };
+        let mut delay = Duration::from_millis(1);
+        loop {
+            if let Some(status) = self.try_wait()? {
+                return Ok(Some(status))
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                return Ok(None)
+            }
+            thread::sleep(cmp::min(delay, deadline.sub_instant(&now)));
+            delay = cmp::min(delay * 2, Duration::from_millis(100));
+        }
+    }
+
+    pub fn set_kill_on_drop(&mut self, kill_on_drop: bool) {
+        self.kill_on_drop = kill_on_drop;
+    }
+}
+
+impl Drop for Process {
+    fn drop(&mut self) {
+        if self.kill_on_drop && self.status.is_none() {
+            let _ = self.kill();
+            let _ = self.wait();
+        }
+    }
 }
@@ -117,3 +117,8 @@ pub mod gnu {
 }
 
 pub struct BacktraceContext;
+
+/// Returns a context which can be used to resolve frames captured earlier.
+pub fn resolve_context() -> ::io::Result<BacktraceContext> {
+    Ok(BacktraceContext)
+}
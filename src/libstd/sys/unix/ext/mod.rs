@@ -43,6 +43,7 @@ pub mod process;
 pub mod raw;
 pub mod thread;
 pub mod net;
+pub mod poll;
 
 /// A prelude for conveniently writing platform-specific code.
 ///
@@ -0,0 +1,383 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Readiness polling for Unix file descriptors.
+//!
+//! This module provides [`poll`], which blocks the calling thread until one
+//! of a set of sockets, pipes or other file descriptors becomes ready for I/O,
+//! or a timeout elapses. It is typically combined with non-blocking handles
+//! (see for example [`TcpStream::set_nonblocking`]) so that a single thread
+//! can serve many connections.
+//!
+//! [`poll`]: fn.poll.html
+//! [`TcpStream::set_nonblocking`]: ../../../net/struct.TcpStream.html#method.set_nonblocking
+//!
+//! # Examples
+//!
+//! ```no_run
+//! #![feature(unix_poll)]
+//! use std::net::TcpListener;
+//! use std::os::unix::poll::{poll, Interest, PollFd};
+//! use std::time::Duration;
+//!
+//! let a = TcpListener::bind("127.0.0.1:8080").unwrap();
+//! let b = TcpListener::bind("127.0.0.1:8081").unwrap();
+//!
+//! let mut fds = [PollFd::new(&a, Interest::READABLE),
+//!                PollFd::new(&b, Interest::READABLE)];
+//! loop {
+//!     if poll(&mut fds, Some(Duration::from_secs(1))).unwrap() == 0 {
+//!         println!("nothing happened for a second");
+//!         continue
+//!     }
+//!     if fds[0].is_readable() {
+//!         let (_stream, addr) = a.accept().unwrap();
+//!         println!("connection to a from {}", addr);
+//!     }
+//!     if fds[1].is_readable() {
+//!         let (_stream, addr) = b.accept().unwrap();
+//!         println!("connection to b from {}", addr);
+//!     }
+//! }
+//! ```
+
+#![unstable(feature = "unix_poll", issue = "0")]
+
+#[cfg(unix)]
+use libc;
+
+// FIXME(#43348): Make libc adapt #[doc(cfg(...))] so we don't need these fake definitions here?
+#[cfg(not(unix))]
+mod libc {
+    pub use libc::{c_int, c_short};
+    #[derive(Copy, Clone)]
+    pub struct pollfd {
+        pub fd: c_int,
+        pub events: c_short,
+        pub revents: c_short,
+    }
+    pub const POLLIN: c_short = 0x1;
+    pub const POLLPRI: c_short = 0x2;
+    pub const POLLOUT: c_short = 0x4;
+    pub const POLLERR: c_short = 0x8;
+    pub const POLLHUP: c_short = 0x10;
+    pub const POLLNVAL: c_short = 0x20;
+}
+
+use fmt;
+use io;
+use ops::BitOr;
+use os::unix::io::{AsRawFd, RawFd};
+use time::Duration;
+
+#[cfg(unix)]
+use sys::net::poll as sys_poll;
+
+#[cfg(not(unix))]
+fn sys_poll(_fds: &mut [libc::pollfd], _timeout: Option<Duration>) -> io::Result<usize> {
+    Err(io::Error::new(io::ErrorKind::Other, "poll is not supported on this platform"))
+}
+
+/// The kinds of readiness a [`PollFd`] waits for.
+///
+/// Interests can be combined with the `|` operator.
+///
+/// [`PollFd`]: struct.PollFd.html
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+#[unstable(feature = "unix_poll", issue = "0")]
+pub struct Interest(libc::c_short);
+
+impl Interest {
+    /// Wait for the handle to become readable: data is available, a listener
+    /// has a pending connection, or the peer has closed its end.
+    #[unstable(feature = "unix_poll", issue = "0")]
+    pub const READABLE: Interest = Interest(libc::POLLIN);
+
+    /// Wait for the handle to become writable without blocking.
+    #[unstable(feature = "unix_poll", issue = "0")]
+    pub const WRITABLE: Interest = Interest(libc::POLLOUT);
+
+    /// Wait for urgent (out-of-band) data to become available.
+    #[unstable(feature = "unix_poll", issue = "0")]
+    pub const PRIORITY: Interest = Interest(libc::POLLPRI);
+
+    /// Returns whether `self` includes all of `other`.
+    #[unstable(feature = "unix_poll", issue = "0")]
+    pub fn contains(&self, other: Interest) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+#[unstable(feature = "unix_poll", issue = "0")]
+impl BitOr for Interest {
+    type Output = Interest;
+
+    fn bitor(self, other: Interest) -> Interest {
+        Interest(self.0 | other.0)
+    }
+}
+
+#[unstable(feature = "unix_poll", issue = "0")]
+impl fmt::Debug for Interest {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Interest")
+            .field("readable", &self.contains(Interest::READABLE))
+            .field("writable", &self.contains(Interest::WRITABLE))
+            .field("priority", &self.contains(Interest::PRIORITY))
+            .finish()
+    }
+}
+
+/// A file descriptor to be polled, together with the readiness it is polled
+/// for and the events reported for it by the last call to [`poll`].
+///
+/// A `PollFd` only records the raw file descriptor of the handle it was
+/// created from. The handle must therefore be kept open for as long as the
+/// `PollFd` is passed to `poll`; otherwise the descriptor may be closed, or
+/// even reused for an unrelated file.
+///
+/// [`poll`]: fn.poll.html
+#[derive(Copy, Clone)]
+#[repr(C)]
+#[unstable(feature = "unix_poll", issue = "0")]
+pub struct PollFd(libc::pollfd);
+
+impl PollFd {
+    /// Creates a `PollFd` polling the file descriptor of `handle` for the
+    /// readiness described by `interest`.
+    #[unstable(feature = "unix_poll", issue = "0")]
+    pub fn new<T: AsRawFd + ?Sized>(handle: &T, interest: Interest) -> PollFd {
+        PollFd::from_raw_fd(handle.as_raw_fd(), interest)
+    }
+
+    /// Creates a `PollFd` polling the raw file descriptor `fd`.
+    ///
+    /// A negative `fd` is ignored by [`poll`], which makes it possible to
+    /// temporarily disable an entry without removing it from the set.
+    ///
+    /// [`poll`]: fn.poll.html
+    #[unstable(feature = "unix_poll", issue = "0")]
+    pub fn from_raw_fd(fd: RawFd, interest: Interest) -> PollFd {
+        PollFd(libc::pollfd { fd: fd, events: interest.0, revents: 0 })
+    }
+
+    /// Returns the raw file descriptor being polled.
+    #[unstable(feature = "unix_poll", issue = "0")]
+    pub fn fd(&self) -> RawFd {
+        self.0.fd
+    }
+
+    /// Returns the readiness being polled for.
+    #[unstable(feature = "unix_poll", issue = "0")]
+    pub fn interest(&self) -> Interest {
+        Interest(self.0.events)
+    }
+
+    /// Changes the readiness being polled for.
+    #[unstable(feature = "unix_poll", issue = "0")]
+    pub fn set_interest(&mut self, interest: Interest) {
+        self.0.events = interest.0;
+    }
+
+    /// Returns whether the last call to `poll` reported the handle as
+    /// readable.
+    ///
+    /// Note that a socket whose peer has shut down its end is readable, with
+    /// a read returning 0 bytes.
+    #[unstable(feature = "unix_poll", issue = "0")]
+    pub fn is_readable(&self) -> bool {
+        self.0.revents & libc::POLLIN != 0
+    }
+
+    /// Returns whether the last call to `poll` reported the handle as
+    /// writable.
+    #[unstable(feature = "unix_poll", issue = "0")]
+    pub fn is_writable(&self) -> bool {
+        self.0.revents & libc::POLLOUT != 0
+    }
+
+    /// Returns whether the last call to `poll` reported urgent data on the
+    /// handle.
+    #[unstable(feature = "unix_poll", issue = "0")]
+    pub fn is_priority(&self) -> bool {
+        self.0.revents & libc::POLLPRI != 0
+    }
+
+    /// Returns whether the last call to `poll` reported a hangup: the peer
+    /// of a socket or the other end of a pipe has been closed.
+    ///
+    /// Hangups are always reported, whatever the interest.
+    #[unstable(feature = "unix_poll", issue = "0")]
+    pub fn is_hangup(&self) -> bool {
+        self.0.revents & libc::POLLHUP != 0
+    }
+
+    /// Returns whether the last call to `poll` reported an error condition on
+    /// the handle, or that the file descriptor isn't open.
+    ///
+    /// For sockets, the pending error can be retrieved with `take_error`.
+    /// Errors are always reported, whatever the interest.
+    #[unstable(feature = "unix_poll", issue = "0")]
+    pub fn is_error(&self) -> bool {
+        self.0.revents & (libc::POLLERR | libc::POLLNVAL) != 0
+    }
+
+    /// Returns whether the last call to `poll` reported any event at all for
+    /// this handle.
+    #[unstable(feature = "unix_poll", issue = "0")]
+    pub fn is_ready(&self) -> bool {
+        self.0.revents != 0
+    }
+}
+
+#[unstable(feature = "unix_poll", issue = "0")]
+impl fmt::Debug for PollFd {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("PollFd")
+            .field("fd", &self.fd())
+            .field("interest", &self.interest())
+            .field("readable", &self.is_readable())
+            .field("writable", &self.is_writable())
+            .field("hangup", &self.is_hangup())
+            .field("error", &self.is_error())
+            .finish()
+    }
+}
+
+/// Waits until at least one of `fds` is ready, or until `timeout` elapses.
+///
+/// Returns the number of entries of `fds` for which an event was reported,
+/// which is 0 if the timeout elapsed first. The events themselves are
+/// queried through the methods of each [`PollFd`], and are reset by every
+/// call to `poll`.
+///
+/// A `timeout` of `None` blocks indefinitely, while a zero duration only
+/// checks for readiness without blocking. If the call is interrupted by a
+/// signal it is restarted with the remaining time.
+///
+/// This is implemented with the `poll` system call, so it scales linearly
+/// with the number of file descriptors passed in.
+///
+/// [`PollFd`]: struct.PollFd.html
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(unix_poll)]
+/// use std::io::Read;
+/// use std::os::unix::net::UnixStream;
+/// use std::os::unix::poll::{poll, Interest, PollFd};
+///
+/// let mut stream = UnixStream::connect("/path/to/my/socket").unwrap();
+/// let mut fds = [PollFd::new(&stream, Interest::READABLE)];
+///
+/// poll(&mut fds, None).unwrap();
+/// if fds[0].is_readable() {
+///     let mut buf = [0; 1024];
+///     let n = stream.read(&mut buf).unwrap();
+///     println!("read {} bytes", n);
+/// }
+/// ```
+#[unstable(feature = "unix_poll", issue = "0")]
+pub fn poll(fds: &mut [PollFd], timeout: Option<Duration>) -> io::Result<usize> {
+    // `PollFd` is a `repr(C)` wrapper around `pollfd`, so the slice can be
+    // handed to the OS as is.
+    let fds = unsafe {
+        ::slice::from_raw_parts_mut(fds.as_mut_ptr() as *mut libc::pollfd, fds.len())
+    };
+    sys_poll(fds, timeout)
+}
+
+#[cfg(all(test, not(target_os = "emscripten")))]
+mod test {
+    use io::prelude::*;
+    use net::{TcpListener, TcpStream};
+    use os::unix::net::UnixStream;
+    use super::*;
+    use time::{Duration, Instant};
+
+    macro_rules! or_panic {
+        ($e:expr) => {
+            match $e {
+                Ok(e) => e,
+                Err(e) => panic!("{}", e),
+            }
+        }
+    }
+
+    #[test]
+    fn timeout() {
+        let (a, _b) = or_panic!(UnixStream::pair());
+        let mut fds = [PollFd::new(&a, Interest::READABLE)];
+
+        let start = Instant::now();
+        assert_eq!(or_panic!(poll(&mut fds, Some(Duration::from_millis(50)))), 0);
+        assert!(start.elapsed() >= Duration::from_millis(50));
+        assert!(!fds[0].is_ready());
+
+        assert_eq!(or_panic!(poll(&mut fds, Some(Duration::from_secs(0)))), 0);
+    }
+
+    #[test]
+    fn huge_timeout() {
+        let (a, _b) = or_panic!(UnixStream::pair());
+        let mut fds = [PollFd::new(&a, Interest::WRITABLE)];
+
+        let timeout = Duration::new(u64::max_value(), 999_999_999);
+        assert_eq!(or_panic!(poll(&mut fds, Some(timeout))), 1);
+        assert!(fds[0].is_writable());
+    }
+
+    #[test]
+    fn readable_writable() {
+        let (a, mut b) = or_panic!(UnixStream::pair());
+        let mut fds = [PollFd::new(&a, Interest::READABLE | Interest::WRITABLE),
+                       PollFd::new(&b, Interest::READABLE)];
+
+        assert_eq!(or_panic!(poll(&mut fds, None)), 1);
+        assert!(fds[0].is_writable());
+        assert!(!fds[0].is_readable());
+        assert!(!fds[1].is_ready());
+
+        or_panic!(b.write_all(b"hello"));
+        fds[0].set_interest(Interest::READABLE);
+        assert_eq!(or_panic!(poll(&mut fds, None)), 1);
+        assert!(fds[0].is_readable());
+        assert!(!fds[0].is_writable());
+    }
+
+    #[test]
+    fn hangup() {
+        let (a, b) = or_panic!(UnixStream::pair());
+        let mut fds = [PollFd::new(&a, Interest::READABLE)];
+        drop(b);
+
+        assert_eq!(or_panic!(poll(&mut fds, Some(Duration::from_secs(10)))), 1);
+        assert!(fds[0].is_readable() || fds[0].is_hangup());
+    }
+
+    #[test]
+    fn listener() {
+        let listener = or_panic!(TcpListener::bind("127.0.0.1:0"));
+        let addr = or_panic!(listener.local_addr());
+        let mut fds = [PollFd::new(&listener, Interest::READABLE)];
+
+        assert_eq!(or_panic!(poll(&mut fds, Some(Duration::from_millis(10)))), 0);
+        let _stream = or_panic!(TcpStream::connect(addr));
+        assert_eq!(or_panic!(poll(&mut fds, Some(Duration::from_secs(10)))), 1);
+        assert!(fds[0].is_readable());
+    }
+
+    #[test]
+    fn negative_fd_is_ignored() {
+        let mut fds = [PollFd::from_raw_fd(-1, Interest::READABLE)];
+        assert_eq!(or_panic!(poll(&mut fds, Some(Duration::from_millis(1)))), 0);
+    }
+}
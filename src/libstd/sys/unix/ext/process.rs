@@ -117,6 +117,108 @@ impl CommandExt for process::Command {
     }
 }
 
+/// Unix-specific extensions to the [`process::Command`] builder for
+/// controlling the process group and session of the child.
+///
+/// [`process::Command`]: ../../../process/struct.Command.html
+#[unstable(feature = "process_group", issue = "0")]
+pub trait CommandGroupExt {
+    /// Sets the process group of the child process. This translates to a
+    /// `setpgid` call in the child process.
+    ///
+    /// If `pgroup` is 0, the child is made the leader of a new process group
+    /// whose id is the child's own process id. Otherwise the child joins the
+    /// existing process group `pgroup`. Either way the whole group can
+    /// afterwards be signalled through [`ChildExt::signal_group`].
+    ///
+    /// This is overridden by [`setsid`], which also creates a new process
+    /// group.
+    ///
+    /// [`ChildExt::signal_group`]: trait.ChildExt.html#tymethod.signal_group
+    /// [`setsid`]: #tymethod.setsid
+    #[unstable(feature = "process_group", issue = "0")]
+    fn process_group(&mut self, pgroup: i32) -> &mut process::Command;
+
+    /// Sets whether the child process is run in a new session. This
+    /// translates to a `setsid` call in the child process.
+    ///
+    /// The child becomes the leader of both a new session and a new process
+    /// group, and is detached from the controlling terminal of the parent.
+    #[unstable(feature = "process_group", issue = "0")]
+    fn setsid(&mut self, setsid: bool) -> &mut process::Command;
+}
+
+#[unstable(feature = "process_group", issue = "0")]
+impl CommandGroupExt for process::Command {
+    fn process_group(&mut self, pgroup: i32) -> &mut process::Command {
+        self.as_inner_mut().pgroup(pgroup);
+        self
+    }
+
+    fn setsid(&mut self, setsid: bool) -> &mut process::Command {
+        self.as_inner_mut().setsid(setsid);
+        self
+    }
+}
+
+/// Unix-specific extensions to `std::process::Child`
+#[unstable(feature = "process_group", issue = "0")]
+pub trait ChildExt {
+    /// Sends the signal `signal` to the child process.
+    ///
+    /// As with [`kill`], this returns an error if the child has already been
+    /// waited on, since its process id may have been reused.
+    ///
+    /// [`kill`]: ../../../process/struct.Child.html#method.kill
+    #[unstable(feature = "process_group", issue = "0")]
+    fn signal(&mut self, signal: i32) -> io::Result<()>;
+
+    /// Sends the signal `signal` to every process in the child's process
+    /// group.
+    ///
+    /// This requires the child to have been spawned into its own process
+    /// group with [`CommandGroupExt::process_group`] or [`CommandGroupExt::setsid`],
+    /// and returns an error of kind `InvalidInput` otherwise. Processes which
+    /// the child has spawned in turn stay in its group unless they move
+    /// themselves out of it, so this can be used to tear down a whole process
+    /// tree, even after the child itself has exited.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(process_group)]
+    /// use std::process::Command;
+    /// use std::os::unix::process::{ChildExt, CommandGroupExt};
+    ///
+    /// let mut child = Command::new("sh")
+    ///     .arg("-c")
+    ///     .arg("sleep 1000 & sleep 1000")
+    ///     .process_group(0)
+    ///     .spawn()
+    ///     .unwrap();
+    ///
+    /// // Kills both `sleep`s as well as the shell itself.
+    /// child.signal_group(9).unwrap();
+    /// child.wait().unwrap();
+    /// ```
+    ///
+    /// [`CommandGroupExt::process_group`]: trait.CommandGroupExt.html#tymethod.process_group
+    /// [`CommandGroupExt::setsid`]: trait.CommandGroupExt.html#tymethod.setsid
+    #[unstable(feature = "process_group", issue = "0")]
+    fn signal_group(&mut self, signal: i32) -> io::Result<()>;
+}
+
+#[unstable(feature = "process_group", issue = "0")]
+impl ChildExt for process::Child {
+    fn signal(&mut self, signal: i32) -> io::Result<()> {
+        self.as_inner_mut().signal(signal)
+    }
+
+    fn signal_group(&mut self, signal: i32) -> io::Result<()> {
+        self.as_inner_mut().signal_group(signal)
+    }
+}
+
 /// Unix-specific extensions to `std::process::ExitStatus`
 #[stable(feature = "rust1", since = "1.0.0")]
 pub trait ExitStatusExt {
@@ -15,7 +15,7 @@ macro_rules! unimpl {
 pub mod net {
     #![allow(warnings)]
     use fmt;
-    use io;
+    use io::{self, IoVec, IoVecMut};
     use libc;
     use net::{SocketAddr, Shutdown, Ipv4Addr, Ipv6Addr};
     use sys_common::{AsInner, FromInner, IntoInner};
@@ -56,6 +56,10 @@ pub mod net {
             unimpl!();
         }
 
+        pub fn read_vectored(&self, _: &mut [IoVecMut]) -> io::Result<usize> {
+            unimpl!();
+        }
+
         pub fn peek(&self, _: &mut [u8]) -> io::Result<usize> {
             unimpl!();
         }
@@ -72,6 +76,10 @@ pub mod net {
             unimpl!();
         }
 
+        pub fn write_vectored(&self, _: &[IoVec]) -> io::Result<usize> {
+            unimpl!();
+        }
+
         pub fn set_timeout(&self, _: Option<Duration>, _: libc::c_int) -> io::Result<()> {
             unimpl!();
         }
@@ -154,10 +162,18 @@ pub mod net {
             unimpl!();
         }
 
+        pub fn read_vectored(&self, _: &mut [IoVecMut]) -> io::Result<usize> {
+            unimpl!();
+        }
+
         pub fn write(&self, _: &[u8]) -> io::Result<usize> {
             unimpl!();
         }
 
+        pub fn write_vectored(&self, _: &[IoVec]) -> io::Result<usize> {
+            unimpl!();
+        }
+
         pub fn peer_addr(&self) -> io::Result<SocketAddr> {
             unimpl!();
         }
@@ -197,6 +213,54 @@ pub mod net {
         pub fn set_nonblocking(&self, _: bool) -> io::Result<()> {
             unimpl!();
         }
+
+        pub fn set_keepalive(&self, _: Option<Duration>) -> io::Result<()> {
+            unimpl!();
+        }
+
+        pub fn keepalive(&self) -> io::Result<Option<Duration>> {
+            unimpl!();
+        }
+
+        pub fn set_keepalive_interval(&self, _: Duration) -> io::Result<()> {
+            unimpl!();
+        }
+
+        pub fn keepalive_interval(&self) -> io::Result<Duration> {
+            unimpl!();
+        }
+
+        pub fn set_keepalive_retries(&self, _: u32) -> io::Result<()> {
+            unimpl!();
+        }
+
+        pub fn keepalive_retries(&self) -> io::Result<u32> {
+            unimpl!();
+        }
+
+        pub fn set_linger(&self, _: Option<Duration>) -> io::Result<()> {
+            unimpl!();
+        }
+
+        pub fn linger(&self) -> io::Result<Option<Duration>> {
+            unimpl!();
+        }
+
+        pub fn set_recv_buffer_size(&self, _: usize) -> io::Result<()> {
+            unimpl!();
+        }
+
+        pub fn recv_buffer_size(&self) -> io::Result<usize> {
+            unimpl!();
+        }
+
+        pub fn set_send_buffer_size(&self, _: usize) -> io::Result<()> {
+            unimpl!();
+        }
+
+        pub fn send_buffer_size(&self) -> io::Result<usize> {
+            unimpl!();
+        }
     }
 
     impl FromInner<Socket> for TcpStream {
@@ -220,6 +284,12 @@ pub mod net {
             unimpl!();
         }
 
+        pub fn bind_with<F>(_: &SocketAddr, _: u32, _: F) -> io::Result<TcpListener>
+            where F: FnOnce(&TcpListener) -> io::Result<()>
+        {
+            unimpl!();
+        }
+
         pub fn socket(&self) -> &Socket { &self.inner }
 
         pub fn into_socket(self) -> Socket { self.inner }
@@ -259,6 +329,34 @@ pub mod net {
         pub fn set_nonblocking(&self, _: bool) -> io::Result<()> {
             unimpl!();
         }
+
+        pub fn set_reuse_address(&self, _: bool) -> io::Result<()> {
+            unimpl!();
+        }
+
+        pub fn set_reuse_port(&self, _: bool) -> io::Result<()> {
+            unimpl!();
+        }
+
+        pub fn reuse_port(&self) -> io::Result<bool> {
+            unimpl!();
+        }
+
+        pub fn set_recv_buffer_size(&self, _: usize) -> io::Result<()> {
+            unimpl!();
+        }
+
+        pub fn recv_buffer_size(&self) -> io::Result<usize> {
+            unimpl!();
+        }
+
+        pub fn set_send_buffer_size(&self, _: usize) -> io::Result<()> {
+            unimpl!();
+        }
+
+        pub fn send_buffer_size(&self) -> io::Result<usize> {
+            unimpl!();
+        }
     }
 
     impl FromInner<Socket> for TcpListener {
@@ -390,6 +488,30 @@ pub mod net {
             unimpl!();
         }
 
+        pub fn set_reuse_port(&self, _: bool) -> io::Result<()> {
+            unimpl!();
+        }
+
+        pub fn reuse_port(&self) -> io::Result<bool> {
+            unimpl!();
+        }
+
+        pub fn set_recv_buffer_size(&self, _: usize) -> io::Result<()> {
+            unimpl!();
+        }
+
+        pub fn recv_buffer_size(&self) -> io::Result<usize> {
+            unimpl!();
+        }
+
+        pub fn set_send_buffer_size(&self, _: usize) -> io::Result<()> {
+            unimpl!();
+        }
+
+        pub fn send_buffer_size(&self) -> io::Result<usize> {
+            unimpl!();
+        }
+
         pub fn recv(&self, _: &mut [u8]) -> io::Result<usize> {
             unimpl!();
         }
@@ -434,6 +556,10 @@ pub mod net {
     unsafe impl Sync for LookupHost {}
     unsafe impl Send for LookupHost {}
 
+    pub fn poll(_: &mut [libc::pollfd], _: Option<Duration>) -> io::Result<usize> {
+        unimpl!();
+    }
+
     pub fn lookup_host(_: &str) -> io::Result<LookupHost> {
         unimpl!();
     }
@@ -46,6 +46,7 @@ pub mod ext;
 pub mod fast_thread_local;
 pub mod fd;
 pub mod fs;
+pub mod iovec;
 pub mod memchr;
 pub mod mutex;
 #[cfg(not(target_os = "l4re"))]
@@ -9,7 +9,7 @@
 // except according to those terms.
 
 use ffi::CStr;
-use io;
+use io::{self, IoVec, IoVecMut};
 use libc::{self, c_int, c_void, size_t, sockaddr, socklen_t, EAI_SYSTEM, MSG_PEEK};
 use mem;
 use net::{SocketAddr, Shutdown};
@@ -244,6 +244,10 @@ impl Socket {
         self.recv_with_flags(buf, 0)
     }
 
+    pub fn read_vectored(&self, bufs: &mut [IoVecMut]) -> io::Result<usize> {
+        self.0.read_vectored(bufs)
+    }
+
     pub fn peek(&self, buf: &mut [u8]) -> io::Result<usize> {
         self.recv_with_flags(buf, MSG_PEEK)
     }
@@ -276,6 +280,10 @@ impl Socket {
         self.0.write(buf)
     }
 
+    pub fn write_vectored(&self, bufs: &[IoVec]) -> io::Result<usize> {
+        self.0.write_vectored(bufs)
+    }
+
     pub fn set_timeout(&self, dur: Option<Duration>, kind: libc::c_int) -> io::Result<()> {
         let timeout = match dur {
             Some(dur) => {
@@ -353,6 +361,46 @@ impl Socket {
     }
 }
 
+pub fn poll(fds: &mut [libc::pollfd], timeout: Option<Duration>) -> io::Result<usize> {
+    // A timeout too large to compute a deadline for is as good as no timeout.
+    let deadline = timeout.and_then(|timeout| {
+        ::sys::time::Instant::now().checked_add_duration(&timeout)
+    });
+
+    loop {
+        let timeout = match deadline {
+            None => -1,
+            Some(deadline) => {
+                let now = ::sys::time::Instant::now();
+                let timeout = if deadline > now {
+                    deadline.sub_instant(&now)
+                } else {
+                    Duration::new(0, 0)
+                };
+                // Round up to the next millisecond, so that we never return
+                // before the timeout has elapsed.
+                let mut ms = timeout.as_secs()
+                    .saturating_mul(1_000)
+                    .saturating_add(timeout.subsec_nanos() as u64 / 1_000_000);
+                if timeout.subsec_nanos() % 1_000_000 != 0 {
+                    ms = ms.saturating_add(1);
+                }
+                cmp::min(ms, c_int::max_value() as u64) as c_int
+            }
+        };
+
+        match unsafe { libc::poll(fds.as_mut_ptr(), fds.len() as libc::nfds_t, timeout) } {
+            -1 => {
+                let err = io::Error::last_os_error();
+                if err.kind() != io::ErrorKind::Interrupted {
+                    return Err(err);
+                }
+            }
+            n => return Ok(n as usize),
+        }
+    }
+}
+
 impl AsInner<c_int> for Socket {
     fn as_inner(&self) -> &c_int { self.0.as_inner() }
 }
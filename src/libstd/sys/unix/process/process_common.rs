@@ -13,7 +13,8 @@ use os::unix::prelude::*;
 use ffi::{OsString, OsStr, CString, CStr};
 use fmt;
 use io;
-use libc::{self, c_int, gid_t, uid_t, c_char};
+use libc::{self, c_int, gid_t, pid_t, uid_t, c_char};
+use mem;
 use ptr;
 use sys::fd::FileDesc;
 use sys::fs::{File, OpenOptions};
@@ -51,6 +52,8 @@ pub struct Command {
     cwd: Option<CString>,
     uid: Option<uid_t>,
     gid: Option<gid_t>,
+    pgroup: Option<pid_t>,
+    setsid: bool,
     saw_nul: bool,
     closures: Vec<Box<FnMut() -> io::Result<()> + Send + Sync>>,
     stdin: Option<Stdio>,
@@ -84,6 +87,8 @@ pub enum ChildStdio {
     Inherit,
     Explicit(c_int),
     Owned(FileDesc),
+    // only ever used for stderr, which is then dup'd from the child's stdout
+    SameAsStdout,
 }
 
 pub enum Stdio {
@@ -91,6 +96,7 @@ pub enum Stdio {
     Null,
     MakePipe,
     Fd(FileDesc),
+    SameAsStdout,
 }
 
 impl Command {
@@ -105,6 +111,8 @@ impl Command {
             cwd: None,
             uid: None,
             gid: None,
+            pgroup: None,
+            setsid: false,
             saw_nul,
             closures: Vec::new(),
             stdin: None,
@@ -134,6 +142,12 @@ impl Command {
     pub fn gid(&mut self, id: gid_t) {
         self.gid = Some(id);
     }
+    pub fn pgroup(&mut self, pgroup: pid_t) {
+        self.pgroup = Some(pgroup);
+    }
+    pub fn setsid(&mut self, setsid: bool) {
+        self.setsid = setsid;
+    }
 
     pub fn saw_nul(&self) -> bool {
         self.saw_nul
@@ -154,6 +168,14 @@ impl Command {
     pub fn get_gid(&self) -> Option<gid_t> {
         self.gid
     }
+    #[allow(dead_code)]
+    pub fn get_pgroup(&self) -> Option<pid_t> {
+        self.pgroup
+    }
+    #[allow(dead_code)]
+    pub fn get_setsid(&self) -> bool {
+        self.setsid
+    }
 
     pub fn get_closures(&mut self) -> &mut Vec<Box<FnMut() -> io::Result<()> + Send + Sync>> {
         &mut self.closures
@@ -176,6 +198,14 @@ impl Command {
         self.stderr = Some(stderr);
     }
 
+    pub fn replace_stdin(&mut self, stdin: Option<Stdio>) -> Option<Stdio> {
+        mem::replace(&mut self.stdin, stdin)
+    }
+
+    pub fn replace_stdout(&mut self, stdout: Option<Stdio>) -> Option<Stdio> {
+        mem::replace(&mut self.stdout, stdout)
+    }
+
     pub fn env_mut(&mut self) -> &mut CommandEnv<DefaultEnvKey> {
         &mut self.env
     }
@@ -192,6 +222,10 @@ impl Command {
         let stdin = self.stdin.as_ref().unwrap_or(default_stdin);
         let stdout = self.stdout.as_ref().unwrap_or(&default);
         let stderr = self.stderr.as_ref().unwrap_or(&default);
+        if stdin.is_same_as_stdout() || stdout.is_same_as_stdout() {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput,
+                                      "only stderr can be redirected to stdout"));
+        }
         let (their_stdin, our_stdin) = stdin.to_child_stdio(true)?;
         let (their_stdout, our_stdout) = stdout.to_child_stdio(false)?;
         let (their_stderr, our_stderr) = stderr.to_child_stdio(false)?;
@@ -306,6 +340,17 @@ impl Stdio {
                 let fd = File::open_c(&path, &opts)?;
                 Ok((ChildStdio::Owned(fd.into_fd()), None))
             }
+
+            Stdio::SameAsStdout => {
+                Ok((ChildStdio::SameAsStdout, None))
+            }
+        }
+    }
+
+    fn is_same_as_stdout(&self) -> bool {
+        match *self {
+            Stdio::SameAsStdout => true,
+            _ => false,
         }
     }
 }
@@ -328,6 +373,7 @@ impl ChildStdio {
             ChildStdio::Inherit => None,
             ChildStdio::Explicit(fd) => Some(fd),
             ChildStdio::Owned(ref fd) => Some(fd.raw()),
+            ChildStdio::SameAsStdout => None,
         }
     }
 }
@@ -9,9 +9,10 @@
 // except according to those terms.
 
 use io;
-use libc::{self, size_t};
+use libc::{self, c_int, size_t};
 use mem;
 use ptr;
+use time::Duration;
 
 use sys::process::zircon::{Handle, zx_handle_t};
 use sys::process::process_common::*;
@@ -34,7 +35,7 @@ impl Command {
 
         let process_handle = unsafe { self.do_exec(theirs, envp.as_ref())? };
 
-        Ok((Process { handle: Handle::new(process_handle) }, ours))
+        Ok((Process { handle: Handle::new(process_handle), kill_on_drop: false }, ours))
     }
 
     pub fn exec(&mut self, default: Stdio) -> io::Error {
@@ -93,6 +94,12 @@ impl Command {
         } else {
             zx_cvt(launchpad_clone_fd(launchpad, 0, 0))?;
         }
+        // A stderr redirected to stdout has to be cloned from the stdout fd
+        // before that one is transferred to the child.
+        if let ChildStdio::SameAsStdout = stdio.stderr {
+            let fd = stdio.stdout.fd().unwrap_or(1);
+            zx_cvt(launchpad_clone_fd(launchpad, fd, 2))?;
+        }
         if let Some(fd) = stdio.stdout.fd() {
             zx_cvt(launchpad_transfer_fd(launchpad, fd, 1))?;
         } else {
@@ -100,7 +107,7 @@ impl Command {
         }
         if let Some(fd) = stdio.stderr.fd() {
             zx_cvt(launchpad_transfer_fd(launchpad, fd, 2))?;
-        } else {
+        } else if let ChildStdio::Inherit = stdio.stderr {
             zx_cvt(launchpad_clone_fd(launchpad, 2, 2))?;
         }
 
@@ -130,6 +137,7 @@ impl Command {
 
 pub struct Process {
     handle: Handle,
+    kill_on_drop: bool,
 }
 
 impl Process {
@@ -197,4 +205,49 @@ impl Process {
         }
         Ok(Some(ExitStatus::new(proc_info.rec.return_code)))
     }
+
+    pub fn wait_timeout(&mut self, dur: Duration) -> io::Result<Option<ExitStatus>> {
+        use sys::process::zircon::*;
+
+        // The wait takes an absolute deadline rather than a timeout.
+        let deadline = dur.as_secs().checked_mul(1_000_000_000).and_then(|nanos| {
+            nanos.checked_add(dur.subsec_nanos() as u64)
+        }).map_or(ZX_TIME_INFINITE, |nanos| unsafe { zx_deadline_after(nanos) });
+
+        unsafe {
+            let status = zx_object_wait_one(self.handle.raw(), ZX_TASK_TERMINATED,
+                                            deadline, ptr::null_mut());
+            match status {
+                0 => { }, // Success
+                x if x == ERR_TIMED_OUT => {
+                    return Ok(None);
+                },
+                _ => { zx_cvt(status)?; },
+            }
+        }
+        self.try_wait()
+    }
+
+    pub fn signal(&mut self, _signal: c_int) -> io::Result<()> {
+        Err(io::Error::new(io::ErrorKind::Other,
+                           "signals are not supported on Fuchsia"))
+    }
+
+    pub fn signal_group(&mut self, _signal: c_int) -> io::Result<()> {
+        Err(io::Error::new(io::ErrorKind::Other,
+                           "process groups are not supported on Fuchsia"))
+    }
+
+    pub fn set_kill_on_drop(&mut self, kill_on_drop: bool) {
+        self.kill_on_drop = kill_on_drop;
+    }
+}
+
+impl Drop for Process {
+    fn drop(&mut self) {
+        if self.kill_on_drop {
+            // Killing a task which has already terminated is harmless.
+            let _ = self.kill();
+        }
+    }
 }
@@ -8,11 +8,15 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use cmp;
 use io::{self, Error, ErrorKind};
 use libc::{self, c_int, gid_t, pid_t, uid_t};
 use ptr;
+use thread;
+use time::Duration;
 
 use sys::cvt;
+use sys::time::Instant;
 use sys::process::process_common::*;
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -60,7 +64,21 @@ impl Command {
             }
         };
 
-        let mut p = Process { pid: pid, status: None };
+        // The child moves itself into its new process group (or session) in
+        // `do_exec`, but we also do so from the parent to close the window in
+        // which the group doesn't exist yet and can't be signalled. Whichever
+        // of the two calls runs second fails harmlessly.
+        let pgid = if self.get_setsid() {
+            Some(pid)
+        } else {
+            self.get_pgroup().map(|pgroup| {
+                let pgroup = if pgroup == 0 { pid } else { pgroup };
+                unsafe { libc::setpgid(pid, pgroup); }
+                pgroup
+            })
+        };
+
+        let mut p = Process { pid: pid, pgid: pgid, status: None, kill_on_drop: false };
         drop(output);
         let mut bytes = [0; 8];
 
@@ -166,6 +184,16 @@ impl Command {
         }
         if let Some(fd) = stdio.stderr.fd() {
             t!(cvt_r(|| libc::dup2(fd, libc::STDERR_FILENO)));
+        } else if let ChildStdio::SameAsStdout = stdio.stderr {
+            // stdout has already been set up above, so this makes stderr
+            // share whatever it now refers to, pipe or otherwise.
+            t!(cvt_r(|| libc::dup2(libc::STDOUT_FILENO, libc::STDERR_FILENO)));
+        }
+
+        if self.get_setsid() {
+            t!(cvt(libc::setsid()));
+        } else if let Some(pgroup) = self.get_pgroup() {
+            t!(cvt(libc::setpgid(0, pgroup)));
         }
 
         if cfg!(not(any(target_os = "l4re"))) {
@@ -238,7 +266,10 @@ impl Command {
 /// The unique id of the process (this should never be negative).
 pub struct Process {
     pid: pid_t,
+    // The process group the child was moved into when spawned, if any.
+    pgid: Option<pid_t>,
     status: Option<ExitStatus>,
+    kill_on_drop: bool,
 }
 
 impl Process {
@@ -247,6 +278,10 @@ impl Process {
     }
 
     pub fn kill(&mut self) -> io::Result<()> {
+        self.signal(libc::SIGKILL)
+    }
+
+    pub fn signal(&mut self, signal: c_int) -> io::Result<()> {
         // If we've already waited on this process then the pid can be recycled
         // and used for another process, and we probably shouldn't be killing
         // random processes, so just return an error.
@@ -254,10 +289,26 @@ impl Process {
             Err(Error::new(ErrorKind::InvalidInput,
                            "invalid argument: can't kill an exited process"))
         } else {
-            cvt(unsafe { libc::kill(self.pid, libc::SIGKILL) }).map(|_| ())
+            cvt(unsafe { libc::kill(self.pid, signal) }).map(|_| ())
+        }
+    }
+
+    pub fn signal_group(&mut self, signal: c_int) -> io::Result<()> {
+        // Unlike the pid of an exited child, the id of a process group can't
+        // be recycled while any process remains in the group, so it's fine to
+        // signal the group after its leader has been reaped.
+        match self.pgid {
+            Some(pgid) => cvt(unsafe { libc::killpg(pgid, signal) }).map(|_| ()),
+            None => Err(Error::new(ErrorKind::InvalidInput,
+                                   "invalid argument: process was not spawned \
+                                    into its own process group")),
         }
     }
 
+    pub fn set_kill_on_drop(&mut self, kill_on_drop: bool) {
+        self.kill_on_drop = kill_on_drop;
+    }
+
     pub fn wait(&mut self) -> io::Result<ExitStatus> {
         use sys::cvt_r;
         if let Some(status) = self.status {
@@ -284,4 +335,38 @@ impl Process {
             Ok(Some(ExitStatus::new(status)))
         }
     }
+
+    pub fn wait_timeout(&mut self, dur: Duration) -> io::Result<Option<ExitStatus>> {
+        // There's no portable way to block in `waitpid` with a deadline (and
+        // installing a SIGCHLD handler isn't something libstd can do on behalf
+        // of the whole program), so poll with an exponential backoff instead.
+        let deadline = match Instant::now().checked_add_duration(&dur) {
+            Some(deadline) => deadline,
+            // Too far in the future to ever be reached.
+            None => return self.wait().map(Some),
+        };
+        let mut delay = Duration::from_millis(1);
+        loop {
+            if let Some(status) = self.try_wait()? {
+                return Ok(Some(status))
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                return Ok(None)
+            }
+            thread::sleep(cmp::min(delay, deadline.sub_instant(&now)));
+            delay = cmp::min(delay * 2, Duration::from_millis(100));
+        }
+    }
+}
+
+impl Drop for Process {
+    fn drop(&mut self) {
+        if self.kill_on_drop && self.status.is_none() {
+            // Errors are ignored here: the child may have exited (and only
+            // need reaping) or there may be nothing left we can do about it.
+            let _ = self.kill();
+            let _ = self.wait();
+        }
+    }
 }
@@ -20,6 +20,10 @@ pub fn unwind_backtrace(_frames: &mut [Frame])
     unsupported()
 }
 
+pub fn resolve_context() -> io::Result<BacktraceContext> {
+    unsupported()
+}
+
 pub fn resolve_symname<F>(_frame: Frame,
                           _callback: F,
                           _: &BacktraceContext) -> io::Result<()>
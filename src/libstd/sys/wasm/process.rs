@@ -15,6 +15,7 @@ use sys::fs::File;
 use sys::pipe::AnonPipe;
 use sys::{unsupported, Void};
 use sys_common::process::{CommandEnv, DefaultEnvKey};
+use time::Duration;
 
 ////////////////////////////////////////////////////////////////////////////////
 // Command
@@ -36,6 +37,7 @@ pub enum Stdio {
     Inherit,
     Null,
     MakePipe,
+    SameAsStdout,
 }
 
 impl Command {
@@ -64,6 +66,14 @@ impl Command {
     pub fn stderr(&mut self, _stderr: Stdio) {
     }
 
+    pub fn replace_stdin(&mut self, _stdin: Option<Stdio>) -> Option<Stdio> {
+        None
+    }
+
+    pub fn replace_stdout(&mut self, _stdout: Option<Stdio>) -> Option<Stdio> {
+        None
+    }
+
     pub fn spawn(&mut self, _default: Stdio, _needs_stdin: bool)
         -> io::Result<(Process, StdioPipes)> {
         unsupported()
@@ -147,4 +157,12 @@ impl Process {
     pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
         match self.0 {}
     }
+
+    pub fn wait_timeout(&mut self, _dur: Duration) -> io::Result<Option<ExitStatus>> {
+        match self.0 {}
+    }
+
+    pub fn set_kill_on_drop(&mut self, _kill_on_drop: bool) {
+        match self.0 {}
+    }
 }
@@ -54,8 +54,6 @@ pub fn unwind_backtrace(frames: &mut [Frame])
     let dbghelp = DynamicLibrary::open("dbghelp.dll")?;
 
     // Fetch the symbols necessary from dbghelp.dll
-    let SymInitialize = sym!(dbghelp, "SymInitialize", SymInitializeFn)?;
-    let SymCleanup = sym!(dbghelp, "SymCleanup", SymCleanupFn)?;
     let StackWalkEx = sym!(dbghelp, "StackWalkEx", StackWalkExFn)?;
 
     // Allocate necessary structures for doing the stack walk
@@ -67,15 +65,15 @@ pub fn unwind_backtrace(frames: &mut [Frame])
     frame.StackFrameSize = mem::size_of_val(&frame) as c::DWORD;
     let image = init_frame(&mut frame, &context);
 
+    // Initialize this process's symbols
+    let initialized = init_symbols(&dbghelp).is_ok();
+
     let backtrace_context = BacktraceContext {
         handle: process,
-        SymCleanup,
         dbghelp,
     };
 
-    // Initialize this process's symbols
-    let ret = unsafe { SymInitialize(process, ptr::null_mut(), c::TRUE) };
-    if ret != c::TRUE {
+    if !initialized {
         return Ok((0, backtrace_context))
     }
 
@@ -104,11 +102,56 @@ pub fn unwind_backtrace(frames: &mut [Frame])
     Ok((i, backtrace_context))
 }
 
+/// Returns a context in which frames captured earlier can be resolved.
+pub fn resolve_context() -> io::Result<BacktraceContext> {
+    let dbghelp = DynamicLibrary::open("dbghelp.dll")?;
+    init_symbols(&dbghelp)?;
+
+    Ok(BacktraceContext {
+        handle: unsafe { c::GetCurrentProcess() },
+        dbghelp,
+    })
+}
+
+// Whether the symbol handler of this process has been initialized. Only
+// accessed with the backtrace lock held.
+static mut SYMBOLS_INITIALIZED: bool = false;
+
+/// Initializes the symbol handler of this process, the first time it is
+/// called.
+///
+/// `SymInitialize` is expensive, and calling it again without `SymCleanup`
+/// in between fails, so the handler is set up once and kept for the rest of
+/// the process. For the same reason `dbghelp.dll` is never unloaded once it
+/// has been initialized. Must be called with the backtrace lock held, as
+/// dbghelp is not thread safe.
+fn init_symbols(dbghelp: &DynamicLibrary) -> io::Result<()> {
+    unsafe {
+        if SYMBOLS_INITIALIZED {
+            return Ok(())
+        }
+    }
+
+    let SymInitialize = sym!(dbghelp, "SymInitialize", SymInitializeFn)?;
+
+    // Keep a reference to the library alive for good, so that the
+    // handler's state is not lost when the last context is dropped.
+    let pinned = DynamicLibrary::open("dbghelp.dll")?;
+
+    let process = unsafe { c::GetCurrentProcess() };
+    let ret = unsafe { SymInitialize(process, ptr::null_mut(), c::TRUE) };
+    if ret != c::TRUE {
+        return Err(io::Error::last_os_error())
+    }
+
+    mem::forget(pinned);
+    unsafe { SYMBOLS_INITIALIZED = true; }
+    Ok(())
+}
+
 type SymInitializeFn =
     unsafe extern "system" fn(c::HANDLE, *mut c_void,
                               c::BOOL) -> c::BOOL;
-type SymCleanupFn =
-    unsafe extern "system" fn(c::HANDLE) -> c::BOOL;
 
 type StackWalkExFn =
     unsafe extern "system" fn(c::DWORD, c::HANDLE, c::HANDLE,
@@ -141,15 +184,9 @@ fn init_frame(frame: &mut c::STACKFRAME_EX,
 }
 
 pub struct BacktraceContext {
-    handle: c::HANDLE,
-    SymCleanup: SymCleanupFn,
     // Only used in printing for msvc and not gnu
     #[allow(dead_code)]
+    handle: c::HANDLE,
+    #[allow(dead_code)]
     dbghelp: DynamicLibrary,
 }
-
-impl Drop for BacktraceContext {
-    fn drop(&mut self) {
-        unsafe { (self.SymCleanup)(self.handle); }
-    }
-}
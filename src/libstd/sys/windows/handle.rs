@@ -11,7 +11,7 @@
 #![unstable(issue = "0", feature = "windows_handle")]
 
 use cmp;
-use io::{ErrorKind, Read};
+use io::{ErrorKind, Read, IoVec, IoVecMut};
 use io;
 use mem;
 use ops::Deref;
@@ -175,6 +175,17 @@ impl RawHandle {
         (&mut me).read_to_end(buf)
     }
 
+    // `ReadFileScatter` and `WriteFileGather` only work with page-sized,
+    // page-aligned buffers on handles opened for unbuffered overlapped I/O,
+    // so there is no general vectored I/O for handles.
+    pub fn read_vectored(&self, bufs: &mut [IoVecMut]) -> io::Result<usize> {
+        io::default_read_vectored(|buf| self.read(buf), bufs)
+    }
+
+    pub fn write_vectored(&self, bufs: &[IoVec]) -> io::Result<usize> {
+        io::default_write_vectored(|buf| self.write(buf), bufs)
+    }
+
     pub fn write(&self, buf: &[u8]) -> io::Result<usize> {
         let mut amt = 0;
         let len = cmp::min(buf.len(), <c::DWORD>::max_value() as usize) as c::DWORD;
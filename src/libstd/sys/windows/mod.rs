@@ -35,6 +35,7 @@ pub mod ext;
 pub mod fast_thread_local;
 pub mod fs;
 pub mod handle;
+pub mod iovec;
 pub mod memchr;
 pub mod mutex;
 pub mod net;
@@ -29,10 +29,11 @@ use sys::fs::{OpenOptions, File};
 use sys::handle::Handle;
 use sys::pipe::{self, AnonPipe};
 use sys::stdio;
-use sys::cvt;
+use sys::{cvt, dur2timeout};
 use sys_common::{AsInner, FromInner, IntoInner};
 use sys_common::process::{CommandEnv, EnvKey};
 use alloc::borrow::Borrow;
+use time::Duration;
 
 ////////////////////////////////////////////////////////////////////////////////
 // Command
@@ -90,6 +91,7 @@ pub enum Stdio {
     Null,
     MakePipe,
     Handle(Handle),
+    SameAsStdout,
 }
 
 pub struct StdioPipes {
@@ -135,6 +137,12 @@ impl Command {
     pub fn stderr(&mut self, stderr: Stdio) {
         self.stderr = Some(stderr);
     }
+    pub fn replace_stdin(&mut self, stdin: Option<Stdio>) -> Option<Stdio> {
+        mem::replace(&mut self.stdin, stdin)
+    }
+    pub fn replace_stdout(&mut self, stdout: Option<Stdio>) -> Option<Stdio> {
+        mem::replace(&mut self.stdout, stdout)
+    }
     pub fn creation_flags(&mut self, flags: u32) {
         self.flags = flags;
     }
@@ -203,8 +211,13 @@ impl Command {
         let stdin = stdin.to_handle(c::STD_INPUT_HANDLE, &mut pipes.stdin)?;
         let stdout = stdout.to_handle(c::STD_OUTPUT_HANDLE,
                                       &mut pipes.stdout)?;
-        let stderr = stderr.to_handle(c::STD_ERROR_HANDLE,
-                                      &mut pipes.stderr)?;
+        let stderr = match *stderr {
+            Stdio::SameAsStdout => {
+                stdout.duplicate(0, true, c::DUPLICATE_SAME_ACCESS)?
+            }
+            ref stderr => stderr.to_handle(c::STD_ERROR_HANDLE,
+                                           &mut pipes.stderr)?,
+        };
         si.hStdInput = stdin.raw();
         si.hStdOutput = stdout.raw();
         si.hStdError = stderr.raw();
@@ -223,7 +236,7 @@ impl Command {
         // around to be able to close it later.
         drop(Handle::new(pi.hThread));
 
-        Ok((Process { handle: Handle::new(pi.hProcess) }, pipes))
+        Ok((Process { handle: Handle::new(pi.hProcess), kill_on_drop: false }, pipes))
     }
 
 }
@@ -291,6 +304,13 @@ impl Stdio {
                 handle.duplicate(0, true, c::DUPLICATE_SAME_ACCESS)
             }
 
+            // Redirecting stderr is handled when spawning, once the handle
+            // for stdout is known.
+            Stdio::SameAsStdout => {
+                Err(io::Error::new(ErrorKind::InvalidInput,
+                                   "only stderr can be redirected to stdout"))
+            }
+
             // Open up a reference to NUL with appropriate read/write
             // permissions as well as the ability to be inherited to child
             // processes (as this is about to be inherited).
@@ -336,6 +356,7 @@ impl From<File> for Stdio {
 /// for the process to terminate.
 pub struct Process {
     handle: Handle,
+    kill_on_drop: bool,
 }
 
 impl Process {
@@ -365,8 +386,16 @@ impl Process {
     }
 
     pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
+        self.wait_timeout_ms(0)
+    }
+
+    pub fn wait_timeout(&mut self, dur: Duration) -> io::Result<Option<ExitStatus>> {
+        self.wait_timeout_ms(dur2timeout(dur))
+    }
+
+    fn wait_timeout_ms(&mut self, ms: c::DWORD) -> io::Result<Option<ExitStatus>> {
         unsafe {
-            match c::WaitForSingleObject(self.handle.raw(), 0) {
+            match c::WaitForSingleObject(self.handle.raw(), ms) {
                 c::WAIT_OBJECT_0 => {}
                 c::WAIT_TIMEOUT => {
                     return Ok(None);
@@ -379,9 +408,31 @@ impl Process {
         }
     }
 
+    pub fn set_kill_on_drop(&mut self, kill_on_drop: bool) {
+        self.kill_on_drop = kill_on_drop;
+    }
+
     pub fn handle(&self) -> &Handle { &self.handle }
 
-    pub fn into_handle(self) -> Handle { self.handle }
+    pub fn into_handle(self) -> Handle {
+        // `Process` implements `Drop`, so the handle can't be moved out of it
+        // directly. Ownership of the process is being handed over, so skip
+        // our destructor entirely rather than killing anything.
+        let handle = unsafe { ptr::read(&self.handle) };
+        mem::forget(self);
+        handle
+    }
+}
+
+impl Drop for Process {
+    fn drop(&mut self) {
+        if self.kill_on_drop {
+            if let Ok(None) = self.try_wait() {
+                let _ = self.kill();
+                let _ = self.wait();
+            }
+        }
+    }
 }
 
 #[derive(PartialEq, Eq, Clone, Copy, Debug)]
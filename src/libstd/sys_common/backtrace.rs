@@ -11,6 +11,7 @@
 /// Common code for printing the backtrace in the same way across the different
 /// supported platforms.
 
+use cell::Cell;
 use env;
 use io::prelude::*;
 use io;
@@ -24,6 +25,7 @@ pub use sys::backtrace::{
     unwind_backtrace,
     resolve_symname,
     foreach_symbol_fileline,
+    resolve_context,
     BacktraceContext
 };
 
@@ -48,18 +50,66 @@ pub struct Frame {
 /// Max number of frames to print.
 const MAX_NB_FRAMES: usize = 100;
 
-/// Prints the current backtrace.
-pub fn print(w: &mut Write, format: PrintFormat) -> io::Result<()> {
+// Whether the current thread is inside `with_lock`. The lock is not
+// reentrant, so this is what lets `print` avoid deadlocking on it.
+thread_local!(static LOCK_HELD: Cell<bool> = Cell::new(false));
+
+/// Runs `f` while holding the global backtrace lock.
+///
+/// Use a lock to prevent mixed output in multithreading context.
+/// Some platforms also requires it, like `SymFromAddr` on Windows.
+pub fn with_lock<F, T>(f: F) -> T
+    where F: FnOnce() -> T
+{
     static LOCK: Mutex = Mutex::new();
 
-    // Use a lock to prevent mixed output in multithreading context.
-    // Some platforms also requires it, like `SymFromAddr` on Windows.
-    unsafe {
-        LOCK.lock();
-        let res = _print(w, format);
-        LOCK.unlock();
-        res
+    // Releases the lock once `f` returns or has unwound, so that later
+    // backtraces can still be taken after a panic inside `f`.
+    struct Guard;
+
+    impl Drop for Guard {
+        fn drop(&mut self) {
+            let _ = LOCK_HELD.try_with(|held| held.set(false));
+            unsafe { LOCK.unlock() }
+        }
+    }
+
+    unsafe { LOCK.lock() }
+    let _ = LOCK_HELD.try_with(|held| held.set(true));
+    let _guard = Guard;
+    f()
+}
+
+fn lock_held() -> bool {
+    LOCK_HELD.try_with(|held| held.get()).unwrap_or(false)
+}
+
+/// Prints the current backtrace.
+///
+/// If the current thread already holds the backtrace lock, which happens
+/// when the panic hook runs for a panic raised inside `with_lock`, a note is
+/// printed instead of the backtrace.
+pub fn print(w: &mut Write, format: PrintFormat) -> io::Result<()> {
+    if lock_held() {
+        return writeln!(w, "note: the backtrace is not available while one is \
+                            already being taken on this thread.")
     }
+    with_lock(|| _print(w, format))
+}
+
+/// Captures the frames of the current stack without resolving them.
+///
+/// The frames can later be resolved with the context returned by
+/// `resolve_context`.
+#[inline(never)]
+pub fn capture() -> io::Result<Vec<Frame>> {
+    let mut frames = [Frame {
+        exact_position: ptr::null(),
+        symbol_addr: ptr::null(),
+        inline_context: 0,
+    }; MAX_NB_FRAMES];
+    let nb_frames = with_lock(|| unwind_backtrace(&mut frames).map(|r| r.0))?;
+    Ok(frames[..nb_frames].to_vec())
 }
 
 fn _print(w: &mut Write, format: PrintFormat) -> io::Result<()> {
@@ -461,4 +511,24 @@ mod tests {
         t!("_ZN71_$LT$Test$u20$$u2b$$u20$$u27$static$u20$as$u20$foo..Bar$LT$Test$GT$$GT$3barE",
            "<Test + 'static as foo::Bar<Test>>::bar");
     }
+
+    #[test]
+    fn with_lock_released_on_panic() {
+        use panic;
+
+        // The panic hook runs with the lock held, and prints a backtrace if
+        // `RUST_BACKTRACE` is set.
+        let res = panic::catch_unwind(|| {
+            super::with_lock(|| panic!("inside the backtrace lock"))
+        });
+        assert!(res.is_err());
+        assert_eq!(super::with_lock(|| 1), 1);
+    }
+
+    #[test]
+    fn print_inside_with_lock() {
+        let mut out = Vec::new();
+        super::with_lock(|| super::print(&mut out, super::PrintFormat::Short)).unwrap();
+        assert!(String::from_utf8(out).unwrap().starts_with("note: "));
+    }
 }
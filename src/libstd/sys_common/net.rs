@@ -11,7 +11,7 @@
 use cmp;
 use ffi::CString;
 use fmt;
-use io::{self, Error, ErrorKind};
+use io::{self, Error, ErrorKind, IoVec, IoVecMut};
 use libc::{c_int, c_void};
 use mem;
 use net::{SocketAddr, Shutdown, Ipv4Addr, Ipv6Addr};
@@ -53,6 +53,39 @@ use libc::MSG_NOSIGNAL;
               target_os = "haiku", target_os = "bitrig")))]
 const MSG_NOSIGNAL: c_int = 0x0;
 
+// The `IPPROTO_TCP` options tuning keepalive probes: the idle time before the
+// first probe, the interval between probes, and the number of unanswered
+// probes after which the connection is dropped. Not every platform allows
+// these to be set per socket.
+#[cfg(any(target_os = "linux", target_os = "android",
+          target_os = "fuchsia", target_os = "emscripten",
+          target_os = "freebsd", target_os = "dragonfly",
+          target_os = "netbsd"))]
+const TCP_KEEPALIVE_OPTS: Option<(c_int, c_int, c_int)> =
+    Some((c::TCP_KEEPIDLE, c::TCP_KEEPINTVL, c::TCP_KEEPCNT));
+#[cfg(any(target_os = "macos", target_os = "ios", windows))]
+const TCP_KEEPALIVE_OPTS: Option<(c_int, c_int, c_int)> =
+    Some((c::TCP_KEEPALIVE, c::TCP_KEEPINTVL, c::TCP_KEEPCNT));
+#[cfg(not(any(target_os = "linux", target_os = "android",
+              target_os = "fuchsia", target_os = "emscripten",
+              target_os = "freebsd", target_os = "dragonfly",
+              target_os = "netbsd",
+              target_os = "macos", target_os = "ios", windows)))]
+const TCP_KEEPALIVE_OPTS: Option<(c_int, c_int, c_int)> = None;
+
+// `SO_LINGER` counts in clock ticks rather than seconds on Apple platforms.
+#[cfg(any(target_os = "macos", target_os = "ios"))]
+const SO_LINGER: c_int = c::SO_LINGER_SEC;
+#[cfg(not(any(target_os = "macos", target_os = "ios")))]
+const SO_LINGER: c_int = c::SO_LINGER;
+
+#[cfg(all(unix, not(any(target_os = "solaris", target_os = "haiku",
+                        target_os = "emscripten"))))]
+const SO_REUSEPORT: Option<c_int> = Some(c::SO_REUSEPORT);
+#[cfg(not(all(unix, not(any(target_os = "solaris", target_os = "haiku",
+                            target_os = "emscripten")))))]
+const SO_REUSEPORT: Option<c_int> = None;
+
 ////////////////////////////////////////////////////////////////////////////////
 // sockaddr and misc bindings
 ////////////////////////////////////////////////////////////////////////////////
@@ -80,6 +113,49 @@ pub fn getsockopt<T: Copy>(sock: &Socket, opt: c_int,
     }
 }
 
+fn unsupported<T>(what: &str) -> io::Result<T> {
+    Err(io::Error::new(ErrorKind::Other,
+                       format!("{} is not supported on this platform", what)))
+}
+
+// Socket options taking a number of seconds are rounded up to the next whole
+// second, so that a non-zero duration never becomes 0.
+fn dur2secs(dur: Duration) -> io::Result<c_int> {
+    let secs = dur.as_secs() + if dur.subsec_nanos() > 0 { 1 } else { 0 };
+    if secs == 0 {
+        return Err(io::Error::new(ErrorKind::InvalidInput,
+                                  "cannot set a 0 duration"));
+    }
+    Ok(cmp::min(secs, c_int::max_value() as u64) as c_int)
+}
+
+fn set_buffer_size(sock: &Socket, opt: c_int, size: usize) -> io::Result<()> {
+    let size = cmp::min(size, c_int::max_value() as usize) as c_int;
+    setsockopt(sock, c::SOL_SOCKET, opt, size)
+}
+
+fn buffer_size(sock: &Socket, opt: c_int) -> io::Result<usize> {
+    let raw: c_int = getsockopt(sock, c::SOL_SOCKET, opt)?;
+    Ok(raw as usize)
+}
+
+fn set_reuse_port(sock: &Socket, reuse: bool) -> io::Result<()> {
+    match SO_REUSEPORT {
+        Some(opt) => setsockopt(sock, c::SOL_SOCKET, opt, reuse as c_int),
+        None => unsupported("SO_REUSEPORT"),
+    }
+}
+
+fn reuse_port(sock: &Socket) -> io::Result<bool> {
+    match SO_REUSEPORT {
+        Some(opt) => {
+            let raw: c_int = getsockopt(sock, c::SOL_SOCKET, opt)?;
+            Ok(raw != 0)
+        }
+        None => unsupported("SO_REUSEPORT"),
+    }
+}
+
 fn sockname<F>(f: F) -> io::Result<SocketAddr>
     where F: FnOnce(*mut c::sockaddr, *mut c::socklen_t) -> c_int
 {
@@ -227,6 +303,14 @@ impl TcpStream {
         self.inner.read(buf)
     }
 
+    pub fn read_vectored(&self, bufs: &mut [IoVecMut]) -> io::Result<usize> {
+        self.inner.read_vectored(bufs)
+    }
+
+    pub fn write_vectored(&self, bufs: &[IoVec]) -> io::Result<usize> {
+        self.inner.write_vectored(bufs)
+    }
+
     pub fn write(&self, buf: &[u8]) -> io::Result<usize> {
         let len = cmp::min(buf.len(), <wrlen_t>::max_value() as usize) as wrlen_t;
         let ret = cvt(unsafe {
@@ -275,6 +359,107 @@ impl TcpStream {
         Ok(raw as u32)
     }
 
+    pub fn set_keepalive(&self, keepalive: Option<Duration>) -> io::Result<()> {
+        if let Some(dur) = keepalive {
+            let secs = dur2secs(dur)?;
+            match TCP_KEEPALIVE_OPTS {
+                Some((idle, _, _)) => setsockopt(&self.inner, c::IPPROTO_TCP, idle, secs)?,
+                None => return unsupported("setting the keepalive time"),
+            }
+        }
+        setsockopt(&self.inner, c::SOL_SOCKET, c::SO_KEEPALIVE, keepalive.is_some() as c_int)
+    }
+
+    pub fn keepalive(&self) -> io::Result<Option<Duration>> {
+        let raw: c_int = getsockopt(&self.inner, c::SOL_SOCKET, c::SO_KEEPALIVE)?;
+        if raw == 0 {
+            return Ok(None)
+        }
+        match TCP_KEEPALIVE_OPTS {
+            Some((idle, _, _)) => {
+                let secs: c_int = getsockopt(&self.inner, c::IPPROTO_TCP, idle)?;
+                Ok(Some(Duration::from_secs(secs as u64)))
+            }
+            None => unsupported("getting the keepalive time"),
+        }
+    }
+
+    pub fn set_keepalive_interval(&self, interval: Duration) -> io::Result<()> {
+        let secs = dur2secs(interval)?;
+        match TCP_KEEPALIVE_OPTS {
+            Some((_, intvl, _)) => setsockopt(&self.inner, c::IPPROTO_TCP, intvl, secs),
+            None => unsupported("setting the keepalive interval"),
+        }
+    }
+
+    pub fn keepalive_interval(&self) -> io::Result<Duration> {
+        match TCP_KEEPALIVE_OPTS {
+            Some((_, intvl, _)) => {
+                let secs: c_int = getsockopt(&self.inner, c::IPPROTO_TCP, intvl)?;
+                Ok(Duration::from_secs(secs as u64))
+            }
+            None => unsupported("getting the keepalive interval"),
+        }
+    }
+
+    pub fn set_keepalive_retries(&self, retries: u32) -> io::Result<()> {
+        let retries = cmp::min(retries, c_int::max_value() as u32) as c_int;
+        match TCP_KEEPALIVE_OPTS {
+            Some((_, _, cnt)) => setsockopt(&self.inner, c::IPPROTO_TCP, cnt, retries),
+            None => unsupported("setting the keepalive retry count"),
+        }
+    }
+
+    pub fn keepalive_retries(&self) -> io::Result<u32> {
+        match TCP_KEEPALIVE_OPTS {
+            Some((_, _, cnt)) => {
+                let raw: c_int = getsockopt(&self.inner, c::IPPROTO_TCP, cnt)?;
+                Ok(raw as u32)
+            }
+            None => unsupported("getting the keepalive retry count"),
+        }
+    }
+
+    pub fn set_linger(&self, linger: Option<Duration>) -> io::Result<()> {
+        // Unlike other timeouts a zero linger time is meaningful: it makes
+        // `close` reset the connection, discarding any unsent data.
+        let secs = match linger {
+            Some(dur) if dur == Duration::new(0, 0) => 0,
+            Some(dur) => dur2secs(dur)?,
+            None => 0,
+        };
+        let linger = c::linger {
+            l_onoff: linger.is_some() as _,
+            l_linger: secs as _,
+        };
+        setsockopt(&self.inner, c::SOL_SOCKET, SO_LINGER, linger)
+    }
+
+    pub fn linger(&self) -> io::Result<Option<Duration>> {
+        let raw: c::linger = getsockopt(&self.inner, c::SOL_SOCKET, SO_LINGER)?;
+        if raw.l_onoff == 0 {
+            Ok(None)
+        } else {
+            Ok(Some(Duration::from_secs(raw.l_linger as u64)))
+        }
+    }
+
+    pub fn set_recv_buffer_size(&self, size: usize) -> io::Result<()> {
+        set_buffer_size(&self.inner, c::SO_RCVBUF, size)
+    }
+
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        buffer_size(&self.inner, c::SO_RCVBUF)
+    }
+
+    pub fn set_send_buffer_size(&self, size: usize) -> io::Result<()> {
+        set_buffer_size(&self.inner, c::SO_SNDBUF, size)
+    }
+
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        buffer_size(&self.inner, c::SO_SNDBUF)
+    }
+
     pub fn take_error(&self) -> io::Result<Option<io::Error>> {
         self.inner.take_error()
     }
@@ -318,6 +503,15 @@ pub struct TcpListener {
 
 impl TcpListener {
     pub fn bind(addr: &SocketAddr) -> io::Result<TcpListener> {
+        TcpListener::bind_with(addr, 128, |_| Ok(()))
+    }
+
+    // Binds to `addr` and listens with the given backlog, calling `configure`
+    // to set options on the socket in between its creation and `bind`.
+    pub fn bind_with<F>(addr: &SocketAddr, backlog: u32, configure: F)
+                        -> io::Result<TcpListener>
+        where F: FnOnce(&TcpListener) -> io::Result<()>
+    {
         init();
 
         let sock = Socket::new(addr, c::SOCK_STREAM)?;
@@ -330,13 +524,17 @@ impl TcpListener {
                        1 as c_int)?;
         }
 
+        let listener = TcpListener { inner: sock };
+        configure(&listener)?;
+
         // Bind our new socket
         let (addrp, len) = addr.into_inner();
-        cvt(unsafe { c::bind(*sock.as_inner(), addrp, len as _) })?;
+        cvt(unsafe { c::bind(*listener.inner.as_inner(), addrp, len as _) })?;
 
         // Start listening
-        cvt(unsafe { c::listen(*sock.as_inner(), 128) })?;
-        Ok(TcpListener { inner: sock })
+        let backlog = cmp::min(backlog, c_int::max_value() as u32) as c_int;
+        cvt(unsafe { c::listen(*listener.inner.as_inner(), backlog) })?;
+        Ok(listener)
     }
 
     pub fn socket(&self) -> &Socket { &self.inner }
@@ -380,6 +578,34 @@ impl TcpListener {
         Ok(raw != 0)
     }
 
+    pub fn set_reuse_address(&self, reuse: bool) -> io::Result<()> {
+        setsockopt(&self.inner, c::SOL_SOCKET, c::SO_REUSEADDR, reuse as c_int)
+    }
+
+    pub fn set_reuse_port(&self, reuse: bool) -> io::Result<()> {
+        set_reuse_port(&self.inner, reuse)
+    }
+
+    pub fn reuse_port(&self) -> io::Result<bool> {
+        reuse_port(&self.inner)
+    }
+
+    pub fn set_recv_buffer_size(&self, size: usize) -> io::Result<()> {
+        set_buffer_size(&self.inner, c::SO_RCVBUF, size)
+    }
+
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        buffer_size(&self.inner, c::SO_RCVBUF)
+    }
+
+    pub fn set_send_buffer_size(&self, size: usize) -> io::Result<()> {
+        set_buffer_size(&self.inner, c::SO_SNDBUF, size)
+    }
+
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        buffer_size(&self.inner, c::SO_SNDBUF)
+    }
+
     pub fn take_error(&self) -> io::Result<Option<io::Error>> {
         self.inner.take_error()
     }
@@ -557,6 +783,30 @@ impl UdpSocket {
         Ok(raw as u32)
     }
 
+    pub fn set_reuse_port(&self, reuse: bool) -> io::Result<()> {
+        set_reuse_port(&self.inner, reuse)
+    }
+
+    pub fn reuse_port(&self) -> io::Result<bool> {
+        reuse_port(&self.inner)
+    }
+
+    pub fn set_recv_buffer_size(&self, size: usize) -> io::Result<()> {
+        set_buffer_size(&self.inner, c::SO_RCVBUF, size)
+    }
+
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        buffer_size(&self.inner, c::SO_RCVBUF)
+    }
+
+    pub fn set_send_buffer_size(&self, size: usize) -> io::Result<()> {
+        set_buffer_size(&self.inner, c::SO_SNDBUF, size)
+    }
+
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        buffer_size(&self.inner, c::SO_SNDBUF)
+    }
+
     pub fn take_error(&self) -> io::Result<Option<io::Error>> {
         self.inner.take_error()
     }
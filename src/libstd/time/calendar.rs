@@ -0,0 +1,744 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Conversion between `SystemTime` and calendar dates, and their RFC 3339
+//! representation.
+//!
+//! Dates are computed in the proleptic Gregorian calendar, using the
+//! algorithms from Howard Hinnant's "chrono-Compatible Low-Level Date
+//! Algorithms". Leap seconds can be written down, but are otherwise ignored,
+//! just like they are by the clocks `SystemTime` is read from.
+
+use cmp;
+use error::Error;
+use fmt;
+use str::FromStr;
+
+use super::{Duration, SystemTime, UNIX_EPOCH};
+
+const SECS_PER_DAY: i64 = 86_400;
+const NANOS_PER_SEC: u32 = 1_000_000_000;
+// RFC 3339 offsets have two digits for the hours.
+const MAX_OFFSET_MINUTES: i32 = 23 * 60 + 59;
+
+/// A date and time of day in the Gregorian calendar, at a fixed offset from
+/// UTC.
+///
+/// A `DateTime` is a broken-down representation of a [`SystemTime`], which
+/// can be converted back and forth with [`from_system_time`] and
+/// [`to_system_time`]. Dates before the introduction of the Gregorian
+/// calendar use the proleptic Gregorian calendar, and there is a year zero,
+/// as in ISO 8601.
+///
+/// Its [`Display`] implementation writes the date and time in the format of
+/// [RFC 3339], and its [`FromStr`] implementation parses that format. A
+/// precision given to the formatter sets the number of digits written for
+/// fractions of a second. Without one, only as many groups of three digits
+/// are written as the fraction needs.
+///
+/// Two `DateTime`s only compare equal if both their fields and their
+/// offsets are the same, even if they describe the same moment in time.
+///
+/// [`SystemTime`]: struct.SystemTime.html
+/// [`from_system_time`]: #method.from_system_time
+/// [`to_system_time`]: #method.to_system_time
+/// [`Display`]: ../../std/fmt/trait.Display.html
+/// [`FromStr`]: ../../std/str/trait.FromStr.html
+/// [RFC 3339]: https://tools.ietf.org/html/rfc3339
+///
+/// # Examples
+///
+/// ```
+/// #![feature(time_calendar)]
+///
+/// use std::time::{DateTime, Duration, UNIX_EPOCH};
+///
+/// let time = UNIX_EPOCH + Duration::new(1_500_000_000, 250_000_000);
+/// let date = DateTime::from_system_time(time);
+/// assert_eq!((date.year(), date.month(), date.day()), (2017, 7, 14));
+/// assert_eq!(date.to_string(), "2017-07-14T02:40:00.250Z");
+/// assert_eq!(format!("{:.0}", date), "2017-07-14T02:40:00Z");
+///
+/// let parsed: DateTime = "2017-07-14T04:40:00.25+02:00".parse().unwrap();
+/// assert_eq!(parsed.hour(), 4);
+/// assert_eq!(parsed.to_system_time(), time);
+/// ```
+#[unstable(feature = "time_calendar", issue = "0")]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct DateTime {
+    year: i64,
+    month: u8,
+    day: u8,
+    hour: u8,
+    minute: u8,
+    second: u8,
+    nanosecond: u32,
+    offset: i32,
+}
+
+impl DateTime {
+    /// Creates a `DateTime` in UTC from its calendar fields.
+    ///
+    /// Returns [`None`] if any of the fields is out of range, such as the
+    /// 30th of February. A `second` of 60 denotes a leap second.
+    ///
+    /// [`None`]: ../../std/option/enum.Option.html#variant.None
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(time_calendar)]
+    ///
+    /// use std::time::{DateTime, UNIX_EPOCH};
+    ///
+    /// let epoch = DateTime::from_utc(1970, 1, 1, 0, 0, 0, 0).unwrap();
+    /// assert_eq!(epoch.to_system_time(), UNIX_EPOCH);
+    ///
+    /// assert!(DateTime::from_utc(2000, 2, 29, 0, 0, 0, 0).is_some());
+    /// assert!(DateTime::from_utc(1900, 2, 29, 0, 0, 0, 0).is_none());
+    /// assert!(DateTime::from_utc(2016, 12, 31, 23, 59, 60, 0).is_some());
+    /// ```
+    #[unstable(feature = "time_calendar", issue = "0")]
+    pub fn from_utc(year: i64, month: u32, day: u32, hour: u32, minute: u32,
+                    second: u32, nanosecond: u32) -> Option<DateTime> {
+        DateTime::from_fields(year, month, day, hour, minute, second, nanosecond, 0)
+    }
+
+    fn from_fields(year: i64, month: u32, day: u32, hour: u32, minute: u32,
+                   second: u32, nanosecond: u32, offset: i32) -> Option<DateTime> {
+        if month < 1 || month > 12 || day < 1 || day > days_in_month(year, month) ||
+           hour > 23 || minute > 59 || second > 60 || nanosecond >= NANOS_PER_SEC ||
+           offset.abs() > MAX_OFFSET_MINUTES {
+            return None;
+        }
+        let date = DateTime {
+            year,
+            month: month as u8,
+            day: day as u8,
+            hour: hour as u8,
+            minute: minute as u8,
+            second: second as u8,
+            nanosecond,
+            offset,
+        };
+        // Make sure that the moment in time can be computed.
+        date.unix_seconds().map(|_| date)
+    }
+
+    /// Returns the calendar date and time in UTC of a system time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(time_calendar)]
+    ///
+    /// use std::time::{DateTime, Duration, UNIX_EPOCH};
+    ///
+    /// let date = DateTime::from_system_time(UNIX_EPOCH - Duration::from_secs(1));
+    /// assert_eq!(date.to_string(), "1969-12-31T23:59:59Z");
+    /// ```
+    #[unstable(feature = "time_calendar", issue = "0")]
+    pub fn from_system_time(time: SystemTime) -> DateTime {
+        let (secs, nanos) = match time.duration_since(UNIX_EPOCH) {
+            Ok(dur) => (dur.as_secs() as i64, dur.subsec_nanos()),
+            Err(e) => {
+                let dur = e.duration();
+                let secs = (dur.as_secs() as i64).wrapping_neg();
+                match dur.subsec_nanos() {
+                    0 => (secs, 0),
+                    nanos => (secs - 1, NANOS_PER_SEC - nanos),
+                }
+            }
+        };
+        DateTime::from_local_seconds(secs, nanos, 0)
+    }
+
+    // Builds the fields from the seconds since 1970-01-01T00:00:00 in the
+    // time zone given by `offset`.
+    fn from_local_seconds(local: i64, nanosecond: u32, offset: i32) -> DateTime {
+        let days = if local >= 0 {
+            local / SECS_PER_DAY
+        } else {
+            (local + 1) / SECS_PER_DAY - 1
+        };
+        let secs_of_day = (local - days * SECS_PER_DAY) as u32;
+        let (year, month, day) = civil_from_days(days);
+        DateTime {
+            year,
+            month: month as u8,
+            day: day as u8,
+            hour: (secs_of_day / 3600) as u8,
+            minute: (secs_of_day / 60 % 60) as u8,
+            second: (secs_of_day % 60) as u8,
+            nanosecond,
+            offset,
+        }
+    }
+
+    /// Returns the system time of the moment described by this date and
+    /// time.
+    ///
+    /// `SystemTime` doesn't count leap seconds, so a leap second is mapped to
+    /// the second that follows it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the moment can't be represented by `SystemTime` on this
+    /// platform.
+    #[unstable(feature = "time_calendar", issue = "0")]
+    pub fn to_system_time(&self) -> SystemTime {
+        let secs = self.unix_seconds().unwrap();
+        if secs >= 0 {
+            UNIX_EPOCH + Duration::new(secs as u64, self.nanosecond)
+        } else {
+            // `wrapping_neg` gets the magnitude of `i64::MIN` right when it is
+            // reinterpreted as unsigned.
+            UNIX_EPOCH - Duration::from_secs(secs.wrapping_neg() as u64) +
+                Duration::new(0, self.nanosecond)
+        }
+    }
+
+    // The whole seconds since the Unix epoch, or `None` on overflow.
+    fn unix_seconds(&self) -> Option<i64> {
+        let days = days_from_civil(self.year, self.month as u32, self.day as u32)?;
+        let secs_of_day = self.hour as i64 * 3600 + self.minute as i64 * 60 +
+            self.second as i64 - self.offset as i64 * 60;
+        days.checked_mul(SECS_PER_DAY)?.checked_add(secs_of_day)
+    }
+
+    /// Returns the same moment in time, with its fields at an offset of
+    /// `offset_minutes` east of UTC.
+    ///
+    /// Returns [`None`] if the offset is a day or more, or if the moment
+    /// can't be expressed at that offset. A leap second becomes the second
+    /// that follows it, as with [`to_system_time`].
+    ///
+    /// [`to_system_time`]: #method.to_system_time
+    ///
+    /// [`None`]: ../../std/option/enum.Option.html#variant.None
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(time_calendar)]
+    ///
+    /// use std::time::DateTime;
+    ///
+    /// let utc = DateTime::from_utc(2018, 1, 1, 3, 0, 0, 0).unwrap();
+    /// let local = utc.with_offset(-5 * 60).unwrap();
+    /// assert_eq!(local.to_string(), "2017-12-31T22:00:00-05:00");
+    /// assert_eq!(local.with_offset(0), Some(utc));
+    /// ```
+    #[unstable(feature = "time_calendar", issue = "0")]
+    pub fn with_offset(&self, offset_minutes: i32) -> Option<DateTime> {
+        if offset_minutes.abs() > MAX_OFFSET_MINUTES {
+            return None;
+        }
+        let local = self.unix_seconds()?.checked_add(offset_minutes as i64 * 60)?;
+        Some(DateTime::from_local_seconds(local, self.nanosecond, offset_minutes))
+    }
+
+    /// Returns the year. Years before 1 AD are zero or negative.
+    #[unstable(feature = "time_calendar", issue = "0")]
+    pub fn year(&self) -> i64 {
+        self.year
+    }
+
+    /// Returns the month, starting at 1 for January.
+    #[unstable(feature = "time_calendar", issue = "0")]
+    pub fn month(&self) -> u32 {
+        self.month as u32
+    }
+
+    /// Returns the day of the month, starting at 1.
+    #[unstable(feature = "time_calendar", issue = "0")]
+    pub fn day(&self) -> u32 {
+        self.day as u32
+    }
+
+    /// Returns the hour, from 0 to 23.
+    #[unstable(feature = "time_calendar", issue = "0")]
+    pub fn hour(&self) -> u32 {
+        self.hour as u32
+    }
+
+    /// Returns the minute, from 0 to 59.
+    #[unstable(feature = "time_calendar", issue = "0")]
+    pub fn minute(&self) -> u32 {
+        self.minute as u32
+    }
+
+    /// Returns the second, from 0 to 59, or 60 for a leap second.
+    #[unstable(feature = "time_calendar", issue = "0")]
+    pub fn second(&self) -> u32 {
+        self.second as u32
+    }
+
+    /// Returns the fraction of the second in nanoseconds.
+    #[unstable(feature = "time_calendar", issue = "0")]
+    pub fn nanosecond(&self) -> u32 {
+        self.nanosecond
+    }
+
+    /// Returns the offset from UTC in minutes, positive east of Greenwich.
+    /// It is zero for dates in UTC.
+    #[unstable(feature = "time_calendar", issue = "0")]
+    pub fn offset_minutes(&self) -> i32 {
+        self.offset
+    }
+}
+
+fn is_leap_year(year: i64) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+// The number of days from 1970-01-01 to the given date. The year is shifted
+// to start in March, so that the leap day comes last, and then split into
+// 400-year eras which all have the same number of days.
+fn days_from_civil(year: i64, month: u32, day: u32) -> Option<i64> {
+    let year = if month <= 2 { year.checked_sub(1)? } else { year };
+    let era = if year >= 0 { year } else { year.checked_sub(399)? } / 400;
+    let year_of_era = year - era * 400;
+    let month_from_march = (month as i64 + 9) % 12;
+    let day_of_year = (153 * month_from_march + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era.checked_mul(146_097)?.checked_add(day_of_era - 719_468)
+}
+
+// The inverse of `days_from_civil`.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719_468;
+    let era = if days >= 0 { days } else { days - 146_096 } / 146_097;
+    let day_of_era = days - era * 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 -
+                       day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_from_march = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_from_march + 2) / 5 + 1;
+    let month = if month_from_march < 10 { month_from_march + 3 } else { month_from_march - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month as u32, day as u32)
+}
+
+#[unstable(feature = "time_calendar", issue = "0")]
+impl fmt::Display for DateTime {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Years which don't fit into four digits are written with a sign, as
+        // in the expanded representation of ISO 8601.
+        if self.year >= 0 && self.year <= 9999 {
+            write!(f, "{:04}", self.year)?;
+        } else {
+            write!(f, "{:+05}", self.year)?;
+        }
+        write!(f, "-{:02}-{:02}T{:02}:{:02}:{:02}",
+               self.month, self.day, self.hour, self.minute, self.second)?;
+
+        let digits = match f.precision() {
+            Some(precision) => cmp::min(precision, 9) as u32,
+            None if self.nanosecond == 0 => 0,
+            None if self.nanosecond % 1_000_000 == 0 => 3,
+            None if self.nanosecond % 1_000 == 0 => 6,
+            None => 9,
+        };
+        if digits > 0 {
+            write!(f, ".{:0width$}", self.nanosecond / 10u32.pow(9 - digits),
+                   width = digits as usize)?;
+        }
+
+        match self.offset {
+            0 => f.write_str("Z"),
+            offset => {
+                let sign = if offset < 0 { '-' } else { '+' };
+                let offset = offset.abs();
+                write!(f, "{}{:02}:{:02}", sign, offset / 60, offset % 60)
+            }
+        }
+    }
+}
+
+/// An error which can be returned when parsing a [`DateTime`].
+///
+/// [`DateTime`]: struct.DateTime.html
+#[unstable(feature = "time_calendar", issue = "0")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseDateTimeError(());
+
+#[unstable(feature = "time_calendar", issue = "0")]
+impl fmt::Display for ParseDateTimeError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_str(self.description())
+    }
+}
+
+#[unstable(feature = "time_calendar", issue = "0")]
+impl Error for ParseDateTimeError {
+    fn description(&self) -> &str {
+        "invalid RFC 3339 date and time"
+    }
+}
+
+struct Parser<'a> {
+    s: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn next_byte(&mut self) -> Option<u8> {
+        let c = self.s.get(self.pos).cloned();
+        if c.is_some() {
+            self.pos += 1;
+        }
+        c
+    }
+
+    fn expect(&mut self, c: u8) -> Option<()> {
+        if self.next_byte()? == c { Some(()) } else { None }
+    }
+
+    fn digit(&mut self) -> Option<u32> {
+        match self.s.get(self.pos) {
+            Some(&c @ b'0'...b'9') => {
+                self.pos += 1;
+                Some((c - b'0') as u32)
+            }
+            _ => None,
+        }
+    }
+
+    fn number(&mut self, digits: usize) -> Option<u32> {
+        let mut n = 0;
+        for _ in 0..digits {
+            n = n * 10 + self.digit()?;
+        }
+        Some(n)
+    }
+
+    // A four digit year, or the expanded representation of ISO 8601 written
+    // by `Display` for years which don't fit into four digits: a sign
+    // followed by at least four digits.
+    fn year(&mut self) -> Option<i64> {
+        let negative = match self.s.get(self.pos) {
+            Some(&b'+') => false,
+            Some(&b'-') => true,
+            _ => return Some(self.number(4)? as i64),
+        };
+        self.pos += 1;
+        let start = self.pos;
+        let mut year = 0i64;
+        while let Some(d) = self.digit() {
+            year = year.checked_mul(10)?.checked_add(d as i64)?;
+        }
+        if self.pos - start < 4 {
+            return None;
+        }
+        let year = if negative { -year } else { year };
+        // Years with four digits must be written without a sign.
+        if year >= 0 && year <= 9999 { None } else { Some(year) }
+    }
+
+    // date-time = full-date "T" full-time, where the "T" may also be a
+    // lowercase "t" or a space, as permitted by RFC 3339.
+    fn date_time(&mut self) -> Option<DateTime> {
+        let year = self.year()?;
+        self.expect(b'-')?;
+        let month = self.number(2)?;
+        self.expect(b'-')?;
+        let day = self.number(2)?;
+        match self.next_byte()? {
+            b'T' | b't' | b' ' => {}
+            _ => return None,
+        }
+        let hour = self.number(2)?;
+        self.expect(b':')?;
+        let minute = self.number(2)?;
+        self.expect(b':')?;
+        let second = self.number(2)?;
+
+        let mut nanosecond = 0;
+        if self.s.get(self.pos) == Some(&b'.') {
+            self.pos += 1;
+            // Digits beyond nanoseconds are accepted, but truncated.
+            let mut digits = 0;
+            while let Some(d) = self.digit() {
+                if digits < 9 {
+                    nanosecond = nanosecond * 10 + d;
+                }
+                digits += 1;
+            }
+            if digits == 0 {
+                return None;
+            }
+            if digits < 9 {
+                nanosecond *= 10u32.pow(9 - digits);
+            }
+        }
+
+        let offset = match self.next_byte()? {
+            b'Z' | b'z' => 0,
+            c @ b'+' | c @ b'-' => {
+                let hours = self.number(2)? as i32;
+                self.expect(b':')?;
+                let minutes = self.number(2)? as i32;
+                if hours > 23 || minutes > 59 {
+                    return None;
+                }
+                let offset = hours * 60 + minutes;
+                if c == b'-' { -offset } else { offset }
+            }
+            _ => return None,
+        };
+        if self.pos != self.s.len() {
+            return None;
+        }
+        DateTime::from_fields(year, month, day, hour, minute, second, nanosecond, offset)
+    }
+}
+
+#[unstable(feature = "time_calendar", issue = "0")]
+impl FromStr for DateTime {
+    type Err = ParseDateTimeError;
+
+    /// Parses a date and time in the format of RFC 3339, such as
+    /// `1985-04-12T23:20:50.52Z` or `1996-12-19T16:39:57-08:00`.
+    ///
+    /// The fields are kept at the offset given in the string. A second of
+    /// `60` is accepted as a leap second, and years which don't fit into four
+    /// digits may be written with a sign, as `Display` does.
+    fn from_str(s: &str) -> Result<DateTime, ParseDateTimeError> {
+        let mut parser = Parser { s: s.as_bytes(), pos: 0 };
+        parser.date_time().ok_or(ParseDateTimeError(()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DateTime, days_from_civil, civil_from_days};
+    use time::{Duration, UNIX_EPOCH};
+
+    fn date(s: &str) -> DateTime {
+        s.parse().unwrap()
+    }
+
+    // Whether `SystemTime` can represent `secs` seconds after (or before) the
+    // epoch. A 32-bit time_t only reaches 68 years either way, and Windows
+    // can't go back further than 1601.
+    fn representable(secs: u64, before_epoch: bool) -> bool {
+        if cfg!(unix) && ::mem::size_of::<::libc::time_t>() <= 4 {
+            secs < i32::max_value() as u64
+        } else if cfg!(windows) && before_epoch {
+            secs < 11_644_473_600
+        } else {
+            true
+        }
+    }
+
+    #[test]
+    fn epoch() {
+        let epoch = DateTime::from_system_time(UNIX_EPOCH);
+        assert_eq!(epoch, DateTime::from_utc(1970, 1, 1, 0, 0, 0, 0).unwrap());
+        assert_eq!(epoch.to_string(), "1970-01-01T00:00:00Z");
+        assert_eq!(epoch.to_system_time(), UNIX_EPOCH);
+    }
+
+    #[test]
+    fn civil_days() {
+        assert_eq!(days_from_civil(1970, 1, 1), Some(0));
+        assert_eq!(days_from_civil(2000, 3, 1), Some(11_017));
+        assert_eq!(days_from_civil(1969, 12, 31), Some(-1));
+        assert_eq!(days_from_civil(0, 1, 1), Some(-719_528));
+        assert_eq!(days_from_civil(i64::max_value(), 12, 31), None);
+        assert_eq!(days_from_civil(i64::min_value(), 1, 1), None);
+
+        // Every day from 1600 to 2400 round-trips and follows the previous.
+        let start = days_from_civil(1600, 1, 1).unwrap();
+        let mut prev = civil_from_days(start - 1);
+        for days in start..days_from_civil(2400, 1, 1).unwrap() {
+            let (y, m, d) = civil_from_days(days);
+            assert_eq!(days_from_civil(y, m, d), Some(days));
+            if d == 1 {
+                assert_eq!(prev.2, super::days_in_month(prev.0, prev.1));
+            } else {
+                assert_eq!((prev.0, prev.1, prev.2 + 1), (y, m, d));
+            }
+            prev = (y, m, d);
+        }
+    }
+
+    #[test]
+    fn system_time_round_trip() {
+        let secs = [0, 1, 59, 86_399, 86_400, 951_782_400, 1_500_000_000, 4_102_444_800,
+                    253_402_300_799];
+        for &secs in &secs {
+            for &nanos in &[0, 1, 500_000_000, 999_999_999] {
+                let dur = Duration::new(secs, nanos);
+                if representable(secs, false) {
+                    let date = DateTime::from_system_time(UNIX_EPOCH + dur);
+                    assert_eq!(date.nanosecond(), nanos);
+                    assert_eq!(date.to_system_time(), UNIX_EPOCH + dur);
+                    assert_eq!(date.to_string().parse::<DateTime>(), Ok(date));
+                }
+                if representable(secs, true) {
+                    let date = DateTime::from_system_time(UNIX_EPOCH - dur);
+                    assert_eq!(date.to_system_time(), UNIX_EPOCH - dur);
+                    assert_eq!(date.to_string().parse::<DateTime>(), Ok(date));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn before_epoch() {
+        let date = DateTime::from_system_time(UNIX_EPOCH - Duration::new(0, 1));
+        assert_eq!(date.to_string(), "1969-12-31T23:59:59.999999999Z");
+        let date = DateTime::from_system_time(UNIX_EPOCH - Duration::from_secs(86_400 * 365));
+        assert_eq!(date.to_string(), "1969-01-01T00:00:00Z");
+        let date = DateTime::from_utc(-1, 12, 31, 23, 59, 59, 0).unwrap();
+        assert_eq!(date.to_string(), "-0001-12-31T23:59:59Z");
+        assert_eq!(date.to_string().parse::<DateTime>(), Ok(date));
+        if representable(62_167_219_201, true) {
+            assert_eq!(DateTime::from_system_time(date.to_system_time()), date);
+        }
+    }
+
+    #[test]
+    fn leap_second() {
+        let leap = date("2016-12-31T23:59:60.5Z");
+        assert_eq!(leap.second(), 60);
+        assert_eq!(leap.to_string(), "2016-12-31T23:59:60.500Z");
+        let next = DateTime::from_utc(2017, 1, 1, 0, 0, 0, 500_000_000).unwrap();
+        assert_eq!(leap.to_system_time(), next.to_system_time());
+        assert_eq!(leap.with_offset(0), Some(next));
+
+        let leap = date("2016-12-31T18:59:60-05:00");
+        assert_eq!(leap.second(), 60);
+        assert_eq!(leap.to_string(), "2016-12-31T18:59:60-05:00");
+    }
+
+    #[test]
+    fn fields() {
+        let date = date("2000-02-29T13:04:05.06-01:30");
+        assert_eq!(date.year(), 2000);
+        assert_eq!(date.month(), 2);
+        assert_eq!(date.day(), 29);
+        assert_eq!(date.hour(), 13);
+        assert_eq!(date.minute(), 4);
+        assert_eq!(date.second(), 5);
+        assert_eq!(date.nanosecond(), 60_000_000);
+        assert_eq!(date.offset_minutes(), -90);
+        assert_eq!(date.with_offset(0).unwrap().to_string(), "2000-02-29T14:34:05.060Z");
+    }
+
+    #[test]
+    fn invalid_fields() {
+        assert!(DateTime::from_utc(2018, 0, 1, 0, 0, 0, 0).is_none());
+        assert!(DateTime::from_utc(2018, 13, 1, 0, 0, 0, 0).is_none());
+        assert!(DateTime::from_utc(2018, 4, 31, 0, 0, 0, 0).is_none());
+        assert!(DateTime::from_utc(2100, 2, 29, 0, 0, 0, 0).is_none());
+        assert!(DateTime::from_utc(2018, 1, 0, 0, 0, 0, 0).is_none());
+        assert!(DateTime::from_utc(2018, 1, 1, 24, 0, 0, 0).is_none());
+        assert!(DateTime::from_utc(2018, 1, 1, 0, 60, 0, 0).is_none());
+        assert!(DateTime::from_utc(2018, 1, 1, 0, 0, 61, 0).is_none());
+        assert!(DateTime::from_utc(2018, 1, 1, 0, 0, 0, 1_000_000_000).is_none());
+        assert!(DateTime::from_utc(i64::max_value(), 1, 1, 0, 0, 0, 0).is_none());
+
+        let date = DateTime::from_utc(2018, 1, 1, 0, 0, 0, 0).unwrap();
+        assert!(date.with_offset(24 * 60).is_none());
+        assert!(date.with_offset(-24 * 60).is_none());
+        assert!(date.with_offset(23 * 60 + 59).is_some());
+    }
+
+    #[test]
+    fn display() {
+        let date = DateTime::from_utc(1985, 4, 12, 23, 20, 50, 520_000_000).unwrap();
+        assert_eq!(date.to_string(), "1985-04-12T23:20:50.520Z");
+        assert_eq!(format!("{:.0}", date), "1985-04-12T23:20:50Z");
+        assert_eq!(format!("{:.2}", date), "1985-04-12T23:20:50.52Z");
+        assert_eq!(format!("{:.9}", date), "1985-04-12T23:20:50.520000000Z");
+        assert_eq!(format!("{:.12}", date), "1985-04-12T23:20:50.520000000Z");
+
+        let date = DateTime::from_utc(1, 1, 1, 0, 0, 0, 1_000).unwrap();
+        assert_eq!(date.to_string(), "0001-01-01T00:00:00.000001Z");
+        assert_eq!(format!("{:.3}", date), "0001-01-01T00:00:00.000Z");
+        let date = DateTime::from_utc(12_345, 6, 7, 8, 9, 10, 11).unwrap();
+        assert_eq!(date.to_string(), "+12345-06-07T08:09:10.000000011Z");
+
+        let date = date.with_offset(5 * 60 + 45).unwrap();
+        assert_eq!(date.to_string(), "+12345-06-07T13:54:10.000000011+05:45");
+        let date = date.with_offset(-(8 * 60)).unwrap();
+        assert_eq!(date.to_string(), "+12345-06-07T00:09:10.000000011-08:00");
+    }
+
+    #[test]
+    fn parse() {
+        let utc = DateTime::from_utc(1996, 12, 20, 0, 39, 57, 0).unwrap();
+        assert_eq!(date("1996-12-20T00:39:57Z"), utc);
+        assert_eq!(date("1996-12-20t00:39:57z"), utc);
+        assert_eq!(date("1996-12-20 00:39:57Z"), utc);
+        assert_eq!(date("1996-12-20T00:39:57+00:00"), utc);
+        assert_eq!(date("1996-12-20T00:39:57-00:00"), utc);
+        assert_eq!(date("1996-12-19T16:39:57-08:00").with_offset(0), Some(utc));
+        assert_eq!(date("1996-12-20T00:39:57-08:00").to_system_time(),
+                   utc.to_system_time() + Duration::from_secs(8 * 3600));
+
+        assert_eq!(date("1985-04-12T23:20:50.5Z").nanosecond(), 500_000_000);
+        assert_eq!(date("1985-04-12T23:20:50.000000001Z").nanosecond(), 1);
+        assert_eq!(date("1985-04-12T23:20:50.1234567899Z").nanosecond(), 123_456_789);
+        assert_eq!(date("0000-01-01T00:00:00Z").year(), 0);
+        assert_eq!(date("9999-12-31T23:59:59Z").year(), 9999);
+        assert_eq!(date("+10000-01-01T00:00:00Z").year(), 10_000);
+        assert_eq!(date("-0001-12-31T23:59:59Z").year(), -1);
+        assert_eq!(date("-12345-01-01T00:00:00Z").year(), -12_345);
+        assert_eq!(date("+12345-06-07T13:54:10.000000011+05:45").to_string(),
+                   "+12345-06-07T13:54:10.000000011+05:45");
+    }
+
+    #[test]
+    fn parse_invalid() {
+        let invalid = [
+            "",
+            "1996-12-20",
+            "1996-12-20T00:39:57",
+            "1996-12-20T00:39Z",
+            "1996-12-20X00:39:57Z",
+            "96-12-20T00:39:57Z",
+            "+1996-12-20T00:39:57Z",
+            "-0000-12-20T00:39:57Z",
+            "+123-12-20T00:39:57Z",
+            "+99999999999999999999-12-20T00:39:57Z",
+            "1996-12-2T00:39:57Z",
+            "1996-13-20T00:39:57Z",
+            "1996-02-30T00:39:57Z",
+            "1996-12-20T24:00:00Z",
+            "1996-12-20T00:60:00Z",
+            "1996-12-20T23:59:61Z",
+            "1996-12-20T00:39:57.Z",
+            "1996-12-20T00:39:57,5Z",
+            "1996-12-20T00:39:57+0800",
+            "1996-12-20T00:39:57+08",
+            "1996-12-20T00:39:57+24:00",
+            "1996-12-20T00:39:57+08:60",
+            "1996-12-20T00:39:57Z ",
+            "1996-12-20T00:39:57ZZ",
+            "1996-12-20T00:39:57Z\u{e9}",
+        ];
+        for s in &invalid {
+            assert!(s.parse::<DateTime>().is_err(), "parsed {:?}", s);
+        }
+    }
+}
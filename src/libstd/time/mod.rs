@@ -31,6 +31,11 @@ use sys_common::FromInner;
 #[stable(feature = "time", since = "1.3.0")]
 pub use core::time::Duration;
 
+#[unstable(feature = "time_calendar", issue = "0")]
+pub use self::calendar::{DateTime, ParseDateTimeError};
+
+mod calendar;
+
 /// A measurement of a monotonically nondecreasing clock.
 /// Opaque and useful only with `Duration`.
 ///
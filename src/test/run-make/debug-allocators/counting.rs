@@ -0,0 +1,77 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(global_allocator, heap_api, allocator_api, alloc_counting)]
+
+extern crate alloc_counting;
+
+use std::heap::{Heap, Alloc, Layout};
+use std::thread;
+
+use alloc_counting::{Counting, Site};
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+static OUTER: Site = Site::new("outer");
+static INNER: Site = Site::new("inner");
+static UNUSED: Site = Site::new("unused");
+
+fn main() {
+    let before = GLOBAL.stats();
+    unsafe {
+        let _outer = OUTER.enter();
+        let layout = Layout::from_size_align(16, 8).unwrap();
+        let ptr = Heap.alloc(layout.clone()).unwrap();
+        {
+            let _inner = INNER.enter();
+            let new_layout = Layout::from_size_align(64, 8).unwrap();
+            let ptr = Heap.realloc(ptr, layout, new_layout.clone()).unwrap();
+            Heap.dealloc(ptr, new_layout);
+        }
+        let v = vec![0u32; 10];
+        drop(v);
+    }
+
+    let outer = OUTER.stats();
+    assert_eq!(outer.allocations, 2);
+    assert_eq!(outer.deallocations, 1);
+    assert_eq!(outer.reallocations, 0);
+    assert_eq!(outer.bytes_allocated, 56);
+    assert_eq!(outer.live_bytes(), 16);
+
+    let inner = INNER.stats();
+    assert_eq!(inner.allocations, 0);
+    assert_eq!(inner.deallocations, 1);
+    assert_eq!(inner.reallocations, 1);
+    assert_eq!(inner.bytes_allocated, 64);
+    assert_eq!(inner.bytes_deallocated, 80);
+    assert_eq!(inner.live_bytes(), -16);
+
+    let total = GLOBAL.stats();
+    assert!(total.allocations >= before.allocations + 2);
+    assert!(total.bytes_allocated >= before.bytes_allocated + 120);
+
+    // sites are per thread
+    thread::spawn(|| {
+        let _inner = INNER.enter();
+        drop(Box::new(0u64));
+    }).join().unwrap();
+    assert_eq!(INNER.stats().allocations, 1);
+    assert_eq!(OUTER.stats(), outer);
+
+    let names: Vec<_> = alloc_counting::sites().map(|site| site.name()).collect();
+    assert_eq!(names, ["inner", "outer"]);
+    assert_eq!(UNUSED.stats(), Default::default());
+
+    GLOBAL.reset();
+    assert_eq!(OUTER.stats().allocations, 0);
+    assert_eq!(INNER.stats().bytes_allocated, 0);
+}
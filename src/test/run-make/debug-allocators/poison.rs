@@ -0,0 +1,68 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(global_allocator, heap_api, allocator_api, alloc_poison)]
+
+extern crate alloc_poison;
+
+use std::heap::{Heap, Alloc, Layout};
+
+use alloc_poison::{Poison, ALLOC_BYTE, GUARD_BYTE, GUARD_SIZE};
+
+#[global_allocator]
+static GLOBAL: Poison = Poison;
+
+fn main() {
+    unsafe {
+        for &(size, align) in &[(1, 1), (10, 8), (3, 64), (100, 4096)] {
+            let layout = Layout::from_size_align(size, align).unwrap();
+            let ptr = Heap.alloc(layout.clone()).unwrap();
+            assert_eq!(ptr as usize % align, 0);
+            for i in 0..size {
+                assert_eq!(*ptr.offset(i as isize), ALLOC_BYTE);
+            }
+            for i in 1..GUARD_SIZE + 1 {
+                assert_eq!(*ptr.offset(-(i as isize)), GUARD_BYTE);
+                assert_eq!(*ptr.offset((size + i - 1) as isize), GUARD_BYTE);
+            }
+            assert!(GLOBAL.check(ptr, &layout));
+
+            // an overflow is caught, and undoing it makes the block valid again
+            *ptr.offset(size as isize) = 0;
+            assert!(!GLOBAL.check(ptr, &layout));
+            *ptr.offset(size as isize) = GUARD_BYTE;
+            *ptr.offset(-1) = 0;
+            assert!(!GLOBAL.check(ptr, &layout));
+            *ptr.offset(-1) = GUARD_BYTE;
+            assert!(GLOBAL.check(ptr, &layout));
+
+            *ptr = 42;
+            let new_layout = Layout::from_size_align(size * 2, align).unwrap();
+            let new_ptr = Heap.realloc(ptr, layout.clone(), new_layout.clone()).unwrap();
+            assert!(new_ptr != ptr);
+            assert_eq!(*new_ptr, 42);
+            assert_eq!(*new_ptr.offset(size as isize), ALLOC_BYTE);
+            assert!(GLOBAL.check(new_ptr, &new_layout));
+            Heap.dealloc(new_ptr, new_layout);
+
+            let ptr = Heap.alloc_zeroed(layout.clone()).unwrap();
+            for i in 0..size {
+                assert_eq!(*ptr.offset(i as isize), 0);
+            }
+            Heap.dealloc(ptr, layout);
+        }
+    }
+
+    let mut v = Vec::new();
+    for i in 0..1000 {
+        v.push(i);
+    }
+    assert_eq!(v.iter().sum::<i32>(), 499500);
+}
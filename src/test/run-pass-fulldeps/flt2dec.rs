@@ -73,7 +73,7 @@ fn iterate<F, G, V>(func: &str, k: usize, n: usize, mut f: F, mut g: G, mut v: V
     (npassed, nignored)
 }
 
-pub fn f32_random_equivalence_test<F, G>(f: F, g: G, k: usize, n: usize)
+pub fn f32_random_equivalence_test<F, G>(f: F, g: G, k: usize, n: usize) -> (usize, usize)
         where F: FnMut(&Decoded, &mut [u8]) -> Option<(usize, i16)>,
               G: FnMut(&Decoded, &mut [u8]) -> (usize, i16) {
     let mut rng: XorShiftRng = Rand::rand(&mut rand::thread_rng());
@@ -82,10 +82,10 @@ pub fn f32_random_equivalence_test<F, G>(f: F, g: G, k: usize, n: usize)
         let i: u32 = f32_range.ind_sample(&mut rng);
         let x: f32 = unsafe {mem::transmute(i)};
         decode_finite(x)
-    });
+    })
 }
 
-pub fn f64_random_equivalence_test<F, G>(f: F, g: G, k: usize, n: usize)
+pub fn f64_random_equivalence_test<F, G>(f: F, g: G, k: usize, n: usize) -> (usize, usize)
         where F: FnMut(&Decoded, &mut [u8]) -> Option<(usize, i16)>,
               G: FnMut(&Decoded, &mut [u8]) -> (usize, i16) {
     let mut rng: XorShiftRng = Rand::rand(&mut rand::thread_rng());
@@ -94,10 +94,10 @@ pub fn f64_random_equivalence_test<F, G>(f: F, g: G, k: usize, n: usize)
         let i: u64 = f64_range.ind_sample(&mut rng);
         let x: f64 = unsafe {mem::transmute(i)};
         decode_finite(x)
-    });
+    })
 }
 
-pub fn f32_exhaustive_equivalence_test<F, G>(f: F, g: G, k: usize)
+pub fn f32_exhaustive_equivalence_test<F, G>(f: F, g: G, k: usize) -> (usize, usize)
         where F: FnMut(&Decoded, &mut [u8]) -> Option<(usize, i16)>,
               G: FnMut(&Decoded, &mut [u8]) -> (usize, i16) {
     // we have only 2^23 * (2^8 - 1) - 1 = 2,139,095,039 positive finite f32 values,
@@ -107,12 +107,10 @@ pub fn f32_exhaustive_equivalence_test<F, G>(f: F, g: G, k: usize)
     // but with `-C opt-level=3 -C lto` this only takes about an hour or so.
 
     // iterate from 0x0000_0001 to 0x7f7f_ffff, i.e. all finite ranges
-    let (npassed, nignored) = iterate("f32_exhaustive_equivalence_test",
-                                      k, 0x7f7f_ffff, f, g, |i: usize| {
+    iterate("f32_exhaustive_equivalence_test", k, 0x7f7f_ffff, f, g, |i: usize| {
         let x: f32 = unsafe {mem::transmute(i as u32 + 1)};
         decode_finite(x)
-    });
-    assert_eq!((npassed, nignored), (2121451881, 17643158));
+    })
 }
 
 #[test]
@@ -132,7 +130,9 @@ fn shortest_f32_exhaustive_equivalence_test() {
     // `done, ignored=17643158 passed=2121451881 failed=0`.
 
     use core::num::flt2dec::strategy::dragon::format_shortest as fallback;
-    f32_exhaustive_equivalence_test(format_shortest_opt, fallback, MAX_SIG_DIGITS);
+    let (npassed, nignored) = f32_exhaustive_equivalence_test(format_shortest_opt, fallback,
+                                                              MAX_SIG_DIGITS);
+    assert_eq!((npassed, nignored), (2121451881, 17643158));
 }
 
 #[test] #[ignore] // it is too expensive
@@ -144,6 +144,30 @@ fn shortest_f64_hard_random_equivalence_test() {
                                          MAX_SIG_DIGITS, 100_000_000);
 }
 
+#[test]
+fn ryu_shortest_random_equivalence_test() {
+    use core::num::flt2dec::strategy::dragon::format_shortest as fallback;
+    use core::num::flt2dec::strategy::ryu::format_shortest_opt;
+    // unlike Grisu, Ryu never gives up on a finite float.
+    let (_, nignored) = f64_random_equivalence_test(format_shortest_opt, fallback,
+                                                    MAX_SIG_DIGITS, 10_000);
+    assert_eq!(nignored, 0);
+    let (_, nignored) = f32_random_equivalence_test(format_shortest_opt, fallback,
+                                                    MAX_SIG_DIGITS, 10_000);
+    assert_eq!(nignored, 0);
+}
+
+#[test] #[ignore] // it is too expensive
+fn ryu_shortest_f32_exhaustive_equivalence_test() {
+    // this should print `done, ignored=0 passed=2139095039 failed=0`.
+
+    use core::num::flt2dec::strategy::dragon::format_shortest as fallback;
+    use core::num::flt2dec::strategy::ryu::format_shortest_opt;
+    let (npassed, nignored) = f32_exhaustive_equivalence_test(format_shortest_opt, fallback,
+                                                              MAX_SIG_DIGITS);
+    assert_eq!((npassed, nignored), (2139095039, 0));
+}
+
 #[test]
 fn exact_f32_random_equivalence_test() {
     use core::num::flt2dec::strategy::dragon::format_exact as fallback;
@@ -53,6 +53,7 @@ use std::iter::Iterator;
 const EXCEPTION_PATHS: &'static [&'static str] = &[
     // std crates
     "src/liballoc_jemalloc",
+    "src/liballoc_poison",
     "src/liballoc_system",
     "src/libcompiler_builtins",
     "src/liblibc",